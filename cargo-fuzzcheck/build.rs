@@ -1,3 +0,0 @@
-fn main() {
-    println!("cargo:rustc-env=TARGET={}", std::env::var("TARGET").unwrap());
-}
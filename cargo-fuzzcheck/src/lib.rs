@@ -1,14 +1,30 @@
 #![allow(clippy::collapsible_if)]
 
+mod minify_trace;
+pub mod project;
+
 use fuzzcheck_common::arg::*;
+use fuzzcheck_common::results::found_test_failure;
+use minify_trace::MinifyTrace;
 use std::cmp::Ordering;
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::process;
 use std::process::{Command, Stdio};
-const TARGET: &str = env!("TARGET");
+use std::time::{Duration, Instant};
 const BUILD_FOLDER: &str = "target/fuzzcheck";
 
+/// The exit code of `cargo fuzzcheck`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// No test failure was found
+    NoFailure = 0,
+    /// A test failure was found, or a command replaying the corpus failed
+    FailureFound = 1,
+    /// The arguments, the config files, or the fuzz project are invalid, or the fuzz test does not compile
+    ConfigurationError = 2,
+}
+
 pub enum CompiledTarget {
     Lib,
     Bin(String),
@@ -24,23 +40,165 @@ impl CompiledTarget {
     }
 }
 
+/// The target triple of the host, read from the output of `rustc -vV`
+pub fn host_triple() -> std::io::Result<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let output = Command::new(rustc).arg("-vV").output()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_owned())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "could not find the host triple in the output of rustc -vV",
+            )
+        })
+}
+
+/// The target triple that the fuzz test is compiled for: the one given by `--target`, or the host triple
+fn target_triple(args: &Arguments) -> std::io::Result<String> {
+    if let Some(target) = &args.target {
+        Ok(target.clone())
+    } else {
+        host_triple()
+    }
+}
+
+/// The `rustc` flags enabling the sanitizer given by `--sanitizer`, if any
+fn sanitizer_flags(args: &Arguments) -> Vec<String> {
+    if let Some(sanitizer) = args.sanitizer {
+        vec![format!("-Zsanitizer={}", sanitizer.name())]
+    } else {
+        vec![]
+    }
+}
+
+/// The `rustc` flags instrumenting the comparisons for the `ComparisonSensor` if `--trace-compares` is given
+///
+/// The inline boolean flags are only there because the sanitizer coverage pass otherwise defaults to
+/// `trace-pc-guard`, whose callbacks are not used by fuzzcheck.
+fn trace_compares_flags(args: &Arguments) -> Vec<String> {
+    if args.trace_compares {
+        [
+            "-Cpasses=sancov-module",
+            "-Cllvm-args=-sanitizer-coverage-level=1",
+            "-Cllvm-args=-sanitizer-coverage-inline-bool-flag",
+            "-Cllvm-args=-sanitizer-coverage-trace-compares",
+        ]
+        .map(ToOwned::to_owned)
+        .to_vec()
+    } else {
+        vec![]
+    }
+}
+
+/// The flags given with `--rustflags`, which are appended to the `rustc` flags of cargo fuzzcheck
+fn user_rustflags(args: &Arguments) -> Vec<String> {
+    args.rustflags
+        .iter()
+        .flat_map(|flags| flags.split_whitespace())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Set the runtime options of the sanitizer given by `--sanitizer` so that the process aborts
+/// at the first error, which lets the fuzzer save the failing test case.
+///
+/// Options that were already set by the user are kept.
+fn set_sanitizer_options(command: &mut Command, args: &Arguments) {
+    if let Some(sanitizer) = args.sanitizer {
+        let var = sanitizer.options_env_var();
+        if std::env::var(var).map(|x| x.is_empty()).unwrap_or(true) {
+            command.env(var, "abort_on_error=1:halt_on_error=1");
+        }
+    }
+}
+
+/// The folder of the fuzz project: the one given by `--fuzz-dir`, relative to `current_dir`, or `current_dir` itself
+pub fn fuzz_project_folder(current_dir: &Path, fuzz_dir: Option<&str>) -> PathBuf {
+    if let Some(fuzz_dir) = fuzz_dir {
+        current_dir.join(fuzz_dir)
+    } else {
+        current_dir.to_path_buf()
+    }
+}
+
+/// Make the relative paths of `args` absolute by joining them to `base`.
+///
+/// The fuzz test runs in the folder of the fuzz project, which may not be the current directory of `cargo fuzzcheck`,
+/// so the paths given to it must be absolute for both processes to agree on the files they designate.
+pub fn make_paths_absolute(args: &mut Arguments, base: &Path) {
+    let make_absolute = |path: &mut PathBuf| {
+        if path.is_relative() {
+            *path = base.join(&path);
+        }
+    };
+    match &mut args.command {
+        FuzzerCommand::Read { input_file } | FuzzerCommand::MinifyInput { input_file } => make_absolute(input_file),
+        FuzzerCommand::WriteCoverage { snapshot_file } | FuzzerCommand::AssertCoverage { snapshot_file } => {
+            make_absolute(snapshot_file)
+        }
+        FuzzerCommand::CoverageMap { output_file } | FuzzerCommand::CoverageReport { output_file } => {
+            make_absolute(output_file)
+        }
+        FuzzerCommand::Merge { corpora } => corpora.iter_mut().for_each(make_absolute),
+        FuzzerCommand::Fuzz | FuzzerCommand::BisectCorpus | FuzzerCommand::RunCorpus | FuzzerCommand::MinifyCorpus => {}
+    }
+    [
+        &mut args.corpus_in,
+        &mut args.corpus_out,
+        &mut args.artifacts_folder,
+        &mut args.stats_folder,
+        &mut args.resume,
+        &mut args.results_file,
+        &mut args.dictionary,
+    ]
+    .into_iter()
+    .flatten()
+    .for_each(make_absolute);
+}
+
+/// Launch the fuzz test `target_name`.
+///
+/// `fuzz_dir` is the root folder of the crate containing the fuzz test. Cargo is run from
+/// that folder, and the build artifacts are placed in its `target/fuzzcheck` subfolder.
+/// The paths of `args` must be absolute, see [`make_paths_absolute`].
 pub fn launch_executable(
     target_name: &str,
     args: &Arguments,
+    fuzz_dir: &Path,
     name_package: Option<String>,
     compiled_target: &CompiledTarget,
     cargo_args: &[String],
     stdio: impl Fn() -> Stdio,
 ) -> std::io::Result<process::Child> {
-    let args = string_from_args(args);
+    // the results file of a previous run must not be mistaken for the one of this run
+    if let Some(results_file) = &args.results_file {
+        let _ = std::fs::remove_file(results_file);
+    }
+    let target = target_triple(args)?;
+    let sanitizer_flags = sanitizer_flags(args);
+    let trace_compares_flags = trace_compares_flags(args);
+    let user_rustflags = user_rustflags(args);
+    let mut rustflags = "-Ccodegen-units=1".to_owned();
+    for flag in sanitizer_flags.iter() {
+        rustflags.push(' ');
+        rustflags.push_str(flag);
+    }
     let child = if let Some(name_package) = &name_package {
+        for flag in user_rustflags.iter() {
+            rustflags.push(' ');
+            rustflags.push_str(flag);
+        }
         let compiled = Command::new("cargo")
+            .current_dir(fuzz_dir)
             .env("CARGO_BUILD_PIPELINING", "false")
-            .env("RUSTFLAGS", "-Ccodegen-units=1")
+            .env("RUSTFLAGS", &rustflags)
             .arg("rustc")
             .args(compiled_target.to_args())
             .args(cargo_args)
-            .args(["--target", TARGET])
+            .args(["--target", target.as_str()])
             .arg("--release")
             .arg("--all-features")
             .args(["--target-dir", BUILD_FOLDER])
@@ -50,16 +208,16 @@ pub fn launch_executable(
             .args(["--cfg", "test"])
             .args(["-Zinstrument-coverage=except-unused-functions", "-Zno-profiler-runtime"])
             .args(["-Coverflow-checks=yes", "-Cforce-frame-pointers", "-g"])
+            .args(&sanitizer_flags)
+            .args(&trace_compares_flags)
             .stdout(stdio())
             .stderr(stdio())
             .spawn()?
             .wait_with_output()?;
-        assert!(compiled.status.success());
-        let dep_folder = PathBuf::new()
-            .join(BUILD_FOLDER)
-            .join(TARGET)
-            .join("release")
-            .join("deps");
+        if !compiled.status.success() {
+            return Err(compilation_error());
+        }
+        let dep_folder = fuzz_dir.join(BUILD_FOLDER).join(&target).join("release").join("deps");
         let files_inside_dep_folder = std::fs::read_dir(dep_folder)?;
         let mut executables = vec![];
         for file in files_inside_dep_folder.flatten() {
@@ -86,8 +244,11 @@ pub fn launch_executable(
                 .max_by_key(|(time, _)| time)
                 .map(|(_, path)| path)
                 .unwrap();
-            Command::new(most_recent_exec)
-                .env("FUZZCHECK_ARGS", args)
+            let mut command = Command::new(most_recent_exec);
+            set_sanitizer_options(&mut command, args);
+            command
+                .current_dir(fuzz_dir)
+                .env("FUZZCHECK_ARGS", string_from_args(args))
                 .arg("--nocapture")
                 .arg("--exact")
                 .arg(target_name)
@@ -97,39 +258,182 @@ pub fn launch_executable(
                 .spawn()?
         }
     } else {
-        Command::new("cargo")
-            .env("FUZZCHECK_ARGS", args)
-            .env(
-                "RUSTFLAGS",
-                "-Zinstrument-coverage=except-unused-functions -Zno-profiler-runtime -Ccodegen-units=1 -Coverflow-checks=yes -Cforce-frame-pointers -g",
-            )
-            .arg("test")
-            .args(compiled_target.to_args())
-            .args(cargo_args)
-            .args(["--target", TARGET])
-            .arg("--release")
-            .args(["--target-dir", BUILD_FOLDER])
-            .args(["--features", "fuzzing"])
+        // unlike with `cargo rustc`, the flags apply to all the crates, including fuzzcheck itself
+        for flag in trace_compares_flags.iter() {
+            rustflags.push(' ');
+            rustflags.push_str(flag);
+        }
+        let mut rustflags = format!(
+            "-Zinstrument-coverage=except-unused-functions -Zno-profiler-runtime {} -Coverflow-checks=yes -Cforce-frame-pointers -g",
+            rustflags
+        );
+        for flag in user_rustflags.iter() {
+            rustflags.push(' ');
+            rustflags.push_str(flag);
+        }
+        let cargo_test = || {
+            let mut command = Command::new("cargo");
+            set_sanitizer_options(&mut command, args);
+            command
+                .current_dir(fuzz_dir)
+                .env("FUZZCHECK_ARGS", string_from_args(args))
+                .env("RUSTFLAGS", &rustflags)
+                .arg("test")
+                .args(compiled_target.to_args())
+                .args(cargo_args)
+                .args(["--target", target.as_str()])
+                .arg("--release")
+                .args(["--target-dir", BUILD_FOLDER])
+                .args(["--features", "fuzzing"])
+                .stdout(stdio())
+                .stderr(stdio());
+            command
+        };
+        // the fuzz test is compiled first, so that a compilation error is not mistaken for a test failure
+        if !cargo_test().arg("--no-run").status()?.success() {
+            return Err(compilation_error());
+        }
+        cargo_test()
             .arg("--")
             .arg("--nocapture")
             .arg("--exact")
             .arg(target_name)
             .args(["--test-threads", "1"])
-            .stdout(stdio())
-            .stderr(stdio())
             .spawn()?
     };
 
     Ok(child)
 }
 
+/// The error returned when cargo fails to compile the fuzz test
+fn compilation_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, "the fuzz test could not be compiled")
+}
+
+/// The file where the fuzz test `target_name` writes its results when `--results-file` is not given, relative to
+/// the folder of the fuzz project
+///
+/// `cargo fuzzcheck` always asks the fuzzer for its results, so that it can tell whether a test failure was found.
+pub fn default_results_file(target_name: &str) -> PathBuf {
+    Path::new(BUILD_FOLDER)
+        .join("results")
+        .join(format!("{}.json", target_name))
+}
+
+/// The exit code of `cargo fuzzcheck` after the fuzzer launched with `args` exited with `status`
+///
+/// A test failure was found if the fuzzer says so in its results file, or if it did not exit successfully.
+pub fn fuzz_exit_code(args: &Arguments, status: process::ExitStatus) -> ExitCode {
+    let found = args
+        .results_file
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|results| found_test_failure(&results))
+        .unwrap_or(false);
+    if found || !status.success() {
+        ExitCode::FailureFound
+    } else {
+        ExitCode::NoFailure
+    }
+}
+
+/// The path of the file of a worker launched by [`launch_workers`], such as `fuzz_state.bin.worker-2` for `fuzz_state.bin`
+fn worker_file(path: &Path, worker: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".worker-{}", worker));
+    path.with_file_name(name)
+}
+
+/// Launch `jobs` processes of the fuzz test `target_name`, which share the output corpus and the artifacts folder,
+/// and wait for all of them to finish.
+///
+/// Each process is given its index with `--worker`, and writes its statistics, the state given by `--resume`, and
+/// the results given by `--results-file` to its own folder and file. A test failure was found if any process found
+/// one, see [`fuzz_exit_code`].
+pub fn launch_workers(
+    target_name: &str,
+    args: &Arguments,
+    fuzz_dir: &Path,
+    name_package: Option<String>,
+    compiled_target: &CompiledTarget,
+    cargo_args: &[String],
+    jobs: usize,
+) -> std::io::Result<ExitCode> {
+    let mut children = vec![];
+    for worker in 0..jobs {
+        let mut config = args.clone();
+        config.worker = Some(worker);
+        config.stats_folder = args
+            .stats_folder
+            .as_ref()
+            .map(|folder| folder.join(format!("worker-{}", worker)));
+        config.resume = args.resume.as_ref().map(|path| worker_file(path, worker));
+        config.results_file = args.results_file.as_ref().map(|path| worker_file(path, worker));
+        let child = launch_executable(
+            target_name,
+            &config,
+            fuzz_dir,
+            name_package.clone(),
+            compiled_target,
+            cargo_args,
+            Stdio::inherit,
+        )?;
+        children.push((child, config));
+    }
+    let mut exit_code = ExitCode::NoFailure;
+    for (child, config) in children {
+        let output = child.wait_with_output()?;
+        if fuzz_exit_code(&config, output.status) == ExitCode::FailureFound {
+            exit_code = ExitCode::FailureFound;
+        }
+    }
+    Ok(exit_code)
+}
+
+/// The name of the report of the minification of an input file, written in the folder of the minified test cases
+const MINIFY_REPORT_FILE: &str = "minify_report.json";
+
+/// The conditions that stop the minification of an input file by [`input_minify_command`]
+///
+/// Without any of them, the minification goes on until it is interrupted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinifyStopConditions {
+    /// The maximum number of test cases run by each launch of the fuzz test. When a launch runs them all
+    /// without finding a simpler failing test case, the input is considered minimal.
+    pub runs: Option<usize>,
+    /// The maximum duration of the whole minification
+    pub time: Option<Duration>,
+}
+
+/// The folder where the minified versions of `file_to_minify` are saved, such as `crash.minified` for `crash.json`
+fn minified_folder(file_to_minify: &Path) -> PathBuf {
+    let mut x = file_to_minify.parent().unwrap().to_path_buf();
+    x.push(file_to_minify.file_stem().unwrap());
+    x.with_extension("minified")
+}
+
+/// Minify the input file given by `args.command`, which must be [`FuzzerCommand::MinifyInput`].
+///
+/// The fuzz test is launched repeatedly, each time looking for a failing test case simpler than the simplest one
+/// found so far. It stops when a launch finds none, or when one of the `stop` conditions is met, and then prints
+/// the path and complexity of the simplest failing test case.
+///
+/// Each successful reduction of the input is recorded. If `minify_trace` is given, they are written to that file,
+/// which is rewritten after each launch of the fuzz test. At the end, a report listing the successive simpler test
+/// cases, with what changed in their content at each step, is written to `minify_report.json` in the folder of the
+/// minified test cases.
+#[allow(clippy::too_many_arguments)]
 pub fn input_minify_command(
     target_name: &str,
     args: &Arguments,
+    fuzz_dir: &Path,
     compiled_target: &CompiledTarget,
     cargo_args: &[String],
+    minify_trace: Option<&Path>,
+    stop: MinifyStopConditions,
     stdio: &impl Fn() -> Stdio,
 ) -> std::io::Result<()> {
+    let start = Instant::now();
     let mut config = args.clone();
     let file_to_minify = if let FuzzerCommand::MinifyInput { input_file } = config.command {
         input_file
@@ -137,22 +441,29 @@ pub fn input_minify_command(
         panic!()
     };
 
-    let artifacts_folder = {
-        let mut x = file_to_minify.parent().unwrap().to_path_buf();
-        x.push(file_to_minify.file_stem().unwrap());
-        x = x.with_extension("minified");
-        x
-    };
+    let artifacts_folder = minified_folder(&file_to_minify);
 
     let _ = std::fs::create_dir(&artifacts_folder);
     config.artifacts_folder = Some(artifacts_folder.clone());
     config.stop_after_first_failure = true;
+    if let Some(runs) = stop.runs {
+        config.maximum_iterations = runs;
+    }
 
-    fn simplest_input_file(folder: &Path) -> Option<PathBuf> {
-        let files_with_complexity = std::fs::read_dir(folder)
-            .ok()?
+    /// The files of the folder whose name starts with their complexity, along with that complexity
+    fn files_with_complexity(folder: &Path) -> Vec<(PathBuf, f64)> {
+        let entries = if let Ok(entries) = std::fs::read_dir(folder) {
+            entries
+        } else {
+            return vec![];
+        };
+        entries
             .filter_map(|path| -> Option<(PathBuf, f64)> {
                 let path = path.ok()?.path();
+                // the pretty-printed values written next to the failing test cases are not test cases
+                if path.extension() == Some(DEBUG_VALUE_EXTENSION.as_ref()) {
+                    return None;
+                }
                 let name_components: Vec<&str> = path.file_stem()?.to_str()?.splitn(2, "--").collect();
                 if name_components.len() == 2 {
                     let cplx = name_components[0].parse::<f64>().ok()?;
@@ -160,34 +471,125 @@ pub fn input_minify_command(
                 } else {
                     None
                 }
-            });
+            })
+            .collect()
+    }
 
-        files_with_complexity
+    fn simplest_input_file(folder: &Path) -> Option<(PathBuf, f64)> {
+        files_with_complexity(folder)
+            .into_iter()
             .min_by(|x, y| std::cmp::PartialOrd::partial_cmp(&x.1, &y.1).unwrap_or(Ordering::Equal))
-            .map(|x| x.0)
     }
 
-    let mut simplest = simplest_input_file(artifacts_folder.as_path()).unwrap_or(file_to_minify);
+    let mut simplest = simplest_input_file(artifacts_folder.as_path()).map_or(file_to_minify, |x| x.0);
     config.command = FuzzerCommand::Read {
         input_file: simplest.clone(),
     };
 
     println!("launch with config: {:?}", string_from_args(&config));
 
-    let child = launch_executable(target_name, &config, None, compiled_target, cargo_args, stdio)?;
+    let child = launch_executable(target_name, &config, fuzz_dir, None, compiled_target, cargo_args, stdio)?;
     let o = child.wait_with_output()?;
 
     assert!(!o.status.success());
 
+    // the failing input was saved as an artifact by the Read command, which gives its complexity
+    let mut trace = simplest_input_file(&artifacts_folder).map(|(file, cplx)| MinifyTrace::new(file, cplx));
+
+    let mut nbr_launches = 0;
     loop {
-        simplest = simplest_input_file(&artifacts_folder).unwrap_or_else(|| simplest.clone());
+        simplest = simplest_input_file(&artifacts_folder).map_or_else(|| simplest.clone(), |x| x.0);
+        if let Some(time) = stop.time {
+            let elapsed = start.elapsed();
+            if elapsed >= time {
+                break;
+            }
+            config.maximum_duration = time - elapsed;
+        }
         config.command = FuzzerCommand::MinifyInput {
             input_file: simplest.clone(),
         };
         println!("launch with config: {:?}", string_from_args(&config));
-        let mut c = launch_executable(target_name, &config, None, compiled_target, cargo_args, Stdio::inherit)?;
+        let mut c = launch_executable(
+            target_name,
+            &config,
+            fuzz_dir,
+            None,
+            compiled_target,
+            cargo_args,
+            Stdio::inherit,
+        )?;
         c.wait()?;
+        nbr_launches += 1;
+        if let Some(trace) = &mut trace {
+            if trace.record(&files_with_complexity(&artifacts_folder)) {
+                if let Some(trace_path) = minify_trace {
+                    trace.write(trace_path)?;
+                }
+            }
+        }
+        // the launch stopped without finding a simpler failing test case
+        if simplest_input_file(&artifacts_folder).map(|x| x.0).as_ref() == Some(&simplest) {
+            break;
+        }
+    }
+
+    println!("\n================ MINIFICATION SUMMARY ================");
+    if let Some((file, cplx)) = simplest_input_file(&artifacts_folder) {
+        println!(
+            "The simplest failing test case found after {} launches of the fuzz test, in {}s, is {}, of complexity {:.2}.",
+            nbr_launches,
+            start.elapsed().as_secs(),
+            file.display(),
+            cplx
+        );
+    } else {
+        println!("No failing test case was found in {}.", artifacts_folder.display());
+    }
+    if let Some(trace) = &trace {
+        let report_path = artifacts_folder.join(MINIFY_REPORT_FILE);
+        trace.write_report(&report_path)?;
+        println!(
+            "The {} reductions of the test case are described in {}.",
+            trace.nbr_reductions(),
+            report_path.display()
+        );
     }
+    Ok(())
+}
+
+/// The quote surrounding a pattern given to `--ignore-panic` in the arguments of the fuzz test, so that it is not
+/// split at its whitespace, or `None` if the pattern cannot be quoted.
+///
+/// The fuzz test splits its arguments at their whitespace, outside of quotes, and does not unescape them. So a
+/// pattern containing both kinds of quotes, or ending with an odd number of backslashes, which would escape the
+/// closing quote, cannot be given to it.
+fn ignored_panic_quote(pattern: &str) -> Option<char> {
+    let trailing_backslashes = pattern.chars().rev().take_while(|&c| c == '\\').count();
+    if trailing_backslashes % 2 == 1 {
+        None
+    } else if !pattern.contains('\'') {
+        Some('\'')
+    } else if !pattern.contains('"') {
+        Some('"')
+    } else {
+        None
+    }
+}
+
+/// Check that the patterns given to `--ignore-panic` can be passed to the fuzz test, see [`ignored_panic_quote`]
+pub fn check_ignored_panics(args: &Arguments) -> Result<(), ArgumentsError> {
+    for pattern in args.ignored_panics.iter() {
+        if ignored_panic_quote(pattern).is_none() {
+            return Err(ArgumentsError::Validation(format!(
+                "The pattern {:?} given to --{} cannot be passed to the fuzz test: it must not contain both \
+                single and double quotes, nor end with an odd number of backslashes. Use a character class, \
+                such as [\"] or [\\\\], instead.",
+                pattern, IGNORE_PANIC_FLAG
+            )));
+        }
+    }
+    Ok(())
 }
 
 pub fn string_from_args(args: &Arguments) -> String {
@@ -212,6 +614,49 @@ pub fn string_from_args(args: &Arguments) -> String {
             s.push(' ');
             Some(input_file.clone())
         }
+        FuzzerCommand::WriteCoverage { snapshot_file } => {
+            s.push_str(&format!("--{} {} ", WRITE_COVERAGE_FLAG, snapshot_file.display()));
+            None
+        }
+        FuzzerCommand::AssertCoverage { snapshot_file } => {
+            s.push_str(&format!("--{} {} ", ASSERT_COVERAGE_FLAG, snapshot_file.display()));
+            None
+        }
+        FuzzerCommand::CoverageMap { output_file } => {
+            s.push_str(&format!("--{} {} ", COVERAGE_MAP_FLAG, output_file.display()));
+            None
+        }
+        FuzzerCommand::CoverageReport { output_file } => {
+            s.push_str(&format!("--{} {} ", COVERAGE_REPORT_FLAG, output_file.display()));
+            None
+        }
+        FuzzerCommand::BisectCorpus => {
+            s.push_str("--command ");
+            s.push_str(COMMAND_BISECT_CORPUS);
+            s.push(' ');
+            None
+        }
+        FuzzerCommand::Merge { corpora } => {
+            s.push_str("--command ");
+            s.push_str(COMMAND_MERGE);
+            s.push(' ');
+            for corpus in corpora {
+                s.push_str(&format!("--{} {} ", MERGE_FROM_FLAG, corpus.display()));
+            }
+            None
+        }
+        FuzzerCommand::RunCorpus => {
+            s.push_str("--command ");
+            s.push_str(COMMAND_RUN_CORPUS);
+            s.push(' ');
+            None
+        }
+        FuzzerCommand::MinifyCorpus => {
+            s.push_str("--command ");
+            s.push_str(COMMAND_MINIFY_CORPUS);
+            s.push(' ');
+            None
+        }
     };
     if let Some(input_file) = input_file {
         s.push_str(&format!("--{} {} ", INPUT_FILE_FLAG, input_file.display()));
@@ -250,6 +695,10 @@ pub fn string_from_args(args: &Arguments) -> String {
         .unwrap_or_else(|| format!("--{} ", NO_STATS_FLAG));
     s.push_str(&stats_args);
     s.push(' ');
+    s.push_str(&format!("--{} {} ", STATS_FORMAT_FLAG, args.stats_format.name()));
+    if let Some(stats_interval) = args.stats_interval {
+        s.push_str(&format!("--{} {} ", STATS_INTERVAL_FLAG, stats_interval));
+    }
 
     s.push_str(&format!("--{} {} ", MAX_INPUT_CPLX_FLAG, args.max_input_cplx as usize));
     s.push_str(&format!("--{} {} ", MAX_DURATION_FLAG, args.maximum_duration.as_secs()));
@@ -257,5 +706,131 @@ pub fn string_from_args(args: &Arguments) -> String {
     if args.stop_after_first_failure {
         s.push_str(&format!("--{} ", STOP_AFTER_FIRST_FAILURE_FLAG));
     }
+    if args.strict {
+        s.push_str(&format!("--{} ", STRICT_FLAG));
+    }
+    if args.post_minify_check {
+        s.push_str(&format!("--{} ", POST_MINIFY_CHECK_FLAG));
+    }
+    if let Some(slow_threshold) = args.slow_threshold {
+        s.push_str(&format!("--{} {} ", SLOW_THRESHOLD_FLAG, slow_threshold.as_millis()));
+    }
+    if let Some(hang_threshold) = args.hang_threshold {
+        s.push_str(&format!("--{} {} ", HANG_THRESHOLD_FLAG, hang_threshold.as_millis()));
+    }
+    if let Some(max_memory) = args.max_memory {
+        s.push_str(&format!("--{} {} ", MAX_MEMORY_FLAG, max_memory));
+    }
+    if let Some(malloc_limit) = args.malloc_limit {
+        s.push_str(&format!("--{} {} ", MALLOC_LIMIT_FLAG, malloc_limit));
+    }
+    if args.runs_per_input != 1 {
+        s.push_str(&format!("--{} {} ", RUNS_PER_INPUT_FLAG, args.runs_per_input));
+    }
+    if args.max_artifacts_per_signature != 1 {
+        s.push_str(&format!(
+            "--{} {} ",
+            MAX_ARTIFACTS_PER_SIGNATURE_FLAG, args.max_artifacts_per_signature
+        ));
+    }
+    s.push_str(&format!("--{} {} ", BOOTSTRAP_COUNT_FLAG, args.bootstrap_count));
+    if let Some(resume) = &args.resume {
+        s.push_str(&format!("--{} {} ", RESUME_FLAG, resume.display()));
+    }
+    if let Some(results_file) = &args.results_file {
+        s.push_str(&format!("--{} {} ", RESULTS_FILE_FLAG, results_file.display()));
+    }
+    if let Some(dictionary) = &args.dictionary {
+        s.push_str(&format!("--{} {} ", DICTIONARY_FLAG, dictionary.display()));
+    }
+    if let Some(worker) = args.worker {
+        s.push_str(&format!("--{} {} ", WORKER_FLAG, worker));
+    }
+    if let Some(ensemble) = &args.ensemble {
+        s.push_str(&format!("--{} {} ", ENSEMBLE_FLAG, ensemble));
+    }
+    if let Some(fork) = args.fork {
+        s.push_str(&format!("--{} {} ", FORK_FLAG, fork));
+    }
+    for pattern in args.ignored_panics.iter() {
+        // the patterns were validated by `check_ignored_panics`
+        let quote = ignored_panic_quote(pattern).unwrap();
+        s.push_str(&format!("--{} {q}{}{q} ", IGNORE_PANIC_FLAG, pattern, q = quote));
+    }
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_ignored_panics, string_from_args};
+    use super::{fuzz_project_folder, make_paths_absolute, minified_folder};
+    use crate::project::{project_config, PROJECT_CONFIG_FILE};
+    use fuzzcheck_common::arg::{options_parser, parse_arguments_with_defaults, Arguments, FuzzerCommand};
+
+    #[test]
+    fn test_paths_with_relative_fuzz_dir() {
+        let current_dir = std::env::temp_dir().join(format!("cargo-fuzzcheck-paths-{}", std::process::id()));
+        let fuzz_dir = fuzz_project_folder(&current_dir, Some("project"));
+        assert_eq!(fuzz_dir, current_dir.join("project"));
+        std::fs::create_dir_all(fuzz_dir.join("fuzz")).unwrap();
+        std::fs::write(
+            fuzz_dir.join(PROJECT_CONFIG_FILE),
+            "[\"tests::target\"]\ndictionary = \"fuzz/tokens.dict\"\n",
+        )
+        .unwrap();
+
+        let command_line = [
+            "tests::target",
+            "--command",
+            "minify",
+            "--input-file",
+            "artifacts/crash.json",
+            "--in-corpus",
+            "corpus",
+            "--artifacts",
+            "/tmp/artifacts",
+        ];
+        let defaults = project_config(&fuzz_dir, "tests::target").unwrap();
+        let matches = parse_arguments_with_defaults(&options_parser(), &command_line, defaults).unwrap();
+        let mut args = Arguments::from_matches(&matches, true).unwrap();
+        make_paths_absolute(&mut args, &current_dir);
+        std::fs::remove_dir_all(&current_dir).unwrap();
+
+        // the paths of the command line are relative to the current directory, not to the fuzz project
+        let input_file = if let FuzzerCommand::MinifyInput { input_file } = &args.command {
+            input_file.clone()
+        } else {
+            panic!("{:?}", args.command)
+        };
+        assert_eq!(input_file, current_dir.join("artifacts/crash.json"));
+        assert_eq!(
+            minified_folder(&input_file),
+            current_dir.join("artifacts/crash.minified")
+        );
+        assert_eq!(args.corpus_in, Some(current_dir.join("corpus")));
+        assert_eq!(args.artifacts_folder, Some("/tmp/artifacts".into()));
+        // the paths of the config file of the fuzz project are relative to the fuzz project
+        assert_eq!(args.dictionary, Some(fuzz_dir.join("fuzz/tokens.dict")));
+    }
+    #[test]
+    fn test_ignored_panics() {
+        let parse = |patterns: &[&str]| {
+            let mut command_line = vec!["tests::target"];
+            for pattern in patterns {
+                command_line.extend(["--ignore-panic", pattern]);
+            }
+            let matches = parse_arguments_with_defaults(&options_parser(), &command_line, None).unwrap();
+            Arguments::from_matches(&matches, true).unwrap()
+        };
+        let args = parse(&[r"index out of bounds", r#"it's \d+"#, r#"a "quoted" \\"#]);
+        assert!(check_ignored_panics(&args).is_ok());
+        let s = string_from_args(&args);
+        assert!(s.contains(r"--ignore-panic 'index out of bounds' "), "{}", s);
+        assert!(s.contains(r#"--ignore-panic "it's \d+" "#), "{}", s);
+        assert!(s.contains(r#"--ignore-panic 'a "quoted" \\' "#), "{}", s);
+
+        assert!(check_ignored_panics(&parse(&[r#"it's "quoted""#])).is_err());
+        assert!(check_ignored_panics(&parse(&[r"ends with \"])).is_err());
+        assert!(check_ignored_panics(&parse(&[r"ends with \\\"])).is_err());
+    }
+}
@@ -1,15 +1,49 @@
 #![allow(clippy::bool_comparison)]
 extern crate cargo_fuzzcheck;
+use cargo_fuzzcheck::project::project_config;
 use cargo_fuzzcheck::*;
 use fuzzcheck_common::arg::*;
 use std::error::Error;
 use std::path::PathBuf;
 use std::process;
 use std::string::String;
+use std::time::Duration;
 
 const CARGO_ARGS_FLAG: &str = "cargo-args";
+const FUZZ_DIR_FLAG: &str = "fuzz-dir";
+const MINIFY_TRACE_FLAG: &str = "minify-trace";
+const JOBS_FLAG: &str = "jobs";
+const RUNS_FLAG: &str = "runs";
+const TIME_FLAG: &str = "time";
 
-fn main() -> Result<(), Box<dyn Error>> {
+/// `cargo fuzzcheck tmin <FUZZ_TEST> <FILE>` is a shorthand for
+/// `cargo fuzzcheck <FUZZ_TEST> --command minify --input-file <FILE>`
+const TMIN_SUBCOMMAND: &str = "tmin";
+/// `cargo fuzzcheck merge <FUZZ_TEST> --into <DIR> <CORPUS>...` is a shorthand for
+/// `cargo fuzzcheck <FUZZ_TEST> --command merge --out-corpus <DIR> --merge-from <CORPUS>...`
+const MERGE_SUBCOMMAND: &str = "merge";
+const INTO_FLAG: &str = "into";
+/// `cargo fuzzcheck run-corpus <FUZZ_TEST>` is a shorthand for
+/// `cargo fuzzcheck <FUZZ_TEST> --command run-corpus`
+const RUN_CORPUS_SUBCOMMAND: &str = "run-corpus";
+/// `cargo fuzzcheck coverage <FUZZ_TEST> <FILE>` is a shorthand for
+/// `cargo fuzzcheck <FUZZ_TEST> --coverage-report <FILE>`
+const COVERAGE_SUBCOMMAND: &str = "coverage";
+/// `cargo fuzzcheck broker <ADDRESS>` runs the broker of an ensemble of fuzzers launched with `--ensemble <ADDRESS>`
+const BROKER_SUBCOMMAND: &str = "broker";
+
+fn main() {
+    let exit_code = match run() {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::ConfigurationError
+        }
+    };
+    process::exit(exit_code as i32);
+}
+
+fn run() -> Result<ExitCode, Box<dyn Error>> {
     let mut parser = options_parser();
 
     parser.optflag("", "lib", "Test only this package's library unit tests (default)");
@@ -28,6 +62,40 @@ fn main() -> Result<(), Box<dyn Error>> {
         "<NAME>",
     );
 
+    parser.optopt(
+        "",
+        FUZZ_DIR_FLAG,
+        "Path to the crate containing the fuzz test (default: the current directory)",
+        "<PATH>",
+    );
+
+    parser.optopt(
+        "",
+        MINIFY_TRACE_FLAG,
+        "When minifying, record each reduction of the input in the given file (Graphviz if it ends with .dot, JSON otherwise)",
+        "<PATH>",
+    );
+
+    parser.optopt(
+        "",
+        RUNS_FLAG,
+        "When minifying, stop once a launch of the fuzz test runs N test cases without finding a simpler failing one",
+        "N",
+    );
+    parser.optopt(
+        "",
+        TIME_FLAG,
+        "When minifying, stop after N seconds and print the simplest failing test case found",
+        "N",
+    );
+
+    parser.optopt(
+        "",
+        JOBS_FLAG,
+        "Launch N fuzzer processes sharing the output corpus (default: 1)",
+        "N",
+    );
+
     parser.opt(
         "",
         CARGO_ARGS_FLAG,
@@ -49,21 +117,89 @@ fn main() -> Result<(), Box<dyn Error>> {
         return Err(Box::new(ArgumentsError::NoArgumentsGiven(help(&parser))));
     }
 
-    let string_args = env_args[start_idx..].iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    let mut string_args = env_args[start_idx..].to_vec();
+    if string_args[0] == BROKER_SUBCOMMAND {
+        if string_args.len() != 2 {
+            return Err(Box::new(ArgumentsError::Validation(format!(
+                "The {} command requires the address to listen on: cargo fuzzcheck {} <ADDRESS>",
+                BROKER_SUBCOMMAND, BROKER_SUBCOMMAND
+            ))));
+        }
+        fuzzcheck_common::ensemble::run_broker(string_args[1].as_str())?;
+        return Ok(ExitCode::NoFailure);
+    }
+    if string_args[0] == TMIN_SUBCOMMAND {
+        if string_args.len() < 3 {
+            return Err(Box::new(ArgumentsError::Validation(format!(
+                "The {} command requires a fuzz test and the file to minify: cargo fuzzcheck {} <FUZZ_TEST> <FILE>",
+                TMIN_SUBCOMMAND, TMIN_SUBCOMMAND
+            ))));
+        }
+        let fuzz_test = string_args[1].clone();
+        let file = string_args[2].clone();
+        string_args.splice(
+            0..3,
+            [
+                fuzz_test,
+                format!("--{}", COMMAND_FLAG),
+                COMMAND_MINIFY_INPUT.to_owned(),
+                format!("--{}", INPUT_FILE_FLAG),
+                file,
+            ],
+        );
+    } else if string_args[0] == MERGE_SUBCOMMAND {
+        string_args = merge_subcommand_args(&string_args[1..])?;
+    } else if string_args[0] == RUN_CORPUS_SUBCOMMAND {
+        if string_args.len() < 2 {
+            return Err(Box::new(ArgumentsError::Validation(format!(
+                "The {} command requires a fuzz test: cargo fuzzcheck {} <FUZZ_TEST>",
+                RUN_CORPUS_SUBCOMMAND, RUN_CORPUS_SUBCOMMAND
+            ))));
+        }
+        let fuzz_test = string_args[1].clone();
+        string_args.splice(
+            0..2,
+            [fuzz_test, format!("--{}", COMMAND_FLAG), COMMAND_RUN_CORPUS.to_owned()],
+        );
+    } else if string_args[0] == COVERAGE_SUBCOMMAND {
+        if string_args.len() < 3 {
+            return Err(Box::new(ArgumentsError::Validation(format!(
+                "The {} command requires a fuzz test and the file to write the coverage report to: cargo fuzzcheck {} <FUZZ_TEST> <FILE>",
+                COVERAGE_SUBCOMMAND, COVERAGE_SUBCOMMAND
+            ))));
+        }
+        let fuzz_test = string_args[1].clone();
+        let file = string_args[2].clone();
+        string_args.splice(0..3, [fuzz_test, format!("--{}", COVERAGE_REPORT_FLAG), file]);
+    }
 
-    let matches = parser.parse(string_args.clone()).map_err(ArgumentsError::Parsing)?;
+    // the fuzz project and the fuzz test are needed to find the default options of the fuzz test, which are
+    // overridden by the command line and by the config file given to --config
+    let matches = parse_arguments_with_config(&parser, &string_args)?;
+    // the fuzz test runs in the fuzz project, so the paths given to it are made absolute
+    let current_dir = std::env::current_dir()?;
+    let fuzz_dir = fuzz_project_folder(&current_dir, matches.opt_str(FUZZ_DIR_FLAG).as_deref());
+    let defaults = if let Some(target_name) = matches.free.first() {
+        project_config(&fuzz_dir, target_name)?
+    } else {
+        None
+    };
+    let matches = parse_arguments_with_defaults(&parser, &string_args, defaults)?;
 
     let mut args = match Arguments::from_matches(&matches, true) {
         Ok(r) => r,
         Err(ArgumentsError::WantsHelp) => {
             println!("{}", help(&parser));
-            return Ok(());
+            return Ok(ExitCode::NoFailure);
         }
         Err(e) => {
             return Err(Box::new(e));
         }
     };
 
+    make_paths_absolute(&mut args, &current_dir);
+    check_ignored_panics(&args)?;
+
     // this won't crash because we `Arguments::from_matches` would have returned an error otherwise
     let target_name = &matches.free[0];
 
@@ -106,50 +242,245 @@ fn main() -> Result<(), Box<dyn Error>> {
         )));
     }
 
+    let minify_trace = matches.opt_str(MINIFY_TRACE_FLAG).map(PathBuf::from);
+    if minify_trace.is_some() && !matches!(args.command, FuzzerCommand::MinifyInput { .. }) {
+        return Err(Box::new(ArgumentsError::Validation(format!(
+            "--{} can only be used with the command {}.",
+            MINIFY_TRACE_FLAG, COMMAND_MINIFY_INPUT
+        ))));
+    }
+
+    let minify_stop = MinifyStopConditions {
+        runs: positive_number(&matches, RUNS_FLAG)?,
+        time: positive_number(&matches, TIME_FLAG)?.map(|secs| Duration::from_secs(secs as u64)),
+    };
+    if (minify_stop.runs.is_some() || minify_stop.time.is_some())
+        && !matches!(args.command, FuzzerCommand::MinifyInput { .. })
+    {
+        return Err(Box::new(ArgumentsError::Validation(format!(
+            "--{} and --{} can only be used with the command {}.",
+            RUNS_FLAG, TIME_FLAG, COMMAND_MINIFY_INPUT
+        ))));
+    }
+
+    let jobs = if let Some(jobs) = matches.opt_str(JOBS_FLAG) {
+        match jobs.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => jobs,
+            _ => {
+                return Err(Box::new(ArgumentsError::Validation(format!(
+                    "--{} must be a positive number.",
+                    JOBS_FLAG
+                ))));
+            }
+        }
+    } else {
+        1
+    };
+    if jobs > 1 && !matches!(args.command, FuzzerCommand::Fuzz) {
+        return Err(Box::new(ArgumentsError::Validation(format!(
+            "--{} can only be used when fuzzing.",
+            JOBS_FLAG
+        ))));
+    }
+
+    if args.target.is_none() {
+        args.target = Some(host_triple()?);
+    }
+
+    if !fuzz_dir.join("Cargo.toml").is_file() {
+        return Err(Box::new(ArgumentsError::Validation(format!(
+            "{} is not a valid fuzz project: it does not contain a Cargo.toml file.",
+            fuzz_dir.display()
+        ))));
+    }
+
     match args.command {
         FuzzerCommand::Fuzz => {
             if args.corpus_in.is_none() && matches.opt_present(NO_IN_CORPUS_FLAG) == false {
-                args.corpus_in = Some(PathBuf::new().join(format!("fuzz/{}/corpus", target_name)));
+                args.corpus_in = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
             }
             if args.corpus_out.is_none() && matches.opt_present(NO_OUT_CORPUS_FLAG) == false {
-                args.corpus_out = Some(PathBuf::new().join(format!("fuzz/{}/corpus", target_name)));
+                args.corpus_out = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
             }
             if args.artifacts_folder.is_none() && matches.opt_present(NO_ARTIFACTS_FLAG) == false {
-                args.artifacts_folder = Some(PathBuf::new().join(format!("fuzz/{}/artifacts", target_name)));
+                args.artifacts_folder = Some(fuzz_dir.join(format!("fuzz/{}/artifacts", target_name)));
             }
             if args.stats_folder.is_none() && matches.opt_present(NO_STATS_FLAG) == false {
-                args.stats_folder = Some(PathBuf::new().join(format!("fuzz/{}/stats", target_name)));
+                args.stats_folder = Some(fuzz_dir.join(format!("fuzz/{}/stats", target_name)));
             }
+        }
+        FuzzerCommand::WriteCoverage { .. }
+        | FuzzerCommand::AssertCoverage { .. }
+        | FuzzerCommand::CoverageMap { .. }
+        | FuzzerCommand::CoverageReport { .. }
+        | FuzzerCommand::BisectCorpus => {
+            if args.corpus_in.is_none() && matches.opt_present(NO_IN_CORPUS_FLAG) == false {
+                args.corpus_in = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
+            }
+        }
+        FuzzerCommand::RunCorpus => {
+            if args.corpus_in.is_none() && matches.opt_present(NO_IN_CORPUS_FLAG) == false {
+                args.corpus_in = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
+            }
+            if args.artifacts_folder.is_none() && matches.opt_present(NO_ARTIFACTS_FLAG) == false {
+                args.artifacts_folder = Some(fuzz_dir.join(format!("fuzz/{}/artifacts", target_name)));
+            }
+        }
+        FuzzerCommand::MinifyCorpus => {
+            if args.corpus_in.is_none() {
+                args.corpus_in = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
+            }
+            // the input corpus is minified in place by default
+            if args.corpus_out.is_none() && matches.opt_present(NO_OUT_CORPUS_FLAG) == false {
+                args.corpus_out = args.corpus_in.clone();
+            }
+        }
+        FuzzerCommand::Merge { .. } => {
+            if args.corpus_out.is_none() {
+                args.corpus_out = Some(fuzz_dir.join(format!("fuzz/{}/corpus", target_name)));
+            }
+        }
+        FuzzerCommand::MinifyInput { .. } | FuzzerCommand::Read { .. } => {}
+    }
+
+    let resolved_config = args.to_toml();
+    println!("{}", resolved_config);
+    if let Some(dump_path) = matches.opt_str(DUMP_CONFIG_FLAG) {
+        std::fs::write(&dump_path, &resolved_config)?;
+    }
+
+    if matches!(args.command, FuzzerCommand::Fuzz) && args.results_file.is_none() {
+        args.results_file = Some(fuzz_dir.join(default_results_file(target_name)));
+    }
+
+    let exit_code = match args.command {
+        FuzzerCommand::Fuzz if jobs > 1 => launch_workers(
+            target_name,
+            &args,
+            &fuzz_dir,
+            if instrument_only_root { name_exec } else { None },
+            &compiled_target,
+            &cargo_args,
+            jobs,
+        )?,
+        FuzzerCommand::Fuzz => {
             let exec = launch_executable(
                 target_name,
                 &args,
+                &fuzz_dir,
                 if instrument_only_root { name_exec } else { None },
                 &compiled_target,
                 &cargo_args,
                 &process::Stdio::inherit,
             )?;
-            exec.wait_with_output()?;
+            let output = exec.wait_with_output()?;
+            fuzz_exit_code(&args, output.status)
         }
         FuzzerCommand::MinifyInput { .. } => {
             input_minify_command(
                 target_name,
                 &args,
+                &fuzz_dir,
                 &compiled_target,
                 &cargo_args,
+                minify_trace.as_deref(),
+                minify_stop,
                 &process::Stdio::inherit,
             )?;
+            ExitCode::NoFailure
         }
-        FuzzerCommand::Read { .. } => {
+        FuzzerCommand::WriteCoverage { .. }
+        | FuzzerCommand::AssertCoverage { .. }
+        | FuzzerCommand::CoverageMap { .. }
+        | FuzzerCommand::CoverageReport { .. }
+        | FuzzerCommand::BisectCorpus
+        | FuzzerCommand::Merge { .. }
+        | FuzzerCommand::RunCorpus
+        | FuzzerCommand::MinifyCorpus
+        | FuzzerCommand::Read { .. } => {
             let exec = launch_executable(
                 target_name,
                 &args,
+                &fuzz_dir,
                 if instrument_only_root { name_exec } else { None },
                 &compiled_target,
                 &cargo_args,
                 &process::Stdio::inherit,
             )?;
-            exec.wait_with_output()?;
+            let output = exec.wait_with_output()?;
+            if output.status.success() {
+                ExitCode::NoFailure
+            } else {
+                ExitCode::FailureFound
+            }
         }
+    };
+    Ok(exit_code)
+}
+
+/// The value of the given option, which must be a positive number if it is present
+fn positive_number(matches: &getopts::Matches, flag: &str) -> Result<Option<usize>, ArgumentsError> {
+    if let Some(value) = matches.opt_str(flag) {
+        match value.parse::<usize>() {
+            Ok(value) if value > 0 => Ok(Some(value)),
+            _ => Err(ArgumentsError::Validation(format!(
+                "--{} must be a positive number.",
+                flag
+            ))),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Translate the arguments following `cargo fuzzcheck merge` into the ones of the command merge.
+///
+/// The fuzz test comes first. It is followed by `--into <DIR>` and by the corpora to merge into `<DIR>`, in any
+/// order, and then by the other options.
+fn merge_subcommand_args(args: &[String]) -> Result<Vec<String>, ArgumentsError> {
+    let usage = || {
+        ArgumentsError::Validation(format!(
+            "The {} command requires a fuzz test, a destination corpus, and the corpora to merge into it: cargo fuzzcheck {} <FUZZ_TEST> --{} <DIR> <CORPUS>...",
+            MERGE_SUBCOMMAND, MERGE_SUBCOMMAND, INTO_FLAG
+        ))
+    };
+    let fuzz_test = args.first().ok_or_else(usage)?;
+    let into_flag = format!("--{}", INTO_FLAG);
+    let into_prefix = format!("--{}=", INTO_FLAG);
+    let mut into = None;
+    let mut corpora = vec![];
+    let mut idx = 1;
+    while idx < args.len() {
+        let arg = &args[idx];
+        if *arg == into_flag {
+            into = Some(args.get(idx + 1).ok_or_else(usage)?.clone());
+            idx += 2;
+        } else if let Some(dir) = arg.strip_prefix(&into_prefix) {
+            into = Some(dir.to_owned());
+            idx += 1;
+        } else if arg.starts_with('-') {
+            break;
+        } else {
+            corpora.push(arg.clone());
+            idx += 1;
+        }
+    }
+    let into = into.ok_or_else(usage)?;
+    if corpora.is_empty() {
+        return Err(usage());
+    }
+
+    let mut merge_args = vec![
+        fuzz_test.clone(),
+        format!("--{}", COMMAND_FLAG),
+        COMMAND_MERGE.to_owned(),
+        format!("--{}", OUT_CORPUS_FLAG),
+        into,
+    ];
+    for corpus in corpora {
+        merge_args.push(format!("--{}", MERGE_FROM_FLAG));
+        merge_args.push(corpus);
     }
-    Ok(())
+    merge_args.extend_from_slice(&args[idx..]);
+    Ok(merge_args)
 }
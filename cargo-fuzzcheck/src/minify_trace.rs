@@ -0,0 +1,305 @@
+//! A record of the successive reductions of a failing test case during `cargo fuzzcheck <target> minify`.
+//!
+//! The trace is written as a Graphviz `.dot` file if its path has the `dot` extension, and as JSON otherwise.
+//! At the end of the minification, it is also written as a JSON report that shows what changed in the content of the
+//! test case at each step.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+struct Step {
+    file: PathBuf,
+    cplx: f64,
+}
+
+/// The successive simplest failing test cases found while minifying an input, from the most complex to the simplest
+pub struct MinifyTrace {
+    steps: Vec<Step>,
+}
+
+impl MinifyTrace {
+    /// Start a trace whose first step is `file`, of complexity `cplx`
+    pub fn new(file: PathBuf, cplx: f64) -> Self {
+        Self {
+            steps: vec![Step { file, cplx }],
+        }
+    }
+
+    /// Add the given artifacts that are simpler than the last step of the trace, from the most complex to the simplest.
+    ///
+    /// Returns true if a step was added.
+    pub fn record(&mut self, artifacts: &[(PathBuf, f64)]) -> bool {
+        let mut artifacts = artifacts
+            .iter()
+            .filter(|(_, cplx)| *cplx < self.steps.last().unwrap().cplx)
+            .collect::<Vec<_>>();
+        artifacts.sort_by(|x, y| y.1.partial_cmp(&x.1).unwrap_or(std::cmp::Ordering::Equal));
+        for (file, cplx) in artifacts.iter() {
+            self.steps.push(Step {
+                file: file.clone(),
+                cplx: *cplx,
+            });
+        }
+        !artifacts.is_empty()
+    }
+
+    /// Write the trace to `path`, as a Graphviz file if its extension is `dot` and as JSON otherwise
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let content = if path.extension().map(|ext| ext == "dot").unwrap_or(false) {
+            self.to_dot()
+        } else {
+            self.to_json()
+        };
+        std::fs::write(path, content)
+    }
+
+    fn to_json(&self) -> String {
+        let mut s = String::new();
+        s.push_str("{\n  \"steps\": [");
+        for (i, step) in self.steps.iter().enumerate() {
+            if i != 0 {
+                s.push(',');
+            }
+            s.push_str("\n    { \"file\": ");
+            write_json_string(&mut s, &step.file.display().to_string());
+            write!(s, ", \"complexity\": {:.2}", step.cplx).unwrap();
+            if i != 0 {
+                write!(
+                    s,
+                    ", \"parent\": {}, \"complexity_drop\": {:.2}",
+                    i - 1,
+                    self.steps[i - 1].cplx - step.cplx
+                )
+                .unwrap();
+            }
+            s.push_str(" }");
+        }
+        s.push_str("\n  ]\n}\n");
+        s
+    }
+
+    /// Write a JSON report of the trace to `path`, which contains the difference between the content of each step
+    /// and the content of the previous one.
+    ///
+    /// The files of the steps must still exist.
+    pub fn write_report(&self, path: &Path) -> std::io::Result<()> {
+        let contents = self
+            .steps
+            .iter()
+            .map(|step| std::fs::read(&step.file))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        std::fs::write(path, self.to_report_json(&contents))
+    }
+
+    /// The number of reductions of the input, which is the number of steps after the first one
+    pub fn nbr_reductions(&self) -> usize {
+        self.steps.len() - 1
+    }
+
+    fn to_report_json(&self, contents: &[Vec<u8>]) -> String {
+        let mut s = String::new();
+        s.push_str("{\n  \"steps\": [");
+        for (i, (step, content)) in self.steps.iter().zip(contents.iter()).enumerate() {
+            if i != 0 {
+                s.push(',');
+            }
+            s.push_str("\n    {\n      \"file\": ");
+            write_json_string(&mut s, &step.file.display().to_string());
+            write!(s, ",\n      \"complexity\": {:.2}", step.cplx).unwrap();
+            s.push_str(",\n      \"content\": ");
+            write_json_string(&mut s, &String::from_utf8_lossy(content));
+            if i != 0 {
+                write!(
+                    s,
+                    ",\n      \"complexity_drop\": {:.2}",
+                    self.steps[i - 1].cplx - step.cplx
+                )
+                .unwrap();
+                s.push_str(",\n      \"diff\": [");
+                let previous = String::from_utf8_lossy(&contents[i - 1]);
+                let current = String::from_utf8_lossy(content);
+                for (j, hunk) in diff(&previous, &current).iter().enumerate() {
+                    if j != 0 {
+                        s.push(',');
+                    }
+                    write!(s, "\n        {{ \"offset\": {}, \"removed\": ", hunk.offset).unwrap();
+                    write_json_string(&mut s, &hunk.removed);
+                    s.push_str(", \"added\": ");
+                    write_json_string(&mut s, &hunk.added);
+                    s.push_str(" }");
+                }
+                s.push_str("\n      ]");
+            }
+            s.push_str("\n    }");
+        }
+        s.push_str("\n  ]\n}\n");
+        s
+    }
+
+    fn to_dot(&self) -> String {
+        let mut s = String::new();
+        s.push_str("digraph minify {\n    node [shape=box];\n");
+        for (i, step) in self.steps.iter().enumerate() {
+            let name = step
+                .file
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            writeln!(
+                s,
+                "    n{} [label=\"{}\\ncomplexity: {:.2}\"];",
+                i,
+                name.replace('\\', "\\\\").replace('"', "\\\""),
+                step.cplx
+            )
+            .unwrap();
+            if i != 0 {
+                writeln!(
+                    s,
+                    "    n{} -> n{} [label=\"-{:.2}\"];",
+                    i - 1,
+                    i,
+                    self.steps[i - 1].cplx - step.cplx
+                )
+                .unwrap();
+            }
+        }
+        s.push_str("}\n");
+        s
+    }
+}
+
+/// A part of a test case that was replaced in the next step of the minification
+#[derive(Debug, PartialEq)]
+struct Hunk {
+    /// The position of the replaced part in the previous content, in characters
+    offset: usize,
+    removed: String,
+    added: String,
+}
+
+/// Beyond this size of the table of longest common subsequences, the difference between two contents is given as a
+/// single hunk spanning from their first to their last differing characters
+const MAX_DIFF_TABLE_SIZE: usize = 4_000_000;
+
+/// The parts of `previous` that must be replaced to obtain `current`
+fn diff(previous: &str, current: &str) -> Vec<Hunk> {
+    let a = previous.chars().collect::<Vec<_>>();
+    let b = current.chars().collect::<Vec<_>>();
+    let prefix = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    if a.is_empty() && b.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a.len(), b.len());
+    if (n + 1) * (m + 1) > MAX_DIFF_TABLE_SIZE {
+        return vec![Hunk {
+            offset: prefix,
+            removed: a.iter().collect(),
+            added: b.iter().collect(),
+        }];
+    }
+    // lcs[i * (m + 1) + j] is the length of the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * (m + 1) + j] = if a[i] == b[j] {
+                lcs[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                std::cmp::max(lcs[(i + 1) * (m + 1) + j], lcs[i * (m + 1) + j + 1])
+            };
+        }
+    }
+    let mut hunks = vec![];
+    let mut hunk: Option<Hunk> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a[i] == b[j] {
+            hunks.extend(hunk.take());
+            i += 1;
+            j += 1;
+        } else {
+            let hunk = hunk.get_or_insert_with(|| Hunk {
+                offset: prefix + i,
+                removed: String::new(),
+                added: String::new(),
+            });
+            if j == m || (i < n && lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1]) {
+                hunk.removed.push(a[i]);
+                i += 1;
+            } else {
+                hunk.added.push(b[j]);
+                j += 1;
+            }
+        }
+    }
+    hunks.extend(hunk);
+    hunks
+}
+
+fn write_json_string(s: &mut String, value: &str) {
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(s, "\\u{:04x}", c as u32).unwrap(),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Hunk, MinifyTrace};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_minify_trace() {
+        let mut trace = MinifyTrace::new(PathBuf::from("a.minified/5000--a.json"), 50.0);
+        assert!(!trace.record(&[(PathBuf::from("a.minified/5000--a.json"), 50.0)]));
+        assert!(trace.record(&[
+            (PathBuf::from("a.minified/1200--c.json"), 12.0),
+            (PathBuf::from("a.minified/5000--a.json"), 50.0),
+            (PathBuf::from("a.minified/3000--b.json"), 30.0),
+        ]));
+        let cplxs = trace.steps.iter().map(|step| step.cplx).collect::<Vec<_>>();
+        assert_eq!(cplxs, vec![50.0, 30.0, 12.0]);
+
+        let json = trace.to_json();
+        assert!(json.contains(
+            r#"{ "file": "a.minified/1200--c.json", "complexity": 12.00, "parent": 1, "complexity_drop": 18.00 }"#
+        ));
+        let dot = trace.to_dot();
+        assert!(dot.contains("n0 -> n1 [label=\"-20.00\"];"));
+
+        let report = trace.to_report_json(&[b"[1,2,3,4]".to_vec(), b"[1,3,4]".to_vec(), b"[3]".to_vec()]);
+        assert!(report.contains(r#""content": "[1,3,4]""#));
+        assert!(report.contains(r#"{ "offset": 3, "removed": "2,", "added": "" }"#));
+        assert!(!report.contains(r#""diff": [],"#));
+    }
+
+    #[test]
+    fn test_minify_diff() {
+        let hunk = |offset: usize, removed: &str, added: &str| Hunk {
+            offset,
+            removed: removed.to_string(),
+            added: added.to_string(),
+        };
+        assert_eq!(diff("abc", "abc"), vec![]);
+        assert_eq!(diff("[1,2,3]", "[1,3]"), vec![hunk(3, "2,", "")]);
+        assert_eq!(diff("[1,2,3,4]", "[1,3]"), vec![hunk(3, "2,", ""), hunk(6, ",4", "")]);
+        assert_eq!(diff("\"hello\"", "\"ha\""), vec![hunk(2, "ello", "a")]);
+        assert_eq!(diff("", "x"), vec![hunk(0, "", "x")]);
+    }
+}
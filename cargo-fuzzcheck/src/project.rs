@@ -0,0 +1,85 @@
+//! The default options of the fuzz tests of a project, read from its `fuzz/fuzzcheck.toml` file.
+//!
+//! The file is written like the ones given to `--config`, with an additional table for each fuzz test whose
+//! options differ from the ones at the top of the file:
+//! ```toml
+//! max-cplx = 4096
+//! stop-after-duration = 3600
+//!
+//! ["tests::parse_request"]
+//! max-cplx = 1024
+//! in-corpus = "fuzz/requests"
+//! dictionary = "fuzz/http.dict"
+//! hang-threshold = 500
+//! rustflags = ["-Zsanitizer=address"]
+//! ```
+//! Its options have a lower precedence than the ones of the command line and of the file given to `--config`.
+//! Unlike the paths given on the command line, which are relative to the current directory, its paths are relative
+//! to the folder of the fuzz project.
+
+use fuzzcheck_common::arg::*;
+use fuzzcheck_common::config::{parse_target_config, ConfigValue};
+use std::path::{Path, PathBuf};
+
+/// The path of the file containing the default options of the fuzz tests, relative to the folder of the fuzz project
+pub const PROJECT_CONFIG_FILE: &str = "fuzz/fuzzcheck.toml";
+
+/// The options whose values are paths
+const PATH_FLAGS: [&str; 14] = [
+    INPUT_FILE_FLAG,
+    IN_CORPUS_FLAG,
+    OUT_CORPUS_FLAG,
+    ARTIFACTS_FLAG,
+    STATS_FLAG,
+    WRITE_COVERAGE_FLAG,
+    ASSERT_COVERAGE_FLAG,
+    COVERAGE_MAP_FLAG,
+    COVERAGE_REPORT_FLAG,
+    RESUME_FLAG,
+    SAVE_STATE_FLAG,
+    RESULTS_FILE_FLAG,
+    DICTIONARY_FLAG,
+    MERGE_FROM_FLAG,
+];
+
+/// The path of a config file along with the options it gives to a fuzz test
+pub type TargetConfig = (PathBuf, Vec<(String, ConfigValue)>);
+
+/// Read the default options of the fuzz test `target_name` from the [`PROJECT_CONFIG_FILE`] of `fuzz_dir`.
+///
+/// Returns the path of the file along with the options, or `None` if the project does not have such a file.
+/// The relative paths of the options are joined to `fuzz_dir`.
+pub fn project_config(fuzz_dir: &Path, target_name: &str) -> Result<Option<TargetConfig>, ArgumentsError> {
+    let path = fuzz_dir.join(PROJECT_CONFIG_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        ArgumentsError::Validation(format!("The config file {} could not be read: {}", path.display(), e))
+    })?;
+    let config = parse_target_config(&content, target_name)
+        .map_err(|e| ArgumentsError::Validation(format!("The config file {} is invalid: {}", path.display(), e)))?;
+    let config = config
+        .into_iter()
+        .map(|(key, value)| {
+            let value = if PATH_FLAGS.contains(&key.as_str()) {
+                relative_to(fuzz_dir, value)
+            } else {
+                value
+            };
+            (key, value)
+        })
+        .collect();
+    Ok(Some((path, config)))
+}
+
+/// Join the path, or paths, of the value of an option to `folder`
+fn relative_to(folder: &Path, value: ConfigValue) -> ConfigValue {
+    match value {
+        ConfigValue::String(path) => ConfigValue::String(folder.join(path).display().to_string()),
+        ConfigValue::Array(values) => {
+            ConfigValue::Array(values.into_iter().map(|value| relative_to(folder, value)).collect())
+        }
+        value => value,
+    }
+}
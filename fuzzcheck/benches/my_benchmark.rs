@@ -1,4 +1,6 @@
 extern crate fuzzcheck;
+use fuzzcheck::mutators::cached_complexity::CachedComplexityMutator;
+use fuzzcheck::mutators::map::MapMutator;
 use fuzzcheck::{mutators::integer::U8Mutator, mutators::vector::VecMutator, Mutator};
 
 use criterion::{criterion_group, criterion_main, Criterion};
@@ -26,6 +28,32 @@ fn criterion_benchmark(c: &mut Criterion) {
             })
         },
     );
+    // a deep value whose complexity is computed by visiting all of its elements
+    let deep_vector = vec![vec![vec![1u8, 2, 3]; 20]; 20];
+    let mapped_mutator = || {
+        MapMutator::new(
+            VecMutator::new(
+                VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), 0..=usize::MAX),
+                0..=usize::MAX,
+            ),
+            |v: &Vec<Vec<Vec<u8>>>| Some(v.clone()),
+            |v: &Vec<Vec<Vec<u8>>>| v.clone(),
+            |v: &Vec<Vec<Vec<u8>>>, _cplx| v.iter().flatten().flatten().count() as f64,
+        )
+    };
+    c.bench_function("Vec<Vec<Vec<u8>>> complexity computed by a MapMutator", |b| {
+        let m = mapped_mutator();
+        let cache = m.validate_value(&deep_vector).unwrap();
+        b.iter(|| m.complexity(&deep_vector, &cache))
+    });
+    c.bench_function(
+        "Vec<Vec<Vec<u8>>> complexity computed by a MapMutator, cached by CachedComplexityMutator",
+        |b| {
+            let m = CachedComplexityMutator::new(mapped_mutator());
+            let cache = m.validate_value(&deep_vector).unwrap();
+            b.iter(|| m.complexity(&deep_vector, &cache))
+        },
+    );
 }
 
 criterion_group!(benches, criterion_benchmark);
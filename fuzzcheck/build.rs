@@ -1,6 +1,10 @@
 fn main() {
     let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
     let file_to_compile = match target_os.as_str() {
+        // the fuzz target is run by libFuzzer, without the coverage counters of -Cinstrument-coverage
+        _ if std::env::var_os("CARGO_FEATURE_LIBFUZZER").is_some() => {
+            "src/code_coverage_sensor/instrumentation_pointers_libfuzzer.c"
+        }
         "macos" | "ios" => "src/code_coverage_sensor/instrumentation_pointers_mac.c",
         "linux" => "src/code_coverage_sensor/instrumentation_pointers_linux.c",
         _ => panic!("fuzzcheck only work on macOS and Linux"),
@@ -59,22 +59,34 @@ is to use the [`SensorAndPoolBuilder`], although it only offers a couple limited
 */
 
 use crate::code_coverage_sensor::CodeCoverageSensor;
-use crate::fuzzer::{Fuzzer, FuzzingResult};
+use crate::dictionary::read_dictionary_file;
+use crate::fuzzer::{run_corpus_without_exiting, CrashInfo, Fuzzer, FuzzingResult, CURRENT_TEST_FUNCTION};
+use crate::sensors_and_pools::AllocationSensor;
 use crate::sensors_and_pools::MaximiseEachCounterPool;
 use crate::sensors_and_pools::MostNDiversePool;
+use crate::sensors_and_pools::RecencyBias;
 use crate::sensors_and_pools::SimplestToActivateCounterPool;
+use crate::sensors_and_pools::TargetCounterPool;
 use crate::sensors_and_pools::WrapperSensor;
-use crate::sensors_and_pools::{AndPool, SameObservations};
+use crate::sensors_and_pools::{AndPool, AndSensor, SameObservations};
+use crate::sensors_and_pools::{ComparisonDistancePool, ComparisonSensor};
 use crate::sensors_and_pools::{DifferentObservations, MaximiseObservationPool};
+use crate::sensors_and_pools::{ExecutionTimeSensor, FastestToExecutePool, MaximiseExecutionTimePool};
+use crate::sensors_and_pools::{MaximisedValuesSensor, MAX_MAXIMISED_VALUES};
 use crate::traits::{CompatibleWithObservations, Mutator, PoolExt, Sensor, SensorExt, Serializer};
+use crate::world::CorpusStorage;
 use crate::{split_string_by_whitespace, DefaultMutator};
 
 #[cfg(feature = "serde_json_serializer")]
 use crate::SerdeSerializer;
 
-use fuzzcheck_common::arg::{options_parser, ArgumentsError, COMMAND_FUZZ, COMMAND_MINIFY_INPUT, INPUT_FILE_FLAG};
+use fuzzcheck_common::arg::{
+    options_parser, parse_arguments_with_config, ARTIFACTS_FLAG, COMMAND_FLAG, COMMAND_FUZZ, COMMAND_MINIFY_INPUT,
+    COMMAND_RUN_CORPUS, INPUT_FILE_FLAG, IN_CORPUS_FLAG,
+};
 use fuzzcheck_common::arg::{Arguments, FuzzerCommand};
 use std::borrow::Borrow;
+use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::result::Result;
@@ -226,6 +238,11 @@ where
 /// * [`self.stop_after_iterations(..)`](FuzzerBuilder5::stop_after_iterations)
 /// * [`self.stop_after_duration(..)`](FuzzerBuilder5::stop_after_duration)
 /// * [`self.stop_after_first_test_failure(..)`](FuzzerBuilder5::stop_after_first_test_failure)
+/// * [`self.on_crash(..)`](FuzzerBuilder5::on_crash)
+/// * [`self.debug_failing_test_cases()`](FuzzerBuilder5::debug_failing_test_cases)
+/// * [`self.corpus_storage(..)`](FuzzerBuilder5::corpus_storage)
+///
+/// and seed the fuzzer with test cases written in code with [`self.initial_inputs(..)`](FuzzerBuilder5::initial_inputs).
 pub struct FuzzerBuilder5<F, M, V, Sens, P>
 where
     F: Fn(&V) -> bool + 'static,
@@ -240,6 +257,10 @@ where
     sensor: Sens,
     pool: P,
     pub arguments: Arguments,
+    on_crash: Option<Box<dyn FnMut(&CrashInfo)>>,
+    debug_value: Option<Box<dyn Fn(&V) -> String>>,
+    initial_inputs: Vec<V>,
+    corpus_storage: Option<Box<dyn CorpusStorage>>,
     _phantom: PhantomData<*const V>,
 }
 
@@ -268,6 +289,113 @@ where
     }
 }
 
+/**
+A list of named test functions taking the same argument, to fuzz-test them together with [`fuzz_tests`].
+
+Each function can be of any of the kinds accepted by [`fuzz_test`].
+```no_run
+# fn parse_json(x: &str) -> bool { true }
+# fn parse_toml(x: &str) {}
+use fuzzcheck::builder::TestFunctions;
+
+let _ = fuzzcheck::fuzz_tests(
+    TestFunctions::<str>::new()
+        .add("json", parse_json)
+        .add("toml", parse_toml),
+)
+.default_options()
+.launch();
+```
+*/
+pub struct TestFunctions<T>
+where
+    T: ?Sized + ToOwned,
+{
+    functions: Vec<(&'static str, Box<dyn Fn(&T::Owned) -> bool>)>,
+}
+
+impl<T> TestFunctions<T>
+where
+    T: ?Sized + ToOwned + 'static,
+    T::Owned: Clone,
+{
+    #[no_coverage]
+    pub fn new() -> Self {
+        Self { functions: vec![] }
+    }
+    /// Add a test function, identified by `name` in the description of its test failures
+    #[no_coverage]
+    pub fn add<F, TestFunctionKind>(self, name: &'static str, test_function: F) -> Self
+    where
+        F: FuzzTestFunction<T::Owned, T, TestFunctionKind>,
+        F::NormalizedFunction: 'static,
+    {
+        let mut x = self;
+        x.functions.push((name, Box::new(test_function.test_function())));
+        x
+    }
+}
+
+impl<T> Default for TestFunctions<T>
+where
+    T: ?Sized + ToOwned + 'static,
+    T::Owned: Clone,
+{
+    #[no_coverage]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+    Build a fuzz test for several functions taking the same argument, such as related parsers.
+
+    The returned value is a [`FuzzerBuilder1`], which is used in the same way as the one returned by [`fuzz_test`].
+
+    Each test case is given to every function, in the order in which they were added to the [`TestFunctions`],
+    until one of them fails. The functions therefore share the same mutator, serializer, and corpus: a test case
+    that is interesting to one function is kept and mutated to test all the others. The default sensor observes the
+    code coverage of all the functions, so a test case is kept if it reaches new code in any of them.
+
+    When a function fails, its name is part of the description of the test failure, and failures of different
+    functions are kept apart by the pool of test failures. To see the coverage reached in each function, write
+    the coverage map of the corpus with `--coverage-map`: it lists every instrumented function with the number
+    of test cases reaching it, which includes the test functions and the code they call.
+
+    ## Panics
+    Panics if `test_functions` is empty.
+*/
+#[no_coverage]
+pub fn fuzz_tests<T>(test_functions: TestFunctions<T>) -> FuzzerBuilder1<T::Owned, impl Fn(&T::Owned) -> bool>
+where
+    T: ?Sized + ToOwned + 'static,
+    T::Owned: Clone,
+{
+    assert!(
+        !test_functions.functions.is_empty(),
+        "at least one test function must be given to fuzz_tests"
+    );
+    let functions = test_functions.functions;
+    FuzzerBuilder1 {
+        test_function: #[no_coverage]
+        move |x: &T::Owned| {
+            for (name, test_function) in functions.iter() {
+                unsafe {
+                    CURRENT_TEST_FUNCTION = Some(*name);
+                }
+                if !test_function(x) {
+                    return false;
+                }
+            }
+            unsafe {
+                CURRENT_TEST_FUNCTION = None;
+            }
+            true
+        },
+        _phantom: PhantomData,
+    }
+}
+
 #[cfg(feature = "serde_json_serializer")]
 impl<T, F> FuzzerBuilder1<T, F>
 where
@@ -480,6 +608,10 @@ where
             sensor: self.sensor,
             pool: self.pool,
             arguments,
+            on_crash: None,
+            debug_value: None,
+            initial_inputs: vec![],
+            corpus_storage: None,
             _phantom: self._phantom,
         }
     }
@@ -522,7 +654,7 @@ fuzzcheck {minify} --{input_file} "artifacts/crash.json"
 
         let arguments = std::env::var("FUZZCHECK_ARGS").unwrap();
         let arguments = split_string_by_whitespace(&arguments);
-        let matches = parser.parse(arguments).map_err(ArgumentsError::from);
+        let matches = parse_arguments_with_config(&parser, &arguments);
         let arguments = match matches.and_then(
             #[no_coverage]
             |matches| Arguments::from_matches(&matches, false),
@@ -540,11 +672,71 @@ fuzzcheck {minify} --{input_file} "artifacts/crash.json"
             sensor: self.sensor,
             pool: self.pool,
             arguments,
+            on_crash: None,
+            debug_value: None,
+            initial_inputs: vec![],
+            corpus_storage: None,
             _phantom: PhantomData,
         }
     }
 }
 
+impl<F, M, V, Sens, P> FuzzerBuilder4<F, M, V, Sens, P>
+where
+    F: Fn(&V) -> bool + 'static,
+    V: Clone,
+    M: Mutator<V>,
+    Sens: Sensor + 'static,
+    P: CompatibleWithObservations<Sens::Observations> + 'static,
+    Fuzzer<V, M>: 'static,
+{
+    /// Fuzz the test function if it is launched by `cargo fuzzcheck`, or test it on its corpus otherwise.
+    ///
+    /// This is the test generated by the [`#[fuzz_test]`](macro@crate::fuzz_test) attribute. The fuzz test is
+    /// named after the path of the test function within its crate, which is `module_path` without the name of
+    /// the crate, followed by `function_name`.
+    ///
+    /// When it is launched by `cargo fuzzcheck`, it is fuzzed with the arguments given to `cargo fuzzcheck`.
+    /// Otherwise, it is a regular test, launched by `cargo test`, which runs the test function on each test case
+    /// of the `fuzz/<name>/corpus` and `fuzz/<name>/artifacts` folders written by `cargo fuzzcheck`, such that the
+    /// test failures found by the fuzzer become regression tests. The folders do not have to exist.
+    ///
+    /// In both cases, it panics if a test case fails.
+    #[no_coverage]
+    pub fn launch_or_run_corpus(self, module_path: &str, function_name: &str) {
+        // the name of the test given to `cargo fuzzcheck` does not contain the name of the crate
+        let name = match module_path.split_once("::") {
+            Some((_crate_name, module_path)) => format!("{}::{}", module_path, function_name),
+            None => function_name.to_owned(),
+        };
+        if std::env::var("FUZZCHECK_ARGS").is_ok() {
+            let result = self.arguments_from_cargo_fuzzcheck().launch();
+            assert!(
+                !result.found_test_failure,
+                "the fuzz test {} found a failing test case",
+                name
+            );
+        } else {
+            let folder = Path::new("fuzz").join(&name);
+            let arguments = [
+                format!("--{}", COMMAND_FLAG),
+                COMMAND_RUN_CORPUS.to_owned(),
+                format!("--{}", IN_CORPUS_FLAG),
+                folder.join("corpus").display().to_string(),
+                format!("--{}", ARTIFACTS_FLAG),
+                folder.join("artifacts").display().to_string(),
+            ];
+            let matches = options_parser()
+                .parse(arguments)
+                .expect("invalid arguments for run-corpus");
+            let arguments = Arguments::from_matches(&matches, false).expect("invalid arguments for run-corpus");
+            let passed =
+                run_corpus_without_exiting(&self.test_function, &self.mutator, self.serializer.as_ref(), arguments);
+            assert!(passed, "some test cases of the corpus of {} failed", name);
+        }
+    }
+}
+
 impl<F, M, V, Sens, P> FuzzerBuilder5<F, M, V, Sens, P>
 where
     F: Fn(&V) -> bool + 'static,
@@ -602,6 +794,116 @@ where
         x.arguments.stop_after_first_failure = stop_after_first_test_failure;
         x
     }
+    /// Save the test cases that take longer than `threshold` to run to the `slow` folder of the output corpus.
+    ///
+    /// Slow test cases are not test failures. See [`Arguments::slow_threshold`].
+    #[no_coverage]
+    pub fn slow_threshold(self, threshold: Duration) -> Self {
+        let mut x = self;
+        x.arguments.slow_threshold = Some(threshold);
+        x
+    }
+    /// Interrupt the test cases that take longer than `threshold` to run and treat them as crashes.
+    ///
+    /// See [`Arguments::hang_threshold`].
+    #[no_coverage]
+    pub fn hang_threshold(self, threshold: Duration) -> Self {
+        let mut x = self;
+        x.arguments.hang_threshold = Some(threshold);
+        x
+    }
+    /// Treat the test cases that make the resident set size of the fuzzer exceed `megabytes` as crashes.
+    ///
+    /// See [`Arguments::max_memory`].
+    #[no_coverage]
+    pub fn max_memory(self, megabytes: usize) -> Self {
+        let mut x = self;
+        x.arguments.max_memory = Some(megabytes);
+        x
+    }
+    /// Treat the test cases during which the test function holds more than `megabytes` of allocated memory as
+    /// test failures. The fuzz target must install a [`CountingAllocator`](crate::sensors_and_pools::CountingAllocator).
+    ///
+    /// See [`Arguments::malloc_limit`].
+    #[no_coverage]
+    pub fn malloc_limit(self, megabytes: usize) -> Self {
+        let mut x = self;
+        x.arguments.malloc_limit = Some(megabytes);
+        x
+    }
+    /// Call `callback` each time the fuzzer finds a crash in a new bucket, with a description of the crash.
+    ///
+    /// This lets a program that embeds the fuzzer react to each distinct crash as soon as it is found,
+    /// for example by filing a ticket, instead of parsing the output of the fuzzer or waiting for its
+    /// exit code. Crashes are the test failures found by the `fuzz` command (panics, and
+    /// test functions returning `false` or `Err(..)`) as well as hangs, fatal signals, and test cases exceeding
+    /// the maximum memory. Test failures
+    /// are bucketed by the identifier of their [`TestFailure`](crate::sensors_and_pools::TestFailure), so
+    /// the callback is only called once per bug, for the first failing test case found.
+    ///
+    /// The callback is called synchronously, on the thread running the fuzz test, from the fuzz loop, after
+    /// the failing test case was saved to the output corpus (or to the artifacts folder when the fuzzer stops
+    /// after the first test failure). Fuzzing resumes once it returns, so it should not block for long.
+    /// Because it is never called from another thread, it does not need to be `Send`. A hang or a fatal
+    /// signal is reported from within the signal handler, just before the process exits: the callback
+    /// must then not rely on locks that the test function may hold, and spawning another process or
+    /// writing a file is the safest way to hand the crash over.
+    #[no_coverage]
+    pub fn on_crash(self, callback: impl FnMut(&CrashInfo) + 'static) -> Self {
+        let mut x = self;
+        x.on_crash = Some(Box::new(callback));
+        x
+    }
+    /// Pretty-print each failing test case with its `Debug` implementation, in addition to serializing it.
+    ///
+    /// When a test case fails, its value is printed to stderr and written next to the artifact where it is saved,
+    /// in a file with the same name and the extension `.debug`. This makes it possible to understand a failing
+    /// test case without decoding its serialized form by hand, which is tedious for large nested values.
+    /// The failing test cases saved to the `test_failures` folder of the output corpus are only printed.
+    #[no_coverage]
+    pub fn debug_failing_test_cases(self) -> Self
+    where
+        V: Debug,
+    {
+        let mut x = self;
+        x.debug_value = Some(Box::new(
+            #[no_coverage]
+            |value: &V| format!("{:#?}", value),
+        ));
+        x
+    }
+    /// Test the given values before the test cases of the input corpus, as if they were its first files.
+    ///
+    /// This seeds the fuzzer with interesting test cases written in code, such as valid protocol messages,
+    /// without having to serialize them into the corpus folder beforehand. Like the test cases of the input
+    /// corpus, they are added to the pool, and thus to the output corpus, if they are interesting. The values
+    /// rejected by the mutator or more complex than the maximum complexity are skipped. Calling this method
+    /// several times adds to the list of initial inputs.
+    ///
+    /// The initial inputs are only used by the `fuzz` command.
+    /// ```no_run
+    /// # fn parse(message: &Vec<u8>) {}
+    /// let result = fuzzcheck::fuzz_test(parse)
+    ///     .default_options()
+    ///     .initial_inputs(vec![b"GET / HTTP/1.1\r\n\r\n".to_vec(), b"HEAD / HTTP/1.0\r\n\r\n".to_vec()])
+    ///     .launch();
+    /// ```
+    #[no_coverage]
+    pub fn initial_inputs(self, inputs: Vec<V>) -> Self {
+        let mut x = self;
+        x.initial_inputs.extend(inputs);
+        x
+    }
+    /// Save the test cases of the output corpus in the given storage instead of the `--out-corpus` folder.
+    ///
+    /// An [`InMemoryCorpusStorage`](crate::InMemoryCorpusStorage) keeps the corpus in memory, such that benchmarks
+    /// and tests of the fuzzer do not write to the disk. The storage is only used by the `fuzz` command.
+    #[no_coverage]
+    pub fn corpus_storage(self, storage: impl CorpusStorage + 'static) -> Self {
+        let mut x = self;
+        x.corpus_storage = Some(Box::new(storage));
+        x
+    }
     /// Launch the fuzz test!
     #[no_coverage]
     pub fn launch(self) -> FuzzingResult<V> {
@@ -618,6 +920,10 @@ where
             pool,
             sensor,
             arguments,
+            on_crash,
+            debug_value,
+            initial_inputs,
+            corpus_storage,
             _phantom,
         } = self;
 
@@ -627,11 +933,57 @@ where
             serializer,
             Box::new((sensor, pool)),
             arguments,
+            on_crash,
+            debug_value,
+            initial_inputs,
+            corpus_storage,
         )
     }
 }
 
+/**
+    Read the tokens of the dictionary file given to `cargo fuzzcheck --dictionary <PATH>`, if any.
+
+    The tokens are meant to be given to [`VecMutator::with_dictionary`](crate::mutators::vector::VecMutator::with_dictionary)
+    when creating the mutator of the fuzz test. The dictionary file is written in the format of libFuzzer and AFL,
+    with one double-quoted token per line, optionally preceded by a name, such as `kw1="Content-Length:"` or `"\x7fELF"`.
+
+    An empty list is returned if the fuzz test is not launched by `cargo fuzzcheck` or if no dictionary is given.
+    If the dictionary file cannot be read or is invalid, the error is printed and the process exits.
+*/
+#[no_coverage]
+pub fn dictionary_from_cargo_fuzzcheck() -> Vec<Vec<u8>> {
+    let arguments = if let Ok(arguments) = std::env::var("FUZZCHECK_ARGS") {
+        arguments
+    } else {
+        return vec![];
+    };
+    let arguments = split_string_by_whitespace(&arguments);
+    let dictionary = parse_arguments_with_config(&options_parser(), &arguments)
+        .and_then(
+            #[no_coverage]
+            |matches| Arguments::from_matches(&matches, false),
+        )
+        .ok()
+        .and_then(
+            #[no_coverage]
+            |arguments| arguments.dictionary,
+        );
+    if let Some(dictionary) = dictionary {
+        match read_dictionary_file(&dictionary) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                println!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        vec![]
+    }
+}
+
 pub type BasicSensor = CodeCoverageSensor;
+pub type BasicAndComparisonSensor = AndSensor<CodeCoverageSensor, ComparisonSensor>;
 pub type DiverseSensor = impl WrapperSensor<
     Wrapped = CodeCoverageSensor,
     Observations = (<CodeCoverageSensor as Sensor>::Observations, usize),
@@ -649,6 +1001,8 @@ pub type DiverseAndMaxHitsSensor =
     impl Sensor<Observations = (<CodeCoverageSensor as Sensor>::Observations, (usize, u64))>;
 
 pub type BasicPool = SimplestToActivateCounterPool;
+pub type BasicAndTargetPool = AndPool<SimplestToActivateCounterPool, TargetCounterPool, SameObservations>;
+pub type BasicAndComparisonPool = AndPool<SimplestToActivateCounterPool, ComparisonDistancePool, DifferentObservations>;
 pub type DiversePool = AndPool<MostNDiversePool, MaximiseObservationPool<u64>, DifferentObservations>;
 pub type MaxHitsPool = AndPool<MaximiseEachCounterPool, MaximiseObservationPool<u64>, DifferentObservations>;
 pub type BasicAndDiversePool = AndPool<
@@ -714,8 +1068,12 @@ pub fn basic_sensor_and_pool() -> SensorAndPoolBuilder<BasicSensor, BasicPool> {
 
 /// Create the [sensor and pool builder](SensorAndPoolBuilder) that is used by default by fuzzcheck
 ///
-/// Currently, the result cannot be augmented any further. Thus, the only action you can take on the result is to
-/// use [`.finish()`](SensorAndPoolBuilder::finish) to obtain the concrete sensor and pool.
+/// Currently, the result can only be augmented with
+/// [`.find_slowest_test_cases()`](SensorAndPoolBuilder::find_slowest_test_cases),
+/// [`.find_test_cases_allocating_the_most_memory()`](SensorAndPoolBuilder::find_test_cases_allocating_the_most_memory),
+/// [`.find_test_cases_maximising_values()`](SensorAndPoolBuilder::find_test_cases_maximising_values) or
+/// [`.prefer_fast_test_cases()`](SensorAndPoolBuilder::prefer_fast_test_cases). Then use
+/// [`.finish()`](SensorAndPoolBuilder::finish) to obtain the concrete sensor and pool.
 #[no_coverage]
 pub fn default_sensor_and_pool() -> SensorAndPoolBuilder<DiverseAndMaxHitsSensor, BasicAndDiverseAndMaxHitsPool> {
     basic_sensor_and_pool()
@@ -753,9 +1111,97 @@ where
     pub fn finish(self) -> (S, P) {
         (self.sensor, self.pool)
     }
+    /// Augment the current pool such that it also keeps the `size` test cases that take the longest to run, and
+    /// mutates them to find even slower ones. It reveals algorithmic complexity issues that do not cause any crash.
+    ///
+    /// The additional pool is a [`MaximiseExecutionTimePool`], whose test cases are saved in the `slowest_<size>`
+    /// folder of the output corpus.
+    #[no_coverage]
+    pub fn find_slowest_test_cases(
+        self,
+        size: usize,
+    ) -> SensorAndPoolBuilder<
+        AndSensor<S, ExecutionTimeSensor>,
+        AndPool<P, MaximiseExecutionTimePool, DifferentObservations>,
+    > {
+        SensorAndPoolBuilder {
+            sensor: AndSensor(self.sensor, ExecutionTimeSensor::default()),
+            pool: self.pool.and(
+                MaximiseExecutionTimePool::new(&format!("slowest_{}", size), size),
+                Some(0.1),
+                DifferentObservations,
+            ),
+        }
+    }
+    /// Augment the current pool such that it also tries to maximise each value given to the
+    /// [`maximise!`](crate::maximise) macro by the test function.
+    ///
+    /// The additional pool is a [`MaximiseEachCounterPool`] observing a [`MaximisedValuesSensor`], whose test cases
+    /// are saved in the `maximised_values` folder of the output corpus.
+    #[no_coverage]
+    pub fn find_test_cases_maximising_values(
+        self,
+    ) -> SensorAndPoolBuilder<
+        AndSensor<S, MaximisedValuesSensor>,
+        AndPool<P, MaximiseEachCounterPool, DifferentObservations>,
+    > {
+        SensorAndPoolBuilder {
+            sensor: AndSensor(self.sensor, MaximisedValuesSensor),
+            pool: self.pool.and(
+                MaximiseEachCounterPool::new("maximised_values", MAX_MAXIMISED_VALUES),
+                Some(1.0),
+                DifferentObservations,
+            ),
+        }
+    }
+    /// Measure the execution time of each test case and choose the fastest test cases of the pool more often,
+    /// such that slow test cases do not slow down the whole fuzz test.
+    ///
+    /// The pool is wrapped in a [`FastestToExecutePool`], which chooses the fastest of two candidates given by the
+    /// current pool.
+    #[no_coverage]
+    pub fn prefer_fast_test_cases(
+        self,
+    ) -> SensorAndPoolBuilder<AndSensor<S, ExecutionTimeSensor>, FastestToExecutePool<P>> {
+        SensorAndPoolBuilder {
+            sensor: AndSensor(self.sensor, ExecutionTimeSensor::default()),
+            pool: FastestToExecutePool::new(self.pool),
+        }
+    }
+    /// Augment the current pool such that it also keeps the test cases that allocate the most memory, and mutates
+    /// them to find test cases allocating even more. It reveals inputs that cause huge allocations before they
+    /// exhaust the memory of the machine, and works well with the `--malloc-limit` option.
+    ///
+    /// The fuzz target must install a [`CountingAllocator`](crate::sensors_and_pools::CountingAllocator) as its
+    /// global allocator. The additional pool is a [`MaximiseEachCounterPool`] observing the counters of an
+    /// [`AllocationSensor`], whose test cases are saved in the `allocations` folder of the output corpus.
+    #[no_coverage]
+    pub fn find_test_cases_allocating_the_most_memory(
+        self,
+    ) -> SensorAndPoolBuilder<AndSensor<S, AllocationSensor>, AndPool<P, MaximiseEachCounterPool, DifferentObservations>>
+    {
+        SensorAndPoolBuilder {
+            sensor: AndSensor(self.sensor, AllocationSensor::default()),
+            pool: self.pool.and(
+                MaximiseEachCounterPool::new("allocations", 2),
+                Some(0.1),
+                DifferentObservations,
+            ),
+        }
+    }
 }
 
 impl SensorAndPoolBuilder<BasicSensor, BasicPool> {
+    /// Choose the test cases that were recently added to the pool more often than the others.
+    ///
+    /// By default, there is no recency bias. See [`RecencyBias`] for a description of the decay schedule.
+    #[no_coverage]
+    pub fn with_recency_bias(self, recency_bias: RecencyBias) -> Self {
+        SensorAndPoolBuilder {
+            sensor: self.sensor,
+            pool: self.pool.with_recency_bias(recency_bias),
+        }
+    }
     /// Augment the current pool such that it also tries to find a fixed-length set of test cases which, together,
     /// trigger the most code coverage.
     ///
@@ -788,6 +1234,75 @@ impl SensorAndPoolBuilder<BasicSensor, BasicPool> {
             );
         SensorAndPoolBuilder { sensor, pool }
     }
+    /// Augment the current pool such that it also directs the fuzzer towards the coverage counter at index `target`.
+    ///
+    /// The additional pool is a [`TargetCounterPool`], which keeps the test cases that activate the counters
+    /// that are the closest to the target, as estimated by [`CodeCoverageSensor::distances_to_counter`].
+    /// The resulting pool cannot be augmented any further.
+    ///
+    /// ### Finding the index of the target counter
+    /// The counters are indexed in the same way for all the executions of the same fuzz test binary.
+    /// There are two ways to find the index of the counter guarding the code that you want to reach:
+    /// 1. the `coverage_sensor.json` file, which is written in the `stats` folder at the end of a fuzzing run
+    /// when the `serde_json_serializer` feature is enabled. It lists the counters of each function, along with
+    /// the lines and columns of their code regions. The index of a counter is given by its `id` field.
+    /// 2. the snapshot written by `cargo fuzzcheck <test> --in-corpus <corpus> --write-coverage coverage.txt`.
+    /// Each of its lines contains the index of a counter activated by the corpus, followed by the name of its
+    /// function. It is only useful to find counters that are close to the target, since the target itself is not
+    /// activated yet.
+    ///
+    /// ## Panics
+    /// Panics if `target` is not the index of a counter of the sensor.
+    ///
+    /// ```no_run
+    /// use fuzzcheck::builder::basic_sensor_and_pool;
+    ///
+    /// let (sensor, pool) = basic_sensor_and_pool()
+    ///     .find_test_cases_reaching_counter(1234)
+    ///     .finish();
+    /// ```
+    #[no_coverage]
+    pub fn find_test_cases_reaching_counter(
+        self,
+        target: usize,
+    ) -> SensorAndPoolBuilder<BasicSensor, BasicAndTargetPool> {
+        let distances = self.sensor.distances_to_counter(target);
+        let pool = self.pool.and(
+            TargetCounterPool::new(&format!("target_{}", target), distances),
+            Some(1.0),
+            SameObservations,
+        );
+        SensorAndPoolBuilder {
+            sensor: self.sensor,
+            pool,
+        }
+    }
+    /// Augment the current pool such that it also keeps the test cases that bring the operands of each comparison
+    /// made by the test function closer to each other, which helps solving the checks against magic numbers.
+    ///
+    /// The additional sensor is a [`ComparisonSensor`] and the additional pool is a [`ComparisonDistancePool`].
+    /// The comparisons are only observed if the fuzz test is launched with `cargo fuzzcheck --trace-compares`.
+    /// The resulting pool cannot be augmented any further.
+    ///
+    /// ```no_run
+    /// use fuzzcheck::builder::basic_sensor_and_pool;
+    ///
+    /// let (sensor, pool) = basic_sensor_and_pool()
+    ///     .find_test_cases_solving_comparisons()
+    ///     .finish();
+    /// ```
+    #[no_coverage]
+    pub fn find_test_cases_solving_comparisons(
+        self,
+    ) -> SensorAndPoolBuilder<BasicAndComparisonSensor, BasicAndComparisonPool> {
+        let sensor = AndSensor(self.sensor, ComparisonSensor::new());
+        let pool = self.pool.and(
+            ComparisonDistancePool::new("cmp_distance"),
+            Some(0.2),
+            DifferentObservations,
+        );
+        SensorAndPoolBuilder { sensor, pool }
+    }
     /// Augment the current pool such that it also tries to find test cases repeatedly hitting the same regions of code.
     #[no_coverage]
     pub fn find_test_cases_repeatedly_hitting_coverage_counters(
@@ -0,0 +1,226 @@
+//! An aggregate, per-function summary of the coverage of a whole corpus.
+//!
+//! For each instrumented function, the map records how many inputs of the corpus activate at least
+//! one of its counters, the sum of the hit counts of its counters over all inputs, and how many of its
+//! counters are activated by the corpus. Functions are grouped by the source file that defines them,
+//! and are sorted by decreasing total hit count within each file.
+//!
+//! The map is written as JSON, so that it can be given to flamegraph or treemap visualization tools.
+
+use super::CodeCoverageSensor;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FunctionCoverage {
+    name: String,
+    file: String,
+    nbr_counters: usize,
+    nbr_activated_counters: usize,
+    nbr_inputs: usize,
+    total_hits: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CorpusCoverageMap {
+    nbr_inputs: usize,
+    functions: Vec<FunctionCoverage>,
+    /// The index of the function of each counter of the sensor
+    function_of_counter: Vec<usize>,
+    activated_counters: Vec<bool>,
+    /// The number of inputs activating at least one counter of each file
+    file_inputs: BTreeMap<String, usize>,
+}
+
+impl CorpusCoverageMap {
+    #[no_coverage]
+    pub fn new(sensor: &CodeCoverageSensor) -> Self {
+        Self::from_functions(
+            sensor
+                .coverage
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |coverage| {
+                        let f_record = &coverage.function_record;
+                        let file = f_record
+                            .filenames
+                            .first()
+                            .map(
+                                #[no_coverage]
+                                |f| f.display().to_string(),
+                            )
+                            .unwrap_or_default();
+                        let nbr_counters = coverage.single_counters.len() + coverage.expression_counters.len();
+                        (f_record.name_function.clone(), file, nbr_counters)
+                    },
+                )
+                .collect(),
+        )
+    }
+
+    /// Create an empty map from the name, file, and number of counters of each function, in the order
+    /// in which their counters are indexed by the sensor.
+    #[no_coverage]
+    fn from_functions(functions: Vec<(String, String, usize)>) -> Self {
+        let mut function_of_counter = vec![];
+        let mut file_inputs = BTreeMap::new();
+        let functions = functions
+            .into_iter()
+            .enumerate()
+            .map(
+                #[no_coverage]
+                |(i, (name, file, nbr_counters))| {
+                    function_of_counter.extend(std::iter::repeat(i).take(nbr_counters));
+                    file_inputs.insert(file.clone(), 0);
+                    FunctionCoverage {
+                        name,
+                        file,
+                        nbr_counters,
+                        nbr_activated_counters: 0,
+                        nbr_inputs: 0,
+                        total_hits: 0,
+                    }
+                },
+            )
+            .collect();
+        let activated_counters = vec![false; function_of_counter.len()];
+        Self {
+            nbr_inputs: 0,
+            functions,
+            function_of_counter,
+            activated_counters,
+            file_inputs,
+        }
+    }
+
+    /// Add the observations of the code coverage sensor for one input of the corpus.
+    ///
+    /// The observations must be sorted by counter index, as returned by the sensor.
+    #[no_coverage]
+    pub fn add_observations(&mut self, observations: &[(usize, u64)]) {
+        self.nbr_inputs += 1;
+        let mut touched_functions: Vec<usize> = vec![];
+        for &(counter, hits) in observations {
+            let function_idx = self.function_of_counter[counter];
+            let function = &mut self.functions[function_idx];
+            function.total_hits += hits;
+            if !self.activated_counters[counter] {
+                self.activated_counters[counter] = true;
+                function.nbr_activated_counters += 1;
+            }
+            if touched_functions.last() != Some(&function_idx) {
+                touched_functions.push(function_idx);
+                function.nbr_inputs += 1;
+            }
+        }
+        let mut touched_files: Vec<&str> = vec![];
+        for function_idx in touched_functions {
+            let file = self.functions[function_idx].file.as_str();
+            if !touched_files.contains(&file) {
+                touched_files.push(file);
+            }
+        }
+        for file in touched_files {
+            *self.file_inputs.get_mut(file).unwrap() += 1;
+        }
+    }
+}
+
+#[no_coverage]
+pub(crate) fn write_json_string(s: &mut String, value: &str) {
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(s, "\\u{:04x}", c as u32).unwrap(),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+impl Display for CorpusCoverageMap {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut functions_by_file = BTreeMap::<&str, Vec<&FunctionCoverage>>::new();
+        for function in self.functions.iter() {
+            functions_by_file.entry(&function.file).or_default().push(function);
+        }
+        let mut s = String::new();
+        write!(s, "{{\n  \"nbr_inputs\": {},\n  \"files\": [", self.nbr_inputs)?;
+        for (i, (file, functions)) in functions_by_file.iter_mut().enumerate() {
+            functions.sort_by(
+                #[no_coverage]
+                |a, b| b.total_hits.cmp(&a.total_hits).then(a.name.cmp(&b.name)),
+            );
+            let total_hits: u64 = functions
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |f| f.total_hits,
+                )
+                .sum();
+            if i != 0 {
+                s.push(',');
+            }
+            s.push_str("\n    {\n      \"file\": ");
+            write_json_string(&mut s, file);
+            write!(
+                s,
+                ",\n      \"nbr_inputs\": {},\n      \"total_hits\": {},\n      \"functions\": [",
+                self.file_inputs[*file], total_hits
+            )?;
+            for (j, function) in functions.iter().enumerate() {
+                if j != 0 {
+                    s.push(',');
+                }
+                s.push_str("\n        { \"name\": ");
+                write_json_string(&mut s, &function.name);
+                write!(
+                    s,
+                    ", \"nbr_inputs\": {}, \"total_hits\": {}, \"nbr_counters\": {}, \"nbr_activated_counters\": {} }}",
+                    function.nbr_inputs, function.total_hits, function.nbr_counters, function.nbr_activated_counters
+                )?;
+            }
+            s.push_str("\n      ]\n    }");
+        }
+        s.push_str("\n  ]\n}\n");
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CorpusCoverageMap;
+
+    #[test]
+    #[no_coverage]
+    fn test_corpus_coverage_map() {
+        let mut map = CorpusCoverageMap::from_functions(vec![
+            ("foo".to_owned(), "src/a.rs".to_owned(), 2),
+            ("bar".to_owned(), "src/a.rs".to_owned(), 1),
+            ("baz".to_owned(), "src/\"b\".rs".to_owned(), 3),
+        ]);
+        map.add_observations(&[(0, 1), (1, 4), (2, 2)]);
+        map.add_observations(&[(1, 1), (5, 10)]);
+        map.add_observations(&[]);
+
+        assert_eq!(map.nbr_inputs, 3);
+        let [foo, bar, baz] = [&map.functions[0], &map.functions[1], &map.functions[2]];
+        assert_eq!((foo.nbr_inputs, foo.total_hits, foo.nbr_activated_counters), (2, 6, 2));
+        assert_eq!((bar.nbr_inputs, bar.total_hits, bar.nbr_activated_counters), (1, 2, 1));
+        assert_eq!((baz.nbr_inputs, baz.total_hits, baz.nbr_activated_counters), (1, 10, 1));
+        assert_eq!(map.file_inputs["src/a.rs"], 2);
+        assert_eq!(map.file_inputs["src/\"b\".rs"], 1);
+
+        let json = map.to_string();
+        assert!(json.contains(r#""file": "src/\"b\".rs""#));
+        // functions are sorted by decreasing total hit count
+        assert!(json.find(r#""name": "foo""#).unwrap() < json.find(r#""name": "bar""#).unwrap());
+    }
+}
@@ -0,0 +1,152 @@
+//! A static estimate of the distance between the counters of the code coverage sensor and a target counter.
+//!
+//! Fuzzcheck does not have access to the call graph of the program, so the distance is based
+//! on the location of the counters in the source code only. A counter is considered close to
+//! the target if it belongs to the same function, and then the same file, and if its code region
+//! starts a few lines away from the target’s code region.
+
+use super::CodeCoverageSensor;
+use std::path::Path;
+
+/// The distance added to the counters that are in the same file as the target but in a different function
+const OTHER_FUNCTION_DISTANCE: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CounterLocation {
+    function: usize,
+    /// The index of the file of the function, if it is known
+    file: Option<usize>,
+    /// The first line of the code regions of the counter
+    line: Option<usize>,
+}
+
+impl CodeCoverageSensor {
+    /// Estimate the distance between each counter of the sensor and the `target` counter.
+    ///
+    /// * the target counter is at distance `0`
+    /// * a counter of the same function is at distance `1 + d`, where `d` is the number of lines separating
+    /// the start of its code region from the start of the target’s code region
+    /// * a counter of another function of the same file is at distance `1000 + d`
+    /// * the counters of the other files are not given a distance
+    ///
+    /// ## Panics
+    /// Panics if `target` is not smaller than [`self.count_instrumented`](CodeCoverageSensor::count_instrumented).
+    #[no_coverage]
+    pub fn distances_to_counter(&self, target: usize) -> Vec<Option<u64>> {
+        assert!(
+            target < self.count_instrumented,
+            "the target counter {} does not exist, the sensor only has {} counters",
+            target,
+            self.count_instrumented
+        );
+        distances(&self.counter_locations(), target)
+    }
+
+    /// The location of each counter of the sensor, in the order in which they are indexed by the sensor
+    #[no_coverage]
+    fn counter_locations(&self) -> Vec<CounterLocation> {
+        let mut files: Vec<&Path> = vec![];
+        let mut locations = vec![];
+        for (function, coverage) in self.coverage.iter().enumerate() {
+            let f_record = &coverage.function_record;
+            let file = f_record.filenames.first().map(
+                #[no_coverage]
+                |path| {
+                    if let Some(idx) = files.iter().position(
+                        #[no_coverage]
+                        |f| *f == path.as_path(),
+                    ) {
+                        idx
+                    } else {
+                        files.push(path);
+                        files.len() - 1
+                    }
+                },
+            );
+            // the counters are indexed in the same order as in `Coverage::new`: the single counters
+            // first, and then the expression counters
+            let single_counters = f_record.expressions.iter().filter(
+                #[no_coverage]
+                |(e, _)| e.add_terms.len() == 1 && e.sub_terms.is_empty(),
+            );
+            let expression_counters = f_record.expressions.iter().filter(
+                #[no_coverage]
+                |(e, _)| !(e.add_terms.len() == 1 && e.sub_terms.is_empty()) && !e.add_terms.is_empty(),
+            );
+            for (_, regions) in single_counters.chain(expression_counters) {
+                let line = regions
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |region| region.line_start,
+                    )
+                    .min();
+                locations.push(CounterLocation { function, file, line });
+            }
+        }
+        locations
+    }
+}
+
+#[no_coverage]
+fn distances(locations: &[CounterLocation], target: usize) -> Vec<Option<u64>> {
+    let target_location = locations[target];
+    locations
+        .iter()
+        .enumerate()
+        .map(
+            #[no_coverage]
+            |(i, location)| {
+                if i == target {
+                    return Some(0);
+                }
+                if location.file.is_none() || location.file != target_location.file {
+                    return None;
+                }
+                let lines = match (location.line, target_location.line) {
+                    (Some(a), Some(b)) => a.abs_diff(b) as u64,
+                    _ => 0,
+                };
+                if location.function == target_location.function {
+                    Some(1 + lines)
+                } else {
+                    Some(OTHER_FUNCTION_DISTANCE + lines)
+                }
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distances, CounterLocation};
+
+    #[test]
+    #[no_coverage]
+    fn test_distances() {
+        let locations = [
+            CounterLocation {
+                function: 0,
+                file: Some(0),
+                line: Some(10),
+            },
+            CounterLocation {
+                function: 0,
+                file: Some(0),
+                line: Some(14),
+            },
+            CounterLocation {
+                function: 1,
+                file: Some(0),
+                line: Some(30),
+            },
+            CounterLocation {
+                function: 2,
+                file: Some(1),
+                line: Some(14),
+            },
+        ];
+        assert_eq!(distances(&locations, 1), vec![Some(5), Some(0), Some(1016), None]);
+        assert_eq!(distances(&locations, 3), vec![None, None, None, Some(0)]);
+    }
+}
@@ -1,16 +1,25 @@
 //! Code coverage analysis
 
+mod corpus_map;
+mod distance;
 mod leb128;
 mod llvm_coverage;
 #[cfg(feature = "serde_json_serializer")]
 mod serialized;
+mod snapshot;
+mod source_report;
+
+pub(crate) use corpus_map::{write_json_string, CorpusCoverageMap};
+pub(crate) use llvm_coverage::get_counters;
+pub(crate) use snapshot::CoverageSnapshot;
+pub(crate) use source_report::CoverageReport;
 
 use crate::traits::{SaveToStatsFolder, Sensor};
 use std::convert::TryFrom;
 use std::path::Path;
 use std::{collections::HashMap, path::PathBuf};
 
-use self::llvm_coverage::{get_counters, get_prf_data, read_covmap, Coverage, LLVMCovSections};
+use self::llvm_coverage::{get_prf_data, read_covmap, Coverage, LLVMCovSections};
 
 /// A sensor that automatically records the code coverage of the program through an array of counters.
 ///
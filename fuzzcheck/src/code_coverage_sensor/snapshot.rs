@@ -0,0 +1,99 @@
+//! A plain-text snapshot of the coverage counters activated by a corpus.
+//!
+//! Each line of the snapshot contains the index of an activated counter followed by the
+//! name of the function it belongs to, separated by a tab character. The index is the `id` of
+//! the counter in the `coverage_sensor.json` file of the stats folder, where its source regions
+//! can be looked up.
+//!
+//! That file, written by [`save_to_stats_folder`](crate::SaveToStatsFolder::save_to_stats_folder),
+//! is not reused as the snapshot: it describes every counter of the sensor rather than the ones
+//! activated by the corpus, it is only available with the `serde_json_serializer` feature, and a
+//! single line of JSON cannot be diffed or reviewed when the snapshot is committed alongside the
+//! code. One line per counter keeps the changes to the coverage readable in a diff.
+
+use super::CodeCoverageSensor;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct CoverageSnapshot {
+    counters: BTreeMap<usize, String>,
+}
+
+impl CoverageSnapshot {
+    #[no_coverage]
+    pub fn new(sensor: &CodeCoverageSensor, activated: &BTreeSet<usize>) -> Self {
+        let mut counters = BTreeMap::new();
+        let mut index = 0;
+        for coverage in sensor.coverage.iter() {
+            let nbr_counters = coverage.single_counters.len() + coverage.expression_counters.len();
+            for counter in index..index + nbr_counters {
+                if activated.contains(&counter) {
+                    counters.insert(counter, coverage.function_record.name_function.clone());
+                }
+            }
+            index += nbr_counters;
+        }
+        Self { counters }
+    }
+
+    #[no_coverage]
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut counters = BTreeMap::new();
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (index, name) = line.split_once('\t')?;
+            let index = index.parse::<usize>().ok()?;
+            counters.insert(index, name.to_owned());
+        }
+        Some(Self { counters })
+    }
+
+    /// Returns the lines describing the counters that are only activated in `self` (prefixed with `+`)
+    /// and those that are only activated in `expected` (prefixed with `-`).
+    #[no_coverage]
+    pub fn diff(&self, expected: &Self) -> Vec<String> {
+        let mut lines = vec![];
+        for (index, name) in expected.counters.iter() {
+            if !self.counters.contains_key(index) {
+                lines.push(format!("- {}\t{}", index, name));
+            }
+        }
+        for (index, name) in self.counters.iter() {
+            if !expected.counters.contains_key(index) {
+                lines.push(format!("+ {}\t{}", index, name));
+            }
+        }
+        lines
+    }
+}
+
+impl Display for CoverageSnapshot {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, name) in self.counters.iter() {
+            writeln!(f, "{}\t{}", index, name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoverageSnapshot;
+
+    #[test]
+    #[no_coverage]
+    fn test_parse_and_diff() {
+        let a = CoverageSnapshot::parse("1\tfoo\n2\tfoo\n7\tbar\n").unwrap();
+        assert_eq!(CoverageSnapshot::parse(&a.to_string()), Some(a.clone()));
+
+        let b = CoverageSnapshot::parse("1\tfoo\n7\tbar\n9\tbaz\n").unwrap();
+        assert_eq!(b.diff(&a), vec!["- 2\tfoo".to_owned(), "+ 9\tbaz".to_owned()]);
+        assert!(a.diff(&a).is_empty());
+
+        assert!(CoverageSnapshot::parse("not a snapshot").is_none());
+    }
+}
@@ -0,0 +1,411 @@
+//! A report of the code coverage of a whole corpus, mapped back to the lines of the source files.
+//!
+//! Each counter of the code coverage sensor covers one or more regions of the source code. The report sums
+//! the hit counts of each counter over all the inputs of the corpus. Like in `llvm-cov`, a line is then given
+//! the greatest hit count among the regions that start on it and the innermost region that spans it from an
+//! earlier line.
+//!
+//! The report can be written in the lcov tracefile format, understood by `genhtml` and by most editors and
+//! CI services, or as JSON. The JSON report also lists the hit count of each region, so that the branches
+//! that the corpus never takes can be found even when they share a line with code that it runs.
+
+use super::corpus_map::write_json_string;
+use super::CodeCoverageSensor;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Region {
+    file: usize,
+    lines: (usize, usize),
+    cols: (usize, usize),
+}
+
+impl Region {
+    /// A key such that a region nested in another one has a smaller key
+    #[no_coverage]
+    fn size(&self) -> (usize, usize) {
+        (
+            self.lines.1.saturating_sub(self.lines.0),
+            self.cols.1.saturating_sub(self.cols.0),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Function {
+    name: String,
+    file: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Counter {
+    function: usize,
+    regions: Vec<Region>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CoverageReport {
+    nbr_inputs: usize,
+    files: Vec<String>,
+    functions: Vec<Function>,
+    /// The function and the regions of each counter of the sensor
+    counters: Vec<Counter>,
+    /// The sum of the hit counts of each counter over all inputs
+    hits: Vec<u64>,
+}
+
+impl CoverageReport {
+    #[no_coverage]
+    pub fn new(sensor: &CodeCoverageSensor) -> Self {
+        #[no_coverage]
+        fn file_index(files: &mut Vec<String>, file_indices: &mut HashMap<String, usize>, file: String) -> usize {
+            *file_indices.entry(file).or_insert_with_key(
+                #[no_coverage]
+                |file| {
+                    files.push(file.clone());
+                    files.len() - 1
+                },
+            )
+        }
+        let mut file_indices = HashMap::new();
+        let mut files = vec![];
+        let mut functions = vec![];
+        let mut counters = vec![];
+        for coverage in sensor.coverage.iter() {
+            let f_record = &coverage.function_record;
+            let function = functions.len();
+            let function_file = f_record
+                .filenames
+                .first()
+                .map(
+                    #[no_coverage]
+                    |f| f.display().to_string(),
+                )
+                .unwrap_or_default();
+            functions.push(Function {
+                name: f_record.name_function.clone(),
+                file: file_index(&mut files, &mut file_indices, function_file),
+            });
+            // the sensor indexes the single counters of the function first, then its expressions
+            let singles = f_record.expressions.iter().filter(
+                #[no_coverage]
+                |(e, _)| e.add_terms.len() == 1 && e.sub_terms.is_empty(),
+            );
+            let expressions = f_record.expressions.iter().filter(
+                #[no_coverage]
+                |(e, _)| e.add_terms.len() > 1 || (e.add_terms.len() == 1 && !e.sub_terms.is_empty()),
+            );
+            for (_, regions) in singles.chain(expressions) {
+                let regions = regions
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |region| {
+                            let file = f_record
+                                .file_id_mapping
+                                .filename_indices
+                                .iter()
+                                .position(
+                                    #[no_coverage]
+                                    |idx| *idx == region.filename_index,
+                                )
+                                .and_then(
+                                    #[no_coverage]
+                                    |idx| f_record.filenames.get(idx),
+                                )
+                                .map(
+                                    #[no_coverage]
+                                    |f| f.display().to_string(),
+                                )
+                                .unwrap_or_default();
+                            Region {
+                                file: file_index(&mut files, &mut file_indices, file),
+                                lines: (region.line_start, region.line_end),
+                                cols: (region.col_start, region.col_end),
+                            }
+                        },
+                    )
+                    .collect();
+                counters.push(Counter { function, regions });
+            }
+        }
+        Self::from_counters(files, functions, counters)
+    }
+
+    #[no_coverage]
+    fn from_counters(files: Vec<String>, functions: Vec<Function>, counters: Vec<Counter>) -> Self {
+        let hits = vec![0; counters.len()];
+        Self {
+            nbr_inputs: 0,
+            files,
+            functions,
+            counters,
+            hits,
+        }
+    }
+
+    /// Add the observations of the code coverage sensor for one input of the corpus.
+    #[no_coverage]
+    pub fn add_observations(&mut self, observations: &[(usize, u64)]) {
+        self.nbr_inputs += 1;
+        for &(counter, hits) in observations {
+            self.hits[counter] += hits;
+        }
+    }
+
+    /// The hit count of each line of each file that contains at least one region
+    #[no_coverage]
+    fn line_hits(&self) -> BTreeMap<&str, BTreeMap<usize, u64>> {
+        #[derive(Default)]
+        struct LineHits {
+            /// the greatest hit count of the regions starting on the line
+            starting: u64,
+            /// the size and hit count of the innermost region spanning the line from an earlier line
+            wrapped: Option<((usize, usize), u64)>,
+        }
+        let mut lines_by_file = BTreeMap::<&str, BTreeMap<usize, LineHits>>::new();
+        for (counter, &hits) in self.counters.iter().zip(self.hits.iter()) {
+            for region in counter.regions.iter() {
+                let lines = lines_by_file.entry(&self.files[region.file]).or_default();
+                for line in region.lines.0..=region.lines.1.max(region.lines.0) {
+                    let line_hits = lines.entry(line).or_default();
+                    if line == region.lines.0 {
+                        line_hits.starting = line_hits.starting.max(hits);
+                    } else {
+                        let size = region.size();
+                        if !matches!(line_hits.wrapped, Some((s, _)) if s <= size) {
+                            line_hits.wrapped = Some((size, hits));
+                        }
+                    }
+                }
+            }
+        }
+        lines_by_file
+            .into_iter()
+            .map(
+                #[no_coverage]
+                |(file, lines)| {
+                    let lines = lines
+                        .into_iter()
+                        .map(
+                            #[no_coverage]
+                            |(line, line_hits)| {
+                                let wrapped = line_hits.wrapped.map_or(
+                                    0,
+                                    #[no_coverage]
+                                    |(_, hits)| hits,
+                                );
+                                (line, line_hits.starting.max(wrapped))
+                            },
+                        )
+                        .collect();
+                    (file, lines)
+                },
+            )
+            .collect()
+    }
+
+    /// The first line and the hit count of each function, which are the ones of its outermost region
+    #[no_coverage]
+    fn function_entries(&self) -> Vec<Option<(usize, u64)>> {
+        let mut entries: Vec<Option<(Region, u64)>> = vec![None; self.functions.len()];
+        for (counter, &hits) in self.counters.iter().zip(self.hits.iter()) {
+            let function = &self.functions[counter.function];
+            for region in counter.regions.iter().filter(
+                #[no_coverage]
+                |region| region.file == function.file,
+            ) {
+                let entry = &mut entries[counter.function];
+                if !matches!(entry, Some((r, _)) if (r.lines.0, r.cols.0) <= (region.lines.0, region.cols.0)) {
+                    *entry = Some((*region, hits));
+                }
+            }
+        }
+        entries
+            .into_iter()
+            .map(
+                #[no_coverage]
+                |entry| {
+                    entry.map(
+                        #[no_coverage]
+                        |(region, hits)| (region.lines.0, hits),
+                    )
+                },
+            )
+            .collect()
+    }
+
+    /// The report in the lcov tracefile format
+    #[no_coverage]
+    pub fn to_lcov(&self) -> String {
+        let entries = self.function_entries();
+        let mut s = String::new();
+        for (file, lines) in self.line_hits() {
+            s.push_str("TN:\n");
+            writeln!(s, "SF:{}", file).unwrap();
+            let functions = self
+                .functions
+                .iter()
+                .zip(entries.iter())
+                .filter_map(
+                    #[no_coverage]
+                    |(function, entry)| match entry {
+                        Some((line, hits)) if self.files[function.file] == file => {
+                            Some((function.name.as_str(), *line, *hits))
+                        }
+                        _ => None,
+                    },
+                )
+                .collect::<Vec<_>>();
+            for (name, line, _) in functions.iter() {
+                writeln!(s, "FN:{},{}", line, name).unwrap();
+            }
+            for (name, _, hits) in functions.iter() {
+                writeln!(s, "FNDA:{},{}", hits, name).unwrap();
+            }
+            writeln!(s, "FNF:{}", functions.len()).unwrap();
+            writeln!(
+                s,
+                "FNH:{}",
+                functions
+                    .iter()
+                    .filter(
+                        #[no_coverage]
+                        |(_, _, hits)| *hits > 0
+                    )
+                    .count()
+            )
+            .unwrap();
+            for (line, hits) in lines.iter() {
+                writeln!(s, "DA:{},{}", line, hits).unwrap();
+            }
+            writeln!(s, "LF:{}", lines.len()).unwrap();
+            writeln!(
+                s,
+                "LH:{}",
+                lines
+                    .values()
+                    .filter(
+                        #[no_coverage]
+                        |hits| **hits > 0
+                    )
+                    .count()
+            )
+            .unwrap();
+            s.push_str("end_of_record\n");
+        }
+        s
+    }
+
+    /// The report as JSON, with the hit count of each line and of each region, grouped by file
+    #[no_coverage]
+    pub fn to_json(&self) -> String {
+        let mut regions_by_file = BTreeMap::<&str, Vec<(&Region, &str, u64)>>::new();
+        for (counter, &hits) in self.counters.iter().zip(self.hits.iter()) {
+            let name = self.functions[counter.function].name.as_str();
+            for region in counter.regions.iter() {
+                regions_by_file
+                    .entry(&self.files[region.file])
+                    .or_default()
+                    .push((region, name, hits));
+            }
+        }
+        let mut s = String::new();
+        write!(s, "{{\n  \"nbr_inputs\": {},\n  \"files\": [", self.nbr_inputs).unwrap();
+        for (i, (file, lines)) in self.line_hits().into_iter().enumerate() {
+            if i != 0 {
+                s.push(',');
+            }
+            s.push_str("\n    {\n      \"file\": ");
+            write_json_string(&mut s, file);
+            s.push_str(",\n      \"lines\": [");
+            for (j, (line, hits)) in lines.iter().enumerate() {
+                if j != 0 {
+                    s.push_str(", ");
+                }
+                write!(s, "[{}, {}]", line, hits).unwrap();
+            }
+            s.push_str("],\n      \"regions\": [");
+            let regions = regions_by_file.get_mut(file).unwrap();
+            regions.sort_by_key(
+                #[no_coverage]
+                |(region, _, _)| (region.lines.0, region.cols.0, region.lines.1, region.cols.1),
+            );
+            for (j, (region, name, hits)) in regions.iter().enumerate() {
+                if j != 0 {
+                    s.push(',');
+                }
+                write!(
+                    s,
+                    "\n        {{ \"lines\": [{}, {}], \"cols\": [{}, {}], \"hits\": {}, \"function\": ",
+                    region.lines.0, region.lines.1, region.cols.0, region.cols.1, hits
+                )
+                .unwrap();
+                write_json_string(&mut s, name);
+                s.push_str(" }");
+            }
+            s.push_str("\n      ]\n    }");
+        }
+        s.push_str("\n  ]\n}\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, CoverageReport, Function, Region};
+
+    #[test]
+    #[no_coverage]
+    fn test_coverage_report() {
+        let region = |lines: (usize, usize), cols: (usize, usize)| Region { file: 0, lines, cols };
+        // fn parse(x: u8) {        // line 1
+        //     if x == 0 {          // line 2
+        //         return;          // line 3
+        //     }                    // line 4
+        //     x.checked_add(1);    // line 5
+        // }                        // line 6
+        let mut report = CoverageReport::from_counters(
+            vec!["src/parser.rs".to_owned()],
+            vec![Function {
+                name: "parse".to_owned(),
+                file: 0,
+            }],
+            vec![
+                Counter {
+                    function: 0,
+                    regions: vec![region((1, 6), (1, 2))],
+                },
+                Counter {
+                    function: 0,
+                    regions: vec![region((2, 4), (17, 6))],
+                },
+                Counter {
+                    function: 0,
+                    regions: vec![region((5, 5), (5, 22))],
+                },
+            ],
+        );
+        report.add_observations(&[(0, 1), (2, 1)]);
+        report.add_observations(&[(0, 2), (2, 2)]);
+
+        let lines = report.line_hits();
+        let lines = &lines["src/parser.rs"];
+        assert_eq!(lines.get(&1), Some(&3));
+        assert_eq!(lines.get(&2), Some(&3));
+        // the branch is never taken
+        assert_eq!(lines.get(&3), Some(&0));
+        assert_eq!(lines.get(&5), Some(&3));
+        assert_eq!(lines.get(&6), Some(&3));
+
+        let lcov = report.to_lcov();
+        assert!(lcov.starts_with("TN:\nSF:src/parser.rs\nFN:1,parse\nFNDA:3,parse\nFNF:1\nFNH:1\n"));
+        assert!(lcov.contains("DA:3,0\n"));
+        assert!(lcov.ends_with("LF:6\nLH:4\nend_of_record\n"));
+
+        let json = report.to_json();
+        assert!(json.contains(r#"[1, 3], [2, 3], [3, 0], [4, 0], [5, 3], [6, 3]"#));
+        assert!(json.contains(r#"{ "lines": [2, 4], "cols": [17, 6], "hits": 0, "function": "parse" }"#));
+    }
+}
@@ -262,6 +262,11 @@ impl<T> RcSlab<T> {
             self.available_slots.sort_unstable();
         }
     }
+    /// The number of references to the element at the given key
+    #[no_coverage]
+    pub fn ref_count(&self, key: usize) -> usize {
+        self.storage[key].ref_count
+    }
     #[no_coverage]
     pub fn next_slot(&self) -> usize {
         if let Some(&slot) = self.available_slots.last() {
@@ -305,3 +310,122 @@ impl<T> RcSlab<T> {
             .filter(move |i| !self.available_slots.contains(i))
     }
 }
+
+// ========= Slowest inputs ============
+
+/// A test case that took a long time to run, as recorded by [`SlowestInputs`]
+#[derive(Clone, Debug)]
+pub struct SlowInput {
+    pub duration: std::time::Duration,
+    pub cplx: f64,
+    /// The hash of the serialized test case, which is also its file name in the corpus
+    pub name: String,
+}
+impl PartialEq for SlowInput {
+    #[no_coverage]
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration
+    }
+}
+impl Eq for SlowInput {}
+impl PartialOrd for SlowInput {
+    #[no_coverage]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for SlowInput {
+    #[no_coverage]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.duration.cmp(&other.duration)
+    }
+}
+
+/// Keeps track of the `capacity` slowest test cases that were given to it.
+pub struct SlowestInputs {
+    capacity: usize,
+    /// A min-heap, so that the fastest of the slowest inputs can be replaced quickly
+    heap: std::collections::BinaryHeap<std::cmp::Reverse<SlowInput>>,
+}
+
+impl SlowestInputs {
+    #[no_coverage]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: std::collections::BinaryHeap::with_capacity(capacity + 1),
+        }
+    }
+
+    /// Returns true if a test case that took `duration` to run would be recorded by [`insert`](Self::insert).
+    ///
+    /// It is cheap to call and should be used to avoid computing the name of a test case that
+    /// will not be recorded.
+    #[no_coverage]
+    pub fn accepts(&self, duration: std::time::Duration) -> bool {
+        if self.heap.len() < self.capacity {
+            return self.capacity > 0;
+        }
+        self.heap.peek().map_or(
+            false,
+            #[no_coverage]
+            |fastest| duration > fastest.0.duration,
+        )
+    }
+
+    #[no_coverage]
+    pub fn insert(&mut self, input: SlowInput) {
+        if !self.accepts(input.duration) {
+            return;
+        }
+        self.heap.push(std::cmp::Reverse(input));
+        if self.heap.len() > self.capacity {
+            self.heap.pop();
+        }
+    }
+
+    /// The recorded test cases, from the slowest to the fastest
+    #[no_coverage]
+    pub fn sorted(&self) -> Vec<SlowInput> {
+        let mut inputs = self
+            .heap
+            .iter()
+            .map(
+                #[no_coverage]
+                |x| x.0.clone(),
+            )
+            .collect::<Vec<_>>();
+        inputs.sort_by(
+            #[no_coverage]
+            |a, b| b.cmp(a),
+        );
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    #[no_coverage]
+    fn test_slowest_inputs() {
+        let mut slowest = SlowestInputs::new(3);
+        for (i, millis) in [5, 1, 9, 3, 7, 2].into_iter().enumerate() {
+            slowest.insert(SlowInput {
+                duration: Duration::from_millis(millis),
+                cplx: i as f64,
+                name: format!("{}", i),
+            });
+        }
+        let durations = slowest
+            .sorted()
+            .into_iter()
+            .map(|x| x.duration.as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(durations, vec![9, 7, 5]);
+        assert!(!slowest.accepts(Duration::from_millis(4)));
+        assert!(slowest.accepts(Duration::from_millis(6)));
+    }
+}
@@ -0,0 +1,129 @@
+//! Parsing of the dictionary files of libFuzzer and AFL, given to `cargo fuzzcheck --dictionary`
+
+use std::path::Path;
+
+/// Read the tokens of a dictionary file
+#[no_coverage]
+pub(crate) fn read_dictionary_file(path: &Path) -> Result<Vec<Vec<u8>>, String> {
+    let content = std::fs::read_to_string(path).map_err(
+        #[no_coverage]
+        |e| format!("could not read the dictionary file {}: {}", path.display(), e),
+    )?;
+    parse_dictionary(&content).map_err(
+        #[no_coverage]
+        |e| format!("invalid dictionary file {}: {}", path.display(), e),
+    )
+}
+
+/// Parse the tokens of a dictionary, which are written one per line, such as:
+/// ```text
+/// # a comment
+/// kw1="Content-Length:"
+/// "\x7fELF"
+/// ```
+/// Each token is a double-quoted string, optionally preceded by a name and `=`.
+/// In the string, `\\`, `\"`, and `\xNN` are escaped characters.
+#[no_coverage]
+pub(crate) fn parse_dictionary(content: &str) -> Result<Vec<Vec<u8>>, String> {
+    let mut tokens = vec![];
+    for (line_idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let token = parse_token(line).ok_or_else(
+            #[no_coverage]
+            || format!("line {} is not a valid token: {}", line_idx + 1, line),
+        )?;
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+#[no_coverage]
+fn parse_token(line: &str) -> Option<Vec<u8>> {
+    let quoted = if line.starts_with('"') {
+        line
+    } else {
+        let (name, quoted) = line.split_once('=')?;
+        let name = name.trim_end();
+        // the name may be followed by a level, such as kw@1, which is ignored
+        let name = name.split_once('@').map_or(
+            name,
+            #[no_coverage]
+            |(name, _level)| name,
+        );
+        if name.is_empty()
+            || !name.chars().all(
+                #[no_coverage]
+                |c| c.is_ascii_alphanumeric() || c == '_',
+            )
+        {
+            return None;
+        }
+        quoted.trim_start()
+    };
+    let content = quoted.strip_prefix('"')?.strip_suffix('"')?.as_bytes();
+
+    let mut token = Vec::with_capacity(content.len());
+    let mut i = 0;
+    while i < content.len() {
+        match content[i] {
+            b'\\' => {
+                match content.get(i + 1)? {
+                    b'\\' => token.push(b'\\'),
+                    b'"' => token.push(b'"'),
+                    b'x' => {
+                        let hex = std::str::from_utf8(content.get(i + 2..i + 4)?).ok()?;
+                        token.push(u8::from_str_radix(hex, 16).ok()?);
+                        i += 2;
+                    }
+                    _ => return None,
+                }
+                i += 2;
+            }
+            // an unescaped quote ends the token too early
+            b'"' => return None,
+            byte => {
+                token.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dictionary;
+
+    #[test]
+    fn test_parse_dictionary() {
+        let content = r#"
+# a comment
+kw1="Content-Length:"
+kw2@1 = "a\"b\\c"
+"\x7fELF"
+""
+"#;
+        let tokens = parse_dictionary(content).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                b"Content-Length:".to_vec(),
+                b"a\"b\\c".to_vec(),
+                b"\x7fELF".to_vec(),
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_dictionary() {
+        assert!(parse_dictionary("kw1=Content-Length:").is_err());
+        assert!(parse_dictionary("\"abc").is_err());
+        assert!(parse_dictionary("\"a\"b\"").is_err());
+        assert!(parse_dictionary("\"\\x7\"").is_err());
+        assert!(parse_dictionary("kw-1=\"abc\"").is_err());
+    }
+}
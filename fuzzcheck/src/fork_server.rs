@@ -0,0 +1,464 @@
+//! Run the test function in a child process, with `--fork N`, such that memory corruption or an `abort()` in the
+//! tested code does not destroy the state of the fuzzer.
+//!
+//! The child process is forked from the fuzzer, and tests up to `N` test cases before it exits and is replaced by a
+//! new one. Each test case is serialized and sent to the child process through a pipe. The child process runs the
+//! test function, copies the code coverage counters to memory shared with the fuzzer, and sends the outcome of the
+//! test back through another pipe. The fuzzer then copies the counters into its own, such that its sensors observe
+//! the code coverage as if the test function had run in its own process.
+//!
+//! If the child process dies while testing a test case, the fuzzer learns how from its wait status.
+
+use std::fs::File;
+use std::io::{self, Read, Result, Write};
+use std::os::unix::io::FromRawFd;
+use std::time::Duration;
+
+use crate::code_coverage_sensor::get_counters;
+use crate::sensors_and_pools::{Allocations, TestFailure};
+
+/// How the test function returned in the child process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChildResult {
+    /// The test function returned `true`
+    Passed = 0,
+    /// The test function returned `false`
+    Failed = 1,
+    /// The test function panicked
+    Panicked = 2,
+    /// The test function panicked with a message matching a pattern given to `--ignore-panic`
+    IgnoredPanic = 3,
+}
+
+/// The outcome of a test case in the child process
+#[derive(Debug, Clone)]
+pub(crate) struct ChildOutcome {
+    pub result: ChildResult,
+    /// The failure recorded by the panic hook, if the test function panicked
+    pub failure: Option<TestFailure>,
+    /// The average duration of a run of the test function
+    pub duration: Duration,
+    /// The peak memory usage of the child process, in megabytes
+    pub peak_memory: usize,
+    /// The memory allocated by the test function, as counted by the `CountingAllocator`
+    pub allocations: Allocations,
+    /// The non-zero values given to the `maximise!` macro, with the location of the call
+    pub maximised_values: Vec<(String, u64)>,
+}
+
+/// The outcome of a test case sent to the child process
+#[derive(Debug, Clone)]
+pub(crate) enum ForkedRun {
+    /// The child process tested the test case and sent back its outcome
+    Completed(ChildOutcome),
+    /// The child process was killed by the given signal, such as `SIGSEGV`, or `SIGALRM` for a hang
+    Signaled(i32),
+    /// The child process exited with the given code, for example because the tested code called `exit`
+    Exited(i32),
+}
+
+struct Child {
+    pid: libc::pid_t,
+    to_child: File,
+    from_child: File,
+    nbr_tested: usize,
+}
+
+/// The memory shared between the fuzzer and its child processes, where the child process copies its code coverage
+/// counters after each test case
+struct SharedCounters {
+    ptr: *mut u64,
+    len: usize,
+}
+
+impl SharedCounters {
+    #[no_coverage]
+    fn new(len: usize) -> Result<Self> {
+        // mmap does not accept an empty mapping
+        let size = std::mem::size_of::<u64>() * len.max(1);
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            ptr: ptr as *mut u64,
+            len,
+        })
+    }
+
+    #[no_coverage]
+    fn as_mut_slice(&mut self) -> &mut [u64] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for SharedCounters {
+    #[no_coverage]
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(
+                self.ptr as *mut libc::c_void,
+                std::mem::size_of::<u64>() * self.len.max(1),
+            );
+        }
+    }
+}
+
+pub(crate) struct ForkServer {
+    /// The number of test cases tested by a child process before it is replaced
+    batch_size: usize,
+    child: Option<Child>,
+    counters: SharedCounters,
+}
+
+impl ForkServer {
+    #[no_coverage]
+    pub fn new(batch_size: usize) -> Result<Self> {
+        let nbr_counters = unsafe { get_counters() }.len();
+        Ok(Self {
+            batch_size,
+            child: None,
+            counters: SharedCounters::new(nbr_counters)?,
+        })
+    }
+
+    /// Test a serialized test case in the child process, which is forked first if needed.
+    ///
+    /// The functions `decode` and `test` are only called in the child process. The first one deserializes the
+    /// test case, which is considered as passing if it fails. The second one runs the test function while the
+    /// code coverage counters are recording.
+    #[no_coverage]
+    pub fn run<T>(
+        &mut self,
+        content: &[u8],
+        decode: &dyn Fn(&[u8]) -> Option<T>,
+        test: &dyn Fn(&T) -> ChildOutcome,
+    ) -> Result<ForkedRun> {
+        let mut child = if let Some(child) = self.child.take() {
+            child
+        } else {
+            self.fork(decode, test)?
+        };
+        let outcome = write_message(&mut child.to_child, content).and_then(
+            #[no_coverage]
+            |_| read_outcome(&mut child.from_child),
+        );
+        match outcome {
+            Ok(outcome) => {
+                unsafe { get_counters() }.copy_from_slice(self.counters.as_mut_slice());
+                child.nbr_tested += 1;
+                if child.nbr_tested < self.batch_size {
+                    self.child = Some(child);
+                } else {
+                    // the child process exits when its pipe is closed
+                    wait_for_child(child)?;
+                }
+                Ok(ForkedRun::Completed(outcome))
+            }
+            Err(e) if matches!(e.kind(), io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe) => {
+                let status = wait_for_child(child)?;
+                if libc::WIFSIGNALED(status) {
+                    Ok(ForkedRun::Signaled(libc::WTERMSIG(status)))
+                } else {
+                    Ok(ForkedRun::Exited(libc::WEXITSTATUS(status)))
+                }
+            }
+            Err(e) => {
+                let _ = kill_child(child);
+                Err(e)
+            }
+        }
+    }
+
+    #[no_coverage]
+    fn fork<T>(&mut self, decode: &dyn Fn(&[u8]) -> Option<T>, test: &dyn Fn(&T) -> ChildOutcome) -> Result<Child> {
+        let (from_child, child_to_parent) = pipe()?;
+        let (parent_to_child, to_child) = pipe()?;
+        // otherwise the output buffered before the fork would be printed by both processes
+        io::stdout().flush()?;
+        match unsafe { libc::fork() } {
+            -1 => Err(io::Error::last_os_error()),
+            0 => {
+                drop(from_child);
+                drop(to_child);
+                unsafe { set_up_child_process() };
+                serve(parent_to_child, child_to_parent, &mut self.counters, decode, test);
+                unsafe { libc::_exit(0) }
+            }
+            pid => Ok(Child {
+                pid,
+                to_child,
+                from_child,
+                nbr_tested: 0,
+            }),
+        }
+    }
+}
+
+impl Drop for ForkServer {
+    #[no_coverage]
+    fn drop(&mut self) {
+        if let Some(child) = self.child.take() {
+            let _ = kill_child(child);
+        }
+    }
+}
+
+/// The loop of the child process, which stops when the fuzzer closes the pipe
+#[no_coverage]
+fn serve<T>(
+    mut from_parent: File,
+    mut to_parent: File,
+    counters: &mut SharedCounters,
+    decode: &dyn Fn(&[u8]) -> Option<T>,
+    test: &dyn Fn(&T) -> ChildOutcome,
+) {
+    while let Ok(Some(content)) = read_message(&mut from_parent) {
+        let value = decode(&content);
+        let own_counters = unsafe { get_counters() };
+        own_counters.fill(0);
+        let outcome = if let Some(value) = value {
+            test(&value)
+        } else {
+            ChildOutcome {
+                result: ChildResult::Passed,
+                failure: None,
+                duration: Duration::default(),
+                peak_memory: 0,
+                allocations: Allocations::default(),
+                maximised_values: vec![],
+            }
+        };
+        counters.as_mut_slice().copy_from_slice(own_counters);
+        if write_outcome(&mut to_parent, &outcome).is_err() {
+            break;
+        }
+    }
+}
+
+/// Restore the default signal handlers, such that a crash kills the child process instead of running the handlers
+/// of the fuzzer. The interruptions are left to the fuzzer, which stops the child process.
+#[no_coverage]
+unsafe fn set_up_child_process() {
+    crate::signals_handler::reset_signal_handlers();
+    libc::signal(libc::SIGINT, libc::SIG_IGN);
+    libc::signal(libc::SIGTERM, libc::SIG_IGN);
+    // a child process that hangs without a --hang-threshold must not outlive the fuzzer
+    #[cfg(target_os = "linux")]
+    libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+}
+
+#[no_coverage]
+fn pipe() -> Result<(File, File)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) })
+}
+
+/// Close the pipes of the child process and wait for it to exit, then return its wait status
+#[no_coverage]
+fn wait_for_child(child: Child) -> Result<i32> {
+    let Child {
+        pid,
+        to_child,
+        from_child,
+        ..
+    } = child;
+    drop(to_child);
+    drop(from_child);
+    let mut status = 0;
+    loop {
+        if unsafe { libc::waitpid(pid, &mut status, 0) } != -1 {
+            return Ok(status);
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+#[no_coverage]
+fn kill_child(child: Child) -> Result<i32> {
+    unsafe { libc::kill(child.pid, libc::SIGKILL) };
+    wait_for_child(child)
+}
+
+#[no_coverage]
+fn write_message(writer: &mut impl Write, content: &[u8]) -> Result<()> {
+    let len = u32::try_from(content.len()).map_err(
+        #[no_coverage]
+        |_| io::Error::new(io::ErrorKind::InvalidInput, "the test case is too large"),
+    )?;
+    let mut bytes = Vec::with_capacity(4 + content.len());
+    bytes.extend(len.to_be_bytes());
+    bytes.extend(content);
+    writer.write_all(&bytes)
+}
+
+/// Read the next message, or return `None` if the pipe was closed between two messages
+#[no_coverage]
+fn read_message(reader: &mut impl Read) -> Result<Option<Vec<u8>>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let mut content = vec![0; u32::from_be_bytes(len) as usize];
+    reader.read_exact(&mut content)?;
+    Ok(Some(content))
+}
+
+#[no_coverage]
+fn write_outcome(writer: &mut impl Write, outcome: &ChildOutcome) -> Result<()> {
+    let mut bytes = vec![outcome.result as u8];
+    bytes.extend((outcome.duration.as_nanos() as u64).to_be_bytes());
+    bytes.extend((outcome.peak_memory as u64).to_be_bytes());
+    bytes.extend((outcome.allocations.total_bytes as u64).to_be_bytes());
+    bytes.extend((outcome.allocations.peak_live_bytes as u64).to_be_bytes());
+    if let Some(failure) = &outcome.failure {
+        bytes.push(1);
+        bytes.extend(failure.id.to_be_bytes());
+        bytes.extend(failure.signature.to_be_bytes());
+        write_message(&mut bytes, failure.display.as_bytes())?;
+    } else {
+        bytes.push(0);
+    }
+    bytes.extend((outcome.maximised_values.len() as u64).to_be_bytes());
+    for (location, value) in outcome.maximised_values.iter() {
+        write_message(&mut bytes, location.as_bytes())?;
+        bytes.extend(value.to_be_bytes());
+    }
+    writer.write_all(&bytes)
+}
+
+#[no_coverage]
+fn read_outcome(reader: &mut impl Read) -> Result<ChildOutcome> {
+    #[no_coverage]
+    fn read_u64(reader: &mut impl Read) -> Result<u64> {
+        let mut bytes = [0; 8];
+        reader.read_exact(&mut bytes)?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+    let mut byte = [0; 1];
+    reader.read_exact(&mut byte)?;
+    let result = match byte[0] {
+        0 => ChildResult::Passed,
+        1 => ChildResult::Failed,
+        2 => ChildResult::Panicked,
+        3 => ChildResult::IgnoredPanic,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid result")),
+    };
+    let duration = Duration::from_nanos(read_u64(reader)?);
+    let peak_memory = read_u64(reader)? as usize;
+    let allocations = Allocations {
+        total_bytes: read_u64(reader)? as usize,
+        peak_live_bytes: read_u64(reader)? as usize,
+    };
+    reader.read_exact(&mut byte)?;
+    let failure = if byte[0] == 1 {
+        let id = read_u64(reader)?;
+        let signature = read_u64(reader)?;
+        let display = read_message(reader)?.ok_or_else(
+            #[no_coverage]
+            || io::Error::from(io::ErrorKind::UnexpectedEof),
+        )?;
+        Some(TestFailure {
+            display: String::from_utf8_lossy(&display).into_owned(),
+            id,
+            signature,
+        })
+    } else {
+        None
+    };
+    let mut maximised_values = vec![];
+    for _ in 0..read_u64(reader)? {
+        let location = read_message(reader)?.ok_or_else(
+            #[no_coverage]
+            || io::Error::from(io::ErrorKind::UnexpectedEof),
+        )?;
+        let value = read_u64(reader)?;
+        maximised_values.push((String::from_utf8_lossy(&location).into_owned(), value));
+    }
+    Ok(ChildOutcome {
+        result,
+        failure,
+        duration,
+        peak_memory,
+        allocations,
+        maximised_values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_message, read_outcome, write_message, write_outcome, ChildOutcome, ChildResult};
+    use crate::sensors_and_pools::{Allocations, TestFailure};
+    use std::time::Duration;
+
+    #[test]
+    #[no_coverage]
+    fn test_fork_server_messages() {
+        let mut bytes = vec![];
+        write_message(&mut bytes, b"[1,2,3]").unwrap();
+        write_message(&mut bytes, b"").unwrap();
+        let mut reader = bytes.as_slice();
+        assert_eq!(read_message(&mut reader).unwrap(), Some(b"[1,2,3]".to_vec()));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(vec![]));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+
+        let mut bytes = vec![];
+        for failure in [
+            None,
+            Some(TestFailure {
+                display: "panicked at 'index out of bounds'".to_string(),
+                id: 1,
+                signature: 2,
+            }),
+        ] {
+            let outcome = ChildOutcome {
+                result: ChildResult::Panicked,
+                failure,
+                duration: Duration::from_micros(1500),
+                peak_memory: 12,
+                allocations: Allocations {
+                    total_bytes: 300,
+                    peak_live_bytes: 200,
+                },
+                maximised_values: vec![("src/lib.rs:10:5".to_string(), 42)],
+            };
+            write_outcome(&mut bytes, &outcome).unwrap();
+        }
+        let mut reader = bytes.as_slice();
+        let outcome = read_outcome(&mut reader).unwrap();
+        assert_eq!(outcome.result, ChildResult::Panicked);
+        assert!(outcome.failure.is_none());
+        assert_eq!(outcome.duration, Duration::from_micros(1500));
+        assert_eq!(outcome.peak_memory, 12);
+        assert_eq!(
+            outcome.allocations,
+            Allocations {
+                total_bytes: 300,
+                peak_live_bytes: 200
+            }
+        );
+        assert_eq!(outcome.maximised_values, vec![("src/lib.rs:10:5".to_string(), 42)]);
+        let failure = read_outcome(&mut reader).unwrap().failure.unwrap();
+        assert_eq!(failure.display, "panicked at 'index out of bounds'");
+        assert_eq!((failure.id, failure.signature), (1, 2));
+        // the pipe was closed in the middle of an outcome, because the child process crashed
+        assert!(read_outcome(&mut &bytes[..5]).is_err());
+    }
+}
@@ -1,20 +1,34 @@
-use crate::data_structures::RcSlab;
+use crate::code_coverage_sensor::{CodeCoverageSensor, CorpusCoverageMap, CoverageReport, CoverageSnapshot};
+use crate::data_structures::{RcSlab, SlowInput, SlowestInputs};
+use crate::fork_server::{ChildOutcome, ChildResult, ForkServer, ForkedRun};
+use crate::mutators::profiled::{self, ProfiledSensorAndPool};
+use crate::saved_state::{SavedInput, SavedState};
 use crate::sensors_and_pools::{
-    AndSensorAndPool, NoopSensor, TestFailure, TestFailurePool, TestFailureSensor, UnitPool, TEST_FAILURE,
+    allocation_counters, counting_allocator_is_installed, maximised_values, reset_allocation_counters,
+    reset_maximised_values, set_allocation_counters, set_maximised_values, AndSensorAndPool, NoopSensor, TestFailure,
+    TestFailurePool, TestFailureSensor, UnitPool, TEST_FAILURE, TEST_RETURNED_TRUE,
 };
-use crate::signals_handler::set_signal_handlers;
-use crate::traits::{CorpusDelta, Mutator, SaveToStatsFolder, SensorAndPool, Serializer};
-use crate::world::World;
+use crate::signals_handler::{set_alarm, set_signal_handlers};
+use crate::traits::{CorpusDelta, Mutator, SaveToStatsFolder, Sensor, SensorAndPool, Serializer};
+use crate::world::{CorpusStorage, World};
 use crate::{CSVField, FuzzedInput, ToCSV};
-use fuzzcheck_common::arg::{Arguments, FuzzerCommand};
+use fuzzcheck_common::arg::{Arguments, FuzzerCommand, MALLOC_LIMIT_FLAG, MAX_INPUT_CPLX_FLAG, STRICT_FLAG};
+use fuzzcheck_common::results::CampaignResults;
 use fuzzcheck_common::{FuzzerEvent, FuzzerStats};
 use libc::{SIGABRT, SIGALRM, SIGBUS, SIGFPE, SIGINT, SIGSEGV, SIGTERM, SIGTRAP};
+use regex::Regex;
+use std::any::Any;
+use std::backtrace::Backtrace;
 use std::borrow::Borrow;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::hash::{Hash, Hasher};
-use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::panic::{catch_unwind, AssertUnwindSafe, PanicInfo};
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::result::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 static WRITE_STATS_ERROR: &str = "the stats could not be written to the file system";
 static WORLD_NEW_ERROR: &str = "an IO operation failed when setting up the fuzzer";
@@ -22,8 +36,59 @@ static SERIALIZER_FROM_DATA_ERROR: &str = "the file could not be decoded into a
 static READ_INPUT_FILE_ERROR: &str = "the input file could not be read";
 static SAVE_ARTIFACTS_ERROR: &str = "the artifact could not be saved";
 static UPDATE_CORPUS_ERROR: &str = "the corpus could not be updated on the file system";
+static IGNORE_PANIC_PATTERN_ERROR: &str = "a pattern given to --ignore-panic is not a valid regular expression";
+static READ_COVERAGE_SNAPSHOT_ERROR: &str = "the coverage snapshot could not be read";
+static WRITE_COVERAGE_SNAPSHOT_ERROR: &str = "the coverage snapshot could not be written";
+static WRITE_COVERAGE_MAP_ERROR: &str = "the corpus coverage map could not be written";
+static WRITE_COVERAGE_REPORT_ERROR: &str = "the coverage report could not be written";
+static READ_STATE_ERROR: &str = "the state of the fuzzer could not be read";
+static SAVE_STATE_ERROR: &str = "the state of the fuzzer could not be saved";
+static WRITE_RESULTS_ERROR: &str = "the results of the fuzz test could not be written";
+static MERGE_OUTPUT_CORPUS_ERROR: &str = "the merge command requires an output corpus";
+static MINIFY_CORPUS_INPUT_CORPUS_ERROR: &str = "the minify-corpus command requires an input corpus";
+static SYNC_CORPUS_ERROR: &str = "the test cases of the other workers could not be listed";
+static CONNECT_ENSEMBLE_ERROR: &str = "the fuzzer could not connect to the broker of the ensemble";
+static FORK_SERVER_ERROR: &str = "the test case could not be tested in a child process";
 
 static mut DID_FIND_ANY_TEST_FAILURE: bool = false;
+/// Set by the panic hook when the panic of the last test was ignored because of `--ignore-panic`
+static mut DID_IGNORE_PANIC: bool = false;
+/// The name of the test function being run, when several test functions are fuzz-tested together with
+/// [`fuzz_tests`](crate::builder::fuzz_tests). It is included in the description of the test failures.
+pub(crate) static mut CURRENT_TEST_FUNCTION: Option<&'static str> = None;
+
+/// The maximum number of files of the input corpus that are read and tested at once.
+///
+/// Between two batches, the fuzzer runs as many mutations as the number of files in a batch,
+/// so that fuzzing begins before the whole corpus is loaded.
+const CORPUS_BATCH_SIZE: usize = 256;
+
+/// The number of slowest test cases that are listed at the end of a fuzz test
+const SLOWEST_INPUTS_COUNT: usize = 10;
+
+/// The folder of the output corpus where the test cases that exceed the `--slow-threshold` are saved
+const SLOW_INPUTS_FOLDER: &str = "slow";
+
+/// The folder of the output corpus where the failing test cases are saved
+const TEST_FAILURES_FOLDER: &str = "test_failures";
+
+/// The interval at which the state of the fuzzer is saved to the file given by `--resume`
+const SAVE_STATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The interval at which a worker of `cargo fuzzcheck --jobs` reads the test cases added by the other workers
+const CORPUS_SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often the fuzzer tests the test cases received from the other fuzzers of its ensemble, see `--ensemble`
+const ENSEMBLE_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// On average, one in this many mutations of a test case of the pool combines it with another test case of the pool
+const CROSSOVER_PERIOD: usize = 16;
+
+/// The bucket of the crashes caused by test cases exceeding the `--max-memory`, which are all the same failure
+const OUT_OF_MEMORY_BUCKET_ID: u64 = u64::MAX;
+
+/// The bucket of the test failures caused by test cases exceeding the `--malloc-limit`
+const MALLOC_LIMIT_BUCKET_ID: u64 = u64::MAX - 1;
 
 /// The result of a fuzz test, if it ends.
 ///
@@ -49,10 +114,57 @@ pub enum ReasonForStopping<T> {
     LaunchedFuzzcheckWithoutCfgFuzzing,
 }
 
+impl<T> ReasonForStopping<T> {
+    /// The name of the reason in the file given by `--results-file`
+    #[no_coverage]
+    fn name(&self) -> &'static str {
+        match self {
+            ReasonForStopping::TestFailure(_) => "test_failure",
+            ReasonForStopping::ExhaustedAllPossibleMutations => "exhausted_all_possible_mutations",
+            ReasonForStopping::MaxIterationsReached => "max_iterations",
+            ReasonForStopping::MaxDurationReached => "max_duration",
+            ReasonForStopping::LaunchedFuzzcheckWithoutCfgFuzzing => "launched_without_cfg_fuzzing",
+        }
+    }
+}
+
+/// The kind of a crash reported to the callback given to
+/// [`FuzzerBuilder5::on_crash`](crate::builder::FuzzerBuilder5::on_crash).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashKind {
+    /// The test function panicked
+    Panic,
+    /// The test function returned `false` or `Err(..)`
+    Failure,
+    /// The test function did not return within the `--hang-threshold`
+    Hang,
+    /// The memory usage of the fuzzer exceeded the `--max-memory` while the test function was running
+    OutOfMemory,
+    /// The test function held more memory than the `--malloc-limit` allows
+    MallocLimit,
+    /// The process received the given signal, such as `SIGSEGV` or `SIGABRT`
+    Signal(i32),
+}
+
+/// A description of a crash found by the fuzzer, given to the callback passed to
+/// [`FuzzerBuilder5::on_crash`](crate::builder::FuzzerBuilder5::on_crash).
+#[derive(Debug, Clone)]
+pub struct CrashInfo {
+    /// The file where the crashing test case was saved, or `None` if it was not saved
+    /// because there is no output corpus or artifacts folder
+    pub input_path: Option<PathBuf>,
+    pub kind: CrashKind,
+    /// A description of the crash, such as the panic message
+    pub message: String,
+    /// The identifier of the bucket of the crash. Two crashes with the same identifier are considered to be
+    /// the same bug, and only the first one is reported. For crashes caused by a signal, it is the signal number.
+    pub bucket_id: u64,
+}
+
 /// The index to a test case in the fuzzer’s storage.
 #[cfg_attr(feature = "serde_json_serializer", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct PoolStorageIndex(usize);
+pub struct PoolStorageIndex(pub(crate) usize);
 
 // #[cfg(test)]
 impl PoolStorageIndex {
@@ -77,11 +189,29 @@ struct FuzzerState<T: Clone, M: Mutator<T>> {
     input_idx: FuzzerInputIndex<FuzzedInput<T, M>>,
     /// Various statistics about the fuzzer run
     fuzzer_stats: FuzzerStats,
+    /// The test cases that took the longest to run, measured by wall-clock time
+    slowest_inputs: SlowestInputs,
 
     settings: Arguments,
+    /// The compiled patterns of the panics that are not considered test failures
+    ignored_panics: Arc<Vec<Regex>>,
     serializer: Box<dyn Serializer<Value = T>>,
     /// The world handles effects
     world: World,
+    /// The callback given to [`FuzzerBuilder5::on_crash`](crate::builder::FuzzerBuilder5::on_crash)
+    on_crash: Option<Box<dyn FnMut(&CrashInfo)>>,
+    /// The buckets of the crashes that were already given to `on_crash`
+    reported_crashes: BTreeSet<u64>,
+    /// Pretty-prints the failing test cases, set by
+    /// [`FuzzerBuilder5::debug_failing_test_cases`](crate::builder::FuzzerBuilder5::debug_failing_test_cases)
+    debug_value: Option<Box<dyn Fn(&T) -> String>>,
+    /// The child process running the test function, with `--fork`
+    fork_server: Option<ForkServer>,
+    /// The fingerprint of the fuzz target saved with the state of the pools, with `--resume`
+    fingerprint: String,
+    /// Whether the pools were updated with the current test case but the pool storage and the corpus were not yet,
+    /// in which case the state of the pools cannot be saved
+    is_updating_pools: bool,
 }
 
 impl<T: Clone, M: Mutator<T>> Drop for FuzzerState<T, M> {
@@ -104,6 +234,114 @@ impl<T: Clone, M: Mutator<T>> FuzzerState<T, M> {
     }
 }
 
+/// Run the test function on the value `runs_per_input` times, or until it does not return true, and return the
+/// result of the last run and the average duration of a run
+#[no_coverage]
+fn run_test<T>(
+    test: &dyn Fn(&T) -> bool,
+    value: &T,
+    runs_per_input: u32,
+    hang_threshold: Option<Duration>,
+) -> (std::thread::Result<bool>, Duration) {
+    // with --runs-per-input, the sensor observes all the runs of the input, until one of them does not return true
+    let mut result = Ok(true);
+    let mut nbr_runs = 0;
+    let start = Instant::now();
+    while nbr_runs < runs_per_input && matches!(result, Ok(true)) {
+        if hang_threshold.is_some() {
+            // if the test function does not return in time, the SIGALRM handler saves the input as an artifact,
+            // or, with --fork, the child process is killed by SIGALRM
+            unsafe { set_alarm(hang_threshold) };
+        }
+        result = catch_unwind(AssertUnwindSafe(
+            #[no_coverage]
+            || test(value),
+        ));
+        if hang_threshold.is_some() {
+            unsafe { set_alarm(None) };
+        }
+        nbr_runs += 1;
+    }
+    // the average duration of a run
+    (result, start.elapsed() / nbr_runs)
+}
+
+/// Give the crash to the `on_crash` callback, if any
+#[no_coverage]
+fn report_crash(on_crash: &mut Option<Box<dyn FnMut(&CrashInfo)>>, crash: CrashInfo) {
+    if let Some(on_crash) = on_crash {
+        on_crash(&crash);
+    }
+}
+
+/// Print the failing test case with `debug_value`, if any, and write it next to the artifact where it was saved
+#[no_coverage]
+fn describe_failing_test_case<T>(
+    debug_value: &Option<Box<dyn Fn(&T) -> String>>,
+    world: &World,
+    value: &T,
+    artifact: Option<&Path>,
+) {
+    if let Some(debug_value) = debug_value {
+        world
+            .describe_failing_test_case(&debug_value(value), artifact)
+            .expect(SAVE_ARTIFACTS_ERROR);
+    }
+}
+
+/// The message given to `panic!`, or an empty string if the payload of the panic is not a string
+#[no_coverage]
+fn panic_message(panic_info: &PanicInfo) -> String {
+    payload_message(panic_info.payload())
+}
+
+/// Whether the panic message matches one of the patterns given to `--ignore-panic`
+#[no_coverage]
+fn is_ignored_panic(ignored_panics: &[Regex], message: &str) -> bool {
+    ignored_panics.iter().any(
+        #[no_coverage]
+        |pattern| pattern.is_match(message),
+    )
+}
+
+/// The message contained in the payload of a panic, or an empty string if the payload is not a string
+#[no_coverage]
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::new()
+    }
+}
+
+/// Decode the content of a file with [`Serializer::from_file`], given the path of the file
+#[no_coverage]
+fn decode_file<T>(serializer: &dyn Serializer<Value = T>, path: &Path, data: &[u8]) -> Option<T> {
+    let extension = path
+        .extension()
+        .and_then(
+            #[no_coverage]
+            |extension| extension.to_str(),
+        )
+        .unwrap_or("");
+    serializer.from_file(extension, data)
+}
+
+/// A hash of the executable of the fuzz target and of the names of its pools, which identifies the fuzz targets
+/// that can restore the state of the pools saved with `--resume`. It is empty if the executable cannot be read.
+#[no_coverage]
+fn fuzz_target_fingerprint(sensor_and_pool: &dyn SensorAndPool) -> String {
+    let executable = std::env::current_exe().and_then(std::fs::read);
+    if let Ok(mut bytes) = executable {
+        bytes.extend(CSVField::to_bytes(&sensor_and_pool.stats().csv_headers()));
+        format!("{:x}", md5::compute(bytes))
+    } else {
+        String::new()
+    }
+}
+
 #[no_coverage]
 fn update_fuzzer_stats(stats: &mut FuzzerStats, world: &mut World) {
     let microseconds = world.elapsed_time_since_last_checkpoint();
@@ -138,31 +376,174 @@ where
         self.world.write_stats_content(self.save_to_stats_folder())
     }
 
+    /// Write the outcome of the fuzzing campaign to the file given by `--results-file`, if any
+    #[no_coverage]
+    fn write_results(&self, found_test_failure: bool, reason_for_stopping: &'static str) -> Result<(), std::io::Error> {
+        if !matches!(self.settings.command, FuzzerCommand::Fuzz) {
+            return Ok(());
+        }
+        let stats = self.sensor_and_pool.stats();
+        let pool_stats = stats
+            .csv_headers()
+            .into_iter()
+            .zip(stats.to_csv_record())
+            .filter_map(
+                #[no_coverage]
+                |(header, value)| match (header, value) {
+                    (CSVField::String(name), CSVField::Integer(value)) => Some((name, value as f64)),
+                    (CSVField::String(name), CSVField::Float(value)) => Some((name, value)),
+                    _ => None,
+                },
+            )
+            .collect();
+        let results = CampaignResults {
+            found_test_failure,
+            // the failures that stop the fuzzer, such as crashes, are not counted in `reported_crashes`
+            failures: self.reported_crashes.len().max(found_test_failure as usize),
+            reason_for_stopping,
+            iterations: self.fuzzer_stats.total_number_of_runs,
+            duration: self.world.elapsed_time_since_start(),
+            initial_corpus_size: self.world.input_corpus_size(),
+            final_corpus_size: self.world.corpus_size(),
+            pool_stats,
+        };
+        self.world.write_results_file(&results)
+    }
+
+    /// Save the test cases of the pool storage and the progress of the fuzzer to the file given by `--resume`, if any
+    #[no_coverage]
+    fn save_state(&self) -> Result<(), std::io::Error> {
+        if self.settings.resume.is_none() {
+            return Ok(());
+        }
+        let inputs = self
+            .pool_storage
+            .keys()
+            .map(
+                #[no_coverage]
+                |key| SavedInput {
+                    key,
+                    ref_count: self.pool_storage.ref_count(key),
+                    generation: self.pool_storage[key].generation,
+                    nbr_mutations: self.pool_storage[key].nbr_mutations,
+                    content: self.serializer.to_data(&self.pool_storage[key].value),
+                },
+            )
+            .collect();
+        let corpus = self
+            .world
+            .corpus_files()
+            .map(
+                #[no_coverage]
+                |(folder, idx, hash)| (folder.to_path_buf(), idx.0, hash.to_owned()),
+            )
+            .collect();
+        // a signal may interrupt the fuzzer between the update of the pools and that of the pool storage
+        let pools = if self.is_updating_pools {
+            None
+        } else {
+            self.sensor_and_pool.save_state()
+        };
+        let state = SavedState {
+            total_number_of_runs: self.fuzzer_stats.total_number_of_runs,
+            elapsed_time: self.world.elapsed_time_since_start(),
+            fingerprint: self.fingerprint.clone(),
+            seed: fastrand::get_seed(),
+            inputs,
+            corpus,
+            pools,
+        };
+        self.world.save_state(&state)
+    }
+
+    /// Prepare a value read from the file system to be tested, unless it is invalid or too complex
+    #[no_coverage]
+    fn input_from_value(&self, value: T) -> Option<FuzzedInput<T, M>> {
+        let cache = self.mutator.validate_value(&value)?;
+        let mutation_step = self.mutator.default_mutation_step(&value, &cache);
+        let input = FuzzedInput::new(value, cache, mutation_step, 0);
+        if input.complexity(&self.mutator) > self.settings.max_input_cplx {
+            None
+        } else {
+            Some(input)
+        }
+    }
+
     #[no_coverage]
     fn receive_signal(&mut self, signal: i32) -> ! {
-        self.world.report_event(
-            FuzzerEvent::CaughtSignal(signal as i32),
-            Some((&self.fuzzer_stats, self.sensor_and_pool.stats().as_ref())),
-        );
+        // SIGALRM is only sent by the timer set up for the --hang-threshold
+        let event = if signal == SIGALRM {
+            FuzzerEvent::Hang
+        } else {
+            FuzzerEvent::CaughtSignal(signal as i32)
+        };
+        self.world
+            .report_event(event, Some((&self.fuzzer_stats, self.sensor_and_pool.stats().as_ref())));
 
         match signal {
             SIGABRT | SIGBUS | SIGSEGV | SIGFPE | SIGALRM | SIGTRAP => {
+                let crash_reason = if signal == SIGALRM { "hang" } else { "crash" };
+                let (kind, message) = if signal == SIGALRM {
+                    (
+                        CrashKind::Hang,
+                        "the test function did not return within the hang threshold".to_string(),
+                    )
+                } else {
+                    (
+                        CrashKind::Signal(signal),
+                        format!("the process received the signal {}", signal),
+                    )
+                };
                 if let Some(input) = Self::get_input(&self.input_idx, &self.pool_storage) {
                     let cplx = input.complexity(&self.mutator);
                     let content = self.serializer.to_data(&input.value);
-                    let _ = self.world.save_artifact(content, cplx, self.serializer.extension());
+                    let extension = self.serializer.extension();
+                    let input_path = if signal == SIGALRM {
+                        self.world.save_hang_artifact(content, cplx, extension)
+                    } else {
+                        self.world.save_artifact(content, cplx, extension)
+                    }
+                    .unwrap_or(None);
+                    describe_failing_test_case(&self.debug_value, &self.world, &input.value, input_path.as_deref());
+                    report_crash(
+                        &mut self.on_crash,
+                        CrashInfo {
+                            input_path,
+                            kind,
+                            message,
+                            bucket_id: signal as u64,
+                        },
+                    );
                     self.write_stats().expect(WRITE_STATS_ERROR);
+                    let _ = self.write_results(true, crash_reason);
+                    // the signal comes from the test function, so no file of the corpus is being written
+                    let _ = self.world.mark_output_corpus_complete();
                     exit(TerminationStatus::Crash as i32);
                 } else {
                     self.world.report_event(
                         FuzzerEvent::CrashNoInput,
                         Some((&self.fuzzer_stats, self.sensor_and_pool.stats().as_ref())),
                     );
+                    report_crash(
+                        &mut self.on_crash,
+                        CrashInfo {
+                            input_path: None,
+                            kind,
+                            message,
+                            bucket_id: signal as u64,
+                        },
+                    );
+                    let _ = self.write_results(true, crash_reason);
                     exit(TerminationStatus::Crash as i32);
                 }
             }
             SIGINT | SIGTERM => {
                 self.write_stats().expect(WRITE_STATS_ERROR);
+                self.save_state().expect(SAVE_STATE_ERROR);
+                self.write_results(unsafe { DID_FIND_ANY_TEST_FAILURE }, "interrupted")
+                    .expect(WRITE_RESULTS_ERROR);
+                self.world.mark_output_corpus_complete().expect(UPDATE_CORPUS_ERROR);
+                self.world.report_slowest_inputs(&self.slowest_inputs.sorted());
                 self.world.stop()
             }
             _ => exit(TerminationStatus::Unknown as i32),
@@ -199,6 +580,9 @@ where
 {
     state: FuzzerState<T, M>,
     test: Box<dyn Fn(&T) -> bool>,
+    /// The test cases given to [`initial_inputs`](crate::builder::FuzzerBuilder5::initial_inputs), which are
+    /// tested before the input corpus
+    initial_inputs: Vec<T>,
 }
 
 impl<T, M> Fuzzer<T, M>
@@ -215,8 +599,32 @@ where
         sensor_and_pool: Box<dyn SensorAndPool>,
         settings: Arguments,
         world: World,
+        on_crash: Option<Box<dyn FnMut(&CrashInfo)>>,
+        debug_value: Option<Box<dyn Fn(&T) -> String>>,
     ) -> Self {
+        let sensor_and_pool: Box<dyn SensorAndPool> = if profiled::has_profiled_mutators() {
+            Box::new(ProfiledSensorAndPool { sap: sensor_and_pool })
+        } else {
+            sensor_and_pool
+        };
+        let fork_server = settings.fork.map(
+            #[no_coverage]
+            |batch_size| ForkServer::new(batch_size).expect(FORK_SERVER_ERROR),
+        );
         let arbitrary_step = mutator.default_arbitrary_step();
+        let ignored_panics = settings
+            .ignored_panics
+            .iter()
+            .map(
+                #[no_coverage]
+                |pattern| Regex::new(pattern).expect(IGNORE_PANIC_PATTERN_ERROR),
+            )
+            .collect();
+        let fingerprint = if settings.resume.is_some() {
+            fuzz_target_fingerprint(sensor_and_pool.as_ref())
+        } else {
+            String::new()
+        };
         Fuzzer {
             state: FuzzerState {
                 sensor_and_pool,
@@ -225,11 +633,20 @@ where
                 arbitrary_step,
                 input_idx: FuzzerInputIndex::None,
                 fuzzer_stats: FuzzerStats::default(),
+                slowest_inputs: SlowestInputs::new(SLOWEST_INPUTS_COUNT),
                 settings,
+                ignored_panics: Arc::new(ignored_panics),
                 serializer,
                 world,
+                on_crash,
+                reported_crashes: BTreeSet::new(),
+                debug_value,
+                fork_server,
+                fingerprint,
+                is_updating_pools: false,
             },
             test,
+            initial_inputs: vec![],
         }
     }
 
@@ -243,8 +660,15 @@ where
                     pool_storage,
                     input_idx,
                     fuzzer_stats,
+                    slowest_inputs,
                     serializer,
                     world,
+                    ignored_panics,
+                    on_crash,
+                    reported_crashes,
+                    debug_value,
+                    fork_server,
+                    is_updating_pools,
                     ..
                 },
             test,
@@ -254,34 +678,133 @@ where
         // we have verified in the caller function that there is an input
         let input = FuzzerState::<T, M>::get_input(input_idx, pool_storage).unwrap();
 
+        unsafe {
+            DID_IGNORE_PANIC = false;
+        }
+        let ignored_panics = ignored_panics.clone();
         std::panic::set_hook(Box::new(
             #[no_coverage]
             move |panic_info| {
+                let message = panic_message(panic_info);
+                if is_ignored_panic(&ignored_panics, &message) {
+                    unsafe {
+                        DID_IGNORE_PANIC = true;
+                    }
+                    return;
+                }
+                let backtrace = Backtrace::force_capture().to_string();
+                let signature = TestFailure::panic_signature(&message, &backtrace);
                 let mut hasher = DefaultHasher::new();
                 panic_info.location().hash(&mut hasher);
+                let test_function = unsafe { CURRENT_TEST_FUNCTION };
+                test_function.hash(&mut hasher);
+                let display = if let Some(name) = test_function {
+                    format!("test function `{}` {}", name, panic_info)
+                } else {
+                    format!("{}", panic_info)
+                };
                 unsafe {
                     TEST_FAILURE = Some(TestFailure {
-                        display: format!("{}", panic_info),
+                        display,
                         id: hasher.finish(),
+                        signature,
                     });
                 }
             },
         ));
 
+        let hang_threshold = self.state.settings.hang_threshold;
+        let runs_per_input = self.state.settings.runs_per_input;
         sensor_and_pool.start_recording();
-        let result = catch_unwind(AssertUnwindSafe(
-            #[no_coverage]
-            || (test)(input.value.borrow()),
-        ));
+        let (result, duration, child_peak_memory) = if let Some(fork_server) = fork_server {
+            let decode = #[no_coverage]
+            |data: &[u8]| serializer.from_data(data);
+            // runs in the child process, whose panic hook is the one set above
+            let run_in_child = #[no_coverage]
+            |value: &T| {
+                unsafe {
+                    DID_IGNORE_PANIC = false;
+                    TEST_FAILURE = None;
+                }
+                reset_allocation_counters();
+                reset_maximised_values();
+                let (result, duration) = run_test(test.as_ref(), value, runs_per_input, hang_threshold);
+                let result = match result {
+                    Ok(true) => ChildResult::Passed,
+                    Ok(false) => ChildResult::Failed,
+                    Err(_) if unsafe { DID_IGNORE_PANIC } => ChildResult::IgnoredPanic,
+                    Err(_) => ChildResult::Panicked,
+                };
+                ChildOutcome {
+                    result,
+                    failure: if result == ChildResult::Panicked {
+                        unsafe { TEST_FAILURE.clone() }
+                    } else {
+                        None
+                    },
+                    duration,
+                    peak_memory: world.peak_memory_usage(),
+                    allocations: allocation_counters(),
+                    maximised_values: maximised_values(),
+                }
+            };
+            let content = serializer.to_data(&input.value);
+            match fork_server
+                .run(&content, &decode, &run_in_child)
+                .expect(FORK_SERVER_ERROR)
+            {
+                ForkedRun::Completed(outcome) => {
+                    let result: std::thread::Result<bool> = match outcome.result {
+                        ChildResult::Passed => Ok(true),
+                        ChildResult::Failed => Ok(false),
+                        ChildResult::Panicked | ChildResult::IgnoredPanic => Err(Box::new(())),
+                    };
+                    unsafe {
+                        DID_IGNORE_PANIC = outcome.result == ChildResult::IgnoredPanic;
+                        TEST_FAILURE = outcome.failure;
+                    }
+                    set_allocation_counters(outcome.allocations);
+                    set_maximised_values(&outcome.maximised_values);
+                    (result, outcome.duration, Some(outcome.peak_memory))
+                }
+                run => {
+                    let _ = std::panic::take_hook();
+                    sensor_and_pool.stop_recording();
+                    return self.process_child_crash(run, cplx);
+                }
+            }
+        } else {
+            reset_allocation_counters();
+            let (result, duration) = run_test(test.as_ref(), input.value.borrow(), runs_per_input, hang_threshold);
+            (result, duration, None)
+        };
         let _ = std::panic::take_hook();
+        // read by the ConditionalSensor when it stops recording
+        unsafe {
+            TEST_RETURNED_TRUE = matches!(result, Ok(true));
+        }
         let test_failure = match result {
             Ok(false) => unsafe {
-                TEST_FAILURE = Some(TestFailure {
-                    display: "test function returned false".to_string(),
-                    id: 0,
+                TEST_FAILURE = Some(if let Some(name) = CURRENT_TEST_FUNCTION {
+                    let mut hasher = DefaultHasher::new();
+                    name.hash(&mut hasher);
+                    let id = hasher.finish();
+                    TestFailure {
+                        display: format!("test function `{}` returned false", name),
+                        id,
+                        signature: id,
+                    }
+                } else {
+                    TestFailure {
+                        display: "test function returned false".to_string(),
+                        id: 0,
+                        signature: 0,
+                    }
                 });
                 true
             },
+            // the panic matched a pattern given to --ignore-panic, it is a normal outcome
+            Err(_) if unsafe { DID_IGNORE_PANIC } => false,
             Err(_) => {
                 // the panic handler already changed the value of TEST_FAILURE
                 // so we don't need to do anything
@@ -289,26 +812,116 @@ where
             }
             Ok(true) => false,
         };
+        // checked before the sensors stop recording, such that the test failure sensor observes the failure
+        let exceeded_malloc_limit = !test_failure
+            && matches!(self.state.settings.malloc_limit, Some(malloc_limit) if exceeds_malloc_limit(malloc_limit));
+        let test_failure = test_failure || exceeded_malloc_limit;
         if test_failure {
             unsafe {
                 DID_FIND_ANY_TEST_FAILURE = true;
             }
         }
         sensor_and_pool.stop_recording();
+        if let Some(max_memory) = self.state.settings.max_memory {
+            // the peak memory usage never decreases, so the test case during which it first exceeds the limit
+            // is the culprit, and all the following ones would exceed it too
+            let memory_usage = child_peak_memory.unwrap_or_else(
+                #[no_coverage]
+                || world.peak_memory_usage(),
+            );
+            if memory_usage > max_memory {
+                unsafe {
+                    DID_FIND_ANY_TEST_FAILURE = true;
+                }
+                world.report_event(
+                    FuzzerEvent::OutOfMemory,
+                    Some((fuzzer_stats, sensor_and_pool.stats().as_ref())),
+                );
+                let input_path = world
+                    .save_oom_artifact(serializer.to_data(&input.value), cplx, serializer.extension())
+                    .expect(SAVE_ARTIFACTS_ERROR);
+                describe_failing_test_case(debug_value, world, &input.value, input_path.as_deref());
+                report_crash(
+                    on_crash,
+                    CrashInfo {
+                        input_path,
+                        kind: CrashKind::OutOfMemory,
+                        message: format!(
+                            "the memory usage of the fuzzer reached {}MB, which exceeds the maximum of {}MB",
+                            memory_usage, max_memory
+                        ),
+                        bucket_id: OUT_OF_MEMORY_BUCKET_ID,
+                    },
+                );
+                return Err(ReasonForStopping::TestFailure(input.value.clone()));
+            }
+        }
+        let crash = if test_failure {
+            unsafe { TEST_FAILURE.clone() }.map(
+                #[no_coverage]
+                |failure| CrashInfo {
+                    input_path: None,
+                    kind: if exceeded_malloc_limit {
+                        CrashKind::MallocLimit
+                    } else if result.is_err() {
+                        CrashKind::Panic
+                    } else {
+                        CrashKind::Failure
+                    },
+                    message: failure.display,
+                    bucket_id: failure.id,
+                },
+            )
+        } else {
+            None
+        };
         if test_failure && self.state.settings.stop_after_first_failure {
             let serialized_input = serializer.to_data(&input.value);
-            self.state
+            let input_path = self
+                .state
                 .world
                 .save_artifact(serialized_input, cplx, serializer.extension())
                 .expect(SAVE_ARTIFACTS_ERROR);
+            describe_failing_test_case(debug_value, &self.state.world, &input.value, input_path.as_deref());
+            if let Some(crash) = crash {
+                report_crash(&mut self.state.on_crash, CrashInfo { input_path, ..crash });
+            }
             return Err(ReasonForStopping::TestFailure(input.value.clone()));
         }
 
         fuzzer_stats.total_number_of_runs += 1;
 
+        if !test_failure && matches!(self.state.settings.slow_threshold, Some(threshold) if duration >= threshold) {
+            let content = serializer.to_data(&input.value);
+            let name = world.hash(&content);
+            world
+                .add_to_output_corpus(
+                    Path::new(SLOW_INPUTS_FOLDER),
+                    name,
+                    content,
+                    Some(cplx),
+                    serializer.extension(),
+                )
+                .expect(UPDATE_CORPUS_ERROR);
+            world.report_event(
+                FuzzerEvent::Slow,
+                Some((fuzzer_stats, sensor_and_pool.stats().as_ref())),
+            );
+        }
+
+        if slowest_inputs.accepts(duration) {
+            let name = world.hash(&serializer.to_data(&input.value));
+            slowest_inputs.insert(SlowInput { duration, cplx, name });
+        }
+
         let input_id = PoolStorageIndex(pool_storage.next_slot());
 
+        *is_updating_pools = true;
         let deltas = sensor_and_pool.process(input_id, cplx);
+        profiled::finish_mutation(deltas.iter().any(
+            #[no_coverage]
+            |delta| delta.add,
+        ));
 
         if !deltas.is_empty() {
             let add_ref_count = deltas.iter().fold(
@@ -324,9 +937,31 @@ where
                 vec![]
             };
             world
-                .update_corpus(input_id, content, &deltas, serializer.extension())
+                .update_corpus(input_id, content, cplx, &deltas, serializer.extension())
                 .expect(UPDATE_CORPUS_ERROR);
             world.report_event(event, Some((fuzzer_stats, sensor_and_pool.stats().as_ref())));
+            // the failing test case was just saved by the test failure pool, in a folder named after its bucket
+            if let Some(crash) = crash.filter(
+                #[no_coverage]
+                |crash| reported_crashes.insert(crash.bucket_id),
+            ) {
+                let input_path = deltas
+                    .iter()
+                    .find(
+                        #[no_coverage]
+                        |delta| delta.add && delta.path.starts_with(TEST_FAILURES_FOLDER),
+                    )
+                    .and_then(
+                        #[no_coverage]
+                        |delta| {
+                            let name = world.hash(&serializer.to_data(&input.value));
+                            world.output_corpus_file(&delta.path, &name, serializer.extension())
+                        },
+                    );
+                // the corpus folders only contain serialized test cases, so the description is only printed
+                describe_failing_test_case(debug_value, world, &input.value, None);
+                report_crash(on_crash, CrashInfo { input_path, ..crash });
+            }
             if add_ref_count > 0 {
                 let new_input = input.new_source(mutator);
                 // here I don't check the complexity of the new input,
@@ -341,10 +976,85 @@ where
                 }
             }
         }
+        *is_updating_pools = false;
 
         Ok(())
     }
 
+    /// Save the test case that killed the child process of `--fork` as an artifact, if it is the first one with
+    /// that signal or exit code, and report the crash. The fuzzer then continues with a new child process, unless
+    /// `--stop-after-first-failure` is given.
+    #[no_coverage]
+    fn process_child_crash(&mut self, run: ForkedRun, cplx: f64) -> Result<(), ReasonForStopping<T>> {
+        let (event, kind, message, bucket_id) = match run {
+            ForkedRun::Signaled(SIGALRM) => (
+                FuzzerEvent::Hang,
+                CrashKind::Hang,
+                "the test function did not return within the hang threshold".to_string(),
+                SIGALRM as u64,
+            ),
+            ForkedRun::Signaled(signal) => (
+                FuzzerEvent::CaughtSignal(signal),
+                CrashKind::Signal(signal),
+                format!("the child process received the signal {}", signal),
+                signal as u64,
+            ),
+            ForkedRun::Exited(code) => {
+                let mut hasher = DefaultHasher::new();
+                ("exit", code).hash(&mut hasher);
+                (
+                    FuzzerEvent::TestFailure,
+                    CrashKind::Failure,
+                    format!("the child process exited with code {}", code),
+                    hasher.finish(),
+                )
+            }
+            ForkedRun::Completed(_) => unreachable!(),
+        };
+        unsafe {
+            DID_FIND_ANY_TEST_FAILURE = true;
+        }
+        profiled::finish_mutation(false);
+        self.state.fuzzer_stats.total_number_of_runs += 1;
+        self.state.world.report_event(
+            event,
+            Some((&self.state.fuzzer_stats, self.state.sensor_and_pool.stats().as_ref())),
+        );
+        let stop = self.state.settings.stop_after_first_failure;
+        let input = FuzzerState::<T, M>::get_input(&self.state.input_idx, &self.state.pool_storage).unwrap();
+        let value = input.value.clone();
+        if self.state.reported_crashes.insert(bucket_id) || stop {
+            let content = self.state.serializer.to_data(&value);
+            let extension = self.state.serializer.extension();
+            let input_path = if kind == CrashKind::Hang {
+                self.state.world.save_hang_artifact(content, cplx, extension)
+            } else {
+                self.state.world.save_artifact(content, cplx, extension)
+            }
+            .expect(SAVE_ARTIFACTS_ERROR);
+            describe_failing_test_case(
+                &self.state.debug_value,
+                &self.state.world,
+                &value,
+                input_path.as_deref(),
+            );
+            report_crash(
+                &mut self.state.on_crash,
+                CrashInfo {
+                    input_path,
+                    kind,
+                    message,
+                    bucket_id,
+                },
+            );
+        }
+        if stop {
+            Err(ReasonForStopping::TestFailure(value))
+        } else {
+            Ok(())
+        }
+    }
+
     #[no_coverage]
     fn process_next_input(&mut self) -> Result<(), ReasonForStopping<T>> {
         let FuzzerState {
@@ -358,11 +1068,33 @@ where
         loop {
             if let Some(idx) = sensor_and_pool.get_random_index() {
                 *input_idx = FuzzerInputIndex::Pool(idx);
+                profiled::start_mutation();
+                let mut mutation = None;
+                if fastrand::usize(..CROSSOVER_PERIOD) == 0 {
+                    if let Some(other_idx) = sensor_and_pool.get_random_index() {
+                        if other_idx != idx {
+                            let other = &pool_storage[other_idx.0];
+                            let (other_value, other_cache) = (other.value.clone(), other.cache.clone());
+                            mutation = pool_storage[idx.0].crossover_mutate(
+                                mutator,
+                                &other_value,
+                                &other_cache,
+                                settings.max_input_cplx,
+                            );
+                        }
+                    }
+                }
                 let input = &mut pool_storage[idx.0];
                 let generation = input.generation;
-                if let Some((unmutate_token, cplx)) = input.mutate(mutator, settings.max_input_cplx) {
+                // fall back to a regular mutation if the mutator could not combine the two test cases
+                if mutation.is_none() {
+                    mutation = input.mutate(mutator, settings.max_input_cplx);
+                }
+                if let Some((unmutate_token, cplx)) = mutation {
                     if cplx < self.state.settings.max_input_cplx {
                         self.test_and_process_input(cplx)?;
+                    } else {
+                        profiled::finish_mutation(false);
                     }
 
                     // Retrieving the input may fail because the input may have been deleted
@@ -398,26 +1130,195 @@ where
         }
     }
 
+    /// Read the next batch of files from the input corpus and test them. Return the number of tested inputs.
     #[no_coverage]
-    fn process_initial_inputs(&mut self) -> Result<(), ReasonForStopping<T>> {
-        let mut inputs: Vec<FuzzedInput<T, M>> = self
+    fn process_next_corpus_batch(&mut self) -> Result<usize, ReasonForStopping<T>> {
+        let inputs: Vec<FuzzedInput<T, M>> = self
             .state
             .world
-            .read_input_corpus()
+            .read_input_corpus_batch(CORPUS_BATCH_SIZE)
             .expect(READ_INPUT_FILE_ERROR)
             .into_iter()
             .filter_map(
                 #[no_coverage]
-                |value| {
-                    let value = self.state.serializer.from_data(&value)?;
-                    let cache = self.state.mutator.validate_value(&value)?;
-                    let mutation_step = self.state.mutator.default_mutation_step(&value, &cache);
-                    Some(FuzzedInput::new(value, cache, mutation_step, 0))
+                |(path, data)| {
+                    let value = decode_file(self.state.serializer.as_ref(), &path, &data)?;
+                    self.state.input_from_value(value)
+                },
+            )
+            .collect();
+
+        let nbr_inputs = inputs.len();
+        for input in inputs {
+            let cplx = input.complexity(&self.state.mutator);
+            self.state.input_idx = FuzzerInputIndex::Temporary(input);
+            self.test_and_process_input(cplx)?;
+        }
+        Ok(nbr_inputs)
+    }
+
+    /// Test the test cases received from the other fuzzers of the ensemble since the last call, and return their
+    /// number. The ones that are interesting to the pools are added to the corpus, like the test cases of the input
+    /// corpus.
+    #[no_coverage]
+    fn process_ensemble_test_cases(&mut self) -> Result<usize, ReasonForStopping<T>> {
+        let inputs: Vec<FuzzedInput<T, M>> = self
+            .state
+            .world
+            .receive_from_ensemble()
+            .into_iter()
+            .filter_map(
+                #[no_coverage]
+                |(path, data)| {
+                    let value = decode_file(self.state.serializer.as_ref(), &path, &data)?;
+                    self.state.input_from_value(value)
                 },
             )
             .collect();
 
-        for _ in 0..100 {
+        let nbr_inputs = inputs.len();
+        for input in inputs {
+            let cplx = input.complexity(&self.state.mutator);
+            self.state.input_idx = FuzzerInputIndex::Temporary(input);
+            self.test_and_process_input(cplx)?;
+        }
+        Ok(nbr_inputs)
+    }
+
+    /// Restore the state saved by a previous run of the fuzz test, if any, and continue counting the iterations and
+    /// the elapsed time from where that run stopped. Return the number of tested inputs.
+    ///
+    /// The pools are restored without running the test function if the fuzz target did not change since the state
+    /// was saved. Otherwise, the inputs of the state are tested again to rebuild the pools.
+    #[no_coverage]
+    fn resume_from_saved_state(&mut self) -> Result<usize, ReasonForStopping<T>> {
+        let state = if let Some(state) = self.state.world.read_saved_state().expect(READ_STATE_ERROR) {
+            state
+        } else {
+            return Ok(0);
+        };
+        let inputs: Vec<(&SavedInput, FuzzedInput<T, M>)> = state
+            .inputs
+            .iter()
+            .filter_map(
+                #[no_coverage]
+                |saved| {
+                    let value = self.state.serializer.from_data(&saved.content)?;
+                    let mut input = self.state.input_from_value(value)?;
+                    input.generation = saved.generation;
+                    Some((saved, input))
+                },
+            )
+            .collect();
+        let nbr_inputs = inputs.len();
+        let can_restore_pools = nbr_inputs == state.inputs.len()
+            && !self.state.fingerprint.is_empty()
+            && self.state.fingerprint == state.fingerprint;
+        match &state.pools {
+            Some(pools) if can_restore_pools => {
+                let mut new_keys = HashMap::new();
+                for (saved, mut input) in inputs {
+                    input.replay_mutations(
+                        &self.state.mutator,
+                        saved.nbr_mutations,
+                        self.state.settings.max_input_cplx,
+                    );
+                    let key = self.state.pool_storage.insert(input, saved.ref_count);
+                    new_keys.insert(saved.key, PoolStorageIndex(key));
+                }
+                let new_index = #[no_coverage]
+                |idx: PoolStorageIndex| new_keys.get(&idx.0).copied();
+                self.state
+                    .sensor_and_pool
+                    .restore_state(pools, &new_index)
+                    .map_err(
+                        #[no_coverage]
+                        |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+                    )
+                    .expect(READ_STATE_ERROR);
+                for (folder, key, hash) in state.corpus {
+                    if let Some(idx) = new_index(PoolStorageIndex(key)) {
+                        self.state.world.restore_corpus_file(folder, idx, hash);
+                    }
+                }
+            }
+            _ => {
+                if state.pools.is_some() {
+                    println!(
+                        "The state of the pools was saved by a different fuzz target. Its test cases are tested again \
+                        to rebuild the pools."
+                    );
+                }
+                for (_, input) in inputs {
+                    let cplx = input.complexity(&self.state.mutator);
+                    self.state.input_idx = FuzzerInputIndex::Temporary(input);
+                    self.test_and_process_input(cplx)?;
+                }
+            }
+        }
+        let stats = &mut self.state.fuzzer_stats;
+        stats.total_number_of_runs = std::cmp::max(stats.total_number_of_runs, state.total_number_of_runs);
+        stats.number_of_runs_since_last_reset_time = stats.total_number_of_runs;
+        self.state.world.set_elapsed_time_since_start(state.elapsed_time);
+        fastrand::seed(state.seed);
+        Ok(nbr_inputs)
+    }
+
+    /// Test the inputs given to [`initial_inputs`](crate::builder::FuzzerBuilder5::initial_inputs). Return the number
+    /// of tested inputs.
+    ///
+    /// The inputs rejected by the mutator or more complex than the maximum complexity are skipped, with a warning.
+    #[no_coverage]
+    fn process_seed_inputs(&mut self) -> Result<usize, ReasonForStopping<T>> {
+        let values = std::mem::take(&mut self.initial_inputs);
+        let nbr_values = values.len();
+        let inputs: Vec<FuzzedInput<T, M>> = values
+            .into_iter()
+            .filter_map(
+                #[no_coverage]
+                |value| self.state.input_from_value(value),
+            )
+            .collect();
+        let nbr_inputs = inputs.len();
+        if nbr_inputs < nbr_values {
+            println!(
+                "WARNING: {} of the {} initial inputs were skipped, because the mutator rejected them or they are \
+                more complex than the maximum complexity.",
+                nbr_values - nbr_inputs,
+                nbr_values
+            );
+        }
+        for input in inputs {
+            let cplx = input.complexity(&self.state.mutator);
+            self.state.input_idx = FuzzerInputIndex::Temporary(input);
+            self.test_and_process_input(cplx)?;
+        }
+        Ok(nbr_inputs)
+    }
+
+    /// Start reading the input corpus and test the first `--bootstrap-count` inputs before mutating any of them.
+    ///
+    /// If the fuzzer resumes from a saved state, the inputs of that state are tested first, followed by the
+    /// initial inputs given to the fuzzer builder. Then the input corpus is read, in batches of [`CORPUS_BATCH_SIZE`] files, until enough inputs were tested. Arbitrary
+    /// inputs make up the difference. The rest of the corpus is read by the main loop.
+    #[no_coverage]
+    fn process_initial_inputs(&mut self) -> Result<(), ReasonForStopping<T>> {
+        let mut nbr_tested_inputs = self.resume_from_saved_state()?;
+        nbr_tested_inputs += self.process_seed_inputs()?;
+
+        self.state
+            .world
+            .start_reading_input_corpus()
+            .expect(READ_INPUT_FILE_ERROR);
+
+        self.state.world.set_checkpoint_instant();
+        nbr_tested_inputs += self.process_next_corpus_batch()?;
+        while nbr_tested_inputs < self.state.settings.bootstrap_count && self.state.world.is_reading_input_corpus() {
+            nbr_tested_inputs += self.process_next_corpus_batch()?;
+        }
+
+        let mut inputs: Vec<FuzzedInput<T, M>> = vec![];
+        for _ in nbr_tested_inputs..self.state.settings.bootstrap_count {
             if let Some((input, _)) = self.state.arbitrary_input() {
                 inputs.push(input);
             } else {
@@ -429,9 +1330,7 @@ where
             #[no_coverage]
             |i| i.complexity(&self.state.mutator) > self.state.settings.max_input_cplx,
         );
-        // assert!(!inputs.is_empty());
 
-        self.state.world.set_checkpoint_instant();
         for input in inputs {
             let cplx = input.complexity(&self.state.mutator);
             self.state.input_idx = FuzzerInputIndex::Temporary(input);
@@ -449,16 +1348,50 @@ where
         );
         if !minify {
             self.process_initial_inputs()?;
-            self.state.world.report_event(
-                FuzzerEvent::DidReadCorpus,
-                Some((&self.state.fuzzer_stats, self.state.sensor_and_pool.stats().as_ref())),
-            );
         }
+        let mut is_reading_corpus = !minify;
 
         self.state.world.set_checkpoint_instant();
         let mut next_milestone = (self.state.fuzzer_stats.total_number_of_runs + 10) * 2;
+        let mut next_corpus_batch = self.state.fuzzer_stats.total_number_of_runs + CORPUS_BATCH_SIZE;
+        let mut next_state_save = self.state.world.elapsed_time_since_start() + SAVE_STATE_INTERVAL;
+        let mut next_corpus_sync = self.state.world.elapsed_time_since_start() + CORPUS_SYNC_INTERVAL;
+        let mut next_ensemble_sync = self.state.world.elapsed_time_since_start() + ENSEMBLE_SYNC_INTERVAL;
+        let stats_interval = self.state.settings.stats_interval.unwrap_or(usize::MAX);
+        let mut next_stats_record = self
+            .state
+            .fuzzer_stats
+            .total_number_of_runs
+            .saturating_add(stats_interval);
         loop {
+            if is_reading_corpus {
+                if !self.state.world.is_reading_input_corpus() {
+                    is_reading_corpus = false;
+                    self.state.world.report_event(
+                        FuzzerEvent::DidReadCorpus,
+                        Some((&self.state.fuzzer_stats, self.state.sensor_and_pool.stats().as_ref())),
+                    );
+                } else if self.state.fuzzer_stats.total_number_of_runs >= next_corpus_batch {
+                    self.process_next_corpus_batch()?;
+                    next_corpus_batch = self.state.fuzzer_stats.total_number_of_runs + CORPUS_BATCH_SIZE;
+                }
+            }
             let duration_since_beginning = self.state.world.elapsed_time_since_start();
+            if duration_since_beginning >= next_corpus_sync {
+                // the new test cases of the other workers are read in batches, like the input corpus
+                if self.state.world.sync_with_other_workers().expect(SYNC_CORPUS_ERROR) > 0 {
+                    is_reading_corpus = true;
+                }
+                next_corpus_sync = duration_since_beginning + CORPUS_SYNC_INTERVAL;
+            }
+            if duration_since_beginning >= next_ensemble_sync {
+                self.process_ensemble_test_cases()?;
+                next_ensemble_sync = duration_since_beginning + ENSEMBLE_SYNC_INTERVAL;
+            }
+            if duration_since_beginning >= next_state_save {
+                self.state.save_state().expect(SAVE_STATE_ERROR);
+                next_state_save = duration_since_beginning + SAVE_STATE_INTERVAL;
+            }
             if duration_since_beginning > self.state.settings.maximum_duration {
                 return Err(ReasonForStopping::MaxDurationReached);
             }
@@ -474,7 +1407,646 @@ where
                 );
                 next_milestone = self.state.fuzzer_stats.total_number_of_runs * 2;
             }
+            if self.state.fuzzer_stats.total_number_of_runs >= next_stats_record {
+                update_fuzzer_stats(&mut self.state.fuzzer_stats, &mut self.state.world);
+                self.state.world.report_event(
+                    FuzzerEvent::Stats,
+                    Some((&self.state.fuzzer_stats, self.state.sensor_and_pool.stats().as_ref())),
+                );
+                next_stats_record = self
+                    .state
+                    .fuzzer_stats
+                    .total_number_of_runs
+                    .saturating_add(stats_interval);
+            }
+        }
+    }
+}
+
+/// Run every test case of the input corpus and return the snapshot of the coverage counters
+/// that were activated by at least one of them.
+///
+/// Coverage is recorded by a [`CodeCoverageSensor`] observing only the files from the current
+/// directory, independently of the sensor that was given to the fuzzer.
+#[no_coverage]
+fn replay_corpus_coverage<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+) -> CoverageSnapshot
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let mut sensor = CodeCoverageSensor::observing_only_files_from_current_dir();
+    let mut activated = BTreeSet::new();
+    replay_corpus(
+        test,
+        mutator,
+        serializer,
+        world,
+        &mut sensor,
+        #[no_coverage]
+        |observations| {
+            activated.extend(observations.into_iter().map(
+                #[no_coverage]
+                |(index, _)| index,
+            ))
+        },
+    );
+    CoverageSnapshot::new(&sensor, &activated)
+}
+
+/// Run every test case of the input corpus and return the per-function summary of their coverage,
+/// recorded by the same sensor as [`replay_corpus_coverage`].
+#[no_coverage]
+fn replay_corpus_coverage_map<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+) -> CorpusCoverageMap
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let mut sensor = CodeCoverageSensor::observing_only_files_from_current_dir();
+    let mut map = CorpusCoverageMap::new(&sensor);
+    replay_corpus(
+        test,
+        mutator,
+        serializer,
+        world,
+        &mut sensor,
+        #[no_coverage]
+        |observations| map.add_observations(&observations),
+    );
+    map
+}
+
+/// Run every test case of the input corpus and return the hit count of each region of the source files,
+/// recorded by the same sensor as [`replay_corpus_coverage`].
+#[no_coverage]
+fn replay_corpus_coverage_report<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+) -> CoverageReport
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let mut sensor = CodeCoverageSensor::observing_only_files_from_current_dir();
+    let mut report = CoverageReport::new(&sensor);
+    replay_corpus(
+        test,
+        mutator,
+        serializer,
+        world,
+        &mut sensor,
+        #[no_coverage]
+        |observations| report.add_observations(&observations),
+    );
+    report
+}
+
+/// Run the test function on each valid input of the input corpus and give the observations of
+/// the code coverage sensor for each of them to `observe`.
+#[no_coverage]
+fn replay_corpus<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+    sensor: &mut CodeCoverageSensor,
+    mut observe: impl FnMut(Vec<(usize, u64)>),
+) where
+    T: Clone,
+    M: Mutator<T>,
+{
+    world.start_reading_input_corpus().expect(READ_INPUT_FILE_ERROR);
+    while world.is_reading_input_corpus() {
+        let batch = world
+            .read_input_corpus_batch(CORPUS_BATCH_SIZE)
+            .expect(READ_INPUT_FILE_ERROR);
+        for (path, data) in batch {
+            let value = if let Some(value) = decode_file(serializer, &path, &data) {
+                value
+            } else {
+                continue;
+            };
+            if mutator.validate_value(&value).is_none() {
+                continue;
+            }
+            sensor.start_recording();
+            let _ = catch_unwind(AssertUnwindSafe(
+                #[no_coverage]
+                || (test)(value.borrow()),
+            ));
+            sensor.stop_recording();
+            observe(sensor.get_observations());
+        }
+    }
+}
+
+/// Load the files of the input corpus one by one, as the fuzzer does, but without running the test function.
+///
+/// Stop at the first file whose decoding by the serializer or validation by the mutator panics, and
+/// report its path and the panic message. The path of each file is printed before it is loaded, so that
+/// the culprit can also be found if loading it crashes the process without panicking.
+#[no_coverage]
+fn bisect_corpus<T, M>(mutator: &M, serializer: &dyn Serializer<Value = T>, world: &mut World) -> TerminationStatus
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    world.start_reading_input_corpus().expect(READ_INPUT_FILE_ERROR);
+    let mut nbr_files = 0;
+    let mut nbr_undecodable = 0;
+    let mut nbr_invalid = 0;
+    while let Some((path, data)) = world.read_next_input_corpus_file().expect(READ_INPUT_FILE_ERROR) {
+        println!("loading {}", path.display());
+        nbr_files += 1;
+        let result = catch_unwind(AssertUnwindSafe(
+            #[no_coverage]
+            || {
+                let value = if let Some(value) = decode_file(serializer, &path, &data) {
+                    value
+                } else {
+                    nbr_undecodable += 1;
+                    return;
+                };
+                if let Some(cache) = mutator.validate_value(&value) {
+                    let _ = mutator.default_mutation_step(&value, &cache);
+                    let _ = mutator.complexity(&value, &cache);
+                } else {
+                    nbr_invalid += 1;
+                }
+            },
+        ));
+        if let Err(payload) = result {
+            println!("\n================ CORPUS FILE PANICKED ================");
+            println!("Loading the file {} panicked with the message:", path.display());
+            println!("{}", payload_message(payload.as_ref()));
+            return TerminationStatus::TestFailure;
+        }
+    }
+    println!(
+        "The {} files of the input corpus were loaded without panicking. {} of them could not be decoded and {} were rejected by the mutator.",
+        nbr_files, nbr_undecodable, nbr_invalid
+    );
+    TerminationStatus::Success
+}
+
+/// Merge the test cases of `corpora` into the output corpus, keeping only the ones that add code coverage.
+///
+/// The test cases already in the output corpus are replayed first and kept as they are. Then the valid
+/// test cases of all the corpora are replayed from the simplest to the most complex, and a test case is
+/// added to the output corpus only if it activates a coverage counter that no test case replayed before it
+/// activated. Coverage is recorded by the same sensor as [`replay_corpus_coverage`].
+#[no_coverage]
+fn merge_corpora<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+    corpus_out: &Path,
+    corpora: &[PathBuf],
+) -> TerminationStatus
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let mut sensor = CodeCoverageSensor::observing_only_files_from_current_dir();
+    let mut activated = BTreeSet::new();
+
+    let run = #[no_coverage]
+    |value: &T, sensor: &mut CodeCoverageSensor| -> Vec<(usize, u64)> {
+        sensor.start_recording();
+        let _ = catch_unwind(AssertUnwindSafe(
+            #[no_coverage]
+            || (test)(value),
+        ));
+        sensor.stop_recording();
+        sensor.get_observations()
+    };
+
+    let mut nbr_existing = 0;
+    for (path, data) in world.read_corpus_folder(corpus_out).expect(READ_INPUT_FILE_ERROR) {
+        if let Some(value) = decode_file(serializer, &path, &data) {
+            if mutator.validate_value(&value).is_some() {
+                nbr_existing += 1;
+                activated.extend(run(&value, &mut sensor).into_iter().map(
+                    #[no_coverage]
+                    |(index, _)| index,
+                ));
+            }
+        }
+    }
+
+    // (complexity, index of the source corpus, value)
+    let mut inputs = vec![];
+    let mut nbr_inputs = vec![0; corpora.len()];
+    for (corpus_idx, corpus) in corpora.iter().enumerate() {
+        for (path, data) in world.read_corpus_folder(corpus).expect(READ_INPUT_FILE_ERROR) {
+            let value = if let Some(value) = decode_file(serializer, &path, &data) {
+                value
+            } else {
+                continue;
+            };
+            if let Some(cache) = mutator.validate_value(&value) {
+                let cplx = mutator.complexity(&value, &cache);
+                nbr_inputs[corpus_idx] += 1;
+                inputs.push((cplx, corpus_idx, value));
+            }
+        }
+    }
+    // the sort is stable, so test cases of equal complexity are replayed in the order of the corpora
+    inputs.sort_by(
+        #[no_coverage]
+        |a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal),
+    );
+
+    let mut nbr_kept = vec![0; corpora.len()];
+    for (_, corpus_idx, value) in inputs {
+        let mut adds_coverage = false;
+        for (index, _) in run(&value, &mut sensor) {
+            adds_coverage |= activated.insert(index);
+        }
+        if adds_coverage {
+            nbr_kept[corpus_idx] += 1;
+            let content = serializer.to_data(&value);
+            world
+                .add_to_output_corpus(
+                    Path::new(""),
+                    world.hash(&content),
+                    content,
+                    None,
+                    serializer.extension(),
+                )
+                .expect(UPDATE_CORPUS_ERROR);
+        }
+    }
+    world.mark_output_corpus_complete().expect(UPDATE_CORPUS_ERROR);
+
+    println!(
+        "{} test cases of the output corpus {} were kept.",
+        nbr_existing,
+        corpus_out.display()
+    );
+    for (corpus_idx, corpus) in corpora.iter().enumerate() {
+        println!(
+            "{} of the {} test cases of {} were added to the output corpus.",
+            nbr_kept[corpus_idx],
+            nbr_inputs[corpus_idx],
+            corpus.display()
+        );
+    }
+    TerminationStatus::Success
+}
+
+/// Remove from the input corpus the test cases whose coverage is subsumed by simpler test cases.
+///
+/// The valid test cases of the input corpus are replayed from the simplest to the most complex, and a test case is
+/// kept only if it activates a coverage counter that no simpler test case activated. The kept test cases are
+/// written to the output corpus, at the same path as in the input corpus, and the redundant ones are removed from
+/// it, such that the input corpus is minified in place when it is also the output corpus. The number of counters
+/// that each kept test case is the only one to activate is then printed. Coverage is recorded by the same sensor
+/// as [`replay_corpus_coverage`].
+///
+/// With `post_minify_check`, the kept test cases are then decoded from the data written to the output corpus and
+/// replayed, and [`TerminationStatus::TestFailure`] is returned if they do not activate the same counters as the
+/// whole input corpus.
+#[no_coverage]
+fn minify_corpus<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+    corpus_in: &Path,
+    post_minify_check: bool,
+) -> TerminationStatus
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    /// The indices of the coverage counters activated by the test case
+    #[no_coverage]
+    fn activated_counters<T>(sensor: &mut CodeCoverageSensor, test: &dyn Fn(&T) -> bool, value: &T) -> BTreeSet<usize> {
+        // the panics of the test function are expected, and their messages would clutter the output
+        let panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(
+            #[no_coverage]
+            |_| {},
+        ));
+        sensor.start_recording();
+        let _ = catch_unwind(AssertUnwindSafe(
+            #[no_coverage]
+            || (test)(value),
+        ));
+        sensor.stop_recording();
+        std::panic::set_hook(panic_hook);
+        sensor
+            .get_observations()
+            .into_iter()
+            .map(
+                #[no_coverage]
+                |(index, _)| index,
+            )
+            .collect()
+    }
+
+    let mut sensor = CodeCoverageSensor::observing_only_files_from_current_dir();
+
+    // (complexity, path, value)
+    let mut inputs = vec![];
+    let mut nbr_files = 0;
+    world.start_reading_input_corpus().expect(READ_INPUT_FILE_ERROR);
+    while let Some((path, data)) = world.read_next_input_corpus_file().expect(READ_INPUT_FILE_ERROR) {
+        nbr_files += 1;
+        let value = if let Some(value) = decode_file(serializer, &path, &data) {
+            value
+        } else {
+            continue;
+        };
+        if let Some(cache) = mutator.validate_value(&value) {
+            let cplx = mutator.complexity(&value, &cache);
+            inputs.push((cplx, path, value));
+        }
+    }
+    // the sort is stable, so test cases of equal complexity are replayed in the order of their paths
+    inputs.sort_by(
+        #[no_coverage]
+        |a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal),
+    );
+    let nbr_inputs = inputs.len();
+
+    let mut activated = BTreeSet::new();
+    // (path, counters activated by the test case, data written to the output corpus)
+    let mut kept = vec![];
+    for (_, path, value) in inputs {
+        let counters = activated_counters(&mut sensor, test, value.borrow());
+
+        // the test case keeps its subfolder, name, and extension in the output corpus
+        let file = path.strip_prefix(corpus_in).unwrap_or(&path).to_path_buf();
+
+        if counters.is_subset(&activated) {
+            world.remove_file_from_output_corpus(&file).expect(UPDATE_CORPUS_ERROR);
+        } else {
+            activated.extend(counters.iter().copied());
+            let data = serializer.to_data(&value);
+            world
+                .add_file_to_output_corpus(&file, data.clone(), None)
+                .expect(UPDATE_CORPUS_ERROR);
+            kept.push((path, counters, data));
+        }
+    }
+    world.mark_output_corpus_complete().expect(UPDATE_CORPUS_ERROR);
+
+    // the number of kept test cases activating each counter
+    let mut nbr_activating = BTreeMap::<usize, usize>::new();
+    for (_, counters, _) in &kept {
+        for &index in counters {
+            *nbr_activating.entry(index).or_default() += 1;
+        }
+    }
+    println!("Number of coverage counters activated by each kept test case only:");
+    for (path, counters, _) in &kept {
+        let nbr_unique = counters
+            .iter()
+            .filter(
+                #[no_coverage]
+                |index| nbr_activating[*index] == 1,
+            )
+            .count();
+        println!("{:>8} {}", nbr_unique, path.display());
+    }
+    println!(
+        "{} of the {} valid test cases of the input corpus were kept, activating {} coverage counters. {} of its {} files could not be decoded or were rejected by the mutator.",
+        kept.len(),
+        nbr_inputs,
+        activated.len(),
+        nbr_files - nbr_inputs,
+        nbr_files
+    );
+
+    if post_minify_check {
+        let mut replayed = BTreeSet::new();
+        for (path, _, data) in &kept {
+            if let Some(value) = decode_file(serializer, path, data) {
+                replayed.extend(activated_counters(&mut sensor, test, value.borrow()));
+            }
+        }
+        if replayed != activated {
+            let lost = activated.difference(&replayed).copied().collect::<Vec<_>>();
+            let gained = replayed.difference(&activated).copied().collect::<Vec<_>>();
+            println!(
+                "Post-minify check failed: the kept test cases activate {} coverage counters when they are replayed, \
+                but the input corpus activated {}. {} counters were lost: {:?}. {} counters were gained: {:?}.",
+                replayed.len(),
+                activated.len(),
+                lost.len(),
+                lost,
+                gained.len(),
+                gained
+            );
+            return TerminationStatus::TestFailure;
+        }
+        println!(
+            "Post-minify check passed: the kept test cases activate the same {} coverage counters as the input corpus.",
+            activated.len()
+        );
+    }
+    TerminationStatus::Success
+}
+
+/// Run the test function once on each test case of the input corpus and of the artifacts folder, without fuzzing.
+///
+/// The path of each failing test case is reported, along with the panic message if it panicked. The files that
+/// cannot be decoded by the serializer or that are rejected by the mutator are counted but not run. Returns
+/// [`TerminationStatus::TestFailure`] if any test case failed.
+#[no_coverage]
+fn run_corpus<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    world: &mut World,
+    artifacts_folder: Option<&Path>,
+) -> TerminationStatus
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let mut files = vec![];
+    world.start_reading_input_corpus().expect(READ_INPUT_FILE_ERROR);
+    while let Some(file) = world.read_next_input_corpus_file().expect(READ_INPUT_FILE_ERROR) {
+        files.push(file);
+    }
+    if let Some(artifacts_folder) = artifacts_folder {
+        files.extend(world.read_corpus_folder(artifacts_folder).expect(READ_INPUT_FILE_ERROR));
+    }
+
+    let mut nbr_run = 0;
+    let mut nbr_undecodable = 0;
+    let mut nbr_invalid = 0;
+    let mut failures = vec![];
+    for (path, data) in files {
+        let value = if let Some(value) = decode_file(serializer, &path, &data) {
+            value
+        } else {
+            nbr_undecodable += 1;
+            continue;
+        };
+        if mutator.validate_value(&value).is_none() {
+            nbr_invalid += 1;
+            continue;
         }
+        nbr_run += 1;
+        let result = catch_unwind(AssertUnwindSafe(
+            #[no_coverage]
+            || (test)(value.borrow()),
+        ));
+        match result {
+            Ok(true) => {}
+            Ok(false) => failures.push((path, None)),
+            Err(payload) => failures.push((path, Some(payload_message(payload.as_ref())))),
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\n================ TEST FAILURES ================");
+        for (path, message) in &failures {
+            if let Some(message) = message {
+                println!("{} panicked: {}", path.display(), message);
+            } else {
+                println!("{} returned false", path.display());
+            }
+        }
+    }
+    println!(
+        "{} of the {} test cases that were run failed. {} files could not be decoded and {} were rejected by the mutator.",
+        failures.len(),
+        nbr_run,
+        nbr_undecodable,
+        nbr_invalid
+    );
+    if failures.is_empty() {
+        TerminationStatus::Success
+    } else {
+        TerminationStatus::TestFailure
+    }
+}
+
+/// Run the test function once on each test case of the input corpus and of the artifacts folder, like the
+/// `run-corpus` command, but return whether they all passed instead of exiting the process.
+///
+/// It is used by the tests written with the `#[fuzz_test]` attribute when they are launched by `cargo test`.
+#[no_coverage]
+pub(crate) fn run_corpus_without_exiting<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &M,
+    serializer: &dyn Serializer<Value = T>,
+    args: Arguments,
+) -> bool
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let artifacts_folder = args.artifacts_folder.clone();
+    let mut world = World::new(args).expect(WORLD_NEW_ERROR);
+    let status = run_corpus(test, mutator, serializer, &mut world, artifacts_folder.as_deref());
+    matches!(status, TerminationStatus::Success)
+}
+
+/// Create the world of the `fuzz` command, which saves the output corpus in the given storage, if any, and is
+/// connected to the ensemble given by `--ensemble`, if any
+#[no_coverage]
+fn world_with_corpus_storage(args: &Arguments, corpus_storage: Option<Box<dyn CorpusStorage>>) -> World {
+    let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+    if let Some(corpus_storage) = corpus_storage {
+        world.set_corpus_storage(corpus_storage);
+    }
+    if let Some(address) = &args.ensemble {
+        world.connect_to_ensemble(address).expect(CONNECT_ENSEMBLE_ERROR);
+    }
+    world
+}
+
+/// Whether the test function held more than `malloc_limit` megabytes during its last run, in which case
+/// `TEST_FAILURE` is set accordingly
+#[no_coverage]
+fn exceeds_malloc_limit(malloc_limit: usize) -> bool {
+    let peak_live_bytes = allocation_counters().peak_live_bytes;
+    if peak_live_bytes <= malloc_limit * 1024 * 1024 {
+        return false;
+    }
+    let display = format!(
+        "the test function held {}MB of allocated memory, which exceeds the --{} of {}MB",
+        peak_live_bytes / (1024 * 1024),
+        MALLOC_LIMIT_FLAG,
+        malloc_limit
+    );
+    unsafe {
+        TEST_FAILURE = Some(TestFailure {
+            display: if let Some(name) = CURRENT_TEST_FUNCTION {
+                format!("test function `{}`: {}", name, display)
+            } else {
+                display
+            },
+            id: MALLOC_LIMIT_BUCKET_ID,
+            signature: MALLOC_LIMIT_BUCKET_ID,
+        });
+    }
+    true
+}
+
+/// Warn that the `--malloc-limit` has no effect if the fuzz target does not count its allocations.
+/// With `--strict`, exit instead.
+#[no_coverage]
+fn check_malloc_limit(settings: &Arguments) {
+    if settings.malloc_limit.is_none() || counting_allocator_is_installed() {
+        return;
+    }
+    println!("\n================ ALLOCATIONS ARE NOT COUNTED ================");
+    println!(
+        "The --{} option has no effect because the fuzz target does not count its allocations. Install the \
+        allocator with `#[global_allocator] static ALLOCATOR: CountingAllocator<System> = \
+        CountingAllocator::new(System);`, using fuzzcheck::sensors_and_pools::CountingAllocator and std::alloc::System.",
+        MALLOC_LIMIT_FLAG
+    );
+    if settings.strict {
+        println!("The fuzzer is stopped because of --{}.", STRICT_FLAG);
+        exit(TerminationStatus::Unknown as i32);
+    }
+}
+
+/// Warn that the fuzzer cannot generate any test case if the maximum complexity is lower than the minimum
+/// complexity of the values of the mutator. With `--strict`, exit instead.
+#[no_coverage]
+fn check_max_input_cplx<T, M>(mutator: &M, settings: &Arguments)
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    let min_cplx = mutator.min_complexity();
+    if settings.max_input_cplx >= min_cplx {
+        return;
+    }
+    println!("\n================ MAXIMUM COMPLEXITY TOO LOW ================");
+    println!(
+        "The maximum complexity of the test cases ({}) is lower than the minimum complexity of the values \
+        generated by the mutator ({:.2}). The fuzzer cannot generate any test case and will only run the ones \
+        of the input corpus. Use --{} {} or greater.",
+        settings.max_input_cplx,
+        min_cplx,
+        MAX_INPUT_CPLX_FLAG,
+        min_cplx.ceil()
+    );
+    if settings.strict {
+        println!("The fuzzer is stopped because of --{}.", STRICT_FLAG);
+        exit(TerminationStatus::Unknown as i32);
     }
 }
 
@@ -492,6 +2064,10 @@ pub fn launch<T, M>(
     serializer: Box<dyn Serializer<Value = T>>,
     sensor_and_pool: Box<dyn SensorAndPool>,
     mut args: Arguments,
+    on_crash: Option<Box<dyn FnMut(&CrashInfo)>>,
+    debug_value: Option<Box<dyn Fn(&T) -> String>>,
+    initial_inputs: Vec<T>,
+    corpus_storage: Option<Box<dyn CorpusStorage>>,
 ) -> FuzzingResult<T>
 where
     T: Clone,
@@ -501,67 +2077,68 @@ where
     let command = &args.command;
     let reason_for_stopping = match command {
         FuzzerCommand::Fuzz => {
-            if !args.stop_after_first_failure {
+            check_max_input_cplx(&mutator, &args);
+            check_malloc_limit(&args);
+            let sensor_and_pool: Box<dyn SensorAndPool> = if !args.stop_after_first_failure {
                 let test_failure_sensor = TestFailureSensor::default();
-                let test_failure_pool = TestFailurePool::new("test_failures");
-                let sensor_and_pool = AndSensorAndPool::new(
+                let test_failure_pool = TestFailurePool::new(TEST_FAILURES_FOLDER, args.max_artifacts_per_signature);
+                Box::new(AndSensorAndPool::new(
                     sensor_and_pool,
                     Box::new((test_failure_sensor, test_failure_pool)),
                     10.0,
                     1.0,
-                );
-                let mut fuzzer = Fuzzer::new(
-                    test,
-                    mutator,
-                    serializer,
-                    Box::new(sensor_and_pool),
-                    args.clone(),
-                    World::new(args.clone()).expect(WORLD_NEW_ERROR),
-                );
-
-                let mut stats_headers = vec![CSVField::String("time".to_string())];
-                stats_headers.extend(fuzzer.state.fuzzer_stats.csv_headers());
-                stats_headers.extend(fuzzer.state.sensor_and_pool.stats().csv_headers());
-                fuzzer
-                    .state
-                    .world
-                    .append_stats_file(&stats_headers)
-                    .expect(WRITE_STATS_ERROR);
-                unsafe { fuzzer.state.set_up_signal_handler() };
-
-                let reason_for_stopping = fuzzer.main_loop(false).unwrap_err();
-                fuzzer.state.write_stats().expect(WRITE_STATS_ERROR);
-
-                reason_for_stopping
+                ))
             } else {
-                let mut fuzzer = Fuzzer::new(
-                    test,
-                    mutator,
-                    serializer,
-                    sensor_and_pool,
-                    args.clone(),
-                    World::new(args.clone()).expect(WORLD_NEW_ERROR),
-                );
-                unsafe { fuzzer.state.set_up_signal_handler() };
+                sensor_and_pool
+            };
+            let mut fuzzer = Fuzzer::new(
+                test,
+                mutator,
+                serializer,
+                sensor_and_pool,
+                args.clone(),
+                world_with_corpus_storage(&args, corpus_storage),
+                on_crash,
+                debug_value,
+            );
+            fuzzer.initial_inputs = initial_inputs;
 
-                let mut stats_headers = vec![CSVField::String("time".to_string())];
-                stats_headers.extend(fuzzer.state.fuzzer_stats.csv_headers());
-                stats_headers.extend(fuzzer.state.sensor_and_pool.stats().csv_headers());
-                fuzzer
-                    .state
-                    .world
-                    .append_stats_file(&stats_headers)
-                    .expect(WRITE_STATS_ERROR);
-                let reason_for_stopping = fuzzer.main_loop(false).unwrap_err();
-                fuzzer.state.write_stats().expect(WRITE_STATS_ERROR);
+            let mut stats_headers = vec![CSVField::String("time".to_string())];
+            stats_headers.extend(fuzzer.state.fuzzer_stats.csv_headers());
+            stats_headers.extend(fuzzer.state.sensor_and_pool.stats().csv_headers());
+            fuzzer
+                .state
+                .world
+                .append_stats_file(&stats_headers)
+                .expect(WRITE_STATS_ERROR);
+            unsafe { fuzzer.state.set_up_signal_handler() };
 
-                reason_for_stopping
-            }
+            let reason_for_stopping = fuzzer.main_loop(false).unwrap_err();
+            fuzzer.state.write_stats().expect(WRITE_STATS_ERROR);
+            fuzzer.state.save_state().expect(SAVE_STATE_ERROR);
+            let found_test_failure = unsafe {
+                matches!(reason_for_stopping, ReasonForStopping::TestFailure(_)) || DID_FIND_ANY_TEST_FAILURE
+            };
+            fuzzer
+                .state
+                .write_results(found_test_failure, reason_for_stopping.name())
+                .expect(WRITE_RESULTS_ERROR);
+            fuzzer
+                .state
+                .world
+                .mark_output_corpus_complete()
+                .expect(UPDATE_CORPUS_ERROR);
+            fuzzer
+                .state
+                .world
+                .report_slowest_inputs(&fuzzer.state.slowest_inputs.sorted());
+
+            reason_for_stopping
         }
         FuzzerCommand::MinifyInput { input_file } => {
             let world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
             let value = world.read_input_file(input_file).expect(READ_INPUT_FILE_ERROR);
-            let value = serializer.from_data(&value).expect(SERIALIZER_FROM_DATA_ERROR);
+            let value = decode_file(serializer.as_ref(), input_file, &value).expect(SERIALIZER_FROM_DATA_ERROR);
             if let Some(cache) = mutator.validate_value(&value) {
                 let mutation_step = mutator.default_mutation_step(&value, &cache);
                 args.max_input_cplx = mutator.complexity(&value, &cache) - 0.01;
@@ -581,6 +2158,8 @@ where
                     Box::new(sensor_and_pool),
                     args.clone(),
                     world,
+                    on_crash,
+                    debug_value,
                 );
                 fuzzer
                     .state
@@ -596,11 +2175,103 @@ where
                 panic!("A value in the input corpus is invalid.");
             }
         }
+        FuzzerCommand::WriteCoverage { snapshot_file } => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let snapshot = replay_corpus_coverage(test.as_ref(), &mutator, serializer.as_ref(), &mut world);
+            std::fs::write(snapshot_file, snapshot.to_string()).expect(WRITE_COVERAGE_SNAPSHOT_ERROR);
+            println!("Coverage snapshot saved at {:?}", snapshot_file);
+            exit(TerminationStatus::Success as i32);
+        }
+        FuzzerCommand::CoverageMap { output_file } => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let map = replay_corpus_coverage_map(test.as_ref(), &mutator, serializer.as_ref(), &mut world);
+            std::fs::write(output_file, map.to_string()).expect(WRITE_COVERAGE_MAP_ERROR);
+            println!("Corpus coverage map saved at {:?}", output_file);
+            exit(TerminationStatus::Success as i32);
+        }
+        FuzzerCommand::CoverageReport { output_file } => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let report = replay_corpus_coverage_report(test.as_ref(), &mutator, serializer.as_ref(), &mut world);
+            let content = if output_file.extension() == Some("json".as_ref()) {
+                report.to_json()
+            } else {
+                report.to_lcov()
+            };
+            std::fs::write(output_file, content).expect(WRITE_COVERAGE_REPORT_ERROR);
+            println!("Coverage report saved at {:?}", output_file);
+            exit(TerminationStatus::Success as i32);
+        }
+        FuzzerCommand::AssertCoverage { snapshot_file } => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let expected = std::fs::read_to_string(snapshot_file).expect(READ_COVERAGE_SNAPSHOT_ERROR);
+            let expected = CoverageSnapshot::parse(&expected).expect(READ_COVERAGE_SNAPSHOT_ERROR);
+            let snapshot = replay_corpus_coverage(test.as_ref(), &mutator, serializer.as_ref(), &mut world);
+            let diff = snapshot.diff(&expected);
+            if diff.is_empty() {
+                println!(
+                    "The coverage of the corpus is identical to the snapshot at {:?}",
+                    snapshot_file
+                );
+                exit(TerminationStatus::Success as i32);
+            } else {
+                println!("\n================ COVERAGE CHANGED ================");
+                println!(
+                    "The coverage of the corpus differs from the snapshot at {:?}\n(-: no longer activated, +: newly activated)",
+                    snapshot_file
+                );
+                for line in diff {
+                    println!("{}", line);
+                }
+                exit(TerminationStatus::TestFailure as i32);
+            }
+        }
+        FuzzerCommand::BisectCorpus => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let status = bisect_corpus(&mutator, serializer.as_ref(), &mut world);
+            exit(status as i32);
+        }
+        FuzzerCommand::Merge { corpora } => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let corpus_out = args.corpus_out.clone().expect(MERGE_OUTPUT_CORPUS_ERROR);
+            let status = merge_corpora(
+                test.as_ref(),
+                &mutator,
+                serializer.as_ref(),
+                &mut world,
+                &corpus_out,
+                corpora,
+            );
+            exit(status as i32);
+        }
+        FuzzerCommand::MinifyCorpus => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let corpus_in = args.corpus_in.clone().expect(MINIFY_CORPUS_INPUT_CORPUS_ERROR);
+            let status = minify_corpus(
+                test.as_ref(),
+                &mutator,
+                serializer.as_ref(),
+                &mut world,
+                &corpus_in,
+                args.post_minify_check,
+            );
+            exit(status as i32);
+        }
+        FuzzerCommand::RunCorpus => {
+            let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+            let status = run_corpus(
+                test.as_ref(),
+                &mutator,
+                serializer.as_ref(),
+                &mut world,
+                args.artifacts_folder.as_deref(),
+            );
+            exit(status as i32);
+        }
         FuzzerCommand::Read { input_file } => {
             // no signal handlers are installed, but that should be ok as the exit code won't be 0
             let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
             let value = world.read_input_file(input_file).expect(READ_INPUT_FILE_ERROR);
-            let value = serializer.from_data(&value).expect(SERIALIZER_FROM_DATA_ERROR);
+            let value = decode_file(serializer.as_ref(), input_file, &value).expect(SERIALIZER_FROM_DATA_ERROR);
             if let Some(cache) = mutator.validate_value(&value) {
                 let mutation_step = mutator.default_mutation_step(&value, &cache);
                 let input = FuzzedInput::new(value, cache, mutation_step, 0);
@@ -614,9 +2285,10 @@ where
                 if result.is_err() || !result.unwrap() {
                     world.report_event(FuzzerEvent::TestFailure, None);
                     let content = serializer.to_data(&input.value);
-                    world
+                    let input_path = world
                         .save_artifact(content, cplx, serializer.extension())
                         .expect(SAVE_ARTIFACTS_ERROR);
+                    describe_failing_test_case(&debug_value, &world, &input.value, input_path.as_deref());
                     // in this case we really want to exit with a non-zero termination status here
                     // because the Read command is only used by the input minify command from cargo-fuzzcheck
                     // which checks that a crash happens by looking at the exit code
@@ -641,3 +2313,60 @@ where
         reason_for_stopping,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_ignored_panic, payload_message};
+    use crate::mutators::integer::U8Mutator;
+    use crate::{FuzzedInput, Mutator};
+    use regex::Regex;
+    use std::panic::catch_unwind;
+
+    #[test]
+    #[no_coverage]
+    fn test_ignored_panics() {
+        let ignored_panics = vec![
+            Regex::new(r"^index out of bounds: the len is \d+").unwrap(),
+            Regex::new("it's \"not\" a bug").unwrap(),
+        ];
+        let payload =
+            catch_unwind(|| panic!("index out of bounds: the len is {} but the index is {}", 3, 5)).unwrap_err();
+        let message = payload_message(payload.as_ref());
+        assert!(is_ignored_panic(&ignored_panics, &message));
+        assert!(is_ignored_panic(&ignored_panics, "so it's \"not\" a bug"));
+
+        assert!(!is_ignored_panic(&ignored_panics, "attempt to subtract with overflow"));
+        assert!(!is_ignored_panic(&ignored_panics, "out of bounds: the len is 3"));
+        assert!(!is_ignored_panic(&[], &message));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_replay_mutations() {
+        let mut m = U8Mutator::default();
+        let new_input = #[no_coverage]
+        |m: &U8Mutator| {
+            let cache = m.validate_value(&100).unwrap();
+            let step = m.default_mutation_step(&100, &cache);
+            FuzzedInput::<u8, U8Mutator>::new(100, cache, step, 0)
+        };
+        let mut input = new_input(&m);
+        let mut mutated = vec![];
+        for _ in 0..20 {
+            let (token, _) = input.mutate(&mut m, 8.).unwrap();
+            mutated.push(input.value);
+            input.unmutate(&m, token);
+        }
+        assert_eq!(input.nbr_mutations, 20);
+
+        let mut restored = new_input(&m);
+        restored.replay_mutations(&m, 12, 8.);
+        assert_eq!(restored.nbr_mutations, 12);
+        assert_eq!(restored.value, 100);
+        for expected in &mutated[12..] {
+            let (token, _) = restored.mutate(&mut m, 8.).unwrap();
+            assert_eq!(restored.value, *expected);
+            restored.unmutate(&m, token);
+        }
+    }
+}
@@ -9,6 +9,7 @@
 //! The crate documentation contains information on how to set up and launch a fuzz-test ([here](crate::builder)) but
 //! also documents the core traits ([`Pool`], [`Sensor`], [`Mutator`], etc.) that are useful to understand how it works
 //! and to extend it.
+#![feature(backtrace)]
 #![feature(doc_cfg)]
 #![feature(drain_filter)]
 #![feature(never_type)]
@@ -19,6 +20,7 @@
 #![feature(arc_new_cyclic)]
 #![feature(trivial_bounds)]
 #![feature(stmt_expr_attributes)]
+#![feature(link_llvm_intrinsics)]
 #![allow(clippy::nonstandard_macro_braces)]
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::partialeq_ne_impl)]
@@ -36,21 +38,35 @@ mod bitset;
 pub mod builder;
 mod code_coverage_sensor;
 mod data_structures;
+mod dictionary;
 mod fenwick_tree;
+mod fork_server;
 mod fuzzer;
+#[cfg(feature = "libfuzzer")]
+#[doc(cfg(feature = "libfuzzer"))]
+pub mod libfuzzer;
 
 pub mod mutators;
+mod saved_state;
 pub mod sensors_and_pools;
 pub mod serializers;
 mod signals_handler;
 mod split_string;
+#[cfg(feature = "sqlite")]
+mod sqlite_corpus_storage;
 mod traits;
+#[cfg(feature = "tui")]
+mod tui;
 mod world;
 
 pub use fuzzcheck_common::arg::Arguments;
 
 pub(crate) use split_string::split_string_by_whitespace;
 
+#[doc(inline)]
+pub use crate::fuzzer::CrashInfo;
+#[doc(inline)]
+pub use crate::fuzzer::CrashKind;
 #[doc(inline)]
 pub use crate::fuzzer::FuzzingResult;
 #[doc(inline)]
@@ -75,6 +91,16 @@ pub use crate::traits::Stats;
 #[doc(inline)]
 pub use crate::fuzzer::PoolStorageIndex;
 
+#[cfg(feature = "sqlite")]
+#[doc(inline)]
+pub use crate::sqlite_corpus_storage::{SqliteCorpusEntry, SqliteCorpusStorage};
+#[doc(inline)]
+pub use crate::world::CorpusStorage;
+#[doc(inline)]
+pub use crate::world::FileSystemCorpusStorage;
+#[doc(inline)]
+pub use crate::world::InMemoryCorpusStorage;
+
 #[doc(inline)]
 pub use fuzzer::ReasonForStopping;
 #[doc(inline)]
@@ -95,15 +121,32 @@ pub use traits::{CSVField, ToCSV};
 #[doc(inline)]
 pub use builder::fuzz_test;
 
+#[doc(inline)]
+pub use builder::fuzz_tests;
+
 #[doc(inline)]
 pub use serializers::ByteSerializer;
 #[doc(inline)]
+pub use serializers::MultiFormatSerializer;
+#[doc(inline)]
 pub use serializers::StringSerializer;
 
 #[cfg(feature = "serde_json_serializer")]
 #[doc(inline)]
 pub use serializers::SerdeSerializer;
 
+#[cfg(feature = "bincode_serializer")]
+#[doc(inline)]
+pub use serializers::BincodeSerializer;
+
+#[cfg(feature = "prost_serializer")]
+#[doc(inline)]
+pub use serializers::ProstSerializer;
+
+#[cfg(feature = "arbitrary_mutator")]
+#[doc(inline)]
+pub use serializers::ArbitrarySerializer;
+
 /// Implement a mutator for the type and make it the type’s `DefaultMutator`.
 ///
 /// The mutator will be called `<Name>Mutator`. It can be constructed in two ways:
@@ -133,6 +176,26 @@ pub use serializers::SerdeSerializer;
 /// let mutator = EitherMutator::new(u8::default_mutator(), bool::default_mutator());
 /// // mutator impl Mutator<Either<u8, bool>>
 /// ```
+///
+/// The mutator of a field can be constrained to a range with the `#[field_range(min..=max)]` attribute, which
+/// bounds the value of integers, floats, and characters, and the length of vectors and strings. See
+/// [`DefaultMutatorWithinRange`](crate::mutators::DefaultMutatorWithinRange) for the supported types.
+/// ```
+/// # #![feature(no_coverage)]
+/// # #![feature(trivial_bounds)]
+/// use fuzzcheck::DefaultMutator;
+///
+/// #[derive(Clone, DefaultMutator)]
+/// struct Packet {
+///     #[field_range(1..=64)]
+///     ttl: u8,
+///     #[field_range(0.0..=1.0)]
+///     loss: f64,
+///     #[field_range(0..=16)]
+///     payload: Vec<u8>,
+/// }
+/// let mutator = Packet::default_mutator();
+/// ```
 pub use fuzzcheck_mutators_derive::DefaultMutator;
 
 /**
@@ -227,6 +290,36 @@ pub use fuzzcheck_mutators_derive::DefaultMutator;
 */
 pub use fuzzcheck_mutators_derive::make_mutator;
 
+/**
+    Turn a function into a fuzz test that is also a regular test over its saved test cases.
+
+    The function takes the values to test as arguments, and can return `()`, a `bool`, or a `Result`, like the
+    functions given to [`fuzz_test`](fn@crate::fuzz_test). The attribute replaces it with a `#[test]` of the same name,
+    which uses the default mutator of the arguments and serializes them with serde:
+    ```ignore
+    #[cfg(test)]
+    mod tests {
+        #[fuzzcheck::fuzz_test]
+        fn parse_roundtrip(text: &str, max_depth: u8) {
+            // ..
+        }
+    }
+    ```
+    * `cargo fuzzcheck tests::parse_roundtrip` fuzzes it, as if it was written with [`fuzz_test`](fn@crate::fuzz_test)
+      and [`arguments_from_cargo_fuzzcheck`](crate::builder::FuzzerBuilder4::arguments_from_cargo_fuzzcheck).
+    * `cargo test` runs it on each test case of the `fuzz/tests::parse_roundtrip/corpus` and
+      `fuzz/tests::parse_roundtrip/artifacts` folders, and fails if any of them fails.
+
+    A parameter `&T` is given a borrow of a `T::Owned`, while the other parameters are given a clone of a value
+    generated by the fuzzer, so the types of the arguments must implement `Clone`, [`DefaultMutator`], and the
+    `Serialize` and `Deserialize` traits of serde. A function with more than one parameter is fuzzed with a tuple of
+    its arguments. Generic, async, and unsafe functions are not supported.
+
+    See [`FuzzerBuilder4::launch_or_run_corpus`](crate::builder::FuzzerBuilder4::launch_or_run_corpus) for more details.
+*/
+#[cfg(feature = "serde_json_serializer")]
+pub use fuzzcheck_mutators_derive::fuzz_test;
+
 /**
  * A struct that stores the value, cache, and mutation step of an input.
  * It is used for convenience.
@@ -236,6 +329,9 @@ pub(crate) struct FuzzedInput<T: Clone, Mut: Mutator<T>> {
     pub cache: Mut::Cache,
     pub mutation_step: Mut::MutationStep,
     pub generation: usize,
+    /// The number of times the input was mutated with `mutation_step`, which is saved with `--resume` to re-derive
+    /// the mutation step when the fuzz test resumes
+    pub nbr_mutations: usize,
 }
 impl<T: Clone, Mut: Mutator<T>> Clone for FuzzedInput<T, Mut> {
     fn clone(&self) -> Self {
@@ -244,6 +340,7 @@ impl<T: Clone, Mut: Mutator<T>> Clone for FuzzedInput<T, Mut> {
             cache: self.cache.clone(),
             mutation_step: self.mutation_step.clone(),
             generation: self.generation,
+            nbr_mutations: self.nbr_mutations,
         }
     }
 }
@@ -256,6 +353,7 @@ impl<T: Clone, Mut: Mutator<T>> FuzzedInput<T, Mut> {
             cache,
             mutation_step,
             generation,
+            nbr_mutations: 0,
         }
     }
 
@@ -273,7 +371,36 @@ impl<T: Clone, Mut: Mutator<T>> FuzzedInput<T, Mut> {
 
     #[no_coverage]
     pub fn mutate(&mut self, m: &mut Mut, max_cplx: f64) -> Option<(Mut::UnmutateToken, f64)> {
-        m.ordered_mutate(&mut self.value, &mut self.cache, &mut self.mutation_step, max_cplx)
+        let mutation = m.ordered_mutate(&mut self.value, &mut self.cache, &mut self.mutation_step, max_cplx)?;
+        self.nbr_mutations += 1;
+        Some(mutation)
+    }
+
+    /// Mutate and unmutate the input `nbr_mutations` times, such that its mutation step continues from where it
+    /// was when the input was saved
+    #[no_coverage]
+    pub fn replay_mutations(&mut self, m: &Mut, nbr_mutations: usize, max_cplx: f64) {
+        for _ in 0..nbr_mutations {
+            if let Some((token, _)) =
+                m.ordered_mutate(&mut self.value, &mut self.cache, &mut self.mutation_step, max_cplx)
+            {
+                self.unmutate(m, token);
+                self.nbr_mutations += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    #[no_coverage]
+    pub fn crossover_mutate(
+        &mut self,
+        m: &Mut,
+        other: &T,
+        other_cache: &Mut::Cache,
+        max_cplx: f64,
+    ) -> Option<(Mut::UnmutateToken, f64)> {
+        m.crossover_mutate(&mut self.value, &mut self.cache, other, other_cache, max_cplx)
     }
 
     #[no_coverage]
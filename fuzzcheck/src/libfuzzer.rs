@@ -0,0 +1,79 @@
+//! Run a fuzz test of `Vec<u8>` under libFuzzer, for example on OSS-Fuzz.
+//!
+//! It is available with the `libfuzzer` feature, which must only be enabled when the fuzz target is compiled for
+//! libFuzzer. With that feature, fuzzcheck does not export the `__sanitizer_cov_*` hooks of its
+//! [`ComparisonSensor`](crate::sensors_and_pools::ComparisonSensor), which are defined by libFuzzer too, and its
+//! code coverage sensor does not observe anything.
+
+use std::os::raw::c_int;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::builder::FuzzTestFunction;
+
+/**
+Export the given test function as the `LLVMFuzzerTestOneInput` entry point of libFuzzer.
+
+The test function takes a `&[u8]` or a `&Vec<u8>`, like a fuzz test launched with a
+[`ByteSerializer`](crate::ByteSerializer), and returns `()`, a `bool`, or a `Result`. The test cases of libFuzzer
+are the raw bytes of the files of its corpus, which are also the ones saved by a `ByteSerializer`, so the two fuzzers
+can share their corpora.
+
+When the test function panics or fails, the process is aborted so that libFuzzer saves the test case as a crash.
+
+The macro is typically invoked in a `#![no_main]` binary that is compiled with the SanitizerCoverage instrumentation
+and linked with libFuzzer, as done by `cargo fuzz` and by the build scripts of OSS-Fuzz, while the same test function
+is fuzz-tested with fuzzcheck during development:
+```ignore
+#![no_main]
+
+fn parse(bytes: &[u8]) -> bool {
+    // ...
+    # true
+}
+
+fuzzcheck::libfuzzer_target!(parse);
+```
+*/
+#[doc(cfg(feature = "libfuzzer"))]
+#[macro_export]
+macro_rules! libfuzzer_target {
+    ($test_function:expr) => {
+        #[no_mangle]
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub extern "C" fn LLVMFuzzerTestOneInput(data: *const u8, size: usize) -> ::std::os::raw::c_int {
+            // libFuzzer may pass a null pointer for the empty test case
+            let data = if size == 0 {
+                &[][..]
+            } else {
+                unsafe { ::std::slice::from_raw_parts(data, size) }
+            };
+            $crate::libfuzzer::test_one_input($test_function, data)
+        }
+    };
+}
+
+/// Run the test function on one test case of libFuzzer, and abort the process if it fails.
+///
+/// It is called by the entry point defined by [`libfuzzer_target`](crate::libfuzzer_target).
+#[doc(hidden)]
+#[no_coverage]
+pub fn test_one_input<F, FT: ?Sized, TestFunctionKind>(test_function: F, data: &[u8]) -> c_int
+where
+    F: FuzzTestFunction<Vec<u8>, FT, TestFunctionKind>,
+{
+    let test_function = test_function.test_function();
+    let value = data.to_vec();
+    // the panic message is printed by the panic hook, before the process is aborted
+    let passed = catch_unwind(AssertUnwindSafe(
+        #[no_coverage]
+        || test_function(&value),
+    ));
+    match passed {
+        Ok(true) => 0,
+        Ok(false) => {
+            eprintln!("The test function failed on a test case of {} bytes.", data.len());
+            std::process::abort()
+        }
+        Err(_) => std::process::abort(),
+    }
+}
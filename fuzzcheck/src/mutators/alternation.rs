@@ -15,6 +15,11 @@ let m = AlternationMutator::new(vec![m1, m2]);
 
 // m will produce values either in 3..=10 or in 78..=200
 ```
+
+The generation of new values can be restricted to some of the mutators with
+[`only_mutators`](AlternationMutator::only_mutators) and [`exclude_mutators`](AlternationMutator::exclude_mutators).
+Values that can only be handled by the other mutators are still accepted, for example when they come from the corpus.
+By default, each mutator is chosen equally often, which can be changed with [`with_weights`](AlternationMutator::with_weights).
 */
 pub struct AlternationMutator<T, M>
 where
@@ -22,6 +27,10 @@ where
     M: Mutator<T>,
 {
     mutators: Vec<M>,
+    /// The indices of the mutators that are used to generate new values
+    arbitrary_indices: Vec<usize>,
+    /// The relative frequency with which each mutator is chosen to generate new values, if they are not all equal
+    weights: Option<Vec<f64>>,
     complexity_from_choice: f64,
     max_complexity: f64,
     min_complexity: f64,
@@ -76,8 +85,11 @@ where
             )
             .unwrap();
         let complexity_from_choice = crate::mutators::size_to_cplxity(mutators.len());
+        let arbitrary_indices = (0..mutators.len()).collect();
         Self {
             mutators,
+            arbitrary_indices,
+            weights: None,
             complexity_from_choice,
             max_complexity,
             min_complexity,
@@ -85,6 +97,72 @@ where
             _phantom: PhantomData,
         }
     }
+
+    /// The mutators between which the alternation mutator chooses
+    #[doc(hidden)]
+    #[no_coverage]
+    pub fn mutators(&self) -> &[M] {
+        &self.mutators
+    }
+
+    /// Only use the mutators whose index is in `indices` to generate new values.
+    ///
+    /// ## Panics
+    /// Panics if none of the remaining mutators can be used to generate new values.
+    #[no_coverage]
+    pub fn only_mutators(mut self, indices: &[usize]) -> Self {
+        self.arbitrary_indices.retain(
+            #[no_coverage]
+            |idx| indices.contains(idx),
+        );
+        assert!(!self.arbitrary_indices.is_empty(), "all the mutators were excluded");
+        self
+    }
+
+    /// Do not use the mutators whose index is in `indices` to generate new values.
+    ///
+    /// ## Panics
+    /// Panics if none of the remaining mutators can be used to generate new values.
+    #[no_coverage]
+    pub fn exclude_mutators(mut self, indices: &[usize]) -> Self {
+        self.arbitrary_indices.retain(
+            #[no_coverage]
+            |idx| !indices.contains(idx),
+        );
+        assert!(!self.arbitrary_indices.is_empty(), "all the mutators were excluded");
+        self
+    }
+
+    /// Choose the mutator used to generate a new value with a probability proportional to its weight in `weights`,
+    /// which contains one weight for each mutator. The mutators whose weight is 0 are excluded.
+    ///
+    /// The weights also apply when a mutation replaces a value with one generated by another mutator.
+    ///
+    /// ## Panics
+    /// Panics if `weights` does not contain one finite, non-negative weight for each mutator, or if none of the
+    /// remaining mutators can be used to generate new values.
+    #[no_coverage]
+    pub fn with_weights(mut self, weights: &[f64]) -> Self {
+        assert_eq!(
+            weights.len(),
+            self.mutators.len(),
+            "there must be one weight for each mutator"
+        );
+        assert!(
+            weights.iter().all(
+                #[no_coverage]
+                |w| w.is_finite() && *w >= 0.0
+            ),
+            "the weights must be finite and non-negative"
+        );
+        self.arbitrary_indices.retain(
+            #[no_coverage]
+            |&idx| weights[idx] > 0.0,
+        );
+        assert!(!self.arbitrary_indices.is_empty(), "all the mutators were excluded");
+        self.weights = Some(weights.to_vec());
+        self
+    }
 }
 
 #[doc(hidden)]
@@ -160,6 +238,29 @@ where
             cplx
         }
     }
+
+    /// Choose one of the mutators in `indices`, with a probability proportional to its weight
+    #[no_coverage]
+    fn choose_mutator(&self, indices: &[usize]) -> usize {
+        if let Some(weights) = &self.weights {
+            let total = indices.iter().fold(
+                0.0,
+                #[no_coverage]
+                |total, &idx| total + weights[idx],
+            );
+            let mut x = self.rng.f64() * total;
+            for &idx in indices {
+                if x < weights[idx] {
+                    return idx;
+                }
+                x -= weights[idx];
+            }
+            // floating point errors can make `x` slightly larger than the sum of the weights
+            *indices.last().unwrap()
+        } else {
+            indices[self.rng.usize(..indices.len())]
+        }
+    }
 }
 
 impl<T, M> Mutator<T> for AlternationMutator<T, M>
@@ -188,7 +289,7 @@ where
                     |m| m.default_arbitrary_step(),
                 )
                 .collect(),
-            indices: (0..self.mutators.len()).collect(),
+            indices: self.arbitrary_indices.clone(),
             idx: 0,
         }
     }
@@ -226,7 +327,10 @@ where
                         inner: m.default_mutation_step(value, &c.inner),
                         arbitrary: {
                             let mut step = self.default_arbitrary_step();
-                            step.indices.remove(c.mutator_idx);
+                            step.indices.retain(
+                                #[no_coverage]
+                                |&idx| idx != c.mutator_idx,
+                            );
                             step
                         },
                     }
@@ -264,14 +368,25 @@ where
             return None;
         }
 
-        let idx = step.indices[step.idx % step.indices.len()];
+        // the mutators are used in turn, unless they have different weights
+        let position = if self.weights.is_some() {
+            let idx = self.choose_mutator(&step.indices);
+            step.indices.iter().position(
+                #[no_coverage]
+                |&i| i == idx,
+            )
+        } else {
+            Some(step.idx % step.indices.len())
+        }
+        .unwrap();
+        let idx = step.indices[position];
         let mutator = &self.mutators[idx];
         let inner_step = &mut step.inner[idx];
         if let Some((v, c)) = mutator.ordered_arbitrary(inner_step, max_cplx) {
             step.idx += 1;
             Some((v, self.complexity_from_inner(c)))
         } else {
-            step.indices.remove(step.idx % step.indices.len());
+            step.indices.remove(position);
             self.ordered_arbitrary(step, max_cplx)
         }
     }
@@ -279,7 +394,7 @@ where
     #[doc(hidden)]
     #[no_coverage]
     fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
-        let idx = self.rng.usize(..self.mutators.len());
+        let idx = self.choose_mutator(&self.arbitrary_indices);
         let mutator = &self.mutators[idx];
 
         let (v, c) = mutator.random_arbitrary(max_cplx);
@@ -311,7 +426,9 @@ where
         let step_idx = self.rng.usize(..step.len());
         let chosen_step = &mut step[step_idx];
         chosen_step.step += 1;
-        if chosen_step.step < 20 {
+        // with weights, the value is replaced by one of another mutator only as often as that mutator is chosen
+        let replace = self.weights.is_none() || self.choose_mutator(&self.arbitrary_indices) != chosen_step.mutator_idx;
+        if chosen_step.step < 20 && replace {
             if let Some((mut v, cplx)) = self.ordered_arbitrary(&mut chosen_step.arbitrary, max_cplx) {
                 std::mem::swap(value, &mut v);
                 return Some((UnmutateToken::Replace(v), cplx));
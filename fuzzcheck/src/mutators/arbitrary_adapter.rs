@@ -0,0 +1,281 @@
+/*!
+A mutator for the types implementing [`arbitrary::Arbitrary`], so that the fuzz targets written for `cargo fuzz`
+can be reused without writing a fuzzcheck mutator.
+
+[`ArbitraryAdapterMutator<T>`] mutates a sequence of bytes with a [`VecMutator<u8, U8Mutator>`] and decodes
+it into a value of type `T` with [`Arbitrary::arbitrary_take_rest`]. The values it generates are
+[`ArbitraryValue<T>`], which hold both the bytes and the decoded value, and can be borrowed as a `T` by the
+test function. The complexity of a value is the one of its bytes.
+
+The [`ArbitrarySerializer`](crate::ArbitrarySerializer) saves the bytes of the values to the corpus, like `cargo fuzz`
+does, such that an existing `cargo fuzz` corpus can be given as the input corpus:
+```
+use fuzzcheck::mutators::arbitrary_adapter::ArbitraryAdapterMutator;
+use fuzzcheck::ArbitrarySerializer;
+
+// a fuzz target of cargo fuzz, whose argument implements `Arbitrary`
+fn parse_request(request: &(String, Vec<u8>)) {
+    // ..
+}
+fn fuzz_test() {
+    fuzzcheck::fuzz_test(parse_request)
+        .mutator(ArbitraryAdapterMutator::<(String, Vec<u8>)>::default())
+        .serializer(ArbitrarySerializer::new("bin"))
+        // ..
+        # ;
+}
+```
+
+The mutations are not aware of the structure of `T`: changing a single byte can change the decoded value
+completely. A mutator built for `T`, for example with `#[derive(DefaultMutator)]`, is therefore usually
+more efficient.
+*/
+
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use ::arbitrary::{Arbitrary, Unstructured};
+
+use super::integer::U8Mutator;
+use super::vector::VecMutator;
+use crate::Mutator;
+
+/// Decode a value from the bytes.
+///
+/// Arbitrary implementations rarely fail, but they may reject some bytes. These are then decoded as the
+/// value given by an empty sequence of bytes, such that the value is always a function of the bytes.
+#[no_coverage]
+fn decode<T>(bytes: &[u8]) -> T
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    T::arbitrary_take_rest(Unstructured::new(bytes)).unwrap_or_else(
+        #[no_coverage]
+        |_| {
+            T::arbitrary_take_rest(Unstructured::new(&[]))
+                .expect("the Arbitrary implementation cannot decode a value from an empty sequence of bytes")
+        },
+    )
+}
+
+/// A value decoded by [`Arbitrary`] from a sequence of bytes, generated by [`ArbitraryAdapterMutator`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArbitraryValue<T> {
+    bytes: Vec<u8>,
+    value: T,
+}
+
+impl<T> ArbitraryValue<T>
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    /// Decode the value from the bytes
+    #[no_coverage]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let value = decode(&bytes);
+        Self { bytes, value }
+    }
+}
+
+impl<T> ArbitraryValue<T> {
+    /// The decoded value
+    #[no_coverage]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+    /// The bytes that the value was decoded from
+    #[no_coverage]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    #[no_coverage]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Borrow<T> for ArbitraryValue<T> {
+    #[no_coverage]
+    fn borrow(&self) -> &T {
+        &self.value
+    }
+}
+
+/// A mutator for the types implementing [`Arbitrary`], which mutates the bytes that the values are decoded from.
+///
+/// See the [module documentation](self) for an example.
+pub struct ArbitraryAdapterMutator<T> {
+    mutator: VecMutator<u8, U8Mutator>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> ArbitraryAdapterMutator<T> {
+    /// Create a mutator decoding the values from sequences of bytes whose length is within `len_range`
+    #[no_coverage]
+    pub fn new(len_range: RangeInclusive<usize>) -> Self {
+        Self {
+            mutator: VecMutator::new(U8Mutator::default(), len_range),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Insert the given tokens in the sequences of bytes, see [`VecMutator::with_dictionary`]
+    #[no_coverage]
+    pub fn with_dictionary(self, tokens: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        Self {
+            mutator: self.mutator.with_dictionary(tokens),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ArbitraryAdapterMutator<T> {
+    #[no_coverage]
+    fn default() -> Self {
+        Self::new(0..=usize::MAX)
+    }
+}
+
+impl<T> Mutator<ArbitraryValue<T>> for ArbitraryAdapterMutator<T>
+where
+    T: Clone + for<'a> Arbitrary<'a> + 'static,
+{
+    #[doc(hidden)]
+    type Cache = <VecMutator<u8, U8Mutator> as Mutator<Vec<u8>>>::Cache;
+    #[doc(hidden)]
+    type MutationStep = <VecMutator<u8, U8Mutator> as Mutator<Vec<u8>>>::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = <VecMutator<u8, U8Mutator> as Mutator<Vec<u8>>>::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = (<VecMutator<u8, U8Mutator> as Mutator<Vec<u8>>>::UnmutateToken, T);
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &ArbitraryValue<T>) -> Option<Self::Cache> {
+        self.mutator.validate_value(&value.bytes)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, value: &ArbitraryValue<T>, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(&value.bytes, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, value: &ArbitraryValue<T>, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&value.bytes, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(ArbitraryValue<T>, f64)> {
+        let (bytes, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        Some((ArbitraryValue::new(bytes), cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (ArbitraryValue<T>, f64) {
+        let (bytes, cplx) = self.mutator.random_arbitrary(max_cplx);
+        (ArbitraryValue::new(bytes), cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut ArbitraryValue<T>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self.mutator.ordered_mutate(&mut value.bytes, cache, step, max_cplx)?;
+        let old_value = std::mem::replace(&mut value.value, decode(&value.bytes));
+        Some(((token, old_value), cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(
+        &self,
+        value: &mut ArbitraryValue<T>,
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        let (token, cplx) = self.mutator.random_mutate(&mut value.bytes, cache, max_cplx);
+        let old_value = std::mem::replace(&mut value.value, decode(&value.bytes));
+        ((token, old_value), cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut ArbitraryValue<T>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        let (token, old_value) = t;
+        self.mutator.unmutate(&mut value.bytes, cache, token);
+        value.value = old_value;
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut ArbitraryValue<T>,
+        cache: &mut Self::Cache,
+        other: &ArbitraryValue<T>,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) =
+            self.mutator
+                .crossover_mutate(&mut value.bytes, cache, &other.bytes, other_cache, max_cplx)?;
+        let old_value = std::mem::replace(&mut value.value, decode(&value.bytes));
+        Some(((token, old_value), cplx))
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(
+        &self,
+        _value: &ArbitraryValue<T>,
+        _cache: &Self::Cache,
+    ) -> Self::RecursingPartIndex {
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a ArbitraryValue<T>,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        None
+    }
+}
@@ -109,6 +109,24 @@ impl<T: Clone, M: Mutator<T>> Mutator<Arc<T>> for ArcMutator<M> {
         *value = Arc::new(v);
     }
 
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut Arc<T>,
+        cache: &mut Self::Cache,
+        other: &Arc<T>,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let mut v = value.as_ref().clone();
+        let res = self
+            .mutator
+            .crossover_mutate(&mut v, cache, other, other_cache, max_cplx);
+        *value = Arc::new(v);
+        res
+    }
+
     #[doc(hidden)]
     type RecursingPartIndex = M::RecursingPartIndex;
     #[doc(hidden)]
@@ -12,7 +12,9 @@ pub struct RecursingPartIndex<RPI> {
 
 /// A mutator for fixed-size arrays `[T; N]`.
 ///
-/// A different mutator can be used for each element of the array.
+/// A different mutator can be used for each element of the array. If the same mutator is used for all
+/// the elements, see [`new_with_repeated_mutator`](ArrayMutator::new_with_repeated_mutator), the mutations
+/// can also swap two elements of the array.
 pub struct ArrayMutator<M, T, const N: usize>
 where
     T: Clone,
@@ -20,6 +22,8 @@ where
 {
     pub rng: Rng,
     mutators: [M; N],
+    /// Whether all the elements use the same mutator, such that they can be swapped
+    interchangeable: bool,
     min_complexity: f64,
     max_complexity: f64,
     _phantom: PhantomData<T>,
@@ -32,7 +36,10 @@ where
 {
     #[no_coverage]
     pub fn new_with_repeated_mutator(mutator: M) -> Self {
-        Self::new(vec![mutator; N].try_into().ok().unwrap())
+        Self {
+            interchangeable: true,
+            ..Self::new(vec![mutator; N].try_into().ok().unwrap())
+        }
     }
 }
 
@@ -49,6 +56,7 @@ where
         Self {
             rng: Rng::default(),
             mutators,
+            interchangeable: false,
             min_complexity,
             max_complexity,
             _phantom: PhantomData,
@@ -93,6 +101,7 @@ impl<C> Default for ArrayMutatorCache<C> {
 pub enum UnmutateArrayToken<M: Mutator<T>, T: Clone, const N: usize> {
     Element(usize, M::UnmutateToken),
     Elements(Vec<(usize, M::UnmutateToken)>),
+    Swap(usize, usize),
     Replace([T; N]),
 }
 
@@ -127,6 +136,19 @@ impl<M: Mutator<T>, T: Clone + 'static, const N: usize> ArrayMutator<M, T, N> {
         (UnmutateArrayToken::Elements(tokens), cplx)
     }
     #[no_coverage]
+    fn swap_elements(
+        &self,
+        value: &mut [T; N],
+        cache: &mut ArrayMutatorCache<M::Cache>,
+        current_cplx: f64,
+    ) -> (UnmutateArrayToken<M, T, N>, f64) {
+        let idx_a = self.rng.usize(..value.len());
+        let idx_b = (idx_a + self.rng.usize(1..value.len())) % value.len();
+        value.swap(idx_a, idx_b);
+        cache.inner.swap(idx_a, idx_b);
+        (UnmutateArrayToken::Swap(idx_a, idx_b), current_cplx)
+    }
+    #[no_coverage]
     fn mutate_element(
         &self,
         value: &mut [T; N],
@@ -171,7 +193,7 @@ impl<M: Mutator<T>, T: Clone + 'static, const N: usize> ArrayMutator<M, T, N> {
             remaining_cplx -= x_cplx;
             remaining_min_complexity -= mutator.min_complexity();
         }
-        (v.try_into().ok().unwrap(), self.min_complexity + sum_cplx)
+        (v.try_into().ok().unwrap(), sum_cplx)
     }
 }
 
@@ -286,6 +308,9 @@ impl<M: Mutator<T>, T: Clone + 'static, const N: usize> Mutator<[T; N]> for Arra
         }
         let current_cplx = self.complexity(value, cache);
         let spare_cplx = max_cplx - current_cplx;
+        if self.interchangeable && value.len() > 1 && self.rng.usize(..10) == 0 {
+            return Some(self.swap_elements(value, cache, current_cplx));
+        }
         if value.len() > 1 && self.rng.usize(..20) == 0 {
             let mut idcs = (0..value.len()).collect::<Vec<_>>();
             self.rng.shuffle(&mut idcs);
@@ -312,6 +337,9 @@ impl<M: Mutator<T>, T: Clone + 'static, const N: usize> Mutator<[T; N]> for Arra
             return (UnmutateArrayToken::Replace(v), cplx);
         }
         let current_cplx = self.complexity(value, cache);
+        if self.interchangeable && value.len() > 1 && self.rng.usize(..10) == 0 {
+            return self.swap_elements(value, cache, current_cplx);
+        }
         if value.len() > 1 && self.rng.usize(..20) == 0 {
             let mut idcs = (0..value.len()).collect::<Vec<_>>();
             self.rng.shuffle(&mut idcs);
@@ -348,6 +376,10 @@ impl<M: Mutator<T>, T: Clone + 'static, const N: usize> Mutator<[T; N]> for Arra
                     self.mutators[idx].unmutate(el, &mut cache.inner[idx], token);
                 }
             }
+            UnmutateArrayToken::Swap(idx_a, idx_b) => {
+                value.swap(idx_a, idx_b);
+                cache.inner.swap(idx_a, idx_b);
+            }
             UnmutateArrayToken::Replace(new_value) => {
                 let _ = std::mem::replace(value, new_value);
             }
@@ -415,4 +447,27 @@ mod tests {
             eprintln!("{:?}", x);
         }
     }
+
+    #[test]
+    #[no_coverage]
+    fn test_array_mutator_swaps_elements() {
+        let m = ArrayMutator::<U8Mutator, u8, 4>::new_with_repeated_mutator(U8Mutator::default());
+        let mut value = [1, 2, 3, 4];
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut found_swap = false;
+        for _ in 0..1000 {
+            let (token, cplx) = m.random_mutate(&mut value, &mut cache, 100.0);
+            let mut sorted = value;
+            sorted.sort_unstable();
+            if value != [1, 2, 3, 4] && sorted == [1, 2, 3, 4] {
+                found_swap = true;
+                assert_eq!(cplx, m.complexity(&value, &cache));
+            }
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, [1, 2, 3, 4]);
+        }
+        assert!(found_swap);
+
+        crate::mutators::testing_utilities::test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+    }
 }
@@ -99,6 +99,20 @@ impl<T: Clone, M: Mutator<T>> Mutator<Box<T>> for BoxMutator<M> {
         self.mutator.unmutate(value, cache, t)
     }
 
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut Box<T>,
+        cache: &mut Self::Cache,
+        other: &Box<T>,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        self.mutator
+            .crossover_mutate(value, cache, other, other_cache, max_cplx)
+    }
+
     #[doc(hidden)]
     type RecursingPartIndex = M::RecursingPartIndex;
     #[doc(hidden)]
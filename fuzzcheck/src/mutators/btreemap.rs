@@ -1,21 +1,26 @@
 use std::collections::BTreeMap;
 
 use super::map::MapMutator;
+use super::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
+use super::vector::VecMutator;
 use super::wrapper::Wrapper;
-use super::{tuples::TupleMutatorWrapper, vector::VecMutator};
-use crate::mutators::tuples::Tuple2;
-use crate::{mutators::tuples::Tuple2Mutator, DefaultMutator};
+use crate::DefaultMutator;
 
-type AssociativeVecMutator<K, V> = VecMutator<
-    (K, V),
-    TupleMutatorWrapper<Tuple2Mutator<<K as DefaultMutator>::Mutator, <V as DefaultMutator>::Mutator>, Tuple2<K, V>>,
->;
-
-pub type BTreeMapMutator<K, V> = Wrapper<
+/// The default mutator of `BTreeMap<K, V>`.
+///
+/// It mutates a `Vec<(K, V)>` of entries with a [`VecMutator`] and converts it to a `BTreeMap<K, V>`.
+/// The mutations of the vector act on the ordered map as follows:
+/// * mutating an element mutates a key in place, which moves its entry to another position, or its value
+/// * inserting an element adds an entry, which splits the range between two adjacent keys
+/// * removing an element merges the ranges on each side of the removed key
+///
+/// If several entries have the same key, the last one is kept. The complexity of a value is the complexity
+/// of the corresponding vector.
+pub type BTreeMapMutator<K, V, MK, MV> = Wrapper<
     MapMutator<
         Vec<(K, V)>,
         BTreeMap<K, V>,
-        AssociativeVecMutator<K, V>,
+        VecMutator<(K, V), TupleMutatorWrapper<Tuple2Mutator<MK, MV>, Tuple2<K, V>>>,
         fn(&BTreeMap<K, V>) -> Option<Vec<(K, V)>>,
         fn(&Vec<(K, V)>) -> BTreeMap<K, V>,
         fn(&BTreeMap<K, V>, f64) -> f64,
@@ -24,30 +29,41 @@ pub type BTreeMapMutator<K, V> = Wrapper<
 
 #[no_coverage]
 fn avec_from_btreemap<K: Clone, V: Clone>(btree: &BTreeMap<K, V>) -> Option<Vec<(K, V)>> {
-    Some(btree.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    Some(
+        btree
+            .iter()
+            .map(
+                #[no_coverage]
+                |(k, v)| (k.clone(), v.clone()),
+            )
+            .collect(),
+    )
 }
 
 #[no_coverage]
 fn btreemap_from_avec<K: Clone + Ord, V: Clone>(avec: &Vec<(K, V)>) -> BTreeMap<K, V> {
-    BTreeMap::<K, V>::from_iter(avec.iter().map(|(k, v)| (k.clone(), v.clone())))
+    avec.iter().cloned().collect()
 }
 
 #[no_coverage]
-fn complexity<K: Clone, V: Clone>(_t: &BTreeMap<K, V>, cplx: f64) -> f64 {
+fn complexity<K, V>(_t: &BTreeMap<K, V>, cplx: f64) -> f64 {
     cplx
 }
 
-impl<K, V> BTreeMapMutator<K, V>
+impl<K, V, MK, MV> BTreeMapMutator<K, V, MK, MV>
 where
-    K: Clone + Ord + DefaultMutator,
-    V: Clone + DefaultMutator,
+    K: 'static + Clone + Ord,
+    V: 'static + Clone,
+    MK: crate::Mutator<K>,
+    MV: crate::Mutator<V>,
 {
+    /// Create a mutator of maps whose number of entries, before merging the duplicate keys, is within `len_range`
     #[no_coverage]
-    pub fn new() -> Self {
+    pub fn new(key_mutator: MK, value_mutator: MV, len_range: std::ops::RangeInclusive<usize>) -> Self {
         Wrapper(MapMutator::new(
             VecMutator::new(
-                TupleMutatorWrapper::new(Tuple2Mutator::new(K::default_mutator(), V::default_mutator())),
-                0..=10,
+                TupleMutatorWrapper::new(Tuple2Mutator::new(key_mutator, value_mutator)),
+                len_range,
             ),
             avec_from_btreemap,
             btreemap_from_avec,
@@ -61,9 +77,9 @@ where
     K: 'static + Clone + Ord + DefaultMutator,
     V: 'static + Clone + DefaultMutator,
 {
-    type Mutator = BTreeMapMutator<K, V>;
+    type Mutator = BTreeMapMutator<K, V, K::Mutator, V::Mutator>;
     #[no_coverage]
     fn default_mutator() -> Self::Mutator {
-        Self::Mutator::new()
+        Self::Mutator::new(K::default_mutator(), V::default_mutator(), 0..=10)
     }
 }
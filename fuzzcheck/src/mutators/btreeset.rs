@@ -5,43 +5,49 @@ use super::vector::VecMutator;
 use super::wrapper::Wrapper;
 use crate::DefaultMutator;
 
-type AssociativeVecMutator<T> = VecMutator<T, <T as DefaultMutator>::Mutator>;
-
-pub type BTreeSetMutator<T> = Wrapper<
+/// The default mutator of `BTreeSet<K>`.
+///
+/// It mutates a `Vec<K>` with a [`VecMutator`] and converts it to a `BTreeSet<K>`, in which the duplicate
+/// elements are merged. Like for the [`BTreeMapMutator`](crate::mutators::btreemap::BTreeMapMutator),
+/// mutating an element of the vector moves it within the ordered set, and inserting or removing an element
+/// splits or merges the ranges between adjacent elements. The complexity of a value is the complexity of
+/// the corresponding vector.
+pub type BTreeSetMutator<K, MK> = Wrapper<
     MapMutator<
-        Vec<T>,
-        BTreeSet<T>,
-        AssociativeVecMutator<T>,
-        fn(&BTreeSet<T>) -> Option<Vec<T>>,
-        fn(&Vec<T>) -> BTreeSet<T>,
-        fn(&BTreeSet<T>, f64) -> f64,
+        Vec<K>,
+        BTreeSet<K>,
+        VecMutator<K, MK>,
+        fn(&BTreeSet<K>) -> Option<Vec<K>>,
+        fn(&Vec<K>) -> BTreeSet<K>,
+        fn(&BTreeSet<K>, f64) -> f64,
     >,
 >;
 
 #[no_coverage]
-fn avec_from_btreeset<T: Clone>(btree: &BTreeSet<T>) -> Option<Vec<T>> {
+fn avec_from_btreeset<K: Clone>(btree: &BTreeSet<K>) -> Option<Vec<K>> {
     Some(btree.iter().cloned().collect())
 }
 
 #[no_coverage]
-fn btreeset_from_avec<T: Clone + Ord>(avec: &Vec<T>) -> BTreeSet<T> {
+fn btreeset_from_avec<K: Clone + Ord>(avec: &Vec<K>) -> BTreeSet<K> {
     avec.iter().cloned().collect()
-    // BTreeSet::<T>::from_iter()
 }
 
 #[no_coverage]
-fn complexity<T: Clone>(_t: &BTreeSet<T>, cplx: f64) -> f64 {
+fn complexity<K>(_t: &BTreeSet<K>, cplx: f64) -> f64 {
     cplx
 }
 
-impl<T> BTreeSetMutator<T>
+impl<K, MK> BTreeSetMutator<K, MK>
 where
-    T: Clone + Ord + DefaultMutator,
+    K: 'static + Clone + Ord,
+    MK: crate::Mutator<K>,
 {
+    /// Create a mutator of sets whose number of elements, before merging the duplicates, is within `len_range`
     #[no_coverage]
-    pub fn new() -> Self {
+    pub fn new(mutator: MK, len_range: std::ops::RangeInclusive<usize>) -> Self {
         Wrapper(MapMutator::new(
-            VecMutator::new(T::default_mutator(), 0..=10),
+            VecMutator::new(mutator, len_range),
             avec_from_btreeset,
             btreeset_from_avec,
             complexity,
@@ -49,14 +55,13 @@ where
     }
 }
 
-impl<T> DefaultMutator for BTreeSet<T>
+impl<K> DefaultMutator for BTreeSet<K>
 where
-    T: 'static + Clone + Ord + DefaultMutator,
-    T::Mutator: Clone,
+    K: 'static + Clone + Ord + DefaultMutator,
 {
-    type Mutator = BTreeSetMutator<T>;
+    type Mutator = BTreeSetMutator<K, K::Mutator>;
     #[no_coverage]
     fn default_mutator() -> Self::Mutator {
-        Self::Mutator::new()
+        Self::Mutator::new(K::default_mutator(), 0..=10)
     }
 }
@@ -0,0 +1,207 @@
+use crate::Mutator;
+
+/** Wrap a mutator and store the complexity of the value in its cache, so that it is not recomputed.
+
+Computing the complexity of a value can be expensive, for example when it is given by a function that visits
+every node of a deep recursive value, such as the complexity function of a [`MapMutator`](crate::mutators::map::MapMutator).
+The fuzzer asks for the complexity of the test cases many times, which may then dominate the time spent fuzzing.
+The mutators provided by fuzzcheck for vectors, tuples, structs, and enums already keep the complexity of their
+elements in their cache and do not need this wrapper.
+
+With this wrapper, the complexity is computed once by the
+wrapped mutator when the value is validated, and then updated with the complexity returned by each mutation.
+[`unmutate`](Mutator::unmutate) restores the complexity that the value had before the mutation, so
+[`complexity`](Mutator::complexity) is always answered from the cache, without calling the wrapped mutator.
+
+```
+use fuzzcheck::DefaultMutator;
+use fuzzcheck::mutators::cached_complexity::CachedComplexityMutator;
+use fuzzcheck::mutators::map::MapMutator;
+
+let m = CachedComplexityMutator::new(MapMutator::new(
+    <Vec<Vec<u8>>>::default_mutator(),
+    |v: &Vec<Vec<u8>>| Some(v.clone()),
+    |v: &Vec<Vec<u8>>| v.clone(),
+    // an expensive complexity function, which is no longer called by `m.complexity(..)`
+    |v: &Vec<Vec<u8>>, _cplx| v.iter().map(|xs| xs.len() as f64 + 1.0).sum(),
+));
+```
+*/
+pub struct CachedComplexityMutator<M> {
+    m: M,
+}
+
+impl<M> CachedComplexityMutator<M> {
+    #[no_coverage]
+    pub fn new(mutator: M) -> Self {
+        Self { m: mutator }
+    }
+}
+
+#[derive(Clone)]
+pub struct Cache<C> {
+    inner: C,
+    cplx: f64,
+}
+
+pub struct UnmutateToken<U> {
+    inner: U,
+    cplx: f64,
+}
+
+impl<T: Clone + 'static, M: Mutator<T>> Mutator<T> for CachedComplexityMutator<M> {
+    #[doc(hidden)]
+    type Cache = Cache<M::Cache>;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.m.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        let inner = self.m.validate_value(value)?;
+        let cplx = self.m.complexity(value, &inner);
+        Some(Cache { inner, cplx })
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.m.default_mutation_step(value, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.m.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.m.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &T, cache: &Self::Cache) -> f64 {
+        cache.cplx
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.m.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.m.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (inner, cplx) = self.m.ordered_mutate(value, &mut cache.inner, step, max_cplx)?;
+        let old_cplx = std::mem::replace(&mut cache.cplx, cplx);
+        Some((UnmutateToken { inner, cplx: old_cplx }, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let (inner, cplx) = self.m.random_mutate(value, &mut cache.inner, max_cplx);
+        let old_cplx = std::mem::replace(&mut cache.cplx, cplx);
+        (UnmutateToken { inner, cplx: old_cplx }, cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        other: &T,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (inner, cplx) = self
+            .m
+            .crossover_mutate(value, &mut cache.inner, other, &other_cache.inner, max_cplx)?;
+        let old_cplx = std::mem::replace(&mut cache.cplx, cplx);
+        Some((UnmutateToken { inner, cplx: old_cplx }, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        cache.cplx = t.cplx;
+        self.m.unmutate(value, &mut cache.inner, t.inner)
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = M::RecursingPartIndex;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, value: &T, cache: &Self::Cache) -> Self::RecursingPartIndex {
+        self.m.default_recursing_part_index(value, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(&self, parent: &N, value: &'a T, index: &mut Self::RecursingPartIndex) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        self.m.recursing_part::<V, N>(parent, value, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedComplexityMutator;
+    use crate::mutators::integer::U8Mutator;
+    use crate::mutators::vector::VecMutator;
+    use crate::Mutator;
+
+    #[test]
+    #[no_coverage]
+    fn test_crossover_updates_cached_complexity() {
+        let inner = VecMutator::new(U8Mutator::default(), 0..=100);
+        let m = CachedComplexityMutator::new(VecMutator::new(U8Mutator::default(), 0..=100));
+        let other = vec![3, 7, 9, 12];
+        let other_cache = m.validate_value(&other).unwrap();
+        for _ in 0..1000 {
+            let (mut x, _) = m.random_arbitrary(100.0);
+            let mut cache = m.validate_value(&x).unwrap();
+            let original = x.clone();
+            let original_cplx = m.complexity(&x, &cache);
+            let (token, cplx) = m
+                .crossover_mutate(&mut x, &mut cache, &other, &other_cache, 1000.0)
+                .unwrap();
+            assert_eq!(cplx, m.complexity(&x, &cache));
+            assert_eq!(cplx, inner.complexity(&x, &inner.validate_value(&x).unwrap()));
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, original);
+            assert_eq!(m.complexity(&x, &cache), original_cplx);
+        }
+    }
+}
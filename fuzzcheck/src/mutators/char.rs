@@ -1,6 +1,7 @@
 use crate::mutators::integer::binary_search_arbitrary_u32;
-use crate::Mutator;
-use std::ops::{Bound, RangeBounds};
+use crate::mutators::DefaultMutatorWithinRange;
+use crate::{DefaultMutator, Mutator};
+use std::ops::{Bound, RangeBounds, RangeInclusive};
 
 const INITIAL_MUTATION_STEP: u64 = 0;
 
@@ -199,3 +200,519 @@ impl Mutator<char> for CharWithinRangeMutator {
         None
     }
 }
+
+/// The code points that are not characters
+const SURROGATES: RangeInclusive<u32> = 0xD800..=0xDFFF;
+
+/// Mutator for a `char` within a list of ranges, such as a subset of ASCII
+///
+/// The ranges may overlap and contain surrogate code points, which are ignored.
+/// [`ordered_arbitrary`](Mutator::ordered_arbitrary) produces each character of the ranges exactly once, and the
+/// mutations never produce the character being mutated.
+/// ```
+/// use fuzzcheck::mutators::char::CharWithinRangesMutator;
+///
+/// // the characters of an identifier
+/// let m = CharWithinRangesMutator::new(['a'..='z', 'A'..='Z', '0'..='9', '_'..='_']);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharWithinRangesMutator {
+    /// The ranges of code points, sorted, disjoint, and without surrogates
+    ranges: Vec<RangeInclusive<u32>>,
+    /// The index of the first character of each range among all the characters of the ranges
+    starts: Vec<u32>,
+    nbr_chars: u32,
+    cplx: f64,
+    rng: fastrand::Rng,
+}
+
+impl CharWithinRangesMutator {
+    #[no_coverage]
+    pub fn new(ranges: impl IntoIterator<Item = RangeInclusive<char>>) -> Self {
+        let mut code_points = vec![];
+        for range in ranges {
+            let (start, end) = (*range.start() as u32, *range.end() as u32);
+            // a range of chars cannot start or end within the surrogates, but it can contain them
+            if start < *SURROGATES.start() && *SURROGATES.end() < end {
+                code_points.push(start..=*SURROGATES.start() - 1);
+                code_points.push(*SURROGATES.end() + 1..=end);
+            } else if start <= end {
+                code_points.push(start..=end);
+            }
+        }
+        code_points.sort_by_key(
+            #[no_coverage]
+            |range| *range.start(),
+        );
+        let mut ranges: Vec<RangeInclusive<u32>> = vec![];
+        for range in code_points {
+            match ranges.last_mut() {
+                Some(last) if *range.start() <= last.end().saturating_add(1) => {
+                    if range.end() > last.end() {
+                        *last = *last.start()..=*range.end();
+                    }
+                }
+                _ => ranges.push(range),
+            }
+        }
+        let mut starts = Vec::with_capacity(ranges.len());
+        let mut nbr_chars = 0;
+        for range in ranges.iter() {
+            starts.push(nbr_chars);
+            nbr_chars += range.end() - range.start() + 1;
+        }
+        assert!(
+            nbr_chars > 0,
+            "A CharWithinRangesMutator must be given at least one character"
+        );
+        Self {
+            ranges,
+            starts,
+            nbr_chars,
+            cplx: crate::mutators::size_to_cplxity(nbr_chars as usize),
+            rng: fastrand::Rng::default(),
+        }
+    }
+
+    /// The character at the given index among all the characters of the ranges
+    #[no_coverage]
+    fn char_at(&self, idx: u32) -> char {
+        let i = self.starts.partition_point(
+            #[no_coverage]
+            |&start| start <= idx,
+        ) - 1;
+        char::from_u32(self.ranges[i].start() + (idx - self.starts[i])).unwrap()
+    }
+
+    /// The index of the character among all the characters of the ranges, if it is within them
+    #[no_coverage]
+    fn index_of(&self, c: char) -> Option<u32> {
+        let c = c as u32;
+        let i = self.ranges.partition_point(
+            #[no_coverage]
+            |range| *range.end() < c,
+        );
+        let range = self.ranges.get(i)?;
+        if range.contains(&c) {
+            Some(self.starts[i] + (c - range.start()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Mutator<char> for CharWithinRangesMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = u64;
+    #[doc(hidden)]
+    type ArbitraryStep = u64;
+    #[doc(hidden)]
+    type UnmutateToken = char; // old value
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &char) -> Option<Self::Cache> {
+        self.index_of(*value).map(
+            #[no_coverage]
+            |_| (),
+        )
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &char, _cache: &Self::Cache) -> Self::MutationStep {
+        INITIAL_MUTATION_STEP
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &char, _cache: &Self::Cache) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(char, f64)> {
+        if max_cplx < self.min_complexity() || *step >= self.nbr_chars as u64 {
+            return None;
+        }
+        let idx = binary_search_arbitrary_u32(0, self.nbr_chars - 1, *step);
+        *step += 1;
+        Some((self.char_at(idx), self.cplx))
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (char, f64) {
+        (self.char_at(self.rng.u32(..self.nbr_chars)), self.cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut char,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        loop {
+            if *step >= self.nbr_chars as u64 {
+                return None;
+            }
+            let idx = binary_search_arbitrary_u32(0, self.nbr_chars - 1, *step);
+            *step += 1;
+            let c = self.char_at(idx);
+            // the current character appears once in the sequence, and is skipped
+            if c != *value {
+                return Some((std::mem::replace(value, c), self.cplx));
+            }
+        }
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut char, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.nbr_chars == 1 {
+            return (*value, self.cplx);
+        }
+        // pick one of the other characters, by skipping over the index of the current one
+        let mut idx = self.rng.u32(..self.nbr_chars - 1);
+        if let Some(current_idx) = self.index_of(*value) {
+            if idx >= current_idx {
+                idx += 1;
+            }
+        }
+        (std::mem::replace(value, self.char_at(idx)), self.cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut char, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &char, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a char,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V> + 'static,
+    {
+        None
+    }
+}
+
+/// The characters that are generated first by the [`CharMutator`]: control characters, the boundaries of
+/// the UTF-8 encoding lengths, the characters around the surrogates, and some characters that are often
+/// handled specially, such as combining marks, the byte order mark, and the right-to-left override.
+const SPECIAL_CHARS: &[char] = &[
+    'a',
+    '0',
+    ' ',
+    '\0',
+    '\n',
+    '\r',
+    '\t',
+    '"',
+    '\\',
+    'A',
+    '\u{7f}',
+    '\u{80}',
+    '\u{ff}',
+    '\u{100}',
+    '\u{300}',
+    '\u{7ff}',
+    '\u{800}',
+    '\u{200b}',
+    '\u{200d}',
+    '\u{202e}',
+    '\u{d7ff}',
+    '\u{e000}',
+    '\u{feff}',
+    '\u{fffd}',
+    '\u{ffff}',
+    '\u{10000}',
+    '\u{1f600}',
+    '\u{10ffff}',
+];
+
+/// The ranges of code points that the [`CharMutator`] walks through, in turn, after the special characters.
+/// They do not overlap, and none contains a surrogate.
+const RANGES: &[RangeInclusive<u32>] = &[
+    // ASCII
+    0x0..=0x7f,
+    // Latin-1 supplement
+    0x80..=0xff,
+    // combining diacritical marks
+    0x300..=0x36f,
+    // the rest of the basic multilingual plane, before and after the surrogates
+    0x100..=0x2ff,
+    0x370..=0xd7ff,
+    0xe000..=0xffff,
+    // emoji and pictographs
+    0x1f300..=0x1faff,
+    // the rest of the supplementary planes
+    0x10000..=0x1f2ff,
+    0x1fb00..=0x10ffff,
+];
+
+/// The default mutator of `char`, aware of the Unicode ranges where bugs are most likely.
+///
+/// Its [`ordered_arbitrary`](Mutator::ordered_arbitrary) method first produces some special characters, such
+/// as `'\0'`, the boundaries of the UTF-8 encoding lengths, the characters adjacent to the surrogates, and the
+/// last characters of the basic multilingual plane. It then produces characters from each of the following
+/// ranges in turn: ASCII, Latin-1, combining marks, the rest of the basic multilingual plane, emoji, and the
+/// rest of the supplementary planes. Thus, ASCII characters are produced as often as emoji, although there are
+/// far fewer of them.
+///
+/// Its [`ordered_mutate`](Mutator::ordered_mutate) method first replaces a character by its neighbours, then
+/// by the special characters, and then by the same sequence of characters as `ordered_arbitrary`.
+pub struct CharMutator {
+    rng: fastrand::Rng,
+    cplx: f64,
+}
+
+impl Default for CharMutator {
+    #[no_coverage]
+    fn default() -> Self {
+        Self {
+            rng: fastrand::Rng::default(),
+            cplx: crate::mutators::size_to_cplxity(char::MAX as usize + 1),
+        }
+    }
+}
+
+impl CharMutator {
+    /// The character at the given step of the walk through [`RANGES`], or `None` if the walk is over.
+    ///
+    /// The inner option is `None` if the step does not correspond to a character, because its range is
+    /// exhausted, or if it is a special character, which was already produced.
+    #[no_coverage]
+    fn walk_ranges(&self, step: u64) -> Option<Option<char>> {
+        let range = &RANGES[(step % RANGES.len() as u64) as usize];
+        let idx = step / RANGES.len() as u64;
+        if idx > 0x10ffff {
+            return None;
+        }
+        let len = range.end() - range.start();
+        if idx > len as u64 {
+            return Some(None);
+        }
+        let c = char::from_u32(range.start() + binary_search_arbitrary_u32(0, len, idx)).filter(
+            #[no_coverage]
+            |c| !SPECIAL_CHARS.contains(c),
+        );
+        Some(c)
+    }
+
+    #[no_coverage]
+    fn random_char_in_ranges(&self) -> char {
+        let range = &RANGES[self.rng.usize(..RANGES.len())];
+        char::from_u32(self.rng.u32(range.clone())).unwrap()
+    }
+}
+
+/// The number of neighbours of a character that are tried first by [`CharMutator::ordered_mutate`]
+const NBR_NEIGHBOURS: u64 = 8;
+
+/// Whether `c` is one of the neighbours of `value` tried first by [`CharMutator::ordered_mutate`]
+#[no_coverage]
+fn is_neighbour(value: char, c: char) -> bool {
+    let (a, b) = (value as u32, c as u32);
+    let distance = if a > b { a - b } else { b - a };
+    distance > 0 && distance as u64 <= NBR_NEIGHBOURS / 2
+}
+
+impl Mutator<char> for CharMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = u64;
+    #[doc(hidden)]
+    type ArbitraryStep = u64;
+    #[doc(hidden)]
+    type UnmutateToken = char; // old value
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, _value: &char) -> Option<Self::Cache> {
+        Some(())
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &char, _cache: &Self::Cache) -> Self::MutationStep {
+        INITIAL_MUTATION_STEP
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &char, _cache: &Self::Cache) -> f64 {
+        self.cplx
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(char, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        loop {
+            let s = *step;
+            *step += 1;
+            if let Some(&c) = SPECIAL_CHARS.get(s as usize) {
+                return Some((c, self.cplx));
+            }
+            if let Some(c) = self.walk_ranges(s - SPECIAL_CHARS.len() as u64)? {
+                return Some((c, self.cplx));
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (char, f64) {
+        let c = if self.rng.usize(..4) == 0 {
+            SPECIAL_CHARS[self.rng.usize(..SPECIAL_CHARS.len())]
+        } else {
+            self.random_char_in_ranges()
+        };
+        (c, self.cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut char,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        loop {
+            let s = *step;
+            *step += 1;
+            let c = if s < NBR_NEIGHBOURS {
+                // the neighbours that are surrogates or out of range are skipped
+                let distance = (s / 2 + 1) as u32;
+                let code_point = if s % 2 == 0 {
+                    (*value as u32).checked_add(distance)
+                } else {
+                    (*value as u32).checked_sub(distance)
+                };
+                code_point.and_then(char::from_u32)
+            } else if let Some(&c) = SPECIAL_CHARS.get((s - NBR_NEIGHBOURS) as usize) {
+                Some(c)
+            } else {
+                self.walk_ranges(s - NBR_NEIGHBOURS - SPECIAL_CHARS.len() as u64)?
+            };
+            match c {
+                // the neighbours are not produced again by the later steps
+                Some(c) if c != *value && (s < NBR_NEIGHBOURS || !is_neighbour(*value, c)) => {
+                    return Some((std::mem::replace(value, c), self.cplx));
+                }
+                _ => {}
+            }
+        }
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut char, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let c = match self.rng.usize(..3) {
+            0 => {
+                let distance = self.rng.u32(1..=4);
+                let code_point = if self.rng.bool() {
+                    (*value as u32).checked_add(distance)
+                } else {
+                    (*value as u32).checked_sub(distance)
+                };
+                code_point.and_then(char::from_u32).unwrap_or_else(
+                    #[no_coverage]
+                    || self.random_char_in_ranges(),
+                )
+            }
+            1 => SPECIAL_CHARS[self.rng.usize(..SPECIAL_CHARS.len())],
+            _ => self.random_char_in_ranges(),
+        };
+        (std::mem::replace(value, c), self.cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut char, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &char, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a char,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V> + 'static,
+    {
+        None
+    }
+}
+
+impl DefaultMutator for char {
+    type Mutator = CharMutator;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        CharMutator::default()
+    }
+}
+
+impl DefaultMutatorWithinRange for char {
+    type Bound = char;
+    type Mutator = CharWithinRangeMutator;
+    #[no_coverage]
+    fn default_mutator_within_range(range: RangeInclusive<char>) -> Self::Mutator {
+        CharWithinRangeMutator::new(range)
+    }
+}
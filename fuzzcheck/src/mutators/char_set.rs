@@ -0,0 +1,122 @@
+/*!
+Mutators for characters and strings over a restricted alphabet.
+
+[`CharSetMutator`] generates the characters within a list of ranges, and [`StringFromCharSetMutator`]
+generates strings made only of those characters. They are convenient for tokens that are a run of
+allowed characters, such as hexadecimal numbers or identifiers, where a full grammar would be overkill.
+
+```
+use fuzzcheck::mutators::char_set::{CharSetMutator, StringFromCharSetMutator};
+
+// hexadecimal digits
+let digit = CharSetMutator::new(&['0'..='9', 'a'..='f']);
+
+// hexadecimal numbers of 1 to 16 digits
+let number = StringFromCharSetMutator::new(&['0'..='9', 'a'..='f'], 1..=16);
+```
+
+The length of the strings is controlled in the same way as the length of the vectors generated by
+[`VecMutator`](crate::mutators::vector::VecMutator): the strings are built from a `Vec<char>` whose
+length is within the given range. The maximum complexity given to the fuzzer (`--max-cplx`) limits the
+length further. To generate strings of a fixed length, give a range containing a single length.
+*/
+
+use std::ops::RangeInclusive;
+
+use super::char::CharWithinRangesMutator;
+use super::map::MapMutator;
+use super::vector::VecMutator;
+use crate::MutatorWrapper;
+
+/// A mutator for the `char`s within a list of ranges.
+///
+/// [`validate_value`](crate::Mutator::validate_value) rejects the characters outside of the ranges.
+pub struct CharSetMutator {
+    mutator: CharWithinRangesMutator,
+}
+
+impl CharSetMutator {
+    /// ## Panics
+    /// Panics if the ranges do not contain any character.
+    #[no_coverage]
+    pub fn new(ranges: &[RangeInclusive<char>]) -> Self {
+        assert!(
+            ranges.iter().any(
+                #[no_coverage]
+                |range| !range.is_empty()
+            ),
+            "the character set must contain at least one character"
+        );
+        Self {
+            mutator: CharWithinRangesMutator::new(ranges.iter().cloned()),
+        }
+    }
+}
+
+impl MutatorWrapper for CharSetMutator {
+    type Wrapped = CharWithinRangesMutator;
+    #[no_coverage]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+type StringFromCharSetMutatorInner = MapMutator<
+    Vec<char>,
+    String,
+    VecMutator<char, CharWithinRangesMutator>,
+    fn(&String) -> Option<Vec<char>>,
+    fn(&Vec<char>) -> String,
+    fn(&String, f64) -> f64,
+>;
+
+/// A mutator for the `String`s made of the `char`s within a list of ranges.
+///
+/// [`validate_value`](crate::Mutator::validate_value) rejects the strings containing a character outside
+/// of the ranges or whose length, in characters, is outside of the length range.
+pub struct StringFromCharSetMutator {
+    mutator: StringFromCharSetMutatorInner,
+}
+
+#[no_coverage]
+fn chars_from_string(string: &String) -> Option<Vec<char>> {
+    Some(string.chars().collect())
+}
+
+#[no_coverage]
+fn string_from_chars(chars: &Vec<char>) -> String {
+    chars.iter().collect()
+}
+
+#[no_coverage]
+fn complexity(_string: &String, cplx: f64) -> f64 {
+    cplx
+}
+
+impl StringFromCharSetMutator {
+    /// Create a mutator for the strings made of the characters within `ranges` and whose
+    /// number of characters is within `len_range`.
+    ///
+    /// ## Panics
+    /// Panics if the ranges do not contain any character.
+    #[no_coverage]
+    pub fn new(ranges: &[RangeInclusive<char>], len_range: RangeInclusive<usize>) -> Self {
+        let CharSetMutator { mutator } = CharSetMutator::new(ranges);
+        Self {
+            mutator: MapMutator::new(
+                VecMutator::new(mutator, len_range),
+                chars_from_string,
+                string_from_chars,
+                complexity,
+            ),
+        }
+    }
+}
+
+impl MutatorWrapper for StringFromCharSetMutator {
+    type Wrapped = StringFromCharSetMutatorInner;
+    #[no_coverage]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
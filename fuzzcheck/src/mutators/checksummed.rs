@@ -0,0 +1,281 @@
+use crate::Mutator;
+
+/// A function that patches a value in place, for example to recompute a checksum or a length field.
+pub type Patch<T> = Box<dyn Fn(&mut T)>;
+
+/** Wrap a mutator and keep integrity fields, such as length prefixes and checksums, consistent with the payload.
+
+After each generated or mutated value, the patches are applied to the value before it is handed to
+the test function. They are applied **in the order in which they were added**. So if a checksum covers
+a length field, the patch writing the length field must be added first.
+
+The patches must not change the complexity of the value. For example, a patch should overwrite some
+bytes of a `Vec<u8>` but should not change its length. The wrapped mutator only ever sees the values
+before they were patched, so its cache never depends on the bytes written by the patches.
+
+```
+use fuzzcheck::DefaultMutator;
+use fuzzcheck::mutators::checksummed::ChecksummedMutator;
+
+let m = ChecksummedMutator::new(<Vec<u8>>::default_mutator())
+    // bytes 0..4 = little-endian length of bytes 4..
+    .with_patch(|bytes: &mut Vec<u8>| {
+        if bytes.len() >= 4 {
+            let len = (bytes.len() - 4) as u32;
+            bytes[0..4].copy_from_slice(&len.to_le_bytes());
+        }
+    })
+    // byte 4 = xor of bytes 5.., computed after the length field was written
+    .with_patch(|bytes: &mut Vec<u8>| {
+        if bytes.len() >= 5 {
+            bytes[4] = bytes[5..].iter().fold(0, |acc, b| acc ^ b);
+        }
+    });
+```
+
+Values from the input corpus are only accepted if their integrity fields are already correct, that is, if
+applying the patches does not change them.
+*/
+pub struct ChecksummedMutator<T: Clone + PartialEq, M: Mutator<T>> {
+    m: M,
+    patches: Vec<Patch<T>>,
+}
+
+impl<T: Clone + PartialEq, M: Mutator<T>> ChecksummedMutator<T, M> {
+    #[no_coverage]
+    pub fn new(mutator: M) -> Self {
+        Self {
+            m: mutator,
+            patches: vec![],
+        }
+    }
+
+    /// Add a patch to apply after the patches that were previously added.
+    #[no_coverage]
+    pub fn with_patch(mut self, patch: impl Fn(&mut T) + 'static) -> Self {
+        self.patches.push(Box::new(patch));
+        self
+    }
+
+    #[no_coverage]
+    fn patched(&self, value: &T) -> T {
+        let mut value = value.clone();
+        for patch in self.patches.iter() {
+            patch(&mut value);
+        }
+        value
+    }
+}
+
+/// The cache of a [`ChecksummedMutator`], which holds the value before it was patched
+pub struct Cache<T, M>
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    unpatched: T,
+    inner: M::Cache,
+}
+impl<T, M> Clone for Cache<T, M>
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn clone(&self) -> Self {
+        Self {
+            unpatched: self.unpatched.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static, M: Mutator<T>> Mutator<T> for ChecksummedMutator<T, M> {
+    #[doc(hidden)]
+    type Cache = Cache<T, M>;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.m.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        if self.patched(value) != *value {
+            return None;
+        }
+        let inner = self.m.validate_value(value)?;
+        Some(Cache {
+            unpatched: value.clone(),
+            inner,
+        })
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.m.default_mutation_step(&cache.unpatched, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.m.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.m.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &T, cache: &Self::Cache) -> f64 {
+        self.m.complexity(&cache.unpatched, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let (value, cplx) = self.m.ordered_arbitrary(step, max_cplx)?;
+        Some((self.patched(&value), cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let (value, cplx) = self.m.random_arbitrary(max_cplx);
+        (self.patched(&value), cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self
+            .m
+            .ordered_mutate(&mut cache.unpatched, &mut cache.inner, step, max_cplx)?;
+        *value = self.patched(&cache.unpatched);
+        Some((token, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let (token, cplx) = self.m.random_mutate(&mut cache.unpatched, &mut cache.inner, max_cplx);
+        *value = self.patched(&cache.unpatched);
+        (token, cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.m.unmutate(&mut cache.unpatched, &mut cache.inner, t);
+        *value = self.patched(&cache.unpatched);
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = M::RecursingPartIndex;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &T, cache: &Self::Cache) -> Self::RecursingPartIndex {
+        // the patches do not change the shape of the value, so the index is valid for the patched value too
+        self.m.default_recursing_part_index(&cache.unpatched, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(&self, parent: &N, value: &'a T, index: &mut Self::RecursingPartIndex) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        self.m.recursing_part::<V, N>(parent, value, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksummedMutator;
+    use crate::mutators::integer::U8Mutator;
+    use crate::mutators::vector::VecMutator;
+    use crate::Mutator;
+
+    /// byte 0 = xor of the other bytes
+    #[no_coverage]
+    fn has_valid_checksum(bytes: &[u8]) -> bool {
+        bytes.is_empty()
+            || bytes[0]
+                == bytes[1..].iter().fold(
+                    0,
+                    #[no_coverage]
+                    |acc, b| acc ^ b,
+                )
+    }
+
+    #[no_coverage]
+    fn mutator() -> ChecksummedMutator<Vec<u8>, VecMutator<u8, U8Mutator>> {
+        ChecksummedMutator::new(VecMutator::new(U8Mutator::default(), 0..=32)).with_patch(
+            #[no_coverage]
+            |bytes: &mut Vec<u8>| {
+                if !bytes.is_empty() {
+                    bytes[0] = bytes[1..].iter().fold(
+                        0,
+                        #[no_coverage]
+                        |acc, b| acc ^ b,
+                    );
+                }
+            },
+        )
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_mutations_keep_valid_checksum() {
+        let m = mutator();
+        for _ in 0..100 {
+            let (mut x, _) = m.random_arbitrary(200.0);
+            assert!(has_valid_checksum(&x));
+            let mut cache = m.validate_value(&x).unwrap();
+            let mut step = m.default_mutation_step(&x, &cache);
+            let original = x.clone();
+            for _ in 0..100 {
+                let (token, cplx) = m.random_mutate(&mut x, &mut cache, 200.0);
+                assert!(has_valid_checksum(&x));
+                assert_eq!(cplx, m.complexity(&x, &m.validate_value(&x).unwrap()));
+                m.unmutate(&mut x, &mut cache, token);
+                assert_eq!(x, original);
+
+                if let Some((token, _)) = m.ordered_mutate(&mut x, &mut cache, &mut step, 200.0) {
+                    assert!(has_valid_checksum(&x));
+                    m.unmutate(&mut x, &mut cache, token);
+                    assert_eq!(x, original);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_validate_value_rejects_wrong_checksum() {
+        let m = mutator();
+        assert!(m.validate_value(&vec![]).is_some());
+        assert!(m.validate_value(&vec![1 ^ 2 ^ 4, 1, 2, 4]).is_some());
+        assert!(m.validate_value(&vec![0, 1, 2, 4]).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+/*!
+A mutator for fixed-point decimal numbers, represented by an integer mantissa and a scale.
+
+The value `(mantissa, scale)` stands for `mantissa × 10^-scale`. For example, `(1234, 2)` is `12.34`. This is
+the representation used by most decimal types, such as `rust_decimal::Decimal`, and by code that stores amounts
+of money as a number of cents.
+
+[`DecimalMutator`] mutates the mantissa with [`I64Mutator`] and the scale with a [`U32WithinRangeMutator`]
+between `0` and a given maximum scale. Its [`ordered_arbitrary`](crate::Mutator::ordered_arbitrary) first
+generates zero, one, minus one, and the extreme values of the representation (the greatest and smallest mantissas,
+with the smallest and greatest scales), as well as the same values written with different scales, such as
+`(1, 0)` and `(100, 2)`, which are equal but often handled differently.
+
+To test a concrete decimal type, map the mutator to it with a [`MapMutator`](crate::mutators::map::MapMutator):
+```
+use fuzzcheck::mutators::decimal::DecimalMutator;
+use fuzzcheck::mutators::map::MapMutator;
+
+// a decimal type, such as rust_decimal::Decimal
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decimal {
+    mantissa: i64,
+    scale: u32,
+}
+
+let m = MapMutator::new(
+    DecimalMutator::new(8),
+    // from the decimal to its representation, or None if it cannot be represented
+    |d: &Decimal| Some((d.mantissa, d.scale)),
+    // from the representation to the decimal
+    |&(mantissa, scale): &(i64, u32)| Decimal { mantissa, scale },
+    // the complexity of the decimal, given the complexity of its representation
+    |_d: &Decimal, cplx: f64| cplx,
+);
+```
+For amounts of money stored as `i64` cents, the scale is always 2 and [`I64Mutator`] can be used directly.
+*/
+
+use super::dictionary::DictionaryMutator;
+use super::integer::I64Mutator;
+use super::integer_within_range::U32WithinRangeMutator;
+use super::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
+use super::wrapper::Wrapper;
+
+/// A mutator for `(mantissa, scale)` pairs representing the decimal number `mantissa × 10^-scale`.
+///
+/// See the [module documentation](crate::mutators::decimal) for more details.
+pub type DecimalMutator = Wrapper<
+    DictionaryMutator<
+        (i64, u32),
+        TupleMutatorWrapper<Tuple2Mutator<I64Mutator, U32WithinRangeMutator>, Tuple2<i64, u32>>,
+    >,
+>;
+
+impl DecimalMutator {
+    /// Create a mutator for the decimal numbers whose scale is at most `max_scale`.
+    #[no_coverage]
+    pub fn new(max_scale: u32) -> Self {
+        Wrapper(DictionaryMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(
+                I64Mutator::default(),
+                U32WithinRangeMutator::new(0..=max_scale),
+            )),
+            decimal_edge_cases(max_scale),
+        ))
+    }
+}
+
+/// The values generated first by [`DecimalMutator`]
+#[no_coverage]
+fn decimal_edge_cases(max_scale: u32) -> Vec<(i64, u32)> {
+    let mut values = vec![
+        (0, 0),
+        (1, 0),
+        (-1, 0),
+        (i64::MAX, 0),
+        (i64::MIN, 0),
+        (0, max_scale),
+        (1, max_scale),
+        (-1, max_scale),
+        (i64::MAX, max_scale),
+        (i64::MIN, max_scale),
+    ];
+    // one and minus one, written with each scale whose power of ten fits in the mantissa
+    for scale in 1..=max_scale {
+        if let Some(one) = 10i64.checked_pow(scale) {
+            values.push((one, scale));
+            values.push((-one, scale));
+        } else {
+            break;
+        }
+    }
+    // with a maximum scale of 0, the same values are listed twice
+    let mut unique_values = vec![];
+    for value in values {
+        if !unique_values.contains(&value) {
+            unique_values.push(value);
+        }
+    }
+    unique_values
+}
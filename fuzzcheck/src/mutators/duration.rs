@@ -1,45 +1,86 @@
-use std::time::Duration;
+/*!
+Mutators for [`Duration`] and [`SystemTime`].
 
+A duration is mutated as its number of whole seconds and its number of nanoseconds, so that both parts reach
+their edge cases, such as `999_999_999` nanoseconds or `u64::MAX` seconds. The mutators first generate a few
+values that are likely to trigger bugs, such as the zero duration, the maximum duration, or the UNIX epoch.
+
+A [`SystemTime`] is generated between the years 1834 and 2514, relative to the UNIX epoch, which is a range
+representable on all platforms and that contains the 2038 and 2106 overflows of 32-bit timestamps.
+*/
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::dictionary::DictionaryMutator;
 use super::integer::U64Mutator;
+use super::integer_within_range::{I64WithinRangeMutator, U32WithinRangeMutator};
 use super::map::MapMutator;
+use super::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
 use super::wrapper::Wrapper;
 
 use crate::DefaultMutator;
 
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// The smallest number of seconds, relative to the UNIX epoch, of a value generated by [`SystemTimeMutator`]
+const MIN_SYSTEM_TIME_SECS: i64 = -(1 << 32);
+/// The largest number of seconds, relative to the UNIX epoch, of a value generated by [`SystemTimeMutator`]
+const MAX_SYSTEM_TIME_SECS: i64 = 1 << 34;
+
 pub type DurationMutator = Wrapper<
-    MapMutator<
-        u64,
+    DictionaryMutator<
         Duration,
-        U64Mutator,
-        fn(&Duration) -> Option<u64>,
-        fn(&u64) -> Duration,
-        fn(&Duration, f64) -> f64,
+        MapMutator<
+            (u64, u32),
+            Duration,
+            TupleMutatorWrapper<Tuple2Mutator<U64Mutator, U32WithinRangeMutator>, Tuple2<u64, u32>>,
+            fn(&Duration) -> Option<(u64, u32)>,
+            fn(&(u64, u32)) -> Duration,
+            fn(&Duration, f64) -> f64,
+        >,
     >,
 >;
 
 #[no_coverage]
-fn u64_from_duration(d: &Duration) -> Option<u64> {
-    Some(d.as_secs())
+fn tuple_from_duration(d: &Duration) -> Option<(u64, u32)> {
+    Some((d.as_secs(), d.subsec_nanos()))
 }
 
 #[no_coverage]
-fn duration_from_u64(u: &u64) -> Duration {
-    Duration::from_secs(*u)
+fn duration_from_tuple(t: &(u64, u32)) -> Duration {
+    Duration::new(t.0, t.1)
 }
 
 #[no_coverage]
-fn complexity(_t: &Duration, cplx: f64) -> f64 {
+fn duration_complexity(_t: &Duration, cplx: f64) -> f64 {
     cplx
 }
 
 impl DurationMutator {
     #[no_coverage]
     pub fn new() -> Self {
-        Wrapper(MapMutator::new(
-            U64Mutator::default(),
-            u64_from_duration,
-            duration_from_u64,
-            complexity,
+        let m = MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(
+                U64Mutator::default(),
+                U32WithinRangeMutator::new(0..NANOS_PER_SEC),
+            )),
+            tuple_from_duration as _,
+            duration_from_tuple as _,
+            duration_complexity as _,
+        );
+        Wrapper(DictionaryMutator::new(
+            m,
+            [
+                Duration::ZERO,
+                Duration::from_nanos(1),
+                Duration::from_nanos(NANOS_PER_SEC as u64 - 1),
+                Duration::from_secs(1),
+                Duration::from_millis(1500),
+                Duration::from_secs(u32::MAX as u64),
+                Duration::from_secs(i64::MAX as u64),
+                Duration::from_secs(u64::MAX),
+                Duration::MAX,
+            ],
         ))
     }
 }
@@ -51,3 +92,89 @@ impl DefaultMutator for Duration {
         Self::Mutator::new()
     }
 }
+
+pub type SystemTimeMutator = Wrapper<
+    DictionaryMutator<
+        SystemTime,
+        MapMutator<
+            (i64, u32),
+            SystemTime,
+            TupleMutatorWrapper<Tuple2Mutator<I64WithinRangeMutator, U32WithinRangeMutator>, Tuple2<i64, u32>>,
+            fn(&SystemTime) -> Option<(i64, u32)>,
+            fn(&(i64, u32)) -> SystemTime,
+            fn(&SystemTime, f64) -> f64,
+        >,
+    >,
+>;
+
+/// The number of seconds and nanoseconds since the UNIX epoch, where the seconds are negative before the epoch
+#[no_coverage]
+fn tuple_from_system_time(t: &SystemTime) -> Option<(i64, u32)> {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => Some((i64::try_from(d.as_secs()).ok()?, d.subsec_nanos())),
+        Err(e) => {
+            let d = e.duration();
+            let secs = i64::try_from(d.as_secs()).ok()?;
+            if d.subsec_nanos() == 0 {
+                Some((-secs, 0))
+            } else {
+                Some((-secs - 1, NANOS_PER_SEC - d.subsec_nanos()))
+            }
+        }
+    }
+}
+
+#[no_coverage]
+fn system_time_from_tuple(t: &(i64, u32)) -> SystemTime {
+    let (secs, nanos) = *t;
+    if secs >= 0 {
+        UNIX_EPOCH + Duration::new(secs as u64, nanos)
+    } else {
+        UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()) + Duration::from_nanos(nanos as u64)
+    }
+}
+
+#[no_coverage]
+fn system_time_complexity(_t: &SystemTime, cplx: f64) -> f64 {
+    cplx
+}
+
+impl SystemTimeMutator {
+    #[no_coverage]
+    pub fn new() -> Self {
+        let m = MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(
+                I64WithinRangeMutator::new(MIN_SYSTEM_TIME_SECS..=MAX_SYSTEM_TIME_SECS),
+                U32WithinRangeMutator::new(0..NANOS_PER_SEC),
+            )),
+            tuple_from_system_time as _,
+            system_time_from_tuple as _,
+            system_time_complexity as _,
+        );
+        Wrapper(DictionaryMutator::new(
+            m,
+            [
+                UNIX_EPOCH,
+                UNIX_EPOCH - Duration::from_nanos(1),
+                UNIX_EPOCH + Duration::from_nanos(1),
+                // the largest and smallest 32-bit timestamps, in 2038 and 1901
+                UNIX_EPOCH + Duration::from_secs(i32::MAX as u64),
+                UNIX_EPOCH + Duration::from_secs(i32::MAX as u64 + 1),
+                UNIX_EPOCH - Duration::from_secs(i32::MAX as u64 + 1),
+                // the largest unsigned 32-bit timestamp, in 2106
+                UNIX_EPOCH + Duration::from_secs(u32::MAX as u64),
+                // 2000-01-01 and 2000-02-29
+                UNIX_EPOCH + Duration::from_secs(946_684_800),
+                UNIX_EPOCH + Duration::from_secs(951_782_400),
+            ],
+        ))
+    }
+}
+
+impl DefaultMutator for SystemTime {
+    type Mutator = SystemTimeMutator;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new()
+    }
+}
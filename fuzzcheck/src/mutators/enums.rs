@@ -10,9 +10,15 @@ pub trait BasicEnumStructure {
 extern crate self as fuzzcheck;
 
 /// A mutator used for enums implementing [BasicEnumStructure]
+///
+/// The generated variants can be restricted with [`only_variants`](BasicEnumMutator::only_variants)
+/// and [`exclude_variants`](BasicEnumMutator::exclude_variants). The variants are identified by
+/// their index in the declaration of the enum. All variants are still accepted in the corpus.
 pub struct BasicEnumMutator {
     rng: fastrand::Rng,
     cplx: f64,
+    /// The indices of the variants that can be generated
+    items: Vec<usize>,
 }
 impl BasicEnumMutator {
     #[no_coverage]
@@ -20,8 +26,37 @@ impl BasicEnumMutator {
         Self {
             rng: <_>::default(),
             cplx: crate::mutators::size_to_cplxity(std::mem::variant_count::<T>()),
+            items: (0..std::mem::variant_count::<T>()).collect(),
         }
     }
+
+    /// Only generate the variants whose index is in `variants`.
+    ///
+    /// ## Panics
+    /// Panics if no variant can be generated anymore.
+    #[no_coverage]
+    pub fn only_variants(mut self, variants: &[usize]) -> Self {
+        self.items.retain(
+            #[no_coverage]
+            |item| variants.contains(item),
+        );
+        assert!(!self.items.is_empty(), "all the variants of the enum were excluded");
+        self
+    }
+
+    /// Do not generate the variants whose index is in `variants`.
+    ///
+    /// ## Panics
+    /// Panics if no variant can be generated anymore.
+    #[no_coverage]
+    pub fn exclude_variants(mut self, variants: &[usize]) -> Self {
+        self.items.retain(
+            #[no_coverage]
+            |item| !variants.contains(item),
+        );
+        assert!(!self.items.is_empty(), "all the variants of the enum were excluded");
+        self
+    }
 }
 
 const INITIAL_MUTATION_STEP: usize = 1;
@@ -81,10 +116,10 @@ where
         if max_cplx < <Self as Mutator<T>>::min_complexity(self) {
             return None;
         }
-        if *step < std::mem::variant_count::<T>() {
+        if *step < self.items.len() {
             let old_step = *step;
             *step += 1;
-            Some((T::from_item_index(old_step), self.cplx))
+            Some((T::from_item_index(self.items[old_step]), self.cplx))
         } else {
             None
         }
@@ -93,7 +128,7 @@ where
     #[doc(hidden)]
     #[no_coverage]
     fn random_arbitrary(&self, _max_cplx: f64) -> (T, f64) {
-        let item_idx = self.rng.usize(..std::mem::variant_count::<T>());
+        let item_idx = self.items[self.rng.usize(..self.items.len())];
         (T::from_item_index(item_idx), self.cplx)
     }
 
@@ -110,23 +145,25 @@ where
             return None;
         }
         // starts at step = 1
-        // create new from (get_item_index + step) % nbr_of_items
-        if *step < std::mem::variant_count::<T>() {
-            let old_index = value.get_item_index();
-            let old_step = *step;
+        // create new from (get_item_index + step) % nbr_of_items, skipping the excluded items
+        let nbr_items = std::mem::variant_count::<T>();
+        let old_index = value.get_item_index();
+        while *step < nbr_items {
+            let item_idx = (old_index + *step) % nbr_items;
             *step += 1;
-            *value = T::from_item_index((old_index + old_step) % std::mem::variant_count::<T>());
-            Some((old_index, self.cplx))
-        } else {
-            None
+            if self.items.contains(&item_idx) {
+                *value = T::from_item_index(item_idx);
+                return Some((old_index, self.cplx));
+            }
         }
+        None
     }
 
     #[doc(hidden)]
     #[no_coverage]
     fn random_mutate(&self, value: &mut T, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
         let old_index = value.get_item_index();
-        let item_idx = self.rng.usize(..std::mem::variant_count::<T>());
+        let item_idx = self.items[self.rng.usize(..self.items.len())];
         *value = T::from_item_index(item_idx);
         (old_index, self.cplx)
     }
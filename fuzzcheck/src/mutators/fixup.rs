@@ -0,0 +1,279 @@
+use crate::Mutator;
+
+/** Wrap a mutator and repair the values it generates with a `fixup` function.
+
+This is useful when some parts of a value must be consistent with each other, for example when a `kind` field
+determines which of the other fields of a struct are meaningful. Instead of rejecting the inconsistent
+values, the `fixup` function modifies them in place such that they satisfy the invariant. It is called after
+each generated or mutated value, before it is handed to the test function.
+
+The `fixup` function must be **idempotent**: applying it to a value that it has already fixed must not change
+that value. Values from the input corpus are only accepted if they are already fixed, that is, if
+calling `fixup` on them does not change them. The fixed values must also be valid values for the wrapped mutator.
+
+```
+# #![feature(no_coverage)]
+use fuzzcheck::DefaultMutator;
+use fuzzcheck::mutators::fixup::FixupMutator;
+
+#[derive(Clone, PartialEq, DefaultMutator)]
+struct Packet {
+    is_ack: bool,
+    // only meaningful when `is_ack` is true
+    ack_number: u32,
+    payload: Vec<u8>,
+}
+
+let m = FixupMutator::new(Packet::default_mutator(), |p: &mut Packet| {
+    if !p.is_ack {
+        p.ack_number = 0;
+    }
+});
+```
+
+The complexity of a value is the complexity that the wrapped mutator gives to the value before it was fixed.
+*/
+pub struct FixupMutator<T, M, F>
+where
+    T: Clone + PartialEq,
+    M: Mutator<T>,
+    F: Fn(&mut T),
+{
+    mutator: M,
+    fixup: F,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T, M, F> FixupMutator<T, M, F>
+where
+    T: Clone + PartialEq,
+    M: Mutator<T>,
+    F: Fn(&mut T),
+{
+    #[no_coverage]
+    pub fn new(mutator: M, fixup: F) -> Self {
+        Self {
+            mutator,
+            fixup,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    #[no_coverage]
+    fn fixed(&self, value: &T) -> T {
+        let mut value = value.clone();
+        (self.fixup)(&mut value);
+        value
+    }
+}
+
+/// The cache of a [`FixupMutator`], which holds the value before it was fixed
+pub struct Cache<T, M>
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    unfixed_value: T,
+    inner: M::Cache,
+}
+impl<T, M> Clone for Cache<T, M>
+where
+    T: Clone,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn clone(&self) -> Self {
+        Self {
+            unfixed_value: self.unfixed_value.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, M, F> Mutator<T> for FixupMutator<T, M, F>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+    F: Fn(&mut T),
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = Cache<T, M>;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        if self.fixed(value) != *value {
+            return None;
+        }
+        let inner = self.mutator.validate_value(value)?;
+        Some(Cache {
+            unfixed_value: value.clone(),
+            inner,
+        })
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(&cache.unfixed_value, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&cache.unfixed_value, &cache.inner)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let (value, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        Some((self.fixed(&value), cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let (value, cplx) = self.mutator.random_arbitrary(max_cplx);
+        (self.fixed(&value), cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self
+            .mutator
+            .ordered_mutate(&mut cache.unfixed_value, &mut cache.inner, step, max_cplx)?;
+        *value = self.fixed(&cache.unfixed_value);
+        Some((token, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let (token, cplx) = self
+            .mutator
+            .random_mutate(&mut cache.unfixed_value, &mut cache.inner, max_cplx);
+        *value = self.fixed(&cache.unfixed_value);
+        (token, cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        _other: &T,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self.mutator.crossover_mutate(
+            &mut cache.unfixed_value,
+            &mut cache.inner,
+            &other_cache.unfixed_value,
+            &other_cache.inner,
+            max_cplx,
+        )?;
+        *value = self.fixed(&cache.unfixed_value);
+        Some((token, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(&mut cache.unfixed_value, &mut cache.inner, t);
+        *value = self.fixed(&cache.unfixed_value);
+    }
+
+    // Like MapMutator, the recursing part would have to be looked up in the cache,
+    // which is not possible with the current signature of `recursing_part`
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &T, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a T,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixupMutator;
+    use crate::mutators::integer::U8Mutator;
+    use crate::mutators::vector::VecMutator;
+    use crate::Mutator;
+
+    #[test]
+    #[no_coverage]
+    fn test_crossover_is_fixed() {
+        let m = FixupMutator::new(
+            VecMutator::new(U8Mutator::default(), 0..=100),
+            #[no_coverage]
+            |v: &mut Vec<u8>| v.sort_unstable(),
+        );
+        let other = vec![3, 7, 9, 12];
+        let other_cache = m.validate_value(&other).unwrap();
+        for _ in 0..1000 {
+            let (mut x, _) = m.random_arbitrary(100.0);
+            let mut cache = m.validate_value(&x).unwrap();
+            let original = x.clone();
+            let (token, cplx) = m
+                .crossover_mutate(&mut x, &mut cache, &other, &other_cache, 1000.0)
+                .unwrap();
+            assert!(x.windows(2).all(
+                #[no_coverage]
+                |w| w[0] <= w[1]
+            ));
+            assert_eq!(x, m.fixed(&cache.unfixed_value));
+            assert_eq!(cplx, m.complexity(&x, &m.validate_value(&x).unwrap()));
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, original);
+        }
+    }
+}
@@ -1,43 +1,561 @@
-use super::integer::U64Mutator;
-use super::map::MapMutator;
-use super::wrapper::Wrapper;
-
+use super::integer::{U32Mutator, U64Mutator};
+use crate::mutators::DefaultMutatorWithinRange;
 use crate::DefaultMutator;
+use crate::Mutator;
+use std::ops::RangeInclusive;
 
-pub type F64Mutator =
-    Wrapper<MapMutator<u64, f64, U64Mutator, fn(&f64) -> Option<u64>, fn(&u64) -> f64, fn(&f64, f64) -> f64>>;
+/*
+    The float mutators work on the bits of the floating point numbers, so that every value, including
+    each NaN payload, can be produced.
 
-#[no_coverage]
-fn u64_from_f64(f: &f64) -> Option<u64> {
-    Some(f.to_bits())
-}
+    `ordered_arbitrary` first produces the special values of the float type: ±0.0, ±1.0, NaN, ±inf,
+    the smallest and largest subnormals, the smallest normal numbers, MIN, MAX, EPSILON, and the powers of two
+    at the boundaries of the integer types and of the integers that can be represented exactly. These values
+    are the most likely to trigger bugs in numeric code. It then follows the uniform permutation of the bits of
+    the corresponding integer mutator, skipping the special values.
 
-#[no_coverage]
-fn f64_from_u64(u: &u64) -> f64 {
-    f64::from_bits(*u)
-}
+    `ordered_mutate` produces, in this order:
+    1. the 8 closest neighbours of the value, by adding or subtracting up to 4 to its bits. For a finite
+       number, they are the next or previous representable numbers.
+    2. the value with one of its bits flipped, from the lowest bit of the mantissa to the sign bit
+    3. the special values
+    4. the uniform permutation of the bits
 
-#[no_coverage]
-fn complexity(_t: &f64, cplx: f64) -> f64 {
-    cplx
-}
+    The `WithinRange` float mutators only produce finite values between the two bounds of a range. They start
+    with the bounds, the midpoint, and the special values that fall within the range (e.g. 0.0, ±1.0), and then
+    interpolate between the two bounds following the uniform permutation of the bits. A value is mutated by
+    nudging its bits first, skipping the neighbours outside of the range.
+*/
+
+const NBR_NUDGES: u64 = 8;
+
+macro_rules! impl_float_mutator {
+    ($name:ident, $name_bits:ident, $bits_mutator:ident, $name_mutator:ident) => {
+        /// The default mutator of
+        #[doc = concat!("`", stringify!($name), "`.")]
+        ///
+        /// It produces the special values of the float type, such as NaN, infinities, and subnormals, before
+        /// other values, and mutates a value by nudging and flipping its bits first.
+        #[derive(Clone)]
+        pub struct $name_mutator {
+            bits_mutator: $bits_mutator,
+            special_values: Vec<$name>,
+            rng: fastrand::Rng,
+        }
+        impl Default for $name_mutator {
+            #[no_coverage]
+            fn default() -> Self {
+                let smallest_subnormal = <$name>::from_bits(1);
+                let largest_subnormal = <$name>::from_bits(<$name>::MIN_POSITIVE.to_bits() - 1);
+                let mut candidates = vec![
+                    0.0,
+                    -0.0,
+                    1.0,
+                    -1.0,
+                    <$name>::NAN,
+                    -<$name>::NAN,
+                    <$name>::INFINITY,
+                    <$name>::NEG_INFINITY,
+                    smallest_subnormal,
+                    -smallest_subnormal,
+                    largest_subnormal,
+                    -largest_subnormal,
+                    <$name>::MIN_POSITIVE,
+                    -<$name>::MIN_POSITIVE,
+                    <$name>::MAX,
+                    <$name>::MIN,
+                    <$name>::EPSILON,
+                    1.0 + <$name>::EPSILON,
+                    1.0 - <$name>::EPSILON / 2.0,
+                    0.5,
+                    -0.5,
+                    2.0,
+                    -2.0,
+                ];
+                for exp in [8, 16, 31, 32, 63, 64, <$name>::MANTISSA_DIGITS as i32] {
+                    let two: $name = 2.0;
+                    let x = two.powi(exp);
+                    candidates.extend([x, x - 1.0, -x]);
+                }
+                // NaN is not equal to itself, so the duplicates are found by comparing the bits
+                let mut special_values: Vec<$name> = vec![];
+                for x in candidates {
+                    if !special_values.iter().any(
+                        #[no_coverage]
+                        |y| y.to_bits() == x.to_bits(),
+                    ) {
+                        special_values.push(x);
+                    }
+                }
+                $name_mutator {
+                    bits_mutator: <$bits_mutator>::default(),
+                    special_values,
+                    rng: fastrand::Rng::default(),
+                }
+            }
+        }
+
+        impl $name_mutator {
+            #[no_coverage]
+            fn is_special_value(&self, value: $name) -> bool {
+                self.special_values.iter().any(
+                    #[no_coverage]
+                    |x| x.to_bits() == value.to_bits(),
+                )
+            }
+        }
+
+        impl Mutator<$name> for $name_mutator {
+            #[doc(hidden)]
+            type Cache = ();
+            #[doc(hidden)]
+            type MutationStep = u64;
+            #[doc(hidden)]
+            type ArbitraryStep = u64;
+            #[doc(hidden)]
+            type UnmutateToken = $name; // old value
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                0
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn validate_value(&self, _value: &$name) -> Option<Self::Cache> {
+                Some(())
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_mutation_step(&self, _value: &$name, _cache: &Self::Cache) -> Self::MutationStep {
+                0
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn max_complexity(&self) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn min_complexity(&self) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn complexity(&self, _value: &$name, _cache: &Self::Cache) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($name, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let nbr_special_values = self.special_values.len() as u64;
+                loop {
+                    if *step < nbr_special_values {
+                        let value = self.special_values[*step as usize];
+                        *step += 1;
+                        return Some((value, <$name_bits>::BITS as f64));
+                    }
+                    let permutation_step = *step - nbr_special_values;
+                    if permutation_step > <$name_bits>::MAX as u64 {
+                        return None;
+                    }
+                    let value = <$name>::from_bits(self.bits_mutator.uniform_permutation(permutation_step));
+                    *step += 1;
+                    // the special values were already produced
+                    if !self.is_special_value(value) {
+                        return Some((value, <$name_bits>::BITS as f64));
+                    }
+                }
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
+                let value = if self.rng.usize(..4) == 0 {
+                    self.special_values[self.rng.usize(..self.special_values.len())]
+                } else {
+                    <$name>::from_bits(self.rng.$name_bits(..))
+                };
+                (value, <$name_bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn ordered_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let nbr_bits = <$name_bits>::BITS as u64;
+                let nbr_special_values = self.special_values.len() as u64;
+                let bits = value.to_bits();
+                loop {
+                    let s = *step;
+                    *step += 1;
+                    let new_bits = if s < NBR_NUDGES {
+                        let nudge = (s / 2 + 1) as $name_bits;
+                        if s % 2 == 0 {
+                            bits.wrapping_add(nudge)
+                        } else {
+                            bits.wrapping_sub(nudge)
+                        }
+                    } else if s < NBR_NUDGES + nbr_bits {
+                        bits ^ (1 << (s - NBR_NUDGES))
+                    } else if s < NBR_NUDGES + nbr_bits + nbr_special_values {
+                        let special_value = self.special_values[(s - NBR_NUDGES - nbr_bits) as usize];
+                        if special_value.to_bits() == bits {
+                            continue;
+                        }
+                        special_value.to_bits()
+                    } else {
+                        let permutation_step = s - NBR_NUDGES - nbr_bits - nbr_special_values;
+                        if permutation_step > <$name_bits>::MAX as u64 {
+                            return None;
+                        }
+                        self.bits_mutator.uniform_permutation(permutation_step)
+                    };
+                    let token = std::mem::replace(value, <$name>::from_bits(new_bits));
+                    return Some((token, <$name_bits>::BITS as f64));
+                }
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn random_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                _max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let bits = value.to_bits();
+                let new_bits = match self.rng.usize(..4) {
+                    0 => {
+                        let nudge = self.rng.$name_bits(1..=4);
+                        if self.rng.bool() {
+                            bits.wrapping_add(nudge)
+                        } else {
+                            bits.wrapping_sub(nudge)
+                        }
+                    }
+                    1 => bits ^ (1 << self.rng.u32(..<$name_bits>::BITS)),
+                    2 => self.special_values[self.rng.usize(..self.special_values.len())].to_bits(),
+                    _ => self.rng.$name_bits(..),
+                };
+                (
+                    std::mem::replace(value, <$name>::from_bits(new_bits)),
+                    <$name_bits>::BITS as f64,
+                )
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn unmutate(&self, value: &mut $name, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                *value = t;
+            }
+
+            #[doc(hidden)]
+            type RecursingPartIndex = ();
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_recursing_part_index(&self, _value: &$name, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+            #[doc(hidden)]
+            #[no_coverage]
+            fn recursing_part<'a, V, N>(
+                &self,
+                _parent: &N,
+                _value: &'a $name,
+                _index: &mut Self::RecursingPartIndex,
+            ) -> Option<&'a V>
+            where
+                V: Clone + 'static,
+                N: Mutator<V> + 'static,
+            {
+                None
+            }
+        }
 
-impl F64Mutator {
-    #[no_coverage]
-    pub fn new() -> Self {
-        Wrapper(MapMutator::new(
-            U64Mutator::default(),
-            u64_from_f64,
-            f64_from_u64,
-            complexity,
-        ))
-    }
+        impl DefaultMutator for $name {
+            type Mutator = $name_mutator;
+            #[no_coverage]
+            fn default_mutator() -> Self::Mutator {
+                <$name_mutator>::default()
+            }
+        }
+    };
 }
 
-impl DefaultMutator for f64 {
-    type Mutator = F64Mutator;
-    #[no_coverage]
-    fn default_mutator() -> Self::Mutator {
-        Self::Mutator::new()
-    }
+impl_float_mutator!(f32, u32, U32Mutator, F32Mutator);
+impl_float_mutator!(f64, u64, U64Mutator, F64Mutator);
+
+macro_rules! impl_float_within_range_mutator {
+    ($name:ident, $name_bits:ident, $bits_mutator:ident, $name_mutator:ident) => {
+        /// A mutator for the finite
+        #[doc = concat!("`", stringify!($name), "`")]
+        /// values within a range.
+        ///
+        /// The bounds of the range are produced first, followed by the other special values within the range.
+        #[derive(Clone)]
+        pub struct $name_mutator {
+            start: $name,
+            end: $name,
+            bits_mutator: $bits_mutator,
+            special_values: Vec<$name>,
+            rng: fastrand::Rng,
+        }
+
+        impl $name_mutator {
+            /// Create a mutator for the values within `range`, whose bounds must be finite
+            #[no_coverage]
+            pub fn new(range: RangeInclusive<$name>) -> Self {
+                let (start, end) = range.into_inner();
+                assert!(
+                    start.is_finite() && end.is_finite() && start <= end,
+                    "The bounds of the range of a float mutator must be finite, and the start of the range must not \
+                    be larger than its end.\nRange start: {}\nRange end: {}",
+                    start,
+                    end
+                );
+                let candidates = [
+                    start,
+                    end,
+                    start / 2.0 + end / 2.0,
+                    0.0,
+                    -0.0,
+                    1.0,
+                    -1.0,
+                    <$name>::from_bits(start.to_bits().wrapping_add(1)),
+                    <$name>::from_bits(start.to_bits().wrapping_sub(1)),
+                    <$name>::from_bits(end.to_bits().wrapping_add(1)),
+                    <$name>::from_bits(end.to_bits().wrapping_sub(1)),
+                ];
+                let mut special_values: Vec<$name> = vec![];
+                for x in candidates {
+                    if start <= x
+                        && x <= end
+                        && !special_values.iter().any(
+                            #[no_coverage]
+                            |y| y.to_bits() == x.to_bits(),
+                        )
+                    {
+                        special_values.push(x);
+                    }
+                }
+                Self {
+                    start,
+                    end,
+                    bits_mutator: <$bits_mutator>::default(),
+                    special_values,
+                    rng: fastrand::Rng::default(),
+                }
+            }
+
+            #[no_coverage]
+            fn contains(&self, value: $name) -> bool {
+                // false for NaN
+                self.start <= value && value <= self.end
+            }
+
+            /// The value at `t` between the start (`t = 0.0`) and the end (`t = 1.0`) of the range
+            #[no_coverage]
+            fn interpolate(&self, t: $name) -> $name {
+                // computed such that it does not overflow, even when the range is larger than MAX
+                let x = self.start * (1.0 - t) + self.end * t;
+                x.max(self.start).min(self.end)
+            }
+
+            #[no_coverage]
+            fn uniform_permutation(&self, step: u64) -> $name {
+                let bits = self.bits_mutator.uniform_permutation(step);
+                self.interpolate(bits as $name / <$name_bits>::MAX as $name)
+            }
+        }
+
+        impl Mutator<$name> for $name_mutator {
+            #[doc(hidden)]
+            type Cache = ();
+            #[doc(hidden)]
+            type MutationStep = u64;
+            #[doc(hidden)]
+            type ArbitraryStep = u64;
+            #[doc(hidden)]
+            type UnmutateToken = $name; // old value
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                0
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn validate_value(&self, value: &$name) -> Option<Self::Cache> {
+                if self.contains(*value) {
+                    Some(())
+                } else {
+                    None
+                }
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_mutation_step(&self, _value: &$name, _cache: &Self::Cache) -> Self::MutationStep {
+                0
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn max_complexity(&self) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn min_complexity(&self) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn complexity(&self, _value: &$name, _cache: &Self::Cache) -> f64 {
+                <$name_bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($name, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let nbr_special_values = self.special_values.len() as u64;
+                if *step < nbr_special_values {
+                    let value = self.special_values[*step as usize];
+                    *step += 1;
+                    return Some((value, <$name_bits>::BITS as f64));
+                }
+                let permutation_step = *step - nbr_special_values;
+                if permutation_step > <$name_bits>::MAX as u64 {
+                    return None;
+                }
+                *step += 1;
+                Some((
+                    self.uniform_permutation(permutation_step),
+                    <$name_bits>::BITS as f64,
+                ))
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
+                let value = if self.rng.usize(..4) == 0 {
+                    self.special_values[self.rng.usize(..self.special_values.len())]
+                } else {
+                    self.interpolate(self.rng.$name())
+                };
+                (value, <$name_bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn ordered_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let nbr_special_values = self.special_values.len() as u64;
+                let bits = value.to_bits();
+                loop {
+                    let s = *step;
+                    *step += 1;
+                    let new_value = if s < NBR_NUDGES {
+                        let nudge = (s / 2 + 1) as $name_bits;
+                        let new_bits = if s % 2 == 0 {
+                            bits.wrapping_add(nudge)
+                        } else {
+                            bits.wrapping_sub(nudge)
+                        };
+                        <$name>::from_bits(new_bits)
+                    } else if s < NBR_NUDGES + nbr_special_values {
+                        self.special_values[(s - NBR_NUDGES) as usize]
+                    } else {
+                        let permutation_step = s - NBR_NUDGES - nbr_special_values;
+                        if permutation_step > <$name_bits>::MAX as u64 {
+                            return None;
+                        }
+                        self.uniform_permutation(permutation_step)
+                    };
+                    if !self.contains(new_value) || new_value.to_bits() == bits {
+                        continue;
+                    }
+                    let token = std::mem::replace(value, new_value);
+                    return Some((token, <$name_bits>::BITS as f64));
+                }
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn random_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                _max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let bits = value.to_bits();
+                let new_value = match self.rng.usize(..3) {
+                    0 => {
+                        let nudge = self.rng.$name_bits(1..=4);
+                        let new_bits = if self.rng.bool() {
+                            bits.wrapping_add(nudge)
+                        } else {
+                            bits.wrapping_sub(nudge)
+                        };
+                        <$name>::from_bits(new_bits)
+                    }
+                    1 => self.special_values[self.rng.usize(..self.special_values.len())],
+                    _ => self.interpolate(self.rng.$name()),
+                };
+                // a nudge may step outside of the range
+                let new_value = if self.contains(new_value) {
+                    new_value
+                } else {
+                    self.interpolate(self.rng.$name())
+                };
+                (std::mem::replace(value, new_value), <$name_bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            fn unmutate(&self, value: &mut $name, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                *value = t;
+            }
+
+            #[doc(hidden)]
+            type RecursingPartIndex = ();
+            #[doc(hidden)]
+            #[no_coverage]
+            fn default_recursing_part_index(&self, _value: &$name, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+            #[doc(hidden)]
+            #[no_coverage]
+            fn recursing_part<'a, V, N>(
+                &self,
+                _parent: &N,
+                _value: &'a $name,
+                _index: &mut Self::RecursingPartIndex,
+            ) -> Option<&'a V>
+            where
+                V: Clone + 'static,
+                N: Mutator<V> + 'static,
+            {
+                None
+            }
+        }
+
+        impl DefaultMutatorWithinRange for $name {
+            type Bound = $name;
+            type Mutator = $name_mutator;
+            #[no_coverage]
+            fn default_mutator_within_range(range: RangeInclusive<$name>) -> Self::Mutator {
+                <$name_mutator>::new(range)
+            }
+        }
+    };
 }
+
+impl_float_within_range_mutator!(f32, u32, U32Mutator, F32WithinRangeMutator);
+impl_float_within_range_mutator!(f64, u64, U64Mutator, F64WithinRangeMutator);
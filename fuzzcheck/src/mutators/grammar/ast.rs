@@ -21,6 +21,8 @@ pub enum AST {
     Sequence(Vec<AST>),
     #[doc(hidden)]
     Box(Box<AST>),
+    #[doc(hidden)]
+    Optional(Option<Box<AST>>),
 }
 
 // /// Like an abstract syntax tree, but augmented with the string indices that correspond to each node
@@ -90,6 +92,11 @@ impl AST {
             AST::Box(ast) => {
                 ast.generate_string_in(string);
             }
+            AST::Optional(ast) => {
+                if let Some(ast) = ast {
+                    ast.generate_string_in(string);
+                }
+            }
         }
     }
 
@@ -133,12 +140,16 @@ impl AST {
 
 /// A type that is exactly the same as AST so that I can derive most of the
 /// Serialize/Deserialize implementation
+///
+/// Its variants must be declared in the same order and with the same payloads as
+/// those of `AST`, since values are transmuted from one type to the other.
 #[cfg(feature = "serde_json_serializer")]
 #[derive(Serialize, Deserialize)]
 enum __AST {
     Token(char),
     Sequence(Vec<__AST>),
     Box(Box<__AST>),
+    Optional(Option<Box<__AST>>),
 }
 
 #[cfg(feature = "serde_json_serializer")]
@@ -0,0 +1,615 @@
+//! Creation of a [`Grammar`] from a textual description in a subset of EBNF.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::rc::{Rc, Weak};
+
+use super::grammar::Grammar;
+use super::{alternation, concatenation, literal, optional, recurse, repetition};
+
+/// The error returned by [`Grammar::from_ebnf`] when the description of the grammar is invalid or unsupported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EbnfError {
+    /// The line, starting at 1, where the error was found
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for EbnfError {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for EbnfError {}
+
+impl Grammar {
+    /// Create a grammar from a textual description in a subset of EBNF.
+    ///
+    /// The returned grammar matches the first rule of the description. Recursive rules are supported and
+    /// are turned into [`recursive`](crate::mutators::grammar::recursive) grammars.
+    ///
+    /// A description is a list of rules of the form `name = expression ;`, where `::=` can be used instead of `=`.
+    /// The expressions are made of:
+    /// * terminals between double or single quotes, such as `"abc"` or `'"'`, which may contain the escape
+    ///   sequences `\\`, `\"`, `\'`, `\n`, `\r`, and `\t`
+    /// * references to other rules, by their name, which is made of ASCII letters, digits, and underscores
+    /// * concatenations, with the expressions separated by `,` or only by whitespace
+    /// * alternations, with the alternatives separated by `|`
+    /// * repetitions between `{` and `}`, which match their content zero or more times
+    /// * optional parts between `[` and `]`
+    /// * groups between `(` and `)`
+    ///
+    /// Comments are written between `(*` and `*)`.
+    ///
+    /// The other constructs of EBNF (exceptions, repetition factors, special sequences, the postfix operators
+    /// `*`, `+`, and `?`, etc.) are rejected with an error that gives the line where they appear.
+    ///
+    /// ```
+    /// use fuzzcheck::mutators::grammar::Grammar;
+    ///
+    /// let grammar = Grammar::from_ebnf(
+    ///     r#"
+    ///     (* a list of numbers between brackets *)
+    ///     list   = "[" , [ number , { "," , number } ] , "]" ;
+    ///     number = [ "-" ] , digit , { digit } ;
+    ///     digit  = "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" ;
+    ///     "#,
+    /// )
+    /// .unwrap();
+    /// ```
+    #[no_coverage]
+    pub fn from_ebnf(text: &str) -> Result<Rc<Grammar>, EbnfError> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let rules = parser.rules()?;
+        let mut builder = Builder::new(&rules)?;
+        Ok(builder.rule(&rules[0].name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Terminal(String),
+    Define,
+    Semicolon,
+    Comma,
+    Pipe,
+    OpenParen,
+    CloseParen,
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    End,
+}
+
+impl Display for Token {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "rule name `{}`", name),
+            Token::Terminal(s) => write!(f, "terminal {:?}", s),
+            Token::Define => write!(f, "`=`"),
+            Token::Semicolon => write!(f, "`;`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Pipe => write!(f, "`|`"),
+            Token::OpenParen => write!(f, "`(`"),
+            Token::CloseParen => write!(f, "`)`"),
+            Token::OpenBrace => write!(f, "`{{`"),
+            Token::CloseBrace => write!(f, "`}}`"),
+            Token::OpenBracket => write!(f, "`[`"),
+            Token::CloseBracket => write!(f, "`]`"),
+            Token::End => write!(f, "the end of the text"),
+        }
+    }
+}
+
+#[no_coverage]
+fn error(line: usize, message: impl Into<String>) -> EbnfError {
+    EbnfError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Split the text into tokens, each paired with its line number. The last token is always [`Token::End`].
+#[no_coverage]
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, EbnfError> {
+    let mut tokens = vec![];
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '(' if chars.peek() == Some(&'*') => {
+                let start_line = line;
+                chars.next();
+                let mut previous = None;
+                loop {
+                    match chars.next() {
+                        Some(')') if previous == Some('*') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = Some(c);
+                        }
+                        None => return Err(error(start_line, "unterminated comment")),
+                    }
+                }
+                continue;
+            }
+            '"' | '\'' => {
+                let quote = c;
+                let mut terminal = String::new();
+                loop {
+                    match chars.next() {
+                        Some(c) if c == quote => break,
+                        Some('\\') => {
+                            let escaped = match chars.next() {
+                                Some('\\') => '\\',
+                                Some('"') => '"',
+                                Some('\'') => '\'',
+                                Some('n') => '\n',
+                                Some('r') => '\r',
+                                Some('t') => '\t',
+                                Some(c) => return Err(error(line, format!("unsupported escape sequence `\\{}`", c))),
+                                None => return Err(error(line, "unterminated terminal")),
+                            };
+                            terminal.push(escaped);
+                        }
+                        Some('\n') | None => return Err(error(line, "unterminated terminal")),
+                        Some(c) => terminal.push(c),
+                    }
+                }
+                if terminal.is_empty() {
+                    return Err(error(
+                        line,
+                        "empty terminals are not supported, use `[ ]` to make a part of a rule optional",
+                    ));
+                }
+                Token::Terminal(terminal)
+            }
+            ':' => {
+                if chars.next() == Some(':') && chars.next() == Some('=') {
+                    Token::Define
+                } else {
+                    return Err(error(line, "expected `::=`"));
+                }
+            }
+            '=' => Token::Define,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        name.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Identifier(name)
+            }
+            '*' | '+' | '?' => {
+                return Err(error(
+                    line,
+                    format!(
+                        "`{}` is not supported, use `{{ }}` for repetitions and `[ ]` for optional parts",
+                        c
+                    ),
+                ))
+            }
+            '-' => return Err(error(line, "exceptions (`-`) are not supported")),
+            c if c.is_ascii_digit() => return Err(error(line, "repetition factors are not supported")),
+            c => return Err(error(line, format!("unexpected character `{}`", c))),
+        };
+        tokens.push((token, line));
+    }
+    tokens.push((Token::End, line));
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Expr {
+    Terminal(String),
+    Rule { name: String, line: usize },
+    Alternation(Vec<Expr>),
+    Concatenation(Vec<Expr>),
+    Repetition(Box<Expr>),
+    Optional(Box<Expr>),
+}
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    line: usize,
+    expr: Expr,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    #[no_coverage]
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+    #[no_coverage]
+    fn line(&self) -> usize {
+        self.tokens[self.pos].1
+    }
+    #[no_coverage]
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if token != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+    #[no_coverage]
+    fn expect(&mut self, expected: Token, context: &str) -> Result<(), EbnfError> {
+        if *self.peek() == expected {
+            self.next();
+            Ok(())
+        } else {
+            Err(error(
+                self.line(),
+                format!("expected {} {}, found {}", expected, context, self.peek()),
+            ))
+        }
+    }
+
+    #[no_coverage]
+    fn rules(&mut self) -> Result<Vec<Rule>, EbnfError> {
+        let mut rules = Vec::<Rule>::new();
+        while *self.peek() != Token::End {
+            let line = self.line();
+            let name = match self.next() {
+                Token::Identifier(name) => name,
+                token => return Err(error(line, format!("expected the name of a rule, found {}", token))),
+            };
+            if let Some(previous) = rules.iter().find(
+                #[no_coverage]
+                |rule| rule.name == name,
+            ) {
+                return Err(error(
+                    line,
+                    format!("the rule `{}` is already defined at line {}", name, previous.line),
+                ));
+            }
+            self.expect(Token::Define, &format!("after the name of the rule `{}`", name))?;
+            let expr = self.alternation()?;
+            self.expect(Token::Semicolon, &format!("at the end of the rule `{}`", name))?;
+            rules.push(Rule { name, line, expr });
+        }
+        if rules.is_empty() {
+            return Err(error(self.line(), "the grammar does not contain any rule"));
+        }
+        Ok(rules)
+    }
+
+    #[no_coverage]
+    fn alternation(&mut self) -> Result<Expr, EbnfError> {
+        let mut alternatives = vec![self.concatenation()?];
+        while *self.peek() == Token::Pipe {
+            self.next();
+            alternatives.push(self.concatenation()?);
+        }
+        if alternatives.len() == 1 {
+            Ok(alternatives.pop().unwrap())
+        } else {
+            Ok(Expr::Alternation(alternatives))
+        }
+    }
+
+    #[no_coverage]
+    fn concatenation(&mut self) -> Result<Expr, EbnfError> {
+        let mut parts = vec![self.factor()?];
+        loop {
+            match self.peek() {
+                Token::Comma => {
+                    self.next();
+                    parts.push(self.factor()?);
+                }
+                // an identifier followed by `=` is the start of the next rule, whose
+                // previous rule is missing its `;`
+                Token::Identifier(_) if self.tokens[self.pos + 1].0 == Token::Define => break,
+                Token::Identifier(_)
+                | Token::Terminal(_)
+                | Token::OpenParen
+                | Token::OpenBrace
+                | Token::OpenBracket => {
+                    parts.push(self.factor()?);
+                }
+                _ => break,
+            }
+        }
+        if parts.len() == 1 {
+            Ok(parts.pop().unwrap())
+        } else {
+            Ok(Expr::Concatenation(parts))
+        }
+    }
+
+    #[no_coverage]
+    fn factor(&mut self) -> Result<Expr, EbnfError> {
+        let line = self.line();
+        match self.next() {
+            Token::Identifier(name) => Ok(Expr::Rule { name, line }),
+            Token::Terminal(terminal) => Ok(Expr::Terminal(terminal)),
+            Token::OpenParen => {
+                let expr = self.alternation()?;
+                self.expect(Token::CloseParen, "at the end of the group")?;
+                Ok(expr)
+            }
+            Token::OpenBrace => {
+                let expr = self.alternation()?;
+                self.expect(Token::CloseBrace, "at the end of the repetition")?;
+                Ok(Expr::Repetition(Box::new(expr)))
+            }
+            Token::OpenBracket => {
+                let expr = self.alternation()?;
+                self.expect(Token::CloseBracket, "at the end of the optional part")?;
+                Ok(Expr::Optional(Box::new(expr)))
+            }
+            token => Err(error(line, format!("expected an expression, found {}", token))),
+        }
+    }
+}
+
+/// Builds the grammar of a rule by replacing the references to other rules by their grammar.
+///
+/// The rules that refer to themselves, directly or through other rules, are built as recursive
+/// grammars, and the references to an enclosing rule are built as [`recurse`] grammars.
+struct Builder<'a> {
+    rules: HashMap<&'a str, &'a Rule>,
+    recursive_rules: HashSet<&'a str>,
+    enclosing_rules: Vec<(&'a str, Weak<Grammar>)>,
+}
+
+impl<'a> Builder<'a> {
+    #[no_coverage]
+    fn new(rules: &'a [Rule]) -> Result<Self, EbnfError> {
+        let mut references = HashMap::<&str, Vec<&str>>::new();
+        for rule in rules {
+            let mut refs = vec![];
+            collect_references(&rule.expr, &mut refs);
+            for &(name, line) in refs.iter() {
+                if !rules.iter().any(
+                    #[no_coverage]
+                    |rule| rule.name == name,
+                ) {
+                    return Err(error(line, format!("the rule `{}` is not defined", name)));
+                }
+            }
+            references.insert(
+                &rule.name,
+                refs.into_iter()
+                    .map(
+                        #[no_coverage]
+                        |(name, _)| name,
+                    )
+                    .collect(),
+            );
+        }
+        let recursive_rules = rules
+            .iter()
+            .map(
+                #[no_coverage]
+                |rule| rule.name.as_str(),
+            )
+            .filter(
+                #[no_coverage]
+                |name| refers_to(&references, name, name),
+            )
+            .collect();
+        Ok(Self {
+            rules: rules
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |rule| (rule.name.as_str(), rule),
+                )
+                .collect(),
+            recursive_rules,
+            enclosing_rules: vec![],
+        })
+    }
+
+    #[no_coverage]
+    fn rule(&mut self, name: &'a str) -> Rc<Grammar> {
+        if let Some((_, weak)) = self.enclosing_rules.iter().find(
+            #[no_coverage]
+            |(enclosing, _)| *enclosing == name,
+        ) {
+            return recurse(weak);
+        }
+        let rule = self.rules[name];
+        if self.recursive_rules.contains(name) {
+            Rc::new(Grammar::Recursive(Rc::new_cyclic(
+                #[no_coverage]
+                |weak| {
+                    self.enclosing_rules.push((name, weak.clone()));
+                    let grammar = self.expr(&rule.expr);
+                    self.enclosing_rules.pop();
+                    Rc::try_unwrap(grammar).unwrap_or_else(
+                        #[no_coverage]
+                        |grammar| grammar.as_ref().clone(),
+                    )
+                },
+            )))
+        } else {
+            self.expr(&rule.expr)
+        }
+    }
+
+    #[no_coverage]
+    fn expr(&mut self, expr: &'a Expr) -> Rc<Grammar> {
+        match expr {
+            Expr::Terminal(terminal) => {
+                let mut chars = terminal.chars().map(literal).collect::<Vec<_>>();
+                if chars.len() == 1 {
+                    chars.pop().unwrap()
+                } else {
+                    concatenation(chars)
+                }
+            }
+            Expr::Rule { name, .. } => self.rule(name),
+            Expr::Alternation(exprs) => {
+                let gs = exprs
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |expr| self.expr(expr),
+                    )
+                    .collect::<Vec<_>>();
+                alternation(gs)
+            }
+            Expr::Concatenation(exprs) => {
+                let gs = exprs
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |expr| self.expr(expr),
+                    )
+                    .collect::<Vec<_>>();
+                concatenation(gs)
+            }
+            Expr::Repetition(expr) => repetition(self.expr(expr), 0..),
+            Expr::Optional(expr) => optional(self.expr(expr)),
+        }
+    }
+}
+
+#[no_coverage]
+fn collect_references<'a>(expr: &'a Expr, refs: &mut Vec<(&'a str, usize)>) {
+    match expr {
+        Expr::Terminal(_) => {}
+        Expr::Rule { name, line } => refs.push((name, *line)),
+        Expr::Alternation(exprs) | Expr::Concatenation(exprs) => {
+            for expr in exprs {
+                collect_references(expr, refs);
+            }
+        }
+        Expr::Repetition(expr) | Expr::Optional(expr) => collect_references(expr, refs),
+    }
+}
+
+/// Returns true if the rule `from` refers to the rule `to`, directly or through other rules
+#[no_coverage]
+fn refers_to(references: &HashMap<&str, Vec<&str>>, from: &str, to: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(rule) = stack.pop() {
+        for &next in references[rule].iter() {
+            if next == to {
+                return true;
+            }
+            if visited.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutators::grammar::grammar_based_ast_mutator;
+    use crate::Mutator;
+
+    /// Returns the rest of the string after an `expr` of the arithmetic grammar below, if `s` starts with one
+    #[no_coverage]
+    fn parse_expr(s: &str) -> Option<&str> {
+        let mut rest = parse_term(s)?;
+        while let Some(after_op) = rest.strip_prefix('+').or_else(
+            #[no_coverage]
+            || rest.strip_prefix('-'),
+        ) {
+            rest = parse_term(after_op)?;
+        }
+        Some(rest)
+    }
+    #[no_coverage]
+    fn parse_term(s: &str) -> Option<&str> {
+        if let Some(rest) = s.strip_prefix('(') {
+            parse_expr(rest)?.strip_prefix(')')
+        } else {
+            let rest = s.trim_start_matches(
+                #[no_coverage]
+                |c: char| c.is_ascii_digit(),
+            );
+            if rest.len() == s.len() {
+                None
+            } else {
+                Some(rest)
+            }
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_from_ebnf_round_trip() {
+        let grammar = Grammar::from_ebnf(
+            r#"
+            (* arithmetic expressions,
+               with a recursion going through two rules *)
+            expr   = term , { ( "+" | '-' ) , term } ;
+            term   ::= number | "(" expr ")" ;
+            number = digit { digit } ;
+            digit  = "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" ;
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(grammar.as_ref(), Grammar::Recursive(_)));
+
+        let mutator = grammar_based_ast_mutator(grammar);
+        let mut nbr_nested = 0;
+        for _ in 0..1000 {
+            let (ast, _) = mutator.random_arbitrary(100.0);
+            let string = ast.to_string();
+            assert_eq!(parse_expr(&string), Some(""), "{:?} does not match the grammar", string);
+            if string.contains('(') {
+                nbr_nested += 1;
+            }
+        }
+        assert!(nbr_nested > 0);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_from_ebnf_errors() {
+        #[no_coverage]
+        fn line(text: &str) -> usize {
+            Grammar::from_ebnf(text).unwrap_err().line
+        }
+        assert_eq!(line(""), 1);
+        assert_eq!(line("a = b ;"), 1);
+        assert_eq!(line("a = \"x\" ;\n\na = \"y\" ;"), 3);
+        assert_eq!(line("a = \"x\"\nb = \"y\" ;"), 2);
+        assert_eq!(line("a = \"x\" ;\nb = { \"y\" ;"), 2);
+        assert_eq!(line("a = \"x\" ;\nb = \"y\"* ;"), 2);
+        assert_eq!(line("a = \"x\" ;\n(* comment\n*)\nb = a - \"x\" ;"), 4);
+        assert_eq!(line("a = \"x\" | \"\" ;"), 1);
+        assert_eq!(line("(* unterminated\ncomment"), 1);
+    }
+}
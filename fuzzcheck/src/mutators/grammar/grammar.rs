@@ -16,10 +16,22 @@ pub enum Grammar {
     Alternation(Vec<Rc<Grammar>>),
     Concatenation(Vec<Rc<Grammar>>),
     Repetition(Rc<Grammar>, Range<usize>),
+    Optional(Rc<Grammar>),
     Recurse(Weak<Grammar>),
     Recursive(Rc<Grammar>),
 }
 
+/// A grammar rule that matches the strings matched by the regular expression `s`
+///
+/// The supported syntax is the one of the `regex` crate, except for word boundaries and byte-oriented
+/// classes. It includes literals, classes such as `[a-z0-9_]` or `\d`, groups, alternations, and repetitions
+/// such as `?`, `*`, `+`, and `{n,m}`. Unbounded repetitions are limited by the maximum complexity of
+/// the fuzz test. Anchors such as `^` and `$` are ignored, because the generated strings always match the
+/// whole regular expression.
+///
+/// ## Panics
+/// Panics if `s` is not a valid regular expression, if it uses an unsupported feature, or if it only
+/// matches the empty string.
 #[cfg(feature = "regex_grammar")]
 #[doc(cfg(feature = "regex_grammar"))]
 #[no_coverage]
@@ -77,13 +89,61 @@ where
     Rc::new(Grammar::Repetition(gs, start..end))
 }
 
+/// A grammar rule that matches either nothing or the given rule
+#[no_coverage]
+pub fn optional(g: Rc<Grammar>) -> Rc<Grammar> {
+    Rc::new(Grammar::Optional(g))
+}
+
+/// A grammar rule that matches the enclosing [`recursive`] rule referred to by `g`
 #[no_coverage]
 pub fn recurse(g: &Weak<Grammar>) -> Rc<Grammar> {
     Rc::new(Grammar::Recurse(g.clone()))
 }
+
+/// A grammar rule that can refer to itself
+///
+/// `data_fn` is given a weak reference to the rule being defined, which is turned into a grammar rule with
+/// [`recurse`]. Recursive rules can be nested, and a nested rule can refer to any of its enclosing rules, which
+/// is how mutually recursive rules are written:
+/// ```
+/// use fuzzcheck::mutators::grammar::{alternation, concatenation, literal, literal_range, optional, recurse, recursive};
+///
+/// // expr := term ('+' term)?
+/// // term := [0-9] | '(' expr ')' | '-' term
+/// let expr = recursive(|expr| {
+///     let term = recursive(|term| {
+///         alternation([
+///             literal_range('0'..='9'),
+///             concatenation([literal('('), recurse(expr), literal(')')]),
+///             concatenation([literal('-'), recurse(term)]),
+///         ])
+///     });
+///     concatenation([term.clone(), optional(concatenation([literal('+'), term]))])
+/// });
+/// ```
 #[no_coverage]
 pub fn recursive(data_fn: impl Fn(&Weak<Grammar>) -> Rc<Grammar>) -> Rc<Grammar> {
-    Rc::new(Grammar::Recursive(Rc::new_cyclic(|g| {
-        Rc::try_unwrap(data_fn(g)).unwrap()
-    })))
+    Rc::new(Grammar::Recursive(Rc::new_cyclic(
+        #[no_coverage]
+        |g| {
+            Rc::try_unwrap(data_fn(g)).unwrap_or_else(
+                #[no_coverage]
+                |grammar| grammar.as_ref().clone(),
+            )
+        },
+    )))
+}
+
+impl Grammar {
+    /// A grammar rule that can refer to itself, see [`recursive`]
+    #[no_coverage]
+    pub fn recursive(data_fn: impl Fn(&Weak<Grammar>) -> Rc<Grammar>) -> Rc<Grammar> {
+        recursive(data_fn)
+    }
+    /// A grammar rule that matches the enclosing recursive rule referred to by `g`, see [`recurse`]
+    #[no_coverage]
+    pub fn recurse(g: &Weak<Grammar>) -> Rc<Grammar> {
+        recurse(g)
+    }
 }
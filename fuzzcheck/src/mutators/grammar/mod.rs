@@ -2,7 +2,9 @@
 //!
 //! This module provides two mutators a grammar-based `impl Mutator<AST>` which generates an abstract syntax
 //! tree satisfying a grammar, created though [`grammar_based_ast_mutator`]. You can then obtain a string
-//! from the [`AST`] by calling [`ast.to_string()`](AST::to_string).
+//! from the [`AST`] by calling [`ast.to_string()`](AST::to_string). To fuzz a typed representation of the syntax
+//! trees instead, such as parsed expressions, use [`grammar_based_map_mutator`] with a function converting an
+//! [`AST`] to that type and a function converting it back.
 //!
 //!
 //! To specify a grammar, you should use the following functions:
@@ -16,7 +18,12 @@
 //! * [`alternation`] for a grammar matching any of a list of grammar rules
 //! * [`concatenation`] matching multiple grammar rules one after the other
 //! * [`repetition`] matching a grammar rule multiple times
-//! * [`recursive`] and [`recurse`] to create recursive grammar rules
+//! * [`optional`] matching a grammar rule zero or one time
+//! * [`recursive`] and [`recurse`] (or [`Grammar::recursive`] and [`Grammar::recurse`]) to create recursive grammar
+//!   rules, including mutually recursive ones, see the documentation of [`recursive`]
+//!
+//! Alternatively, [`Grammar::from_ebnf`] creates a grammar from a textual description in a subset of EBNF,
+//! which is convenient to reuse a grammar written in a specification.
 #![cfg_attr(
     feature = "regex_grammar",
     doc = r###"
@@ -63,6 +70,7 @@ let rule = recursive(|rule| {
 #![allow(clippy::nonstandard_macro_braces)]
 
 mod ast;
+mod ebnf;
 mod grammar;
 // mod incremental_map_conformance;
 // mod list;
@@ -75,9 +83,13 @@ mod regex;
 #[doc(inline)]
 pub use ast::AST;
 #[doc(inline)]
+pub use ebnf::EbnfError;
+#[doc(inline)]
 pub use grammar::Grammar;
 #[doc(inline)]
-pub use grammar::{alternation, concatenation, literal, literal_range, literal_ranges, recurse, recursive, repetition};
+pub use grammar::{
+    alternation, concatenation, literal, literal_range, literal_ranges, optional, recurse, recursive, repetition,
+};
 
 #[cfg(feature = "regex_grammar")]
 #[doc(inline)]
@@ -86,8 +98,12 @@ pub use grammar::regex;
 
 #[doc(inline)]
 pub use mutators::grammar_based_ast_mutator;
+#[doc(inline)]
+pub use mutators::grammar_based_map_mutator;
 
 #[doc(inline)]
 // pub use mutators::grammar_based_string_mutator;
 pub use mutators::GrammarBasedASTMutator;
+#[doc(inline)]
+pub use mutators::GrammarBasedMapMutator;
 // pub use mutators::GrammarBasedStringMutator;
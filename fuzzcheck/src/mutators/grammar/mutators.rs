@@ -8,11 +8,13 @@ use crate::mutators::character_classes::CharacterMutator;
 use crate::mutators::either::Either;
 use crate::mutators::fixed_len_vector::FixedLenVecMutator;
 use crate::mutators::grammar::ast::AST;
+use crate::mutators::map::MapMutator;
+use crate::mutators::option::OptionMutator;
 // use crate::mutators::incremental_map::IncrementalMapMutator;
 use crate::mutators::recursive::{RecurToMutator, RecursiveMutator};
 use crate::mutators::tuples::Tuple1Mutator;
 use crate::mutators::vector::VecMutator;
-use crate::Mutator;
+use crate::{Mutator, MutatorWrapper};
 use fuzzcheck_mutators_derive::make_single_variant_mutator;
 use std::collections::HashMap;
 use std::rc::{Rc, Weak};
@@ -22,6 +24,7 @@ make_single_variant_mutator! {
         Token(char),
         Sequence(Vec<AST>),
         Box(Box<AST>),
+        Optional(Option<Box<AST>>),
     }
 }
 
@@ -32,6 +35,7 @@ type InnerASTMutator = Either<
         Tuple1Mutator<
             BoxMutator<Either<Either<ASTMutator, RecurToMutator<ASTMutator>>, AlternationMutator<AST, ASTMutator>>>,
         >,
+        Tuple1Mutator<OptionMutator<Box<AST>, BoxMutator<ASTMutator>>>,
     >,
     RecursiveMutator<ASTMutator>,
 >;
@@ -63,13 +67,19 @@ impl ASTMutatorMutationStep {
 pub struct ASTMutatorArbitraryStep {
     inner: Box<<InnerASTMutator as Mutator<AST>>::ArbitraryStep>,
 }
-pub struct ASTMutatorUnmutateToken {
-    pub(crate) inner: Box<<InnerASTMutator as Mutator<AST>>::UnmutateToken>,
+pub enum ASTMutatorUnmutateToken {
+    Inner(Box<<InnerASTMutator as Mutator<AST>>::UnmutateToken>),
+    /// Undo a crossover by putting back the subtree at the given index, in pre-order, and the cache of the whole tree
+    Crossover {
+        node_idx: usize,
+        node: AST,
+        cache: ASTMutatorCache,
+    },
 }
 impl ASTMutatorUnmutateToken {
     #[no_coverage]
     fn new(inner: <InnerASTMutator as Mutator<AST>>::UnmutateToken) -> Self {
-        Self { inner: Box::new(inner) }
+        Self::Inner(Box::new(inner))
     }
 }
 #[derive(Clone)]
@@ -161,7 +171,46 @@ impl Mutator<AST> for ASTMutator {
     #[doc(hidden)]
     #[no_coverage]
     fn unmutate(&self, value: &mut AST, cache: &mut Self::Cache, t: Self::UnmutateToken) {
-        self.inner.unmutate(value, &mut cache.inner, *t.inner)
+        match t {
+            ASTMutatorUnmutateToken::Inner(t) => self.inner.unmutate(value, &mut cache.inner, *t),
+            ASTMutatorUnmutateToken::Crossover {
+                node_idx,
+                node,
+                cache: old_cache,
+            } => {
+                *nth_node_mut(value, &mut { node_idx }).unwrap() = node;
+                *cache = old_cache;
+            }
+        }
+    }
+
+    /// Replace a random subtree of `value` with a random subtree of `other`.
+    ///
+    /// The two subtrees may have been generated by different rules of the grammar, so the
+    /// new syntax tree is validated again and the crossover fails if it does not match the grammar.
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut AST,
+        cache: &mut Self::Cache,
+        other: &AST,
+        _other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let node_idx = fastrand::usize(..nbr_nodes(value));
+        let other_node = nth_node(other, &mut fastrand::usize(..nbr_nodes(other)))?.clone();
+        let node = std::mem::replace(nth_node_mut(value, &mut { node_idx })?, other_node);
+
+        if let Some(new_cache) = self.validate_value(value) {
+            let cplx = self.complexity(value, &new_cache);
+            if cplx <= max_cplx {
+                let cache = std::mem::replace(cache, new_cache);
+                return Some((ASTMutatorUnmutateToken::Crossover { node_idx, node, cache }, cplx));
+            }
+        }
+        *nth_node_mut(value, &mut { node_idx }).unwrap() = node;
+        None
     }
 
     #[doc(hidden)]
@@ -191,6 +240,50 @@ impl Mutator<AST> for ASTMutator {
     }
 }
 
+/// The number of nodes of the syntax tree, including its root
+#[no_coverage]
+fn nbr_nodes(ast: &AST) -> usize {
+    match ast {
+        AST::Token(_) | AST::Optional(None) => 1,
+        AST::Sequence(asts) => 1 + asts.iter().map(nbr_nodes).sum::<usize>(),
+        AST::Box(ast) | AST::Optional(Some(ast)) => 1 + nbr_nodes(ast),
+    }
+}
+
+/// The node of the syntax tree at the given index, in pre-order
+#[no_coverage]
+fn nth_node<'a>(ast: &'a AST, idx: &mut usize) -> Option<&'a AST> {
+    if *idx == 0 {
+        return Some(ast);
+    }
+    *idx -= 1;
+    match ast {
+        AST::Token(_) | AST::Optional(None) => None,
+        AST::Sequence(asts) => asts.iter().find_map(
+            #[no_coverage]
+            |ast| nth_node(ast, idx),
+        ),
+        AST::Box(ast) | AST::Optional(Some(ast)) => nth_node(ast, idx),
+    }
+}
+
+/// The node of the syntax tree at the given index, in pre-order
+#[no_coverage]
+fn nth_node_mut<'a>(ast: &'a mut AST, idx: &mut usize) -> Option<&'a mut AST> {
+    if *idx == 0 {
+        return Some(ast);
+    }
+    *idx -= 1;
+    match ast {
+        AST::Token(_) | AST::Optional(None) => None,
+        AST::Sequence(asts) => asts.iter_mut().find_map(
+            #[no_coverage]
+            |ast| nth_node_mut(ast, idx),
+        ),
+        AST::Box(ast) | AST::Optional(Some(ast)) => nth_node_mut(ast, idx),
+    }
+}
+
 // /// A mutator created by [`grammar_based_string_mutator`](crate::mutators::grammar::grammar_based_string_mutator)
 // ///
 // /// It only generates strings which match the given grammar.
@@ -219,6 +312,75 @@ pub fn grammar_based_ast_mutator(grammar: Rc<Grammar>) -> GrammarBasedASTMutator
     ASTMutator::from_grammar(grammar)
 }
 
+type GrammarBasedMapMutatorInner<T> =
+    MapMutator<AST, T, ASTMutator, Box<dyn Fn(&T) -> Option<AST>>, Box<dyn Fn(&AST) -> T>, fn(&T, f64) -> f64>;
+
+/// A mutator created by [`grammar_based_map_mutator`](crate::mutators::grammar::grammar_based_map_mutator)
+///
+/// It generates the values of type `T` that are mapped from the syntax trees matching the given grammar.
+pub struct GrammarBasedMapMutator<T>
+where
+    T: Clone + 'static,
+{
+    mutator: GrammarBasedMapMutatorInner<T>,
+}
+
+impl<T> MutatorWrapper for GrammarBasedMapMutator<T>
+where
+    T: Clone + 'static,
+{
+    type Wrapped = GrammarBasedMapMutatorInner<T>;
+    #[no_coverage]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+#[no_coverage]
+fn ast_complexity<T>(_value: &T, cplx: f64) -> f64 {
+    cplx
+}
+
+/// Create a mutator for a typed representation of the syntax trees generated by the grammar, such as the
+/// expressions of a language after parsing.
+///
+/// The mutations are performed on the [`AST`], which is then converted to a value of type `T` by `map`. The values
+/// thus benefit from the grammar-aware mutations, and their complexity is the one of their syntax tree. `unmap`
+/// converts a value back to its syntax tree, for example to read it from the corpus, or returns `None` if the value
+/// cannot be produced by the grammar, in which case it is rejected.
+/// ```
+/// use fuzzcheck::mutators::grammar::{grammar_based_map_mutator, literal_range, repetition, AST};
+///
+/// // a number, represented by its digits in the syntax tree
+/// let digits = repetition(literal_range('0'..='9'), 1..=4);
+/// let mutator = grammar_based_map_mutator(
+///     digits,
+///     |ast: &AST| ast.to_string().parse::<u16>().unwrap(),
+///     |number: &u16| {
+///         let digits = number.to_string().chars().map(AST::Token).collect();
+///         Some(AST::Sequence(digits))
+///     },
+/// );
+/// ```
+#[no_coverage]
+pub fn grammar_based_map_mutator<T>(
+    grammar: Rc<Grammar>,
+    map: impl Fn(&AST) -> T + 'static,
+    unmap: impl Fn(&T) -> Option<AST> + 'static,
+) -> GrammarBasedMapMutator<T>
+where
+    T: Clone + 'static,
+{
+    GrammarBasedMapMutator {
+        mutator: MapMutator::new(
+            ASTMutator::from_grammar(grammar),
+            Box::new(unmap),
+            Box::new(map),
+            ast_complexity::<T>,
+        ),
+    }
+}
+
 impl ASTMutator {
     #[no_coverage]
     fn token(m: CharacterMutator) -> Self {
@@ -241,6 +403,12 @@ impl ASTMutator {
         }
     }
     #[no_coverage]
+    fn optional(m: OptionMutator<Box<AST>, BoxMutator<ASTMutator>>) -> Self {
+        Self {
+            inner: Box::new(Either::Left(ASTSingleVariant::Optional(Tuple1Mutator::new(m)))),
+        }
+    }
+    #[no_coverage]
     fn recur(m: RecurToMutator<ASTMutator>) -> Self {
         Self {
             inner: Box::new(Either::Left(ASTSingleVariant::Box(Tuple1Mutator::new(
@@ -285,6 +453,10 @@ impl ASTMutator {
                 Self::from_grammar_rec(g.clone(), others),
                 range.start..=range.end - 1,
             ))),
+            Grammar::Optional(g) => Self::optional(OptionMutator::new(BoxMutator::new(Self::from_grammar_rec(
+                g.clone(),
+                others,
+            )))),
             Grammar::Recurse(g) => {
                 if let Some(m) = others.get(&g.as_ptr()) {
                     Self::recur(RecurToMutator::from(m))
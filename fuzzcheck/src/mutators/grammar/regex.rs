@@ -3,18 +3,38 @@ use std::rc::Rc;
 use crate::mutators::grammar::Grammar;
 use regex_syntax::hir::{Class, HirKind, Literal, RepetitionKind, RepetitionRange};
 
-use crate::mutators::grammar::{alternation, concatenation, literal, literal_ranges, repetition};
+use crate::mutators::grammar::{alternation, concatenation, literal, literal_ranges, optional, repetition};
 
 #[no_coverage]
 pub(crate) fn grammar_from_regex(regex: &str) -> Rc<Grammar> {
     let mut parser = regex_syntax::Parser::new();
-    let hir = parser.parse(regex).unwrap();
+    let hir = parser.parse(regex).unwrap_or_else(
+        #[no_coverage]
+        |e| panic!("invalid regex {:?}: {}", regex, e),
+    );
     grammar_from_regex_hir_kind(hir.kind())
 }
+
+/// Whether the regex only matches the empty string.
+///
+/// Anchors are considered empty, because the generated strings always match the whole regex.
+#[no_coverage]
+fn matches_only_empty_string(hir: &HirKind) -> bool {
+    match hir {
+        HirKind::Empty | HirKind::Anchor(_) => true,
+        HirKind::Group(group) => matches_only_empty_string(group.hir.kind()),
+        HirKind::Concat(concat) => concat.iter().all(
+            #[no_coverage]
+            |hir| matches_only_empty_string(hir.kind()),
+        ),
+        _ => false,
+    }
+}
+
 #[no_coverage]
 pub fn grammar_from_regex_hir_kind(hir: &HirKind) -> Rc<Grammar> {
     match hir {
-        HirKind::Empty => panic!("emoty regexes are not supported"),
+        HirKind::Empty | HirKind::Anchor(_) => panic!("regexes matching only the empty string are not supported"),
         HirKind::Literal(l) => match l {
             Literal::Unicode(l) => literal(*l),
             Literal::Byte(_) => panic!("non-unicode regexes are not supported"),
@@ -33,7 +53,6 @@ pub fn grammar_from_regex_hir_kind(hir: &HirKind) -> Rc<Grammar> {
             }
             Class::Bytes(_) => panic!("non-unicode regexes are not supported"),
         },
-        HirKind::Anchor(_) => panic!("anchors are not supported"),
         HirKind::WordBoundary(_) => panic!("word boundaries are not supported"),
         HirKind::Repetition(rep) => {
             let range = match rep.kind.clone() {
@@ -51,14 +70,43 @@ pub fn grammar_from_regex_hir_kind(hir: &HirKind) -> Rc<Grammar> {
             repetition(grammar, range)
         }
         HirKind::Group(group) => grammar_from_regex_hir_kind(group.hir.kind()),
-        HirKind::Concat(concat) => concatenation(concat.iter().map(
-            #[no_coverage]
-            |hir| grammar_from_regex_hir_kind(hir.kind()),
-        )),
-        HirKind::Alternation(alt) => alternation(alt.iter().map(
-            #[no_coverage]
-            |hir| grammar_from_regex_hir_kind(hir.kind()),
-        )),
+        HirKind::Concat(concat) => {
+            let parts = concat
+                .iter()
+                .filter(
+                    #[no_coverage]
+                    |hir| !matches_only_empty_string(hir.kind()),
+                )
+                .collect::<Vec<_>>();
+            match parts.as_slice() {
+                [] => panic!("regexes matching only the empty string are not supported"),
+                [hir] => grammar_from_regex_hir_kind(hir.kind()),
+                _ => concatenation(parts.iter().map(
+                    #[no_coverage]
+                    |hir| grammar_from_regex_hir_kind(hir.kind()),
+                )),
+            }
+        }
+        HirKind::Alternation(alt) => {
+            // an empty branch, as in `a|`, makes the other branches optional
+            let (empty, non_empty): (Vec<_>, Vec<_>) = alt.iter().partition(
+                #[no_coverage]
+                |hir| matches_only_empty_string(hir.kind()),
+            );
+            let grammar = match non_empty.as_slice() {
+                [] => panic!("regexes matching only the empty string are not supported"),
+                [hir] => grammar_from_regex_hir_kind(hir.kind()),
+                _ => alternation(non_empty.iter().map(
+                    #[no_coverage]
+                    |hir| grammar_from_regex_hir_kind(hir.kind()),
+                )),
+            };
+            if empty.is_empty() {
+                grammar
+            } else {
+                optional(grammar)
+            }
+        }
     }
 }
 
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::map::MapMutator;
+use super::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
+use super::vector::VecMutator;
+use super::wrapper::Wrapper;
+use crate::DefaultMutator;
+
+/// The default mutator of `HashMap<K, V>`.
+///
+/// It mutates a `Vec<(K, V)>` of entries with a [`VecMutator`], which inserts, removes, and mutates the entries,
+/// and converts it to a `HashMap<K, V>`. If several entries have the same key, the last one is kept.
+/// The complexity of a value is the complexity of the corresponding vector.
+pub type HashMapMutator<K, V, MK, MV> = Wrapper<
+    MapMutator<
+        Vec<(K, V)>,
+        HashMap<K, V>,
+        VecMutator<(K, V), TupleMutatorWrapper<Tuple2Mutator<MK, MV>, Tuple2<K, V>>>,
+        fn(&HashMap<K, V>) -> Option<Vec<(K, V)>>,
+        fn(&Vec<(K, V)>) -> HashMap<K, V>,
+        fn(&HashMap<K, V>, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn vec_from_hashmap<K: Clone, V: Clone>(value: &HashMap<K, V>) -> Option<Vec<(K, V)>> {
+    Some(
+        value
+            .iter()
+            .map(
+                #[no_coverage]
+                |(k, v)| (k.clone(), v.clone()),
+            )
+            .collect(),
+    )
+}
+
+#[no_coverage]
+fn hashmap_from_vec<K: Clone + Eq + Hash, V: Clone>(vec: &Vec<(K, V)>) -> HashMap<K, V> {
+    vec.iter().cloned().collect()
+}
+
+#[no_coverage]
+fn complexity<K, V>(_t: &HashMap<K, V>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl<K, V, MK, MV> HashMapMutator<K, V, MK, MV>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    MK: crate::Mutator<K>,
+    MV: crate::Mutator<V>,
+{
+    #[no_coverage]
+    pub fn new(key_mutator: MK, value_mutator: MV) -> Self {
+        Wrapper(MapMutator::new(
+            VecMutator::new(
+                TupleMutatorWrapper::new(Tuple2Mutator::new(key_mutator, value_mutator)),
+                0..=usize::MAX,
+            ),
+            vec_from_hashmap,
+            hashmap_from_vec,
+            complexity,
+        ))
+    }
+}
+
+impl<K, V> DefaultMutator for HashMap<K, V>
+where
+    K: 'static + Clone + Eq + Hash + DefaultMutator,
+    V: 'static + Clone + DefaultMutator,
+{
+    type Mutator = HashMapMutator<K, V, K::Mutator, V::Mutator>;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new(K::default_mutator(), V::default_mutator())
+    }
+}
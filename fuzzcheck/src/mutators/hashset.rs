@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use super::map::MapMutator;
+use super::vector::VecMutator;
+use super::wrapper::Wrapper;
+use crate::DefaultMutator;
+
+/// The default mutator of `HashSet<K>`.
+///
+/// It mutates a `Vec<K>` with a [`VecMutator`], which inserts, removes, and mutates the elements,
+/// and converts it to a `HashSet<K>`, in which the duplicate elements are merged.
+/// The complexity of a value is the complexity of the corresponding vector.
+pub type HashSetMutator<K, MK> = Wrapper<
+    MapMutator<
+        Vec<K>,
+        HashSet<K>,
+        VecMutator<K, MK>,
+        fn(&HashSet<K>) -> Option<Vec<K>>,
+        fn(&Vec<K>) -> HashSet<K>,
+        fn(&HashSet<K>, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn vec_from_hashset<K: Clone>(value: &HashSet<K>) -> Option<Vec<K>> {
+    Some(value.iter().cloned().collect())
+}
+
+#[no_coverage]
+fn hashset_from_vec<K: Clone + Eq + Hash>(vec: &Vec<K>) -> HashSet<K> {
+    vec.iter().cloned().collect()
+}
+
+#[no_coverage]
+fn complexity<K>(_t: &HashSet<K>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl<K, MK> HashSetMutator<K, MK>
+where
+    K: 'static + Clone + Eq + Hash,
+    MK: crate::Mutator<K>,
+{
+    #[no_coverage]
+    pub fn new(mutator: MK) -> Self {
+        Wrapper(MapMutator::new(
+            VecMutator::new(mutator, 0..=usize::MAX),
+            vec_from_hashset,
+            hashset_from_vec,
+            complexity,
+        ))
+    }
+}
+
+impl<K> DefaultMutator for HashSet<K>
+where
+    K: 'static + Clone + Eq + Hash + DefaultMutator,
+{
+    type Mutator = HashSetMutator<K, K::Mutator>;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new(K::default_mutator())
+    }
+}
@@ -41,6 +41,10 @@ use crate::Mutator;
     9. etc.
 
     You can find more details on how it is done in `uniform_permutation`
+
+    Before following that sequence, `ordered_arbitrary` first produces the boundary values of the
+    integer type: 0, 1, MAX, MAX-1, MIN, and MIN+1, since they are the most likely to trigger bugs in
+    arithmetic code. These values are then skipped when they appear in the permuted sequence.
 */
 
 macro_rules! binary_search_arbitrary {
@@ -65,8 +69,6 @@ macro_rules! binary_search_arbitrary {
         }
     };
 }
-binary_search_arbitrary!(binary_search_arbitrary_u8, u8);
-binary_search_arbitrary!(binary_search_arbitrary_u16, u16);
 binary_search_arbitrary!(binary_search_arbitrary_u32, u32);
 binary_search_arbitrary!(binary_search_arbitrary_u64, u64);
 
@@ -77,6 +79,7 @@ macro_rules! impl_int_mutator {
         #[derive(Clone)]
         pub struct $name_mutator {
             shuffled_integers: [u8; 256],
+            boundary_values: Vec<$name>,
             rng: fastrand::Rng,
         }
         impl Default for $name_mutator {
@@ -88,8 +91,22 @@ macro_rules! impl_int_mutator {
                 }
                 let rng = fastrand::Rng::default();
                 rng.shuffle(&mut shuffled_integers);
+                let mut boundary_values = vec![];
+                for x in [
+                    0,
+                    1,
+                    <$name>::MAX,
+                    <$name>::MAX - 1,
+                    <$name>::MIN,
+                    <$name>::MIN.wrapping_add(1),
+                ] {
+                    if !boundary_values.contains(&x) {
+                        boundary_values.push(x);
+                    }
+                }
                 $name_mutator {
                     shuffled_integers,
+                    boundary_values,
                     rng,
                 }
             }
@@ -97,7 +114,7 @@ macro_rules! impl_int_mutator {
 
         impl $name_mutator {
             #[no_coverage]
-            fn uniform_permutation(&self, step: u64) -> $name_unsigned {
+            pub(crate) fn uniform_permutation(&self, step: u64) -> $name_unsigned {
                 let size = <$name>::BITS as u64;
 
                 // granularity is the number of bits provided by shuffled_integers
@@ -196,12 +213,23 @@ macro_rules! impl_int_mutator {
                 if max_cplx < self.min_complexity() {
                     return None;
                 }
-                if *step > <$name_unsigned>::MAX as u64 {
-                    None
-                } else {
-                    let value = self.uniform_permutation(*step) as $name;
+                let nbr_boundary_values = self.boundary_values.len() as u64;
+                loop {
+                    if *step < nbr_boundary_values {
+                        let value = self.boundary_values[*step as usize];
+                        *step += 1;
+                        return Some((value, <$name>::BITS as f64));
+                    }
+                    let permutation_step = *step - nbr_boundary_values;
+                    if permutation_step > <$name_unsigned>::MAX as u64 {
+                        return None;
+                    }
+                    let value = self.uniform_permutation(permutation_step) as $name;
                     *step += 1;
-                    Some((value, <$name>::BITS as f64))
+                    // the boundary values were already produced
+                    if !self.boundary_values.contains(&value) {
+                        return Some((value, <$name>::BITS as f64));
+                    }
                 }
             }
             #[doc(hidden)]
@@ -1,203 +1,294 @@
-use crate::mutators::integer::{
-    binary_search_arbitrary_u16, binary_search_arbitrary_u32, binary_search_arbitrary_u64, binary_search_arbitrary_u8,
-};
+/*!
+Mutators for the integers within a range, such as port numbers or the values of an enum-like integer.
+
+[`IntegerWithinRangeMutator<T>`] only produces integers within the range it was created with, and rejects the
+other values. Its `ordered_arbitrary` produces each integer of the range exactly once, starting with the middle of
+the range and then bisecting each half, and its mutations never produce the value being mutated.
+
+```
+use fuzzcheck::mutators::integer_within_range::IntegerWithinRangeMutator;
+
+// the non-privileged ports
+let m = IntegerWithinRangeMutator::<u16>::new(1024..=u16::MAX);
+```
+The type aliases [`U8WithinRangeMutator`], [`I32WithinRangeMutator`], etc. name the mutator of each integer type.
+*/
+
+use crate::mutators::integer::binary_search_arbitrary_u64;
+use crate::mutators::DefaultMutatorWithinRange;
 use crate::Mutator;
+use std::fmt::Debug;
 use std::ops::Bound;
 use std::ops::RangeBounds;
+use std::ops::RangeInclusive;
 
 const INITIAL_MUTATION_STEP: u64 = 0;
 
-macro_rules! impl_int_mutator_constrained {
-    ($name:ident,$name_unsigned:ident, $name_mutator:ident, $name_binary_arbitrary_function: ident) => {
-        pub struct $name_mutator {
-            start_range: $name,
-            len_range: $name_unsigned,
-            rng: fastrand::Rng,
-        }
-        impl $name_mutator {
-            #[no_coverage]
-            pub fn new<RB: RangeBounds<$name>>(range: RB) -> Self {
-                let start = match range.start_bound() {
-                    Bound::Included(b) => *b,
-                    Bound::Excluded(b) => {
-                        assert_ne!(*b, <$name>::MAX);
-                        *b + 1
-                    }
-                    Bound::Unbounded => <$name>::MIN,
-                };
-                let end = match range.end_bound() {
-                    Bound::Included(b) => *b,
-                    Bound::Excluded(b) => {
-                        assert_ne!(*b, <$name>::MIN);
-                        *b - 1
-                    }
-                    Bound::Unbounded => <$name>::MAX,
-                };
-                if !(start <= end) {
-                    panic!(
-                        "You have provided a character range where the value of the start of the range \
-                        is larger than the end of the range!\nRange start: {:#?}\nRange end: {:#?}",
-                        range.start_bound(),
-                        range.end_bound()
-                    )
-                }
-                Self {
-                    start_range: start,
-                    len_range: end.wrapping_sub(start) as $name_unsigned,
-                    rng: fastrand::Rng::default(),
-                }
-            }
-        }
+/// An integer type that can be mutated by [`IntegerWithinRangeMutator`]
+pub trait RangeInteger: Copy + PartialOrd + Debug + 'static {
+    const MIN: Self;
+    const MAX: Self;
+    const BITS: u32;
+    /// The distance from `start` to `self`, assuming that `self` is greater than or equal to `start`
+    fn offset_from(self, start: Self) -> u64;
+    /// The integer at `offset` from `self`, wrapping around at the boundaries of the type
+    fn wrapping_add_offset(self, offset: u64) -> Self;
+    /// A random integer within `range`
+    fn random(rng: &fastrand::Rng, range: RangeInclusive<Self>) -> Self;
+}
 
-        impl Mutator<$name> for $name_mutator {
-            #[doc(hidden)]
-            type Cache = ();
-            #[doc(hidden)]
-            type MutationStep = u64; // mutation step
-            #[doc(hidden)]
-            type ArbitraryStep = u64;
-            #[doc(hidden)]
-            type UnmutateToken = $name; // old value
-
-            #[doc(hidden)]
+macro_rules! impl_range_integer {
+    ($name:ident, $name_unsigned:ident) => {
+        impl RangeInteger for $name {
+            const MIN: Self = <$name>::MIN;
+            const MAX: Self = <$name>::MAX;
+            const BITS: u32 = <$name>::BITS;
             #[no_coverage]
-            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
-                0
+            fn offset_from(self, start: Self) -> u64 {
+                (self as $name_unsigned).wrapping_sub(start as $name_unsigned) as u64
             }
-
-            #[doc(hidden)]
             #[no_coverage]
-            fn validate_value(&self, _value: &$name) -> Option<Self::Cache> {
-                Some(())
+            fn wrapping_add_offset(self, offset: u64) -> Self {
+                (self as $name_unsigned).wrapping_add(offset as $name_unsigned) as $name
             }
-
-            #[doc(hidden)]
             #[no_coverage]
-            fn default_mutation_step(&self, _value: &$name, _cache: &Self::Cache) -> Self::MutationStep {
-                INITIAL_MUTATION_STEP
+            fn random(rng: &fastrand::Rng, range: RangeInclusive<Self>) -> Self {
+                rng.$name(range)
             }
+        }
 
-            #[doc(hidden)]
+        impl DefaultMutatorWithinRange for $name {
+            type Bound = $name;
+            type Mutator = IntegerWithinRangeMutator<$name>;
             #[no_coverage]
-            fn max_complexity(&self) -> f64 {
-                <$name>::BITS as f64
+            fn default_mutator_within_range(range: RangeInclusive<$name>) -> Self::Mutator {
+                IntegerWithinRangeMutator::new(range)
             }
+        }
+    };
+}
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn min_complexity(&self) -> f64 {
-                <$name>::BITS as f64
-            }
+impl_range_integer!(u8, u8);
+impl_range_integer!(u16, u16);
+impl_range_integer!(u32, u32);
+impl_range_integer!(u64, u64);
+impl_range_integer!(usize, usize);
+impl_range_integer!(i8, u8);
+impl_range_integer!(i16, u16);
+impl_range_integer!(i32, u32);
+impl_range_integer!(i64, u64);
+impl_range_integer!(isize, usize);
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn complexity(&self, _value: &$name, _cache: &Self::Cache) -> f64 {
-                <$name>::BITS as f64
-            }
+pub type U8WithinRangeMutator = IntegerWithinRangeMutator<u8>;
+pub type U16WithinRangeMutator = IntegerWithinRangeMutator<u16>;
+pub type U32WithinRangeMutator = IntegerWithinRangeMutator<u32>;
+pub type U64WithinRangeMutator = IntegerWithinRangeMutator<u64>;
+pub type UsizeWithinRangeMutator = IntegerWithinRangeMutator<usize>;
+pub type I8WithinRangeMutator = IntegerWithinRangeMutator<i8>;
+pub type I16WithinRangeMutator = IntegerWithinRangeMutator<i16>;
+pub type I32WithinRangeMutator = IntegerWithinRangeMutator<i32>;
+pub type I64WithinRangeMutator = IntegerWithinRangeMutator<i64>;
+pub type IsizeWithinRangeMutator = IntegerWithinRangeMutator<isize>;
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($name, f64)> {
-                if max_cplx < self.min_complexity() {
-                    return None;
-                }
-                if *step > self.len_range as u64 {
-                    None
-                } else {
-                    let result = $name_binary_arbitrary_function(0, self.len_range, *step);
-                    *step = step.wrapping_add(1);
-                    Some((
-                        self.start_range.wrapping_add(result as $name),
-                        <$name>::BITS as f64,
-                    ))
-                }
-            }
+/// Mutator for an integer within a given range
+///
+/// See the [module documentation](self) for more details.
+#[derive(Clone)]
+pub struct IntegerWithinRangeMutator<T: RangeInteger> {
+    start_range: T,
+    end_range: T,
+    /// The number of integers in the range, minus one
+    len_range: u64,
+    rng: fastrand::Rng,
+}
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
-                let value = self
-                    .rng
-                    .$name(self.start_range..=self.start_range.wrapping_add(self.len_range as $name));
-                (value, <$name>::BITS as f64)
+impl<T: RangeInteger> IntegerWithinRangeMutator<T> {
+    #[no_coverage]
+    pub fn new<RB: RangeBounds<T>>(range: RB) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(b) => *b,
+            Bound::Excluded(b) => {
+                assert_ne!(*b, T::MAX);
+                b.wrapping_add_offset(1)
             }
-
-            #[doc(hidden)]
-            #[no_coverage]
-            fn ordered_mutate(
-                &self,
-                value: &mut $name,
-                _cache: &mut Self::Cache,
-                step: &mut Self::MutationStep,
-                max_cplx: f64,
-            ) -> Option<(Self::UnmutateToken, f64)> {
-                if max_cplx < self.min_complexity() {
-                    return None;
-                }
-                if *step > self.len_range as u64 {
-                    return None;
-                }
-                let token = *value;
-
-                let result = $name_binary_arbitrary_function(0, self.len_range, *step);
-                *value = self.start_range.wrapping_add(result as $name);
-                *step = step.wrapping_add(1);
-
-                Some((token, <$name>::BITS as f64))
+            Bound::Unbounded => T::MIN,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(b) => *b,
+            Bound::Excluded(b) => {
+                assert_ne!(*b, T::MIN);
+                // adding u64::MAX is subtracting one, for every integer type of 64 bits or fewer
+                b.wrapping_add_offset(u64::MAX)
             }
+            Bound::Unbounded => T::MAX,
+        };
+        if !(start <= end) {
+            panic!(
+                "You have provided an integer range where the value of the start of the range \
+                is larger than the end of the range!\nRange start: {:#?}\nRange end: {:#?}",
+                range.start_bound(),
+                range.end_bound()
+            )
+        }
+        Self {
+            start_range: start,
+            end_range: end,
+            len_range: end.offset_from(start),
+            rng: fastrand::Rng::default(),
+        }
+    }
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn random_mutate(
-                &self,
-                value: &mut $name,
-                _cache: &mut Self::Cache,
-                _max_cplx: f64,
-            ) -> (Self::UnmutateToken, f64) {
-                (
-                    std::mem::replace(
-                        value,
-                        self.rng
-                            .$name(self.start_range..=self.start_range.wrapping_add(self.len_range as $name)),
-                    ),
-                    <$name>::BITS as f64,
-                )
-            }
+    #[no_coverage]
+    fn contains(&self, value: T) -> bool {
+        self.start_range <= value && value <= self.end_range
+    }
 
-            #[doc(hidden)]
-            #[no_coverage]
-            fn unmutate(&self, value: &mut $name, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
-                *value = t;
-            }
+    /// The integer at the given step of the permutation of the range used by `ordered_arbitrary`
+    #[no_coverage]
+    fn ordered_value(&self, step: u64) -> T {
+        let offset = binary_search_arbitrary_u64(0, self.len_range, step);
+        self.start_range.wrapping_add_offset(offset)
+    }
+}
 
-            #[doc(hidden)]
-            type RecursingPartIndex = ();
-            #[doc(hidden)]
-            #[no_coverage]
-            fn default_recursing_part_index(&self, _value: &$name, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
-            #[doc(hidden)]
-            #[no_coverage]
-            fn recursing_part<'a, V, N>(
-                &self,
-                _parent: &N,
-                _value: &'a $name,
-                _index: &mut Self::RecursingPartIndex,
-            ) -> Option<&'a V>
-            where
-                V: Clone + 'static,
-                N: Mutator<V>,
-            {
-                None
+impl<T: RangeInteger> Mutator<T> for IntegerWithinRangeMutator<T> {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = u64; // mutation step
+    #[doc(hidden)]
+    type ArbitraryStep = u64;
+    #[doc(hidden)]
+    type UnmutateToken = T; // old value
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        if self.contains(*value) {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &T, _cache: &Self::Cache) -> Self::MutationStep {
+        INITIAL_MUTATION_STEP
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        T::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        T::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, _value: &T, _cache: &Self::Cache) -> f64 {
+        T::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if *step > self.len_range {
+            None
+        } else {
+            let value = self.ordered_value(*step);
+            *step = step.wrapping_add(1);
+            Some((value, T::BITS as f64))
+        }
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (T, f64) {
+        let value = T::random(&self.rng, self.start_range..=self.end_range);
+        (value, T::BITS as f64)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        loop {
+            if *step > self.len_range {
+                return None;
+            }
+            let new_value = self.ordered_value(*step);
+            *step = step.wrapping_add(1);
+            // the permutation of the range contains the current value once, which is skipped
+            if new_value != *value {
+                return Some((std::mem::replace(value, new_value), T::BITS as f64));
             }
         }
-    };
-}
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.len_range == 0 {
+            return (*value, T::BITS as f64);
+        }
+        // pick one of the other integers of the range, by skipping over the offset of the current value
+        let current_offset = value.offset_from(self.start_range);
+        let mut offset = self.rng.u64(..self.len_range);
+        if offset >= current_offset {
+            offset += 1;
+        }
+        (
+            std::mem::replace(value, self.start_range.wrapping_add_offset(offset)),
+            T::BITS as f64,
+        )
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
 
-impl_int_mutator_constrained!(u8, u8, U8WithinRangeMutator, binary_search_arbitrary_u8);
-impl_int_mutator_constrained!(u16, u16, U16WithinRangeMutator, binary_search_arbitrary_u16);
-impl_int_mutator_constrained!(u32, u32, U32WithinRangeMutator, binary_search_arbitrary_u32);
-impl_int_mutator_constrained!(u64, u64, U64WithinRangeMutator, binary_search_arbitrary_u64);
-impl_int_mutator_constrained!(i8, u8, I8WithinRangeMutator, binary_search_arbitrary_u8);
-impl_int_mutator_constrained!(i16, u16, I16WithinRangeMutator, binary_search_arbitrary_u16);
-impl_int_mutator_constrained!(i32, u32, I32WithinRangeMutator, binary_search_arbitrary_u32);
-impl_int_mutator_constrained!(i64, u64, I64WithinRangeMutator, binary_search_arbitrary_u64);
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &T, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a T,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        None
+    }
+}
@@ -0,0 +1,230 @@
+//! A mutator for [`serde_json::Value`], to fuzz consumers of schema-less JSON.
+//!
+//! The mutator first generates the scalar values and the empty array and object, and then
+//! builds nested values using the integer, float, vector, and tuple mutators. Values are
+//! never nested more than [`JSON_MAX_DEPTH`] levels deep.
+//!
+//! ```
+//! use fuzzcheck::{DefaultMutator, Mutator};
+//!
+//! let m = serde_json::Value::default_mutator();
+//! let (value, _cplx) = m.random_arbitrary(100.0);
+//! // `value` can be given to any function consuming JSON
+//! let _ = serde_json::to_string(&value).unwrap();
+//! ```
+use serde_json::{Map, Number, Value};
+
+use crate as fuzzcheck;
+use crate::make_mutator;
+use crate::mutators::bool::BoolMutator;
+use crate::mutators::char::CharWithinRangeMutator;
+use crate::mutators::dictionary::DictionaryMutator;
+use crate::mutators::float::F64Mutator;
+use crate::mutators::integer::I64Mutator;
+use crate::mutators::map::MapMutator;
+use crate::mutators::recursive::{RecurToMutator, RecursiveMutator};
+use crate::mutators::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
+use crate::mutators::vector::VecMutator;
+use crate::mutators::wrapper::Wrapper;
+use crate::DefaultMutator;
+
+/// The maximum number of nested arrays and objects in a value generated by [`JsonValueMutator`].
+///
+/// Deeper values are truncated: arrays and objects beyond that depth are replaced by `null`.
+/// Values from the input corpus that are nested more deeply are rejected.
+pub const JSON_MAX_DEPTH: usize = 8;
+
+/// The value that is actually mutated by [`JsonValueMutator`] before being converted to a [`serde_json::Value`].
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub enum InnerJsonValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(Vec<char>),
+    Array(Vec<InnerJsonValue>),
+    Object(Vec<(Vec<char>, InnerJsonValue)>),
+}
+
+make_mutator! {
+    name: InnerJsonValueMutator,
+    recursive: true,
+    default: false,
+    type:
+        pub enum InnerJsonValue {
+            Null,
+            Bool(bool),
+            Integer(i64),
+            Float(f64),
+            String(#[field_mutator(VecMutator<char, CharWithinRangeMutator>)] Vec<char>),
+            Array(
+                #[field_mutator(
+                    VecMutator<InnerJsonValue, RecurToMutator<InnerJsonValueMutator<M1_0, M2_0, M3_0>>>
+                )]
+                Vec<InnerJsonValue>
+            ),
+            Object(
+                #[field_mutator(
+                    VecMutator<
+                        (Vec<char>, InnerJsonValue),
+                        TupleMutatorWrapper<
+                            Tuple2Mutator<
+                                VecMutator<char, CharWithinRangeMutator>,
+                                RecurToMutator<InnerJsonValueMutator<M1_0, M2_0, M3_0>>
+                            >,
+                            Tuple2<Vec<char>, InnerJsonValue>
+                        >
+                    >
+                )]
+                Vec<(Vec<char>, InnerJsonValue)>
+            ),
+        }
+}
+
+type InnerMutator =
+    DictionaryMutator<InnerJsonValue, RecursiveMutator<InnerJsonValueMutator<BoolMutator, I64Mutator, F64Mutator>>>;
+
+/// The default mutator of [`serde_json::Value`].
+///
+/// See the [module documentation](self) for more details.
+pub type JsonValueMutator = Wrapper<
+    MapMutator<
+        InnerJsonValue,
+        Value,
+        InnerMutator,
+        fn(&Value) -> Option<InnerJsonValue>,
+        fn(&InnerJsonValue) -> Value,
+        fn(&Value, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn string_mutator() -> VecMutator<char, CharWithinRangeMutator> {
+    VecMutator::new(CharWithinRangeMutator::new(..=char::MAX), 0..=usize::MAX)
+}
+
+#[no_coverage]
+fn parse(value: &Value) -> Option<InnerJsonValue> {
+    parse_at_depth(value, 0)
+}
+
+#[no_coverage]
+fn parse_at_depth(value: &Value, depth: usize) -> Option<InnerJsonValue> {
+    match value {
+        Value::Null => Some(InnerJsonValue::Null),
+        Value::Bool(b) => Some(InnerJsonValue::Bool(*b)),
+        Value::Number(n) => {
+            if let Some(n) = n.as_i64() {
+                Some(InnerJsonValue::Integer(n))
+            } else {
+                n.as_f64().map(InnerJsonValue::Float)
+            }
+        }
+        Value::String(s) => Some(InnerJsonValue::String(s.chars().collect())),
+        Value::Array(xs) => {
+            if depth >= JSON_MAX_DEPTH {
+                return None;
+            }
+            let xs = xs
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |x| parse_at_depth(x, depth + 1),
+                )
+                .collect::<Option<Vec<_>>>()?;
+            Some(InnerJsonValue::Array(xs))
+        }
+        Value::Object(map) => {
+            if depth >= JSON_MAX_DEPTH {
+                return None;
+            }
+            let entries = map
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |(k, v)| Some((k.chars().collect(), parse_at_depth(v, depth + 1)?)),
+                )
+                .collect::<Option<Vec<_>>>()?;
+            Some(InnerJsonValue::Object(entries))
+        }
+    }
+}
+
+#[no_coverage]
+fn map(value: &InnerJsonValue) -> Value {
+    map_at_depth(value, 0)
+}
+
+#[no_coverage]
+fn map_at_depth(value: &InnerJsonValue, depth: usize) -> Value {
+    match value {
+        InnerJsonValue::Null => Value::Null,
+        InnerJsonValue::Bool(b) => Value::Bool(*b),
+        InnerJsonValue::Integer(n) => Value::Number(Number::from(*n)),
+        // NaN and infinities cannot be represented in JSON
+        InnerJsonValue::Float(f) => Number::from_f64(*f).map(Value::Number).unwrap_or(Value::Null),
+        InnerJsonValue::String(s) => Value::String(s.iter().collect()),
+        InnerJsonValue::Array(_) | InnerJsonValue::Object(_) if depth >= JSON_MAX_DEPTH => Value::Null,
+        InnerJsonValue::Array(xs) => Value::Array(
+            xs.iter()
+                .map(
+                    #[no_coverage]
+                    |x| map_at_depth(x, depth + 1),
+                )
+                .collect(),
+        ),
+        InnerJsonValue::Object(entries) => {
+            // if a key appears multiple times, its last value is kept
+            let mut map = Map::new();
+            for (k, v) in entries {
+                map.insert(k.iter().collect(), map_at_depth(v, depth + 1));
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+#[no_coverage]
+fn complexity(_value: &Value, cplx: f64) -> f64 {
+    cplx
+}
+
+impl DefaultMutator for Value {
+    type Mutator = JsonValueMutator;
+
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        let inner = RecursiveMutator::new(
+            #[no_coverage]
+            |self_| {
+                InnerJsonValueMutator::new(
+                    bool::default_mutator(),
+                    i64::default_mutator(),
+                    f64::default_mutator(),
+                    string_mutator(),
+                    VecMutator::new(self_.into(), 0..=usize::MAX),
+                    VecMutator::new(
+                        TupleMutatorWrapper::new(Tuple2Mutator::new(string_mutator(), self_.into())),
+                        0..=usize::MAX,
+                    ),
+                )
+            },
+        );
+        // generate the scalar values and the empty array and object first
+        let inner = DictionaryMutator::new(
+            inner,
+            [
+                InnerJsonValue::Null,
+                InnerJsonValue::Bool(false),
+                InnerJsonValue::Bool(true),
+                InnerJsonValue::Integer(0),
+                InnerJsonValue::Float(0.0),
+                InnerJsonValue::String(vec![]),
+                InnerJsonValue::Array(vec![]),
+                InnerJsonValue::Object(vec![]),
+            ],
+        );
+        Wrapper(MapMutator::new(inner, parse, map, complexity))
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::LinkedList;
+
+use super::map::MapMutator;
+use super::vector::VecMutator;
+use super::wrapper::Wrapper;
+use crate::DefaultMutator;
+
+/// The default mutator of `LinkedList<T>`.
+///
+/// It mutates a `Vec<T>` with a [`VecMutator`] and converts it to a `LinkedList<T>`.
+/// The complexity of a value is the complexity of the corresponding vector.
+pub type LinkedListMutator<T, M> = Wrapper<
+    MapMutator<
+        Vec<T>,
+        LinkedList<T>,
+        VecMutator<T, M>,
+        fn(&LinkedList<T>) -> Option<Vec<T>>,
+        fn(&Vec<T>) -> LinkedList<T>,
+        fn(&LinkedList<T>, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn vec_from_linkedlist<T: Clone>(value: &LinkedList<T>) -> Option<Vec<T>> {
+    Some(value.iter().cloned().collect())
+}
+
+#[no_coverage]
+fn linkedlist_from_vec<T: Clone>(vec: &Vec<T>) -> LinkedList<T> {
+    vec.iter().cloned().collect()
+}
+
+#[no_coverage]
+fn complexity<T: Clone>(_t: &LinkedList<T>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl<T, M> LinkedListMutator<T, M>
+where
+    T: 'static + Clone,
+    M: crate::Mutator<T>,
+{
+    #[no_coverage]
+    pub fn new(mutator: M) -> Self {
+        Wrapper(MapMutator::new(
+            VecMutator::new(mutator, 0..=usize::MAX),
+            vec_from_linkedlist,
+            linkedlist_from_vec,
+            complexity,
+        ))
+    }
+}
+
+impl<T> DefaultMutator for LinkedList<T>
+where
+    T: 'static + Clone + DefaultMutator,
+{
+    type Mutator = LinkedListMutator<T, T::Mutator>;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new(T::default_mutator())
+    }
+}
@@ -5,17 +5,32 @@ This module provides the following mutators:
 
 * mutators for basic types such as
     * `bool` ([here](crate::mutators::bool::BoolMutator))
-    * `char` ([here](crate::mutators::char::CharWithinRangeMutator) and [here](crate::mutators::character_classes::CharacterMutator))
+    * `char` ([here](crate::mutators::char::CharMutator), [here](crate::mutators::char::CharWithinRangeMutator), [here](crate::mutators::char::CharWithinRangesMutator), and [here](crate::mutators::character_classes::CharacterMutator))
     * integers ([here](crate::mutators::integer) and [here](crate::mutators::integer_within_range))
+    * `f32` and `f64`, starting with special values such as NaN, infinities, and subnormals, optionally within a range ([here](crate::mutators::float))
+    * fixed-point decimal numbers represented by an integer mantissa and a scale ([here](crate::mutators::decimal))
+    * `String` ([here](crate::mutators::string::StringMutator))
+    * strings made of a restricted set of characters ([here](crate::mutators::char_set::StringFromCharSetMutator))
+    * strings on the edge of numeric validity, to test number parsers ([here](crate::mutators::numeric_string::NumericStringMutator))
     * `Vec` ([here](crate::mutators::vector::VecMutator) and [here](crate::mutators::fixed_len_vector::FixedLenVecMutator))
+    * `VecDeque` ([here](crate::mutators::vecdeque::VecDequeMutator)) and `LinkedList` ([here](crate::mutators::linkedlist::LinkedListMutator))
+    * `HashMap` ([here](crate::mutators::hashmap::HashMapMutator)) and `HashSet` ([here](crate::mutators::hashset::HashSetMutator))
+    * `BTreeMap` ([here](crate::mutators::btreemap::BTreeMapMutator)) and `BTreeSet` ([here](crate::mutators::btreeset::BTreeSetMutator))
     * `Option` ([here](crate::mutators::option::OptionMutator))
     * `Result` ([here](crate::mutators::result::ResultMutator))
     * `Box` ([here](crate::mutators::boxed))
-    * tuples of up to 10 elements ([here](crate::mutators::tuples))
+    * `Duration` and `SystemTime` ([here](crate::mutators::duration))
+    * `Ipv4Addr`, `Ipv6Addr`, `IpAddr`, and `SocketAddr` ([here](crate::mutators::net))
+    * trait objects such as `Box<dyn Trait>`, given a mutator for each concrete type ([here](crate::mutators::trait_object::DynMutator))
+    * tuples of up to 25 elements ([here](crate::mutators::tuples))
+    * `serde_json::Value` ([here](crate::mutators::json)) __(supported on crate feature `serde_json` only)__
+    * any type implementing `arbitrary::Arbitrary`, by mutating the bytes it is decoded from ([here](crate::mutators::arbitrary_adapter)) __(supported on crate feature `arbitrary_mutator` only)__
 
 * procedural macros to generate mutators for custom types:
     * [`#[derive(DefaultMutator)]`](fuzzcheck_mutators_derive::DefaultMutator) which works on most structs and enums
     * [`make_mutator! { .. }`](fuzzcheck_mutators_derive::make_mutator) which works like `#[derive(DefaultMutator)]` but is customisable
+    * on crate feature `field_complexities`, the mutators generated by these macros have a `field_complexities(&value)`
+      method that returns the complexity of each field of a value, which shows which fields use most of the complexity budget
 */
 #![cfg_attr(
     feature = "grammar_mutator",
@@ -32,44 +47,68 @@ This module provides the following mutators:
     * [`Either<M1, M2>`](crate::mutators::either::Either) is the regular `Either` type, which also implements `Mutator<T>` if both `M1` and `M2` implement it too
     * [`RecursiveMutator` and `RecurToMutator`](crate::mutators::recursive) are wrappers allowing mutators to call themselves recursively, which is necessary to mutate recursive types.
     * [`MapMutator<..>`](crate::mutators::map::MapMutator) wraps a mutator and transforms the generated value using a user-provided function.
+    * [`ChecksummedMutator<_, M>`](crate::mutators::checksummed::ChecksummedMutator) wraps a mutator and patches length fields or checksums after each mutation.
+    * [`FixupMutator<_, M, _>`](crate::mutators::fixup::FixupMutator) wraps a mutator and repairs the values it generates such that they satisfy an invariant.
+    * [`CachedComplexityMutator<M>`](crate::mutators::cached_complexity::CachedComplexityMutator) wraps a mutator whose `complexity` method is expensive and keeps the complexity of the value in its cache.
+    * [`ProfiledMutator<M>`](crate::mutators::profiled::ProfiledMutator) wraps a mutator and reports how often its mutations led to a corpus addition in the fuzzer statistics.
+    * [`WithComplexity<_, M, _>`](crate::mutators::with_complexity::WithComplexity) wraps a mutator and overrides the complexity of the values it generates with a user-provided function.
 */
 pub mod alternation;
+#[cfg(feature = "arbitrary_mutator")]
+#[doc(cfg(feature = "arbitrary_mutator"))]
+pub mod arbitrary_adapter;
 pub mod arc;
 pub mod array;
 pub mod bool;
 pub mod boxed;
 pub mod btreemap;
 pub mod btreeset;
+pub mod cached_complexity;
 pub mod char;
+pub mod char_set;
 pub mod character_classes;
+pub mod checksummed;
+pub mod decimal;
 pub mod dictionary;
 pub mod duration;
 pub mod either;
 pub mod enums;
 pub mod fixed_len_vector;
+pub mod fixup;
+pub mod float;
 #[cfg(feature = "grammar_mutator")]
 #[doc(cfg(feature = "grammar_mutator"))]
-pub mod float;
 pub mod grammar;
+pub mod hashmap;
+pub mod hashset;
 pub mod integer;
 pub mod integer_within_range;
+#[cfg(feature = "serde_json")]
+#[doc(cfg(feature = "serde_json"))]
+pub mod json;
+pub mod linkedlist;
 pub mod map;
 pub mod mutations;
 pub mod net;
 pub mod never;
+pub mod numeric_string;
 pub mod option;
+pub mod profiled;
 pub mod range;
 pub mod rc;
 pub mod recursive;
 pub mod result;
 pub mod string;
+pub mod trait_object;
 pub mod tuples;
 pub mod unit;
+pub mod vecdeque;
 pub mod vector;
 pub mod vose_alias;
+pub mod with_complexity;
 pub mod wrapper;
 use crate::Mutator;
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 
 /// A trait for giving a type a default [Mutator]
 pub trait DefaultMutator: Clone {
@@ -77,6 +116,18 @@ pub trait DefaultMutator: Clone {
     fn default_mutator() -> Self::Mutator;
 }
 
+/// A trait for giving a type a default [Mutator] whose values are constrained to a range
+///
+/// The range constrains the value itself for integers, floats, and characters, and the length of vectors
+/// and strings. It is used by the `#[field_range(min..=max)]` attribute of
+/// [`#[derive(DefaultMutator)]`](fuzzcheck_mutators_derive::DefaultMutator).
+pub trait DefaultMutatorWithinRange: Clone {
+    /// The type of the bounds of the range
+    type Bound;
+    type Mutator: Mutator<Self>;
+    fn default_mutator_within_range(range: RangeInclusive<Self::Bound>) -> Self::Mutator;
+}
+
 /// Generate a random f64 within the given range
 /// The start and end of the range must be finite
 /// This is a very naive implementation
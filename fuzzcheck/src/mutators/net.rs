@@ -1,149 +1,266 @@
-use std::net::{IpAddr, SocketAddr};
-
-use crate::{DefaultMutator, ExtendedMutator, MutatorValueConverter, MutatorWrapper};
-
-use super::integer_within_range::U32WithinRangeMutator;
-
-fn ipv4_to_u32(ip: IpAddr) -> u32 {
-    match ip {
-        IpAddr::V4(addr) => {
-            let [a, b, c, d] = addr.octets();
-            let (a, b, c, d) = (a as u32, b as u32, c as u32, d as u32);
-            a * 256 * 256 * 256 + b * 256 * 256 + c * 256 + d
-        }
-        IpAddr::V6(_) => panic!("expected ipv4, found ipv6"),
-    }
+/*!
+Mutators for IP addresses and socket addresses.
+
+An [`Ipv4Addr`] is mutated as a `u32` and an [`Ipv6Addr`] as two `u64`. Both mutators first generate
+addresses that network code often treats specially, such as the unspecified, loopback, broadcast,
+private, link-local, and multicast addresses.
+
+[`IpAddr`] and [`SocketAddr`] are mutated like the enums they are. The mutator of [`SocketAddrV6`] only
+generates addresses whose flow info and scope id are 0, and rejects the other ones.
+*/
+
+extern crate self as fuzzcheck;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use fuzzcheck_mutators_derive::make_mutator;
+
+use super::dictionary::DictionaryMutator;
+use super::integer::{U16Mutator, U32Mutator, U64Mutator};
+use super::map::MapMutator;
+use super::tuples::{Tuple2, Tuple2Mutator, TupleMutatorWrapper};
+use super::wrapper::Wrapper;
+
+use crate::DefaultMutator;
+
+pub type Ipv4AddrMutator = Wrapper<
+    DictionaryMutator<
+        Ipv4Addr,
+        MapMutator<
+            u32,
+            Ipv4Addr,
+            U32Mutator,
+            fn(&Ipv4Addr) -> Option<u32>,
+            fn(&u32) -> Ipv4Addr,
+            fn(&Ipv4Addr, f64) -> f64,
+        >,
+    >,
+>;
+
+#[no_coverage]
+fn u32_from_ipv4(ip: &Ipv4Addr) -> Option<u32> {
+    Some(u32::from(*ip))
 }
 
-fn ipv4_from_u32(id: u32) -> IpAddr {
-    let ip = [
-        ((id / 256 / 256 / 256) % 256) as u8,
-        ((id / 256 / 256) % 256) as u8,
-        ((id / 256) % 256) as u8,
-        (id % 256) as u8,
-    ];
-
-    ip.into()
+#[no_coverage]
+fn ipv4_from_u32(x: &u32) -> Ipv4Addr {
+    Ipv4Addr::from(*x)
 }
 
-fn addr_from_u32(id: u32) -> SocketAddr {
-    (ipv4_from_u32(id), 12345).into()
+#[no_coverage]
+fn ipv4_complexity(_ip: &Ipv4Addr, cplx: f64) -> f64 {
+    cplx
 }
 
-impl DefaultMutator for IpAddr {
-    type Mutator = IpAddrMutator;
+impl Ipv4AddrMutator {
+    #[no_coverage]
+    pub fn new() -> Self {
+        let m = MapMutator::new(
+            U32Mutator::default(),
+            u32_from_ipv4 as _,
+            ipv4_from_u32 as _,
+            ipv4_complexity as _,
+        );
+        Wrapper(DictionaryMutator::new(
+            m,
+            [
+                Ipv4Addr::UNSPECIFIED,
+                Ipv4Addr::LOCALHOST,
+                Ipv4Addr::BROADCAST,
+                Ipv4Addr::new(10, 0, 0, 1),
+                Ipv4Addr::new(172, 16, 0, 1),
+                Ipv4Addr::new(192, 168, 0, 1),
+                Ipv4Addr::new(169, 254, 0, 1),
+                Ipv4Addr::new(224, 0, 0, 1),
+                Ipv4Addr::new(255, 255, 255, 0),
+            ],
+        ))
+    }
+}
 
+impl DefaultMutator for Ipv4Addr {
+    type Mutator = Ipv4AddrMutator;
+    #[no_coverage]
     fn default_mutator() -> Self::Mutator {
-        IpAddrMutator::new(100)
+        Self::Mutator::new()
     }
 }
 
-impl DefaultMutator for SocketAddr {
-    type Mutator = SocketAddrMutator;
+pub type Ipv6AddrMutator = Wrapper<
+    DictionaryMutator<
+        Ipv6Addr,
+        MapMutator<
+            (u64, u64),
+            Ipv6Addr,
+            TupleMutatorWrapper<Tuple2Mutator<U64Mutator, U64Mutator>, Tuple2<u64, u64>>,
+            fn(&Ipv6Addr) -> Option<(u64, u64)>,
+            fn(&(u64, u64)) -> Ipv6Addr,
+            fn(&Ipv6Addr, f64) -> f64,
+        >,
+    >,
+>;
+
+#[no_coverage]
+fn tuple_from_ipv6(ip: &Ipv6Addr) -> Option<(u64, u64)> {
+    let x = u128::from(*ip);
+    Some(((x >> 64) as u64, x as u64))
+}
 
-    fn default_mutator() -> Self::Mutator {
-        SocketAddrMutator::new(100)
-    }
+#[no_coverage]
+fn ipv6_from_tuple(t: &(u64, u64)) -> Ipv6Addr {
+    Ipv6Addr::from(((t.0 as u128) << 64) | t.1 as u128)
 }
 
-pub struct IpAddrU32Converter {}
+#[no_coverage]
+fn ipv6_complexity(_ip: &Ipv6Addr, cplx: f64) -> f64 {
+    cplx
+}
 
-impl IpAddrU32Converter {
+impl Ipv6AddrMutator {
+    #[no_coverage]
     pub fn new() -> Self {
-        Self {}
+        let m = MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(U64Mutator::default(), U64Mutator::default())),
+            tuple_from_ipv6 as _,
+            ipv6_from_tuple as _,
+            ipv6_complexity as _,
+        );
+        Wrapper(DictionaryMutator::new(
+            m,
+            [
+                Ipv6Addr::UNSPECIFIED,
+                Ipv6Addr::LOCALHOST,
+                // IPv4-mapped and IPv4-compatible loopback addresses
+                Ipv4Addr::LOCALHOST.to_ipv6_mapped(),
+                Ipv4Addr::LOCALHOST.to_ipv6_compatible(),
+                Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1),
+                Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 1),
+                Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1),
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                Ipv6Addr::from(u128::MAX),
+            ],
+        ))
     }
 }
 
-impl MutatorValueConverter for IpAddrU32Converter {
-    type InnerValue = u32;
-    type Value = IpAddr;
-
-    fn from_inner_value_ref(&self, inner_value: &Self::InnerValue) -> Self::Value {
-        ipv4_from_u32(*inner_value)
+impl DefaultMutator for Ipv6Addr {
+    type Mutator = Ipv6AddrMutator;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new()
     }
+}
 
-    fn to_inner_value_ref(&self, value: &Self::Value) -> Self::InnerValue {
-        ipv4_to_u32(*value)
+make_mutator! {
+    name: IpAddrMutator,
+    default: true,
+    type: pub enum IpAddr {
+        V4(Ipv4Addr),
+        V6(Ipv6Addr),
     }
 }
 
-pub type IpAddrMutatorInner = ExtendedMutator<IpAddr, u32, U32WithinRangeMutator, IpAddrU32Converter>;
-
-pub struct IpAddrMutator {
-    inner: IpAddrMutatorInner,
+pub type SocketAddrV4Mutator = Wrapper<
+    MapMutator<
+        (Ipv4Addr, u16),
+        SocketAddrV4,
+        TupleMutatorWrapper<Tuple2Mutator<Ipv4AddrMutator, U16Mutator>, Tuple2<Ipv4Addr, u16>>,
+        fn(&SocketAddrV4) -> Option<(Ipv4Addr, u16)>,
+        fn(&(Ipv4Addr, u16)) -> SocketAddrV4,
+        fn(&SocketAddrV4, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn tuple_from_socket_addr_v4(addr: &SocketAddrV4) -> Option<(Ipv4Addr, u16)> {
+    Some((*addr.ip(), addr.port()))
 }
 
-impl IpAddrMutator {
-    pub fn new(limit: u32) -> Self {
-        Self {
-            inner: ExtendedMutator::new(U32WithinRangeMutator::new(1..=limit), IpAddrU32Converter::new()),
-        }
-    }
+#[no_coverage]
+fn socket_addr_v4_from_tuple(t: &(Ipv4Addr, u16)) -> SocketAddrV4 {
+    SocketAddrV4::new(t.0, t.1)
 }
 
-impl MutatorWrapper for IpAddrMutator {
-    type Wrapped = IpAddrMutatorInner;
-
-    fn wrapped_mutator(&self) -> &Self::Wrapped {
-        &self.inner
-    }
+#[no_coverage]
+fn socket_addr_v4_complexity(_addr: &SocketAddrV4, cplx: f64) -> f64 {
+    cplx
 }
 
-pub struct SocketAddrU32Converter {}
-
-impl SocketAddrU32Converter {
+impl SocketAddrV4Mutator {
+    #[no_coverage]
     pub fn new() -> Self {
-        Self {}
+        Wrapper(MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(Ipv4AddrMutator::new(), U16Mutator::default())),
+            tuple_from_socket_addr_v4,
+            socket_addr_v4_from_tuple,
+            socket_addr_v4_complexity,
+        ))
     }
 }
 
-impl MutatorValueConverter for SocketAddrU32Converter {
-    type InnerValue = u32;
-    type Value = SocketAddr;
-
-    fn from_inner_value_ref(&self, inner_value: &Self::InnerValue) -> Self::Value {
-        addr_from_u32(*inner_value)
+impl DefaultMutator for SocketAddrV4 {
+    type Mutator = SocketAddrV4Mutator;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new()
     }
+}
 
-    fn to_inner_value_ref(&self, value: &Self::Value) -> Self::InnerValue {
-        ipv4_to_u32(value.ip())
+pub type SocketAddrV6Mutator = Wrapper<
+    MapMutator<
+        (Ipv6Addr, u16),
+        SocketAddrV6,
+        TupleMutatorWrapper<Tuple2Mutator<Ipv6AddrMutator, U16Mutator>, Tuple2<Ipv6Addr, u16>>,
+        fn(&SocketAddrV6) -> Option<(Ipv6Addr, u16)>,
+        fn(&(Ipv6Addr, u16)) -> SocketAddrV6,
+        fn(&SocketAddrV6, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn tuple_from_socket_addr_v6(addr: &SocketAddrV6) -> Option<(Ipv6Addr, u16)> {
+    if addr.flowinfo() == 0 && addr.scope_id() == 0 {
+        Some((*addr.ip(), addr.port()))
+    } else {
+        None
     }
 }
 
-pub type SocketAddrMutatorInner = ExtendedMutator<SocketAddr, u32, U32WithinRangeMutator, SocketAddrU32Converter>;
-
-pub struct SocketAddrMutator {
-    inner: SocketAddrMutatorInner,
+#[no_coverage]
+fn socket_addr_v6_from_tuple(t: &(Ipv6Addr, u16)) -> SocketAddrV6 {
+    SocketAddrV6::new(t.0, t.1, 0, 0)
 }
 
-impl SocketAddrMutator {
-    pub fn new(limit: u32) -> Self {
-        Self {
-            inner: ExtendedMutator::new(U32WithinRangeMutator::new(1..=limit), SocketAddrU32Converter::new()),
-        }
-    }
+#[no_coverage]
+fn socket_addr_v6_complexity(_addr: &SocketAddrV6, cplx: f64) -> f64 {
+    cplx
 }
 
-impl MutatorWrapper for SocketAddrMutator {
-    type Wrapped = SocketAddrMutatorInner;
-
-    fn wrapped_mutator(&self) -> &Self::Wrapped {
-        &self.inner
+impl SocketAddrV6Mutator {
+    #[no_coverage]
+    pub fn new() -> Self {
+        Wrapper(MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(Ipv6AddrMutator::new(), U16Mutator::default())),
+            tuple_from_socket_addr_v6,
+            socket_addr_v6_from_tuple,
+            socket_addr_v6_complexity,
+        ))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::mutators::testing_utilities::test_mutator;
-
-    #[test]
-    fn test_ipaddr_mutator() {
-        test_mutator(IpAddrMutator::new(100), 99.0, 99.0, true, 0, 99);
+impl DefaultMutator for SocketAddrV6 {
+    type Mutator = SocketAddrV6Mutator;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new()
     }
+}
 
-    #[test]
-    fn test_socketaddr_mutator() {
-        test_mutator(SocketAddrMutator::new(100), 99.0, 99.0, true, 0, 99);
+make_mutator! {
+    name: SocketAddrMutator,
+    default: true,
+    type: pub enum SocketAddr {
+        V4(SocketAddrV4),
+        V6(SocketAddrV6),
     }
 }
@@ -0,0 +1,277 @@
+/*!
+A mutator for strings on the edge of numeric validity, to test number parsers.
+
+[`NumericStringMutator`] first generates the literals of [`NUMERIC_EDGE_CASES`], which are known to
+trip up parsers of integers and floating point numbers, such as `1e999`, `.5`, `0x10`, or `1_000`.
+Then, it generates and mutates strings whose characters are mostly taken from the ones found in numbers
+(digits, signs, `.`, `e`, `x`, `_`, etc.), with occasional other characters. It also sometimes replaces
+the current string by one of the edge cases.
+
+```
+use fuzzcheck::mutators::numeric_string::NumericStringMutator;
+
+let m = NumericStringMutator::new();
+// m can be used to test a function such as:
+fn test_parse_f64(s: &String) -> bool {
+    if let Ok(x) = s.parse::<f64>() {
+        // the number must round-trip through its string representation
+        x.is_nan() || x.to_string().parse::<f64>() == Ok(x)
+    } else {
+        true
+    }
+}
+```
+
+Every string is a valid value for this mutator, so corpora of strings written by other mutators
+can be reused.
+*/
+
+use super::alternation::AlternationMutator;
+use super::character_classes::CharacterMutator;
+use super::dictionary::DictionaryMutator;
+use super::map::MapMutator;
+use super::vector::VecMutator;
+use crate::MutatorWrapper;
+
+/**
+The literals that [`NumericStringMutator`] generates before exploring other strings.
+
+They are:
+* zeros, signs, and leading zeros: `0`, `-0`, `+0`, `00`, `007`, `-007`, `0.0`, `-0.0`
+* missing integer or fractional parts: `.5`, `5.`, `-.5`, `+.5`, `.`, `-.`, `1.e5`, `.e5`
+* lone or repeated signs: `-`, `+`, `--1`, `+-1`, `-+1`, `1-`, `1+`
+* truncated or repeated exponents: `e5`, `1e`, `1e+`, `1e-`, `1E5`, `1e5e5`, `1.2.3`, `1ee5`
+* exponents out of range: `1e999`, `-1e999`, `1e-999`, `1e2147483648`, `1e-2147483649`, `1e00000000000000000000001`
+* the limits of `f64`, around its greatest finite value, its smallest normal value, and its smallest
+  subnormal value, including the values that are exactly halfway between two floats: `1.7976931348623157e308`,
+  `1.7976931348623158e308`, `1.7976931348623159e308`, `2.2250738585072014e-308`, `2.2250738585072011e-308`,
+  `4.9406564584124654e-324`, `2.4703282292062327e-324`, `2.4703282292062328e-324`, `9007199254740993`,
+  `0.30000000000000004`
+* the limits of the integer types: `127`, `128`, `-128`, `-129`, `255`, `256`, `2147483648`, `-2147483649`,
+  `4294967296`, `9223372036854775807`, `9223372036854775808`, `-9223372036854775808`, `-9223372036854775809`,
+  `18446744073709551615`, `18446744073709551616`, `340282366920938463463374607431768211456`
+* long numbers: `123456789012345678901234567890.123456789012345678901234567890e-30`,
+  `0.000000000000000000000000000000000000000000000000000000000000000000000000000000001`
+* other bases and separators: `0x10`, `0X1p3`, `0x`, `0b101`, `0o17`, `017`, `1_000`, `_1`, `1_`, `1__0`, `1,000`
+* special values: `inf`, `-inf`, `+inf`, `infinity`, `Infinity`, `INF`, `infinit`, `NaN`, `nan`, `-nan`, `NaN1`
+* whitespace and other characters: the empty string, ` 1`, `1 `, `1\0`, `1\n`, `\u{a0}1`, `١٢٣` (Arabic-Indic digits),
+  `１` (fullwidth digit), `½`
+*/
+pub const NUMERIC_EDGE_CASES: &[&str] = &[
+    "0",
+    "-0",
+    "+0",
+    "00",
+    "007",
+    "-007",
+    "0.0",
+    "-0.0",
+    ".5",
+    "5.",
+    "-.5",
+    "+.5",
+    ".",
+    "-.",
+    "1.e5",
+    ".e5",
+    "-",
+    "+",
+    "--1",
+    "+-1",
+    "-+1",
+    "1-",
+    "1+",
+    "e5",
+    "1e",
+    "1e+",
+    "1e-",
+    "1E5",
+    "1e5e5",
+    "1.2.3",
+    "1ee5",
+    "1e999",
+    "-1e999",
+    "1e-999",
+    "1e2147483648",
+    "1e-2147483649",
+    "1e00000000000000000000001",
+    "1.7976931348623157e308",
+    "1.7976931348623158e308",
+    "1.7976931348623159e308",
+    "2.2250738585072014e-308",
+    "2.2250738585072011e-308",
+    "4.9406564584124654e-324",
+    "2.4703282292062327e-324",
+    "2.4703282292062328e-324",
+    "9007199254740993",
+    "0.30000000000000004",
+    "127",
+    "128",
+    "-128",
+    "-129",
+    "255",
+    "256",
+    "2147483648",
+    "-2147483649",
+    "4294967296",
+    "9223372036854775807",
+    "9223372036854775808",
+    "-9223372036854775808",
+    "-9223372036854775809",
+    "18446744073709551615",
+    "18446744073709551616",
+    "340282366920938463463374607431768211456",
+    "123456789012345678901234567890.123456789012345678901234567890e-30",
+    "0.000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+    "0x10",
+    "0X1p3",
+    "0x",
+    "0b101",
+    "0o17",
+    "017",
+    "1_000",
+    "_1",
+    "1_",
+    "1__0",
+    "1,000",
+    "inf",
+    "-inf",
+    "+inf",
+    "infinity",
+    "Infinity",
+    "INF",
+    "infinit",
+    "NaN",
+    "nan",
+    "-nan",
+    "NaN1",
+    "",
+    " 1",
+    "1 ",
+    "1\0",
+    "1\n",
+    "\u{a0}1",
+    "١٢٣",
+    "１",
+    "½",
+];
+
+/// The characters that are most often generated: the ones found in numbers, in any base and notation
+#[no_coverage]
+fn numeric_characters() -> Vec<std::ops::RangeInclusive<char>> {
+    vec![
+        '0'..='9',
+        'a'..='f',
+        'A'..='F',
+        '+'..='.', // + , - .
+        '_'..='_',
+        'i'..='i',
+        'n'..='n',
+        'o'..='o',
+        'p'..='p',
+        't'..='t',
+        'x'..='x',
+        'y'..='y',
+        'I'..='I',
+        'N'..='N',
+        'P'..='P',
+        'X'..='X',
+        ' '..=' ',
+    ]
+}
+
+/// All the other valid characters, such that no character is shared with [`numeric_characters`]
+#[no_coverage]
+fn non_numeric_characters() -> Vec<std::ops::RangeInclusive<char>> {
+    let mut numeric = numeric_characters();
+    numeric.sort_by_key(
+        #[no_coverage]
+        |range| *range.start(),
+    );
+    let mut ranges = vec![];
+    let mut start = 0;
+    for range in numeric {
+        let (lower, upper) = (*range.start() as u32, *range.end() as u32);
+        if start < lower {
+            ranges.push(char::from_u32(start).unwrap()..=char::from_u32(lower - 1).unwrap());
+        }
+        start = std::cmp::max(start, upper + 1);
+    }
+    ranges.push(char::from_u32(start).unwrap()..='\u{D7FF}');
+    ranges.push('\u{E000}'..=char::MAX);
+    ranges
+}
+
+type NumericStringMutatorInner = DictionaryMutator<
+    String,
+    MapMutator<
+        Vec<char>,
+        String,
+        VecMutator<char, AlternationMutator<char, CharacterMutator>>,
+        fn(&String) -> Option<Vec<char>>,
+        fn(&Vec<char>) -> String,
+        fn(&String, f64) -> f64,
+    >,
+>;
+
+/// A mutator for `String`s that are on the edge of numeric validity.
+///
+/// See the [module documentation](crate::mutators::numeric_string) for more details.
+pub struct NumericStringMutator {
+    mutator: NumericStringMutatorInner,
+}
+
+#[no_coverage]
+fn chars_from_string(string: &String) -> Option<Vec<char>> {
+    Some(string.chars().collect())
+}
+
+#[no_coverage]
+fn string_from_chars(chars: &Vec<char>) -> String {
+    chars.iter().collect()
+}
+
+#[no_coverage]
+fn complexity(_string: &String, cplx: f64) -> f64 {
+    cplx
+}
+
+impl NumericStringMutator {
+    #[no_coverage]
+    pub fn new() -> Self {
+        let chars = AlternationMutator::new(vec![
+            CharacterMutator::new(numeric_characters()),
+            CharacterMutator::new(non_numeric_characters()),
+        ]);
+        let strings = MapMutator::new(
+            VecMutator::new(chars, 0..=usize::MAX),
+            chars_from_string as fn(&String) -> Option<Vec<char>>,
+            string_from_chars as fn(&Vec<char>) -> String,
+            complexity as fn(&String, f64) -> f64,
+        );
+        Self {
+            mutator: DictionaryMutator::new(
+                strings,
+                NUMERIC_EDGE_CASES.iter().map(
+                    #[no_coverage]
+                    |s| s.to_string(),
+                ),
+            ),
+        }
+    }
+}
+
+impl Default for NumericStringMutator {
+    #[no_coverage]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutatorWrapper for NumericStringMutator {
+    type Wrapped = NumericStringMutatorInner;
+    #[no_coverage]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
@@ -9,3 +9,22 @@ make_mutator! {
         None,
     }
 }
+
+impl<T, M> OptionMutator<T, M>
+where
+    T: Clone + 'static,
+    M: fuzzcheck::Mutator<T>,
+{
+    /// Generate `None` with probability `probability`, instead of choosing `Some` and `None` equally often.
+    ///
+    /// ## Panics
+    /// Panics if `probability` is not between 0 and 1.
+    #[no_coverage]
+    pub fn with_none_probability(self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "the probability of generating None must be between 0 and 1"
+        );
+        self.variant_weights(&[1.0 - probability, probability])
+    }
+}
@@ -0,0 +1,382 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use std::path::PathBuf;
+
+use crate::sensors_and_pools::stats::AndPoolStats;
+use crate::traits::{CSVField, CorpusDelta, SaveToStatsFolder, SensorAndPool, Stats, ToCSV};
+use crate::{Mutator, PoolStorageIndex};
+
+/// The counters of all the profiled mutators, by name
+static mut PROFILED_MUTATORS: Vec<(String, Arc<MutationCounters>)> = Vec::new();
+/// The counters of the profiled mutators that took part in the mutation of the current test case
+static mut CURRENT_MUTATIONS: Vec<Arc<MutationCounters>> = Vec::new();
+
+#[derive(Default)]
+struct MutationCounters {
+    mutations: AtomicUsize,
+    corpus_additions: AtomicUsize,
+}
+
+/** Wrap a mutator and count how often it mutates a test case and how often the mutated test case is added to the corpus.
+
+The counters of each profiled mutator are added to the statistics of the fuzzer, which are printed in the terminal
+and written to the `stats.csv` file of the stats folder, under the columns `<name>-mutations` and
+`<name>-corpus-additions`. Comparing these numbers for different mutators, or for the same mutator configured in
+different ways, shows which ones are worth their share of the fuzzing time.
+
+Profiled mutators with the same name share their counters, and can be nested to profile a part of a larger mutator.
+
+```
+use fuzzcheck::DefaultMutator;
+use fuzzcheck::mutators::profiled::ProfiledMutator;
+use fuzzcheck::mutators::vector::VecMutator;
+
+let m = VecMutator::new(ProfiledMutator::new("elements", u8::default_mutator()), 0..=usize::MAX);
+let m = ProfiledMutator::new("vector", m);
+```
+*/
+pub struct ProfiledMutator<M> {
+    m: M,
+    counters: Arc<MutationCounters>,
+}
+
+impl<M> ProfiledMutator<M> {
+    #[no_coverage]
+    pub fn new(name: &str, mutator: M) -> Self {
+        let profiled_mutators = unsafe { &mut PROFILED_MUTATORS };
+        let counters = if let Some((_, counters)) = profiled_mutators.iter().find(
+            #[no_coverage]
+            |(n, _)| n == name,
+        ) {
+            counters.clone()
+        } else {
+            let counters = Arc::new(MutationCounters::default());
+            profiled_mutators.push((name.to_owned(), counters.clone()));
+            counters
+        };
+        Self { m: mutator, counters }
+    }
+
+    #[no_coverage]
+    fn record_mutation(&self) {
+        self.counters.mutations.fetch_add(1, Ordering::Relaxed);
+        unsafe { CURRENT_MUTATIONS.push(self.counters.clone()) };
+    }
+}
+
+/// Forget the profiled mutators that took part in the previous mutation, called before each mutation
+#[no_coverage]
+pub(crate) fn start_mutation() {
+    unsafe { CURRENT_MUTATIONS.clear() };
+}
+
+/// Count a corpus addition for the profiled mutators that took part in the mutation of the tested test case, if it
+/// was added to the corpus, and forget about them
+#[no_coverage]
+pub(crate) fn finish_mutation(added_to_corpus: bool) {
+    for counters in unsafe { CURRENT_MUTATIONS.drain(..) } {
+        if added_to_corpus {
+            counters.corpus_additions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Whether a [`ProfiledMutator`] was created, in which case the fuzzer reports [`ProfiledMutatorsStats`]
+#[no_coverage]
+pub(crate) fn has_profiled_mutators() -> bool {
+    unsafe { !PROFILED_MUTATORS.is_empty() }
+}
+
+/// The counters of all the profiled mutators at the time the statistics were taken
+#[derive(Clone)]
+pub struct ProfiledMutatorsStats {
+    /// The name, number of mutations, and number of corpus additions of each profiled mutator
+    pub mutators: Vec<(String, usize, usize)>,
+}
+
+impl ProfiledMutatorsStats {
+    #[no_coverage]
+    pub(crate) fn current() -> Self {
+        let mutators = unsafe { &PROFILED_MUTATORS }
+            .iter()
+            .map(
+                #[no_coverage]
+                |(name, counters)| {
+                    (
+                        name.clone(),
+                        counters.mutations.load(Ordering::Relaxed),
+                        counters.corpus_additions.load(Ordering::Relaxed),
+                    )
+                },
+            )
+            .collect();
+        Self { mutators }
+    }
+}
+
+impl Display for ProfiledMutatorsStats {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (name, mutations, corpus_additions)) in self.mutators.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}({}/{})", name, corpus_additions, mutations)?;
+        }
+        Ok(())
+    }
+}
+
+impl ToCSV for ProfiledMutatorsStats {
+    #[no_coverage]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        self.mutators
+            .iter()
+            .flat_map(
+                #[no_coverage]
+                |(name, _, _)| {
+                    [
+                        CSVField::String(format!("{}-mutations", name)),
+                        CSVField::String(format!("{}-corpus-additions", name)),
+                    ]
+                },
+            )
+            .collect()
+    }
+
+    #[no_coverage]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        self.mutators
+            .iter()
+            .flat_map(
+                #[no_coverage]
+                |(_, mutations, corpus_additions)| {
+                    [
+                        CSVField::Integer(*mutations as isize),
+                        CSVField::Integer(*corpus_additions as isize),
+                    ]
+                },
+            )
+            .collect()
+    }
+}
+
+impl Stats for ProfiledMutatorsStats {}
+
+/// Adds the [`ProfiledMutatorsStats`] to the statistics of the wrapped sensor and pool
+pub(crate) struct ProfiledSensorAndPool {
+    pub sap: Box<dyn SensorAndPool>,
+}
+impl SaveToStatsFolder for ProfiledSensorAndPool {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        self.sap.save_to_stats_folder()
+    }
+}
+impl SensorAndPool for ProfiledSensorAndPool {
+    #[no_coverage]
+    fn stats(&self) -> Box<dyn Stats> {
+        Box::new(AndPoolStats(self.sap.stats(), ProfiledMutatorsStats::current()))
+    }
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        self.sap.start_recording();
+    }
+    #[no_coverage]
+    fn stop_recording(&mut self) {
+        self.sap.stop_recording();
+    }
+    #[no_coverage]
+    fn process(&mut self, input_id: PoolStorageIndex, cplx: f64) -> Vec<CorpusDelta> {
+        self.sap.process(input_id, cplx)
+    }
+    #[no_coverage]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        self.sap.get_random_index()
+    }
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        self.sap.save_state()
+    }
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        self.sap.restore_state(state, new_index)
+    }
+}
+
+impl<T: Clone + 'static, M: Mutator<T>> Mutator<T> for ProfiledMutator<M> {
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.m.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.m.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.m.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.m.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.m.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.m.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.m.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.m.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let result = self.m.ordered_mutate(value, cache, step, max_cplx);
+        if result.is_some() {
+            self.record_mutation();
+        }
+        result
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        self.record_mutation();
+        self.m.random_mutate(value, cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.m.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        other: &T,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let result = self.m.crossover_mutate(value, cache, other, other_cache, max_cplx);
+        if result.is_some() {
+            self.record_mutation();
+        }
+        result
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = M::RecursingPartIndex;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, value: &T, cache: &Self::Cache) -> Self::RecursingPartIndex {
+        self.m.default_recursing_part_index(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(&self, parent: &N, value: &'a T, index: &mut Self::RecursingPartIndex) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        self.m.recursing_part::<V, N>(parent, value, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finish_mutation, start_mutation, ProfiledMutator, ProfiledMutatorsStats};
+    use crate::mutators::integer::U8Mutator;
+    use crate::mutators::vector::VecMutator;
+    use crate::{Mutator, ToCSV};
+
+    #[no_coverage]
+    fn counters(name: &str) -> (usize, usize) {
+        let stats = ProfiledMutatorsStats::current();
+        let (_, mutations, corpus_additions) = stats
+            .mutators
+            .into_iter()
+            .find(
+                #[no_coverage]
+                |(n, _, _)| n == name,
+            )
+            .unwrap();
+        (mutations, corpus_additions)
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_profiled_mutator_counts_mutations_and_corpus_additions() {
+        let m = ProfiledMutator::new(
+            "test-vector",
+            VecMutator::new(ProfiledMutator::new("test-elements", U8Mutator::default()), 1..=10),
+        );
+        let (mut x, _) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        assert_eq!(counters("test-vector"), (0, 0));
+
+        for i in 0..100 {
+            start_mutation();
+            let (token, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+            finish_mutation(i % 4 == 0);
+            m.unmutate(&mut x, &mut cache, token);
+        }
+        assert_eq!(counters("test-vector"), (100, 25));
+        let (element_mutations, element_corpus_additions) = counters("test-elements");
+        assert!(element_mutations <= 100 && element_corpus_additions <= element_mutations);
+
+        let stats = ProfiledMutatorsStats::current();
+        assert_eq!(stats.csv_headers().len(), 2 * stats.mutators.len());
+    }
+}
@@ -10,3 +10,24 @@ make_mutator! {
         Err(E)
     }
 }
+
+impl<T, E, MT, ME> ResultMutator<T, E, MT, ME>
+where
+    T: Clone + 'static,
+    E: Clone + 'static,
+    MT: fuzzcheck::Mutator<T>,
+    ME: fuzzcheck::Mutator<E>,
+{
+    /// Generate `Err` with probability `probability`, instead of choosing `Ok` and `Err` equally often.
+    ///
+    /// ## Panics
+    /// Panics if `probability` is not between 0 and 1.
+    #[no_coverage]
+    pub fn with_err_probability(self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "the probability of generating Err must be between 0 and 1"
+        );
+        self.variant_weights(&[1.0 - probability, probability])
+    }
+}
@@ -1,14 +1,503 @@
-use crate as fuzzcheck;
-use crate::{DefaultMutator, ExtendedMutator, MutatorValueConverter, MutatorWrapper};
+/*!
+A mutator for `String`s that works on their characters rather than on their bytes.
+
+[`StringMutator`] is the default mutator of `String`. Its values are always valid UTF-8, so no time is
+wasted on byte sequences that are rejected or lossily converted. It mutates a string by:
+* inserting, removing, or mutating a character, with the [`CharMutator`]
+* replacing a character by a confusable one, such as the Cyrillic `'а'` for the Latin `'a'`, or a
+  fullwidth form for an ASCII character
+* duplicating a substring
+* toggling the case of a character
+* replacing a substring by a substring of another string of the corpus, during a crossover
+
+The complexity of a string is measured in bytes, like the one of a `Vec<u8>`: it is `1 + 8 * string.len()`.
+The length range given to [`StringMutator::new`] is also measured in bytes.
+```
+use fuzzcheck::mutators::string::StringMutator;
+use fuzzcheck::DefaultMutator;
 
+let m = String::default_mutator();
+// strings of at most 64 bytes
+let m = StringMutator::new(0..=64);
+```
+*/
+
+use std::ops::{Range, RangeInclusive};
+
+use super::char::CharMutator;
 use super::enums::BasicEnumMutator;
-use super::unit::UnitMutator;
+use super::DefaultMutatorWithinRange;
+use crate as fuzzcheck;
+use crate::{DefaultMutator, ExtendedMutator, Mutator, MutatorValueConverter, MutatorWrapper};
 
-impl DefaultMutator for String {
-    type Mutator = UnitMutator<String>;
+/// Pairs of characters that look alike, used by [`StringMutator`] to replace a character by the other one.
+///
+/// The fullwidth forms of the ASCII characters are handled separately.
+const CONFUSABLES: &[(char, char)] = &[
+    ('a', 'а'),
+    ('c', 'с'),
+    ('e', 'е'),
+    ('i', 'і'),
+    ('j', 'ј'),
+    ('o', 'о'),
+    ('p', 'р'),
+    ('s', 'ѕ'),
+    ('x', 'х'),
+    ('y', 'у'),
+    ('A', 'А'),
+    ('B', 'В'),
+    ('E', 'Е'),
+    ('H', 'Н'),
+    ('K', 'К'),
+    ('M', 'М'),
+    ('O', 'О'),
+    ('P', 'Р'),
+    ('T', 'Т'),
+    ('X', 'Х'),
+    ('0', 'O'),
+    ('1', 'l'),
+    ('l', 'I'),
+    (' ', '\u{a0}'),
+    ('-', '\u{2010}'),
+    ('-', '\u{2212}'),
+    ('\'', '\u{2019}'),
+    ('"', '\u{201c}'),
+    ('.', '\u{2024}'),
+    ('/', '\u{2215}'),
+];
+
+#[no_coverage]
+fn confusables(c: char) -> Vec<char> {
+    let mut result = vec![];
+    for &(a, b) in CONFUSABLES {
+        if a == c {
+            result.push(b);
+        } else if b == c {
+            result.push(a);
+        }
+    }
+    // fullwidth forms of the printable ASCII characters
+    if ('!'..='~').contains(&c) {
+        result.extend(char::from_u32(c as u32 - 0x21 + 0xff01));
+    } else if ('\u{ff01}'..='\u{ff5e}').contains(&c) {
+        result.extend(char::from_u32(c as u32 - 0xff01 + 0x21));
+    }
+    result
+}
+
+#[no_coverage]
+fn toggled_case(c: char) -> Option<String> {
+    let toggled: String = if c.is_lowercase() {
+        c.to_uppercase().collect()
+    } else if c.is_uppercase() {
+        c.to_lowercase().collect()
+    } else {
+        return None;
+    };
+    let mut buf = [0; 4];
+    if toggled == *c.encode_utf8(&mut buf) {
+        None
+    } else {
+        Some(toggled)
+    }
+}
+
+/// The byte indices of the character boundaries of the string, including its end
+#[no_coverage]
+fn char_boundaries(value: &str) -> Vec<usize> {
+    value
+        .char_indices()
+        .map(
+            #[no_coverage]
+            |(idx, _)| idx,
+        )
+        .chain(std::iter::once(value.len()))
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+enum StringMutation {
+    InsertChar,
+    RemoveChar,
+    MutateChar,
+    ReplaceWithConfusable,
+    DuplicateSubstring,
+    ToggleCase,
+}
+
+const MUTATIONS: [StringMutation; 6] = [
+    StringMutation::InsertChar,
+    StringMutation::RemoveChar,
+    StringMutation::MutateChar,
+    StringMutation::ReplaceWithConfusable,
+    StringMutation::DuplicateSubstring,
+    StringMutation::ToggleCase,
+];
+
+/// A mutator for `String`s, which inserts, removes, and replaces characters.
+///
+/// See the [module documentation](self) for the list of its mutations.
+pub struct StringMutator {
+    char_mutator: CharMutator,
+    len_range: RangeInclusive<usize>,
+    rng: fastrand::Rng,
+}
+
+impl StringMutator {
+    /// Create a mutator for the strings whose length, in bytes, is within `len_range`
+    #[no_coverage]
+    pub fn new(len_range: RangeInclusive<usize>) -> Self {
+        Self {
+            char_mutator: CharMutator::default(),
+            len_range,
+            rng: fastrand::Rng::default(),
+        }
+    }
+
+    /// Forbid the mutator from producing strings shorter than `min_len` bytes.
+    ///
+    /// Values of the input corpus that are shorter than `min_len` are rejected by
+    /// [`validate_value`](Mutator::validate_value), and the first value generated by
+    /// [`ordered_arbitrary`](Mutator::ordered_arbitrary) is a string of exactly `min_len` bytes.
+    ///
+    /// Note that the [minimum complexity](Mutator::min_complexity) of the mutator increases accordingly.
+    /// If it becomes greater than the fuzzer’s `max_input_cplx`, then no value can be generated at all.
+    ///
+    /// ## Panics
+    /// If `min_len` is greater than the maximum length of the string.
+    #[no_coverage]
+    pub fn with_min_len(self, min_len: usize) -> Self {
+        let max_len = *self.len_range.end();
+        assert!(
+            min_len <= max_len,
+            "the minimum length ({}) of a StringMutator cannot be greater than its maximum length ({})",
+            min_len,
+            max_len
+        );
+        Self {
+            len_range: min_len..=max_len,
+            ..self
+        }
+    }
+
+    #[no_coverage]
+    fn complexity_of_len(&self, len: usize) -> f64 {
+        1.0 + 8.0 * len as f64
+    }
+
+    /// A random character of the string, with its byte index
+    #[no_coverage]
+    fn random_char(&self, value: &str) -> Option<(usize, char)> {
+        let nbr_chars = value.chars().count();
+        if nbr_chars == 0 {
+            return None;
+        }
+        value.char_indices().nth(self.rng.usize(..nbr_chars))
+    }
 
+    /// A random character boundary of the string, which may be its end
+    #[no_coverage]
+    fn random_boundary(&self, value: &str) -> usize {
+        match self.random_char(value) {
+            Some((idx, _)) if self.rng.usize(..=value.chars().count()) != 0 => idx,
+            _ => value.len(),
+        }
+    }
+
+    /// The range of bytes to replace and the replacement text that perform the given mutation, if it applies
+    /// to the string
+    #[no_coverage]
+    fn splice(&self, value: &str, mutation: StringMutation) -> Option<(Range<usize>, String)> {
+        match mutation {
+            StringMutation::InsertChar => {
+                let (c, _) = self.char_mutator.random_arbitrary(f64::INFINITY);
+                let idx = self.random_boundary(value);
+                Some((idx..idx, c.to_string()))
+            }
+            StringMutation::RemoveChar => {
+                let (idx, c) = self.random_char(value)?;
+                Some((idx..idx + c.len_utf8(), String::new()))
+            }
+            StringMutation::MutateChar => {
+                let (idx, c) = self.random_char(value)?;
+                let mut new_c = c;
+                self.char_mutator.random_mutate(&mut new_c, &mut (), f64::INFINITY);
+                Some((idx..idx + c.len_utf8(), new_c.to_string()))
+            }
+            StringMutation::ReplaceWithConfusable => {
+                let candidates = value
+                    .char_indices()
+                    .map(
+                        #[no_coverage]
+                        |(idx, c)| (idx, c, confusables(c)),
+                    )
+                    .filter(
+                        #[no_coverage]
+                        |(_, _, confusables)| !confusables.is_empty(),
+                    )
+                    .collect::<Vec<_>>();
+                if candidates.is_empty() {
+                    return None;
+                }
+                let (idx, c, confusables) = &candidates[self.rng.usize(..candidates.len())];
+                let new_c = confusables[self.rng.usize(..confusables.len())];
+                Some((*idx..*idx + c.len_utf8(), new_c.to_string()))
+            }
+            StringMutation::DuplicateSubstring => {
+                let boundaries = char_boundaries(value);
+                let nbr_chars = boundaries.len() - 1;
+                if nbr_chars == 0 {
+                    return None;
+                }
+                let start = self.rng.usize(..nbr_chars);
+                let end = self.rng.usize(start + 1..=nbr_chars);
+                let substring = value[boundaries[start]..boundaries[end]].to_owned();
+                // the copy is inserted right after the substring
+                Some((boundaries[end]..boundaries[end], substring))
+            }
+            StringMutation::ToggleCase => {
+                let candidates = value
+                    .char_indices()
+                    .filter_map(
+                        #[no_coverage]
+                        |(idx, c)| Some((idx, c, toggled_case(c)?)),
+                    )
+                    .collect::<Vec<_>>();
+                if candidates.is_empty() {
+                    return None;
+                }
+                let (idx, c, toggled) = &candidates[self.rng.usize(..candidates.len())];
+                Some((*idx..*idx + c.len_utf8(), toggled.clone()))
+            }
+        }
+    }
+
+    /// Perform the mutation if it applies to the string and if the mutated string is within the length range
+    /// and below the maximum complexity
+    #[no_coverage]
+    fn try_mutate(
+        &self,
+        value: &mut String,
+        mutation: StringMutation,
+        max_cplx: f64,
+    ) -> Option<(<Self as Mutator<String>>::UnmutateToken, f64)> {
+        let (range, replacement) = self.splice(value, mutation)?;
+        self.try_replace(value, range, replacement, max_cplx)
+    }
+
+    /// Replace the range of bytes by the replacement text if the new string is within the length range and
+    /// below the maximum complexity
+    #[no_coverage]
+    fn try_replace(
+        &self,
+        value: &mut String,
+        range: Range<usize>,
+        replacement: String,
+        max_cplx: f64,
+    ) -> Option<(<Self as Mutator<String>>::UnmutateToken, f64)> {
+        let new_len = value.len() - range.len() + replacement.len();
+        let cplx = self.complexity_of_len(new_len);
+        if !self.len_range.contains(&new_len) || cplx > max_cplx {
+            return None;
+        }
+        let old = value[range.clone()].to_owned();
+        value.replace_range(range.clone(), &replacement);
+        Some(((range.start..range.start + replacement.len(), old), cplx))
+    }
+}
+
+impl Default for StringMutator {
+    #[no_coverage]
+    fn default() -> Self {
+        Self::new(0..=usize::MAX)
+    }
+}
+
+/// The number of times each mutation is tried by [`StringMutator::ordered_mutate`] before giving up
+const MAX_ATTEMPTS_PER_MUTATION: u64 = 4;
+
+impl Mutator<String> for StringMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = u64;
+    #[doc(hidden)]
+    type ArbitraryStep = bool; // whether the shortest string was produced
+    #[doc(hidden)]
+    type UnmutateToken = (Range<usize>, String); // the range of the mutated bytes, and the old substring
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        false
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &String) -> Option<Self::Cache> {
+        if self.len_range.contains(&value.len()) {
+            Some(())
+        } else {
+            None
+        }
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, _value: &String, _cache: &Self::Cache) -> Self::MutationStep {
+        0
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.complexity_of_len(*self.len_range.end())
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.complexity_of_len(*self.len_range.start())
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, value: &String, _cache: &Self::Cache) -> f64 {
+        self.complexity_of_len(value.len())
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(String, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if !*step {
+            *step = true;
+            Some(self.random_arbitrary(self.min_complexity()))
+        } else {
+            Some(self.random_arbitrary(max_cplx))
+        }
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (String, f64) {
+        let min_len = *self.len_range.start();
+        let max_len_for_cplx = if max_cplx > 1.0 {
+            ((max_cplx - 1.0) / 8.0) as usize
+        } else {
+            0
+        };
+        let max_len = std::cmp::max(min_len, std::cmp::min(*self.len_range.end(), max_len_for_cplx));
+        let target_len = self.rng.usize(min_len..=max_len);
+        let mut value = String::with_capacity(target_len);
+        while value.len() < target_len {
+            let (mut c, _) = self.char_mutator.random_arbitrary(f64::INFINITY);
+            if value.len() + c.len_utf8() > target_len {
+                // a printable ASCII character always fits
+                c = self.rng.u8(0x20..0x7f) as char;
+            }
+            value.push(c);
+        }
+        let cplx = self.complexity_of_len(value.len());
+        (value, cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut String,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        // the mutations are tried in turn, at random positions, until none of them applies
+        for _ in 0..MAX_ATTEMPTS_PER_MUTATION * MUTATIONS.len() as u64 {
+            let mutation = MUTATIONS[(*step % MUTATIONS.len() as u64) as usize];
+            *step += 1;
+            if let Some(result) = self.try_mutate(value, mutation, max_cplx) {
+                return Some(result);
+            }
+        }
+        None
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut String, _cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        for _ in 0..MAX_ATTEMPTS_PER_MUTATION * MUTATIONS.len() as u64 {
+            let mutation = MUTATIONS[self.rng.usize(..MUTATIONS.len())];
+            if let Some(result) = self.try_mutate(value, mutation, max_cplx) {
+                return result;
+            }
+        }
+        // no mutation applies, so the whole string is replaced
+        let (new_value, cplx) = self.random_arbitrary(max_cplx);
+        let new_len = new_value.len();
+        let old = std::mem::replace(value, new_value);
+        ((0..new_len, old), cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut String,
+        _cache: &mut Self::Cache,
+        other: &String,
+        _other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        // a non-empty substring of `other` replaces a substring of `value`, both on character boundaries
+        let other_boundaries = char_boundaries(other);
+        let other_nbr_chars = other_boundaries.len() - 1;
+        if other_nbr_chars == 0 {
+            return None;
+        }
+        let other_start = self.rng.usize(..other_nbr_chars);
+        let other_end = self.rng.usize(other_start + 1..=other_nbr_chars);
+        let replacement = other[other_boundaries[other_start]..other_boundaries[other_end]].to_owned();
+
+        let boundaries = char_boundaries(value);
+        let start = self.rng.usize(..boundaries.len());
+        let end = self.rng.usize(start..boundaries.len());
+        self.try_replace(value, boundaries[start]..boundaries[end], replacement, max_cplx)
+    }
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut String, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        let (range, old) = t;
+        value.replace_range(range, &old);
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &String, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a String,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V> + 'static,
+    {
+        None
+    }
+}
+
+impl DefaultMutator for String {
+    type Mutator = StringMutator;
+    #[no_coverage]
     fn default_mutator() -> Self::Mutator {
-        UnitMutator::new(String::new())
+        StringMutator::default()
+    }
+}
+
+/// The range constrains the length of the string in bytes
+impl DefaultMutatorWithinRange for String {
+    type Bound = usize;
+    type Mutator = StringMutator;
+    #[no_coverage]
+    fn default_mutator_within_range(range: RangeInclusive<usize>) -> Self::Mutator {
+        StringMutator::new(range)
     }
 }
 
@@ -67,3 +556,62 @@ impl MutatorWrapper for ChainNameStringMutator {
         &self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::StringMutator;
+    use crate::Mutator;
+
+    #[test]
+    #[no_coverage]
+    fn test_crossover_inserts_substring_of_other() {
+        let m = StringMutator::new(0..=64);
+        let other = "αβγ-xyz".to_owned();
+        let other_cache = m.validate_value(&other).unwrap();
+        for _ in 0..1000 {
+            // at most 16 bytes, so that any substring of `other` fits in the length range
+            let (mut x, _) = m.random_arbitrary(129.0);
+            let mut cache = m.validate_value(&x).unwrap();
+            let original = x.clone();
+            let (token, cplx) = m
+                .crossover_mutate(&mut x, &mut cache, &other, &other_cache, 1000.0)
+                .unwrap();
+            let inserted = &x[token.0.clone()];
+            assert!(!inserted.is_empty());
+            assert!(other.contains(inserted));
+            assert_eq!(cplx, m.complexity(&x, &cache));
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, original);
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_crossover_with_empty_other() {
+        let m = StringMutator::new(0..=64);
+        let (mut x, _) = m.random_arbitrary(129.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        assert!(m
+            .crossover_mutate(&mut x, &mut cache, &String::new(), &(), 1000.0)
+            .is_none());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_with_min_len() {
+        let m = StringMutator::new(0..=64).with_min_len(16);
+        assert!(m.validate_value(&"too short".to_owned()).is_none());
+        assert!(m.validate_value(&"just long enough".to_owned()).is_some());
+        assert_eq!(m.min_complexity(), m.complexity(&"x".repeat(16), &()));
+        assert!(m.ordered_arbitrary(&mut m.default_arbitrary_step(), 100.0).is_none());
+
+        let mut step = m.default_arbitrary_step();
+        let (x, cplx) = m.ordered_arbitrary(&mut step, 1000.0).unwrap();
+        assert_eq!(x.len(), 16);
+        assert_eq!(cplx, m.min_complexity());
+        for _ in 0..100 {
+            let (x, _) = m.ordered_arbitrary(&mut step, 1000.0).unwrap();
+            assert!((16..=64).contains(&x.len()));
+        }
+    }
+}
@@ -0,0 +1,681 @@
+//! A mutator for trait objects, such as `Box<dyn Trait>`, that can hold values of several concrete types.
+//!
+//! [`DynMutator`] is built from a list of weighted mutators, one for each concrete type implementing
+//! the trait. Each of them is usually created by [`concrete_type_mutator`], which needs:
+//! * a mutator of the concrete type
+//! * a function to downcast the trait object to the concrete type, which fails if the trait object
+//!   holds a value of a different type
+//! * a function to convert a value of the concrete type into a trait object
+//!
+//! Since all mutators require their values to be `Clone`, the trait object type must implement
+//! `Clone` as well. For `Box<dyn Trait>`, this is typically done by giving `Trait` a method returning
+//! a boxed clone of `self`, which in turn requires each concrete type to implement `Clone`.
+//!
+//! ```
+//! use std::any::Any;
+//! use fuzzcheck::mutators::integer::U8Mutator;
+//! use fuzzcheck::mutators::map::MapMutator;
+//! use fuzzcheck::mutators::trait_object::{concrete_type_mutator, DynMutator};
+//! use fuzzcheck::Mutator;
+//!
+//! pub trait Command {
+//!     fn apply(&self, counter: &mut u64);
+//!     fn clone_box(&self) -> Box<dyn Command>;
+//!     fn as_any(&self) -> &dyn Any;
+//! }
+//! impl Clone for Box<dyn Command> {
+//!     fn clone(&self) -> Self {
+//!         self.clone_box()
+//!     }
+//! }
+//!
+//! #[derive(Clone)]
+//! struct Add(u8);
+//! impl Command for Add {
+//!     fn apply(&self, counter: &mut u64) {
+//!         *counter += self.0 as u64;
+//!     }
+//!     fn clone_box(&self) -> Box<dyn Command> {
+//!         Box::new(self.clone())
+//!     }
+//!     fn as_any(&self) -> &dyn Any {
+//!         self
+//!     }
+//! }
+//!
+//! #[derive(Clone)]
+//! struct Reset;
+//! impl Command for Reset {
+//!     fn apply(&self, counter: &mut u64) {
+//!         *counter = 0;
+//!     }
+//!     fn clone_box(&self) -> Box<dyn Command> {
+//!         Box::new(self.clone())
+//!     }
+//!     fn as_any(&self) -> &dyn Any {
+//!         self
+//!     }
+//! }
+//!
+//! let add_mutator = MapMutator::new(
+//!     U8Mutator::default(),
+//!     |add: &Add| Some(add.0),
+//!     |x: &u8| Add(*x),
+//!     |_, cplx| cplx,
+//! );
+//! let m = DynMutator::<Box<dyn Command>>::new(vec![
+//!     // `Add` commands are generated three times more often than `Reset` commands
+//!     (
+//!         3.0,
+//!         concrete_type_mutator(
+//!             add_mutator,
+//!             |c: &Box<dyn Command>| c.as_any().downcast_ref::<Add>(),
+//!             |add: Add| Box::new(add) as Box<dyn Command>,
+//!         ),
+//!     ),
+//!     (
+//!         1.0,
+//!         concrete_type_mutator(
+//!             fuzzcheck::mutators::unit::UnitMutator::new(Reset),
+//!             |c: &Box<dyn Command>| c.as_any().downcast_ref::<Reset>(),
+//!             |reset: Reset| Box::new(reset) as Box<dyn Command>,
+//!         ),
+//!     ),
+//! ]);
+//! let (command, _cplx) = m.random_arbitrary(10.0);
+//! let mut counter = 1;
+//! command.apply(&mut counter);
+//! ```
+//!
+//! Recursive trait objects, i.e. concrete types containing values of the trait object type, are not
+//! supported yet.
+
+use std::any::Any;
+use std::cmp::Ordering;
+
+use crate::mutators::map::MapMutator;
+use crate::mutators::vose_alias::VoseAlias;
+use crate::Mutator;
+
+/// A value whose concrete type is only known by the mutator that created it.
+#[doc(hidden)]
+pub struct ErasedValue(Box<dyn AnyClone>);
+
+trait AnyClone: Any {
+    fn clone_box(&self) -> Box<dyn AnyClone>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+impl<X> AnyClone for X
+where
+    X: Any + Clone,
+{
+    #[no_coverage]
+    fn clone_box(&self) -> Box<dyn AnyClone> {
+        Box::new(self.clone())
+    }
+    #[no_coverage]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[no_coverage]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Clone for ErasedValue {
+    #[no_coverage]
+    fn clone(&self) -> Self {
+        Self((*self.0).clone_box())
+    }
+}
+
+static ERASED_VALUE_TYPE_ERROR: &str = "a type-erased value was given to the wrong mutator";
+
+impl ErasedValue {
+    #[no_coverage]
+    fn new<X: Any + Clone>(value: X) -> Self {
+        Self(Box::new(value))
+    }
+    #[no_coverage]
+    fn get<X: Any>(&self) -> &X {
+        (*self.0).as_any().downcast_ref().expect(ERASED_VALUE_TYPE_ERROR)
+    }
+    #[no_coverage]
+    fn get_mut<X: Any>(&mut self) -> &mut X {
+        (*self.0).as_any_mut().downcast_mut().expect(ERASED_VALUE_TYPE_ERROR)
+    }
+}
+
+/// An object-safe version of [`Mutator`], whose associated types are erased.
+///
+/// It is implemented by every [`Mutator`], so that mutators of different types can be stored
+/// together in a [`DynMutator`].
+pub trait ErasedMutator<T: Clone>: 'static {
+    #[doc(hidden)]
+    fn erased_default_arbitrary_step(&self) -> ErasedValue;
+    #[doc(hidden)]
+    fn erased_validate_value(&self, value: &T) -> Option<ErasedValue>;
+    #[doc(hidden)]
+    fn erased_default_mutation_step(&self, value: &T, cache: &ErasedValue) -> ErasedValue;
+    #[doc(hidden)]
+    fn erased_max_complexity(&self) -> f64;
+    #[doc(hidden)]
+    fn erased_min_complexity(&self) -> f64;
+    #[doc(hidden)]
+    fn erased_complexity(&self, value: &T, cache: &ErasedValue) -> f64;
+    #[doc(hidden)]
+    fn erased_ordered_arbitrary(&self, step: &mut ErasedValue, max_cplx: f64) -> Option<(T, f64)>;
+    #[doc(hidden)]
+    fn erased_random_arbitrary(&self, max_cplx: f64) -> (T, f64);
+    #[doc(hidden)]
+    fn erased_ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut ErasedValue,
+        step: &mut ErasedValue,
+        max_cplx: f64,
+    ) -> Option<(Box<dyn Any>, f64)>;
+    #[doc(hidden)]
+    fn erased_random_mutate(&self, value: &mut T, cache: &mut ErasedValue, max_cplx: f64) -> (Box<dyn Any>, f64);
+    #[doc(hidden)]
+    fn erased_unmutate(&self, value: &mut T, cache: &mut ErasedValue, t: Box<dyn Any>);
+}
+
+impl<T, M> ErasedMutator<T> for M
+where
+    T: Clone,
+    M: Mutator<T>,
+    M::Cache: 'static,
+    M::MutationStep: 'static,
+    M::ArbitraryStep: 'static,
+    M::UnmutateToken: 'static,
+{
+    #[no_coverage]
+    fn erased_default_arbitrary_step(&self) -> ErasedValue {
+        ErasedValue::new(self.default_arbitrary_step())
+    }
+    #[no_coverage]
+    fn erased_validate_value(&self, value: &T) -> Option<ErasedValue> {
+        self.validate_value(value).map(ErasedValue::new)
+    }
+    #[no_coverage]
+    fn erased_default_mutation_step(&self, value: &T, cache: &ErasedValue) -> ErasedValue {
+        ErasedValue::new(self.default_mutation_step(value, cache.get::<M::Cache>()))
+    }
+    #[no_coverage]
+    fn erased_max_complexity(&self) -> f64 {
+        self.max_complexity()
+    }
+    #[no_coverage]
+    fn erased_min_complexity(&self) -> f64 {
+        self.min_complexity()
+    }
+    #[no_coverage]
+    fn erased_complexity(&self, value: &T, cache: &ErasedValue) -> f64 {
+        self.complexity(value, cache.get::<M::Cache>())
+    }
+    #[no_coverage]
+    fn erased_ordered_arbitrary(&self, step: &mut ErasedValue, max_cplx: f64) -> Option<(T, f64)> {
+        self.ordered_arbitrary(step.get_mut::<M::ArbitraryStep>(), max_cplx)
+    }
+    #[no_coverage]
+    fn erased_random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.random_arbitrary(max_cplx)
+    }
+    #[no_coverage]
+    fn erased_ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut ErasedValue,
+        step: &mut ErasedValue,
+        max_cplx: f64,
+    ) -> Option<(Box<dyn Any>, f64)> {
+        let (token, cplx) = self.ordered_mutate(
+            value,
+            cache.get_mut::<M::Cache>(),
+            step.get_mut::<M::MutationStep>(),
+            max_cplx,
+        )?;
+        Some((Box::new(token), cplx))
+    }
+    #[no_coverage]
+    fn erased_random_mutate(&self, value: &mut T, cache: &mut ErasedValue, max_cplx: f64) -> (Box<dyn Any>, f64) {
+        let (token, cplx) = self.random_mutate(value, cache.get_mut::<M::Cache>(), max_cplx);
+        (Box::new(token), cplx)
+    }
+    #[no_coverage]
+    fn erased_unmutate(&self, value: &mut T, cache: &mut ErasedValue, t: Box<dyn Any>) {
+        let t = *t.downcast::<M::UnmutateToken>().expect(ERASED_VALUE_TYPE_ERROR);
+        self.unmutate(value, cache.get_mut::<M::Cache>(), t)
+    }
+}
+
+/// Create the mutator of the trait objects holding a value of type `C`, from a mutator of `C`.
+///
+/// `downcast` must return `None` when the trait object holds a value of a different type.
+#[no_coverage]
+pub fn concrete_type_mutator<T, C, M>(
+    mutator: M,
+    downcast: fn(&T) -> Option<&C>,
+    upcast: fn(C) -> T,
+) -> Box<dyn ErasedMutator<T>>
+where
+    T: Clone + 'static,
+    C: Clone + 'static,
+    M: Mutator<C>,
+    M::Cache: 'static,
+    M::MutationStep: 'static,
+    M::ArbitraryStep: 'static,
+    M::UnmutateToken: 'static,
+{
+    Box::new(MapMutator::new(
+        mutator,
+        #[no_coverage]
+        move |value: &T| downcast(value).cloned(),
+        #[no_coverage]
+        move |value: &C| upcast(value.clone()),
+        #[no_coverage]
+        |_, cplx| cplx,
+    ))
+}
+
+/// A mutator for trait objects, which chooses between several concrete types.
+///
+/// See the [module documentation](self) for more details.
+pub struct DynMutator<T: Clone> {
+    mutators: Vec<Box<dyn ErasedMutator<T>>>,
+    weights: VoseAlias,
+    complexity_from_choice: f64,
+    max_complexity: f64,
+    min_complexity: f64,
+    rng: fastrand::Rng,
+}
+
+impl<T> DynMutator<T>
+where
+    T: Clone + 'static,
+{
+    /// Create a mutator choosing between the given mutators, each of them handling one concrete type.
+    ///
+    /// The weight of a mutator is proportional to the probability that it is used to generate or
+    /// replace a value. Weights must be positive, and at least one mutator must be given.
+    #[no_coverage]
+    pub fn new(mutators: Vec<(f64, Box<dyn ErasedMutator<T>>)>) -> Self {
+        assert!(!mutators.is_empty());
+        assert!(mutators.iter().all(
+            #[no_coverage]
+            |(weight, _)| *weight > 0.0
+        ));
+        let (weights, mutators): (Vec<_>, Vec<_>) = mutators.into_iter().unzip();
+        let complexity_from_choice = crate::mutators::size_to_cplxity(mutators.len());
+        let max_complexity = mutators
+            .iter()
+            .map(
+                #[no_coverage]
+                |m| m.erased_max_complexity(),
+            )
+            .max_by(
+                #[no_coverage]
+                |x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal),
+            )
+            .unwrap();
+        let min_complexity = mutators
+            .iter()
+            .map(
+                #[no_coverage]
+                |m| m.erased_min_complexity(),
+            )
+            .min_by(
+                #[no_coverage]
+                |x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal),
+            )
+            .unwrap();
+        Self {
+            mutators,
+            weights: VoseAlias::new(weights),
+            complexity_from_choice,
+            max_complexity,
+            min_complexity,
+            rng: fastrand::Rng::default(),
+        }
+    }
+
+    /// Generate a value of a different concrete type than the one at `excluded_idx`
+    #[no_coverage]
+    fn random_arbitrary_of_other_type(&self, excluded_idx: usize, max_cplx: f64) -> (T, Cache, f64) {
+        let mut idx = self.weights.sample();
+        if idx == excluded_idx {
+            idx = (idx + 1 + self.rng.usize(..self.mutators.len() - 1)) % self.mutators.len();
+        }
+        let mutator = &self.mutators[idx];
+        let (value, cplx) = mutator.erased_random_arbitrary(max_cplx - self.complexity_from_choice);
+        let inner = mutator
+            .erased_validate_value(&value)
+            .expect("a mutator generated a value that it cannot validate");
+        (
+            value,
+            Cache {
+                mutator_idx: idx,
+                inner,
+            },
+            cplx + self.complexity_from_choice,
+        )
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct Cache {
+    mutator_idx: usize,
+    inner: ErasedValue,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MutationStep {
+    mutator_idx: usize,
+    inner: ErasedValue,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ArbitraryStep {
+    inner: Vec<ErasedValue>,
+    indices: Vec<usize>,
+    idx: usize,
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<T> {
+    /// The value was replaced by a value of another concrete type
+    Replace(T, Cache),
+    /// The value was mutated by the mutator of its concrete type, at the given index
+    Inner(usize, Box<dyn Any>),
+}
+
+impl<T> Mutator<T> for DynMutator<T>
+where
+    T: Clone + 'static,
+{
+    #[doc(hidden)]
+    type Cache = Cache;
+    #[doc(hidden)]
+    type MutationStep = MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<T>;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        ArbitraryStep {
+            inner: self
+                .mutators
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |m| m.erased_default_arbitrary_step(),
+                )
+                .collect(),
+            indices: (0..self.mutators.len()).collect(),
+            idx: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutators.iter().enumerate().find_map(
+            #[no_coverage]
+            |(mutator_idx, m)| {
+                let inner = m.erased_validate_value(value)?;
+                Some(Cache { mutator_idx, inner })
+            },
+        )
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        MutationStep {
+            mutator_idx: cache.mutator_idx,
+            inner: self.mutators[cache.mutator_idx].erased_default_mutation_step(value, &cache.inner),
+        }
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.max_complexity + self.complexity_from_choice
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.min_complexity + self.complexity_from_choice
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutators[cache.mutator_idx].erased_complexity(value, &cache.inner) + self.complexity_from_choice
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        // the concrete types are used in turn, until all of their mutators are exhausted
+        while !step.indices.is_empty() {
+            let i = step.idx % step.indices.len();
+            let idx = step.indices[i];
+            let mutator = &self.mutators[idx];
+            if let Some((value, cplx)) =
+                mutator.erased_ordered_arbitrary(&mut step.inner[idx], max_cplx - self.complexity_from_choice)
+            {
+                step.idx += 1;
+                return Some((value, cplx + self.complexity_from_choice));
+            } else {
+                step.indices.remove(i);
+            }
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let mutator = &self.mutators[self.weights.sample()];
+        let (value, cplx) = mutator.erased_random_arbitrary(max_cplx - self.complexity_from_choice);
+        (value, cplx + self.complexity_from_choice)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if step.mutator_idx != cache.mutator_idx {
+            // the concrete type of the value was changed by a previous mutation that was not reverted
+            *step = self.default_mutation_step(value, cache);
+        }
+        if self.mutators.len() > 1 && self.rng.usize(..100) == 0 {
+            let (new_value, new_cache, cplx) = self.random_arbitrary_of_other_type(cache.mutator_idx, max_cplx);
+            let old_value = std::mem::replace(value, new_value);
+            let old_cache = std::mem::replace(cache, new_cache);
+            return Some((UnmutateToken::Replace(old_value, old_cache), cplx));
+        }
+        let idx = cache.mutator_idx;
+        let (token, cplx) = self.mutators[idx].erased_ordered_mutate(
+            value,
+            &mut cache.inner,
+            &mut step.inner,
+            max_cplx - self.complexity_from_choice,
+        )?;
+        Some((UnmutateToken::Inner(idx, token), cplx + self.complexity_from_choice))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let idx = cache.mutator_idx;
+        if self.mutators.len() > 1 && (self.rng.usize(..100) == 0 || self.mutators[idx].erased_max_complexity() < 0.1) {
+            let (new_value, new_cache, cplx) = self.random_arbitrary_of_other_type(idx, max_cplx);
+            let old_value = std::mem::replace(value, new_value);
+            let old_cache = std::mem::replace(cache, new_cache);
+            return (UnmutateToken::Replace(old_value, old_cache), cplx);
+        }
+        let (token, cplx) =
+            self.mutators[idx].erased_random_mutate(value, &mut cache.inner, max_cplx - self.complexity_from_choice);
+        (UnmutateToken::Inner(idx, token), cplx + self.complexity_from_choice)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Replace(old_value, old_cache) => {
+                *value = old_value;
+                *cache = old_cache;
+            }
+            UnmutateToken::Inner(idx, t) => {
+                assert_eq!(idx, cache.mutator_idx);
+                self.mutators[idx].erased_unmutate(value, &mut cache.inner, t);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = ();
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, _value: &T, _cache: &Self::Cache) -> Self::RecursingPartIndex {}
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(
+        &self,
+        _parent: &N,
+        _value: &'a T,
+        _index: &mut Self::RecursingPartIndex,
+    ) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{concrete_type_mutator, DynMutator};
+    use crate::mutators::integer::{U16Mutator, U32Mutator};
+    use crate::mutators::testing_utilities::test_mutator;
+    use crate::Mutator;
+
+    /// Stands for a trait object that can hold a value of two concrete types
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    enum Object {
+        Short(u16),
+        Long(u32),
+    }
+
+    #[no_coverage]
+    fn object_mutator() -> DynMutator<Object> {
+        DynMutator::new(vec![
+            (
+                1.0,
+                concrete_type_mutator(
+                    U16Mutator::default(),
+                    #[no_coverage]
+                    |o: &Object| match o {
+                        Object::Short(x) => Some(x),
+                        Object::Long(_) => None,
+                    },
+                    Object::Short,
+                ),
+            ),
+            (
+                1.0,
+                concrete_type_mutator(
+                    U32Mutator::default(),
+                    #[no_coverage]
+                    |o: &Object| match o {
+                        Object::Long(x) => Some(x),
+                        Object::Short(_) => None,
+                    },
+                    Object::Long,
+                ),
+            ),
+        ])
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_unmutate_after_concrete_type_switch() {
+        let m = object_mutator();
+        let mut value = Object::Short(12);
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        let cplx = m.complexity(&value, &cache);
+
+        let mut nbr_switches = 0;
+        for _ in 0..2000 {
+            let (token, mutated_cplx) = m.ordered_mutate(&mut value, &mut cache, &mut step, 100.).unwrap();
+            if matches!(value, Object::Long(_)) {
+                nbr_switches += 1;
+            }
+            let validated_cache = m.validate_value(&value).unwrap();
+            assert_eq!(cache.mutator_idx, validated_cache.mutator_idx);
+            assert_eq!(mutated_cplx, m.complexity(&value, &validated_cache));
+            assert_eq!(mutated_cplx, m.complexity(&value, &cache));
+
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, Object::Short(12));
+            assert_eq!(cache.mutator_idx, 0);
+            assert_eq!(m.complexity(&value, &cache), cplx);
+        }
+        assert!(nbr_switches > 0);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_mutate_after_concrete_type_switch() {
+        let m = object_mutator();
+        let mut value = Object::Short(12);
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+
+        // the mutations are not reverted, so the mutation step must follow the concrete type of the value
+        let mut nbr_switches = 0;
+        for _ in 0..2000 {
+            let previous = value.clone();
+            let (_, cplx) = m.ordered_mutate(&mut value, &mut cache, &mut step, 100.).unwrap();
+            if std::mem::discriminant(&previous) != std::mem::discriminant(&value) {
+                nbr_switches += 1;
+            }
+            let validated_cache = m.validate_value(&value).unwrap();
+            assert_eq!(cache.mutator_idx, validated_cache.mutator_idx);
+            assert_eq!(cplx, m.complexity(&value, &validated_cache));
+        }
+        assert!(nbr_switches > 0);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_dyn_mutator() {
+        test_mutator(object_mutator(), 100., 100., false, true, 100, 100);
+    }
+}
@@ -502,6 +502,11 @@ mod tuple1 {
         pub fn new(mutator_0: M0) -> Self {
             Self { mutator_0 }
         }
+        #[doc(hidden)]
+        #[no_coverage]
+        pub fn mutators(&self) -> (&M0,) {
+            (&self.mutator_0,)
+        }
     }
 
     impl<T, T0, M0> TupleMutator<T, Tuple1<T0>> for Tuple1Mutator<M0>
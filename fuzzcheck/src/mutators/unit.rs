@@ -25,6 +25,12 @@ where
     }
 }
 
+/// A mutator for types that have a single value, such as `()`, `PhantomData<T>`, or a unit struct.
+///
+/// It always produces the value given to [`UnitMutator::new`], with a complexity of `0.0`. Its
+/// [`ordered_arbitrary`](Mutator::ordered_arbitrary) yields that value once and then returns `None`,
+/// and it has no mutations. It is the default mutator of `()` and of the fieldless structs deriving
+/// `DefaultMutator`, so fields of these types add nothing to the complexity of a value.
 #[derive(Clone)]
 pub struct UnitMutator<T>
 where
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use super::map::MapMutator;
+use super::vector::VecMutator;
+use super::wrapper::Wrapper;
+use crate::DefaultMutator;
+
+/// The default mutator of `VecDeque<T>`.
+///
+/// It mutates a `Vec<T>` with a [`VecMutator`] and converts it to a `VecDeque<T>`.
+/// The complexity of a value is the complexity of the corresponding vector.
+pub type VecDequeMutator<T, M> = Wrapper<
+    MapMutator<
+        Vec<T>,
+        VecDeque<T>,
+        VecMutator<T, M>,
+        fn(&VecDeque<T>) -> Option<Vec<T>>,
+        fn(&Vec<T>) -> VecDeque<T>,
+        fn(&VecDeque<T>, f64) -> f64,
+    >,
+>;
+
+#[no_coverage]
+fn vec_from_vecdeque<T: Clone>(value: &VecDeque<T>) -> Option<Vec<T>> {
+    Some(value.iter().cloned().collect())
+}
+
+#[no_coverage]
+fn vecdeque_from_vec<T: Clone>(vec: &Vec<T>) -> VecDeque<T> {
+    vec.iter().cloned().collect()
+}
+
+#[no_coverage]
+fn complexity<T: Clone>(_t: &VecDeque<T>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl<T, M> VecDequeMutator<T, M>
+where
+    T: 'static + Clone,
+    M: crate::Mutator<T>,
+{
+    #[no_coverage]
+    pub fn new(mutator: M) -> Self {
+        Wrapper(MapMutator::new(
+            VecMutator::new(mutator, 0..=usize::MAX),
+            vec_from_vecdeque,
+            vecdeque_from_vec,
+            complexity,
+        ))
+    }
+}
+
+impl<T> DefaultMutator for VecDeque<T>
+where
+    T: 'static + Clone + DefaultMutator,
+{
+    type Mutator = VecDequeMutator<T, T::Mutator>;
+    #[no_coverage]
+    fn default_mutator() -> Self::Mutator {
+        Self::Mutator::new(T::default_mutator())
+    }
+}
@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Replace a slice of the vector with a slice of another vector of the fuzzer’s pool
+///
+/// Unlike the other mutations, it needs a second value. It is therefore only performed by
+/// [`crossover_mutate`](Mutator::crossover_mutate) and has no step: it is never chosen by
+/// `ordered_mutate` or `random_mutate`.
+pub struct Crossover;
+
+#[derive(Clone)]
+pub enum CrossoverStep {}
+
+pub struct ConcreteCrossover<T> {
+    /// the indices of the slice of the vector that is replaced
+    idcs: Range<usize>,
+    /// the elements of the other vector that replace it
+    elements: Vec<T>,
+    new_cplx: f64,
+}
+pub struct RevertCrossover<T> {
+    /// the indices of the elements that were inserted
+    idcs: Range<usize>,
+    /// the elements that were replaced
+    elements: Vec<T>,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertCrossover<T>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        let _ = value.splice(self.idcs, self.elements);
+    }
+}
+
+impl Crossover {
+    /// Choose a slice of `value` and a non-empty slice of `other` such that replacing the former with the latter
+    /// stays within the length range of the mutator and the maximum complexity.
+    #[no_coverage]
+    pub(crate) fn concrete<T, M>(
+        mutator: &VecMutator<T, M>,
+        value: &[T],
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        other: &[T],
+        other_cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        max_cplx: f64,
+    ) -> Option<ConcreteCrossover<T>>
+    where
+        T: Clone + 'static,
+        M: Mutator<T>,
+    {
+        if other.is_empty() {
+            return None;
+        }
+        let start = mutator.rng.usize(..=value.len());
+        let end = mutator.rng.usize(start..=value.len());
+        let other_start = mutator.rng.usize(..other.len());
+        let other_end = mutator.rng.usize(other_start + 1..=other.len());
+
+        let new_len = value.len() - (end - start) + (other_end - other_start);
+        if !mutator.len_range.contains(&new_len) {
+            return None;
+        }
+        let removed_cplx = (start..end).fold(
+            0.0,
+            #[no_coverage]
+            |cplx, i| cplx + mutator.m.complexity(&value[i], &cache.inner[i]),
+        );
+        let inserted_cplx = (other_start..other_end).fold(
+            0.0,
+            #[no_coverage]
+            |cplx, i| cplx + mutator.m.complexity(&other[i], &other_cache.inner[i]),
+        );
+        let new_cplx = mutator.complexity_from_inner(cache.sum_cplx - removed_cplx + inserted_cplx, new_len);
+        if new_cplx > max_cplx {
+            return None;
+        }
+        Some(ConcreteCrossover {
+            idcs: start..end,
+            elements: other[other_start..other_end].to_vec(),
+            new_cplx,
+        })
+    }
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for Crossover
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = CrossoverStep;
+    type Step = CrossoverStep;
+    type Concrete<'a> = ConcreteCrossover<T>;
+    type Revert = RevertCrossover<T>;
+
+    #[no_coverage]
+    fn default_random_step(&self, _mutator: &VecMutator<T, M>, _value: &Vec<T>) -> Option<Self::RandomStep> {
+        None
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        _mutator: &VecMutator<T, M>,
+        _value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        random_step: &Self::RandomStep,
+        _max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        match *random_step {}
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        _mutator: &VecMutator<T, M>,
+        _value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        None
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        _mutator: &VecMutator<T, M>,
+        _value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        _max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        match *step {}
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        let idcs = mutation.idcs.start..mutation.idcs.start + mutation.elements.len();
+        let elements = value.splice(mutation.idcs, mutation.elements).collect();
+        (RevertCrossover { idcs, elements }, mutation.new_cplx)
+    }
+}
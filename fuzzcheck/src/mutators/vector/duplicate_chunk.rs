@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+use super::insert_many_elements::insert_many;
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Insert a copy of a random chunk of the vector at a random position
+pub struct DuplicateChunk;
+
+#[derive(Clone)]
+pub struct DuplicateChunkStep;
+
+pub struct ConcreteDuplicateChunk {
+    chunk: Range<usize>,
+    idx: usize,
+    added_cplx: f64,
+}
+pub struct RevertDuplicateChunk {
+    idcs: Range<usize>,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertDuplicateChunk
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        let _ = value.drain(self.idcs);
+    }
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for DuplicateChunk
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = DuplicateChunkStep;
+    type Step = DuplicateChunkStep;
+    type Concrete<'a> = ConcreteDuplicateChunk;
+    type Revert = RevertDuplicateChunk;
+
+    #[no_coverage]
+    fn default_random_step(&self, mutator: &VecMutator<T, M>, value: &Vec<T>) -> Option<Self::RandomStep> {
+        if mutator.m.max_complexity() == 0. {
+            return None;
+        }
+        if value.is_empty() || value.len() >= *mutator.len_range.end() {
+            None
+        } else {
+            Some(DuplicateChunkStep)
+        }
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _random_step: &Self::RandomStep,
+        max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        let start = mutator.rng.usize(..value.len());
+        let max_chunk_len = std::cmp::min(value.len() - start, *mutator.len_range.end() - value.len());
+        let mut chunk_len = mutator.rng.usize(1..=max_chunk_len);
+        // make the chunk smaller until the new vector is not too complex
+        while chunk_len > 0 {
+            let chunk = start..start + chunk_len;
+            let added_cplx = value[chunk.clone()].iter().zip(cache.inner[chunk.clone()].iter()).fold(
+                0.0,
+                #[no_coverage]
+                |cplx, (el, el_cache)| cplx + mutator.m.complexity(el, el_cache),
+            );
+            if mutator.complexity_from_inner(cache.sum_cplx + added_cplx, value.len() + chunk_len) <= max_cplx {
+                return ConcreteDuplicateChunk {
+                    chunk,
+                    idx: mutator.rng.usize(..=value.len()),
+                    added_cplx,
+                };
+            }
+            chunk_len /= 2;
+        }
+        ConcreteDuplicateChunk {
+            chunk: 0..0,
+            idx: 0,
+            added_cplx: 0.,
+        }
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        self.default_random_step(mutator, value)
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        let concrete = Self::random(mutator, value, cache, step, max_cplx);
+        if concrete.chunk.is_empty() {
+            None
+        } else {
+            Some(concrete)
+        }
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        let els = value[mutation.chunk.clone()].to_vec();
+        let idcs = mutation.idx..mutation.idx + els.len();
+        insert_many(value, mutation.idx, els.into_iter());
+        let cplx = mutator.complexity_from_inner(cache.sum_cplx + mutation.added_cplx, value.len());
+        (RevertDuplicateChunk { idcs }, cplx)
+    }
+}
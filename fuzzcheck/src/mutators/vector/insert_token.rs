@@ -0,0 +1,157 @@
+use std::ops::Range;
+
+use super::insert_many_elements::insert_many;
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Insert one of the tokens given to [`VecMutator::with_dictionary`] at a random position of the vector
+pub struct InsertToken;
+
+#[derive(Clone)]
+pub struct InsertTokenRandomStep;
+
+#[derive(Clone)]
+pub struct InsertTokenStep {
+    /// the index of the next token of the dictionary to insert
+    token_idx: usize,
+}
+
+pub struct ConcreteInsertToken {
+    /// the index of the token in the dictionary, or `None` if no token can be inserted
+    token_idx: Option<usize>,
+    idx: usize,
+}
+pub struct RevertInsertToken {
+    idcs: Range<usize>,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertInsertToken
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        let _ = value.drain(self.idcs);
+    }
+}
+
+/// Whether the token can be inserted into the value without exceeding the maximum length and complexity
+#[no_coverage]
+fn can_insert_token<T, M>(
+    mutator: &VecMutator<T, M>,
+    value: &Vec<T>,
+    cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    token_idx: usize,
+    max_cplx: f64,
+) -> bool
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    let (token, token_cplx) = &mutator.dictionary[token_idx];
+    let new_len = value.len() + token.len();
+    new_len <= *mutator.len_range.end()
+        && mutator.complexity_from_inner(cache.sum_cplx + token_cplx, new_len) <= max_cplx
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for InsertToken
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = InsertTokenRandomStep;
+    type Step = InsertTokenStep;
+    type Concrete<'a> = ConcreteInsertToken;
+    type Revert = RevertInsertToken;
+
+    #[no_coverage]
+    fn default_random_step(&self, mutator: &VecMutator<T, M>, _value: &Vec<T>) -> Option<Self::RandomStep> {
+        if mutator.dictionary.is_empty() {
+            None
+        } else {
+            Some(InsertTokenRandomStep)
+        }
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _random_step: &Self::RandomStep,
+        max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        let token_idx = mutator.rng.usize(..mutator.dictionary.len());
+        let idx = mutator.rng.usize(..=value.len());
+        if can_insert_token(mutator, value, cache, token_idx, max_cplx) {
+            ConcreteInsertToken {
+                token_idx: Some(token_idx),
+                idx,
+            }
+        } else {
+            ConcreteInsertToken { token_idx: None, idx }
+        }
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        self.default_random_step(mutator, value).map(
+            #[no_coverage]
+            |_| InsertTokenStep { token_idx: 0 },
+        )
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        // each token is inserted once, at a random position
+        while step.token_idx < mutator.dictionary.len() {
+            let token_idx = step.token_idx;
+            step.token_idx += 1;
+            if can_insert_token(mutator, value, cache, token_idx, max_cplx) {
+                return Some(ConcreteInsertToken {
+                    token_idx: Some(token_idx),
+                    idx: mutator.rng.usize(..=value.len()),
+                });
+            }
+        }
+        None
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        if let Some(token_idx) = mutation.token_idx {
+            let (token, token_cplx) = &mutator.dictionary[token_idx];
+            let idcs = mutation.idx..mutation.idx + token.len();
+            insert_many(value, mutation.idx, token.iter().cloned());
+            let cplx = mutator.complexity_from_inner(cache.sum_cplx + token_cplx, value.len());
+            (RevertInsertToken { idcs }, cplx)
+        } else {
+            let cplx = mutator.complexity(value, cache);
+            (RevertInsertToken { idcs: 0..0 }, cplx)
+        }
+    }
+}
@@ -1,18 +1,28 @@
 use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::mutators::DefaultMutatorWithinRange;
 use crate::{DefaultMutator, Mutator};
 use std::cmp;
 use std::marker::PhantomData;
 use std::ops::RangeInclusive;
 
-use self::vec_mutation::{RevertVectorMutation, VectorMutation, VectorMutationRandomStep, VectorMutationStep};
+use self::vec_mutation::{
+    InnerVectorMutation, RevertVectorMutation, VectorMutation, VectorMutationRandomStep, VectorMutationStep,
+    WeightedMutation,
+};
 
 pub mod arbitrary;
+pub mod crossover;
+pub mod duplicate_chunk;
 pub mod insert_element;
 pub mod insert_many_elements;
+pub mod insert_token;
 pub mod mutate_element;
 pub mod only_choose_length;
 pub mod remove;
 pub mod remove_and_insert_element;
+pub mod reverse_range;
+pub mod rotate;
+pub mod swap_chunks;
 pub mod swap_elements;
 pub mod vec_mutation;
 
@@ -27,6 +37,19 @@ where
     }
 }
 
+/// The range constrains the length of the vector
+impl<T> DefaultMutatorWithinRange for Vec<T>
+where
+    T: DefaultMutator + 'static,
+{
+    type Bound = usize;
+    type Mutator = VecMutator<T, T::Mutator>;
+    #[no_coverage]
+    fn default_mutator_within_range(range: RangeInclusive<usize>) -> Self::Mutator {
+        VecMutator::new(T::default_mutator(), range)
+    }
+}
+
 #[derive(Clone)]
 pub enum VecArbitraryStep {
     InnerMutatorIsUnit { length_step: usize },
@@ -73,6 +96,10 @@ where
     len_range: RangeInclusive<usize>,
     rng: fastrand::Rng,
     mutations: VectorMutation,
+    /// The tokens given to [`with_dictionary`](VecMutator::with_dictionary), with the sum of the complexities of their elements
+    dictionary: Vec<(Vec<T>, f64)>,
+    /// The complexity added for each element of the vector, see [`with_length_complexity`](VecMutator::with_length_complexity)
+    len_cplx: f64,
     _phantom: PhantomData<T>,
 }
 
@@ -81,6 +108,10 @@ where
     T: Clone + 'static,
     M: Mutator<T>,
 {
+    /// Create a mutator for vectors whose elements are mutated by `m` and whose length is within `len_range`.
+    ///
+    /// The mutations never produce a vector whose length is outside `len_range`, and the values of the input
+    /// corpus whose length is outside `len_range` are rejected.
     #[no_coverage]
     pub fn new(m: M, len_range: RangeInclusive<usize>) -> Self {
         Self {
@@ -88,13 +119,115 @@ where
             len_range,
             rng: fastrand::Rng::new(),
             mutations: VectorMutation::default(),
+            dictionary: vec![],
+            len_cplx: 0.0,
             _phantom: PhantomData,
         }
     }
 
+    /// Forbid the mutator from producing vectors with fewer than `min_len` elements.
+    ///
+    /// Values of the input corpus that are shorter than `min_len` are rejected by
+    /// [`validate_value`](Mutator::validate_value), and the first value generated by
+    /// [`ordered_arbitrary`](Mutator::ordered_arbitrary) is a vector of exactly `min_len` elements.
+    ///
+    /// Note that the [minimum complexity](Mutator::min_complexity) of the mutator increases accordingly.
+    /// If it becomes greater than the fuzzer’s `max_input_cplx`, then no value can be generated at all.
+    ///
+    /// ## Panics
+    /// If `min_len` is greater than the maximum length of the vector.
+    #[no_coverage]
+    pub fn with_min_len(self, min_len: usize) -> Self {
+        let max_len = *self.len_range.end();
+        assert!(
+            min_len <= max_len,
+            "the minimum length ({}) of a VecMutator cannot be greater than its maximum length ({})",
+            min_len,
+            max_len
+        );
+        Self {
+            len_range: min_len..=max_len,
+            ..self
+        }
+    }
+
+    /// Add `cplx` to the complexity of the vector for each of its elements.
+    ///
+    /// By default, the complexity of a vector is the sum of the complexities of its elements, so a long vector of
+    /// simple elements is as complex as a short vector of complicated ones. Giving a positive `cplx` makes the
+    /// fuzzer prefer shorter vectors, which is useful when the length itself matters, such as the number of frames
+    /// of a protocol message.
+    ///
+    /// ## Panics
+    /// If `cplx` is negative or not finite.
+    #[no_coverage]
+    pub fn with_length_complexity(self, cplx: f64) -> Self {
+        assert!(
+            cplx.is_finite() && cplx >= 0.0,
+            "the complexity of the length of a VecMutator must be finite and non-negative, but it is {}",
+            cplx
+        );
+        Self { len_cplx: cplx, ..self }
+    }
+
+    /// Insert the given tokens into the generated vectors, such as the magic values and keywords of a file format.
+    ///
+    /// This is the equivalent of the dictionaries of libFuzzer and AFL. The mutator inserts each token once, at a
+    /// random position, during its ordered mutations, and then occasionally inserts a random token. The tokens whose
+    /// elements are rejected by the element mutator are ignored.
+    ///
+    /// The tokens of a dictionary file given to `cargo fuzzcheck --dictionary` are returned by
+    /// [`dictionary_from_cargo_fuzzcheck`](crate::builder::dictionary_from_cargo_fuzzcheck):
+    /// ```
+    /// use fuzzcheck::builder::dictionary_from_cargo_fuzzcheck;
+    /// use fuzzcheck::mutators::char::CharWithinRangeMutator;
+    /// use fuzzcheck::mutators::integer::U8Mutator;
+    /// use fuzzcheck::mutators::vector::VecMutator;
+    ///
+    /// let bytes = VecMutator::new(U8Mutator::default(), 0..=usize::MAX)
+    ///     .with_dictionary(vec![b"\x7fELF".to_vec(), b"Content-Length:".to_vec()])
+    ///     .with_dictionary(dictionary_from_cargo_fuzzcheck());
+    ///
+    /// // the characters of a string, which can be mapped to a `String` with a `MapMutator`
+    /// let chars = VecMutator::new(CharWithinRangeMutator::new(..), 0..=usize::MAX).with_dictionary(
+    ///     dictionary_from_cargo_fuzzcheck()
+    ///         .into_iter()
+    ///         .map(|token| String::from_utf8_lossy(&token).chars().collect()),
+    /// );
+    /// ```
+    #[no_coverage]
+    pub fn with_dictionary(self, tokens: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut x = self;
+        for token in tokens {
+            let cplx = token.iter().try_fold(
+                0.0,
+                #[no_coverage]
+                |sum_cplx, el| {
+                    let cache = x.m.validate_value(el)?;
+                    Some(sum_cplx + x.m.complexity(el, &cache))
+                },
+            );
+            if let Some(cplx) = cplx {
+                x.dictionary.push((token, cplx));
+            }
+        }
+        let has_insert_token = x.mutations.mutations.iter().any(
+            #[no_coverage]
+            |m| matches!(m.mutation, InnerVectorMutation::InsertToken(_)),
+        );
+        if !x.dictionary.is_empty() && !has_insert_token {
+            x.mutations.mutations.push(WeightedMutation {
+                mutation: InnerVectorMutation::InsertToken(insert_token::InsertToken),
+                random_weight: 20.,
+                ordered_weight: 100.,
+            });
+        }
+        x
+    }
+
     #[no_coverage]
     fn complexity_from_inner(&self, cplx: f64, len: usize) -> f64 {
-        1.0 + if cplx <= 0.0 { len as f64 } else { cplx }
+        1.0 + self.len_cplx * len as f64 + if cplx <= 0.0 { len as f64 } else { cplx }
     }
 }
 
@@ -132,6 +265,9 @@ where
     #[doc(hidden)]
     #[no_coverage]
     fn validate_value(&self, value: &Vec<T>) -> Option<Self::Cache> {
+        if !self.len_range.contains(&value.len()) {
+            return None;
+        }
         let inner_caches: Vec<_> = value
             .iter()
             .map(
@@ -218,7 +354,8 @@ where
                     if self.len_range.contains(&0) {
                         Some((<_>::default(), 1.0))
                     } else {
-                        Some(self.random_arbitrary(max_cplx))
+                        // start with the smallest allowed length
+                        Some(self.random_arbitrary(self.min_complexity()))
                     }
                 } else {
                     Some(self.random_arbitrary(max_cplx))
@@ -245,8 +382,12 @@ where
 
         let target_cplx = crate::mutators::gen_f64(&self.rng, min_cplx..max_cplx);
         let len_range = self.choose_slice_length(target_cplx);
-        let upperbound_max_len = std::cmp::min(*len_range.end(), (max_cplx / self.m.min_complexity()).ceil() as usize);
-        let target_len = self.rng.usize(0..=upperbound_max_len);
+        let upperbound_max_len = std::cmp::min(
+            *len_range.end(),
+            (max_cplx / (self.m.min_complexity() + self.len_cplx)).ceil() as usize,
+        );
+        let upperbound_max_len = std::cmp::max(*self.len_range.start(), upperbound_max_len);
+        let target_len = self.rng.usize(*self.len_range.start()..=upperbound_max_len);
 
         self.new_input_with_length_and_complexity(target_len, target_cplx)
     }
@@ -278,6 +419,21 @@ where
     fn unmutate(&self, value: &mut Vec<T>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
         RevertVectorMutation::revert(t, self, value, cache)
     }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut Vec<T>,
+        cache: &mut Self::Cache,
+        other: &Vec<T>,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let mutation = crossover::Crossover::concrete(self, value, cache, other, other_cache, max_cplx)?;
+        let (revert, cplx) = crossover::Crossover::apply(mutation, self, value, cache, max_cplx);
+        Some((RevertVectorMutation::Crossover(revert), cplx))
+    }
     #[doc(hidden)]
     type RecursingPartIndex = RecursingPartIndex<M::RecursingPartIndex>;
     #[doc(hidden)]
@@ -349,8 +505,8 @@ where
                 (target_cplx / cplx).trunc() as usize
             }
         }
-        let min_len = length_for_elements_of_cplx(target_cplx, self.m.max_complexity());
-        let max_len = length_for_elements_of_cplx(target_cplx, self.m.min_complexity());
+        let min_len = length_for_elements_of_cplx(target_cplx, self.m.max_complexity() + self.len_cplx);
+        let max_len = length_for_elements_of_cplx(target_cplx, self.m.min_complexity() + self.len_cplx);
 
         let min_len = clamp(&self.len_range, min_len);
         let max_len = clamp(&(min_len..=*self.len_range.end()), max_len);
@@ -362,7 +518,7 @@ where
         let mut v = Vec::with_capacity(target_len);
         let mut sum_cplx = 0.0;
 
-        let mut remaining_cplx = target_cplx;
+        let mut remaining_cplx = target_cplx - self.len_cplx * target_len as f64;
         for i in 0..target_len {
             let max_cplx_element = remaining_cplx / ((target_len - i) as f64);
             let min_cplx_el = self.m.min_complexity();
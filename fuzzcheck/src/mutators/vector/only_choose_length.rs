@@ -83,7 +83,11 @@ where
         _random_step: &Self::RandomStep,
         max_cplx: f64,
     ) -> Self::Concrete<'a> {
-        let upperbound = std::cmp::max(*mutator.len_range.start(), max_cplx as usize);
+        let upperbound = std::cmp::max(
+            *mutator.len_range.start(),
+            (max_cplx / (1.0 + mutator.len_cplx)) as usize,
+        );
+        let upperbound = std::cmp::min(upperbound, *mutator.len_range.end());
         ConcreteOnlyChooseLength {
             length: mutator.rng.usize(*mutator.len_range.start()..=upperbound),
         }
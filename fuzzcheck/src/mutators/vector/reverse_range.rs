@@ -0,0 +1,115 @@
+use std::ops::Range;
+
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Reverse the order of the elements within a subrange of the vector
+pub struct ReverseRange;
+
+/// The ordered mutations first reverse the whole vector and then as many random subranges as there are elements
+#[derive(Clone)]
+pub struct ReverseRangeStep {
+    count: usize,
+}
+pub struct ConcreteReverseRange {
+    range: Range<usize>,
+}
+pub struct RevertReverseRange {
+    range: Range<usize>,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertReverseRange
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        value[self.range].reverse();
+    }
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for ReverseRange
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = ();
+    type Step = ReverseRangeStep;
+    type Concrete<'a> = ConcreteReverseRange;
+    type Revert = RevertReverseRange;
+
+    #[no_coverage]
+    fn default_random_step(&self, mutator: &VecMutator<T, M>, value: &Vec<T>) -> Option<Self::RandomStep> {
+        if mutator.m.max_complexity() == 0. || value.len() <= 1 {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _random_step: &Self::RandomStep,
+        _max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        let start = mutator.rng.usize(..value.len() - 1);
+        let end = mutator.rng.usize(start + 2..=value.len());
+        ConcreteReverseRange { range: start..end }
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        self.default_random_step(mutator, value).map(
+            #[no_coverage]
+            |_| ReverseRangeStep { count: 0 },
+        )
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        if step.count > value.len() {
+            return None;
+        }
+        let x = if step.count == 0 {
+            ConcreteReverseRange { range: 0..value.len() }
+        } else {
+            Self::random(mutator, value, cache, &(), max_cplx)
+        };
+        step.count += 1;
+        Some(x)
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        let cplx = mutator.complexity(value, cache);
+        value[mutation.range.clone()].reverse();
+        (RevertReverseRange { range: mutation.range }, cplx)
+    }
+}
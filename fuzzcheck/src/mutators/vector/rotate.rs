@@ -0,0 +1,114 @@
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Rotate the elements of the vector to the left
+pub struct Rotate;
+
+#[derive(Clone)]
+pub struct RotateStep {
+    amount: usize,
+}
+pub struct ConcreteRotate {
+    amount: usize,
+}
+pub struct RevertRotate {
+    amount: usize,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertRotate
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        value.rotate_right(self.amount);
+    }
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for Rotate
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = ();
+    type Step = RotateStep;
+    type Concrete<'a> = ConcreteRotate;
+    type Revert = RevertRotate;
+
+    #[no_coverage]
+    fn default_random_step(&self, mutator: &VecMutator<T, M>, value: &Vec<T>) -> Option<Self::RandomStep> {
+        if mutator.m.max_complexity() == 0. || value.len() <= 1 {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _random_step: &Self::RandomStep,
+        _max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        ConcreteRotate {
+            amount: mutator.rng.usize(1..value.len()),
+        }
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        self.default_random_step(mutator, value).map(
+            #[no_coverage]
+            |_| RotateStep { amount: 1 },
+        )
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        _mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        _max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        if step.amount >= value.len() {
+            None
+        } else {
+            let x = ConcreteRotate { amount: step.amount };
+            step.amount += 1;
+            Some(x)
+        }
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        let cplx = mutator.complexity(value, cache);
+        value.rotate_left(mutation.amount);
+        (
+            RevertRotate {
+                amount: mutation.amount,
+            },
+            cplx,
+        )
+    }
+}
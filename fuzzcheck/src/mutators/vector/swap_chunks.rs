@@ -0,0 +1,144 @@
+use super::VecMutator;
+use crate::mutators::mutations::{Mutation, RevertMutation};
+use crate::Mutator;
+
+/// Swap two non-overlapping chunks of the vector, which may have different lengths
+pub struct SwapChunks;
+
+/// The ordered mutations swap as many random pairs of chunks as there are elements
+#[derive(Clone)]
+pub struct SwapChunksStep {
+    count: usize,
+}
+/// The chunks `start .. start + len_1` and `start + len_1 + middle_len .. start + len_1 + middle_len + len_2`
+pub struct ConcreteSwapChunks {
+    start: usize,
+    len_1: usize,
+    middle_len: usize,
+    len_2: usize,
+}
+pub struct RevertSwapChunks {
+    start: usize,
+    len_1: usize,
+    middle_len: usize,
+    len_2: usize,
+}
+
+impl<T, M> RevertMutation<Vec<T>, VecMutator<T, M>> for RevertSwapChunks
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[no_coverage]
+    fn revert(
+        self,
+        _mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        _cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) {
+        // chunk_2 + middle + chunk_1 -> middle + chunk_2 + chunk_1 -> chunk_1 + middle + chunk_2
+        let slice = &mut value[self.start..self.start + self.len_1 + self.middle_len + self.len_2];
+        slice[..self.len_2 + self.middle_len].rotate_left(self.len_2);
+        slice.rotate_right(self.len_1);
+    }
+}
+
+impl<T, M> Mutation<Vec<T>, VecMutator<T, M>> for SwapChunks
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type RandomStep = ();
+    type Step = SwapChunksStep;
+    type Concrete<'a> = ConcreteSwapChunks;
+    type Revert = RevertSwapChunks;
+
+    #[no_coverage]
+    fn default_random_step(&self, mutator: &VecMutator<T, M>, value: &Vec<T>) -> Option<Self::RandomStep> {
+        if mutator.m.max_complexity() == 0. || value.len() <= 1 {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    #[no_coverage]
+    fn random<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _random_step: &Self::RandomStep,
+        _max_cplx: f64,
+    ) -> Self::Concrete<'a> {
+        let len = value.len();
+        let start = mutator.rng.usize(..len - 1);
+        // leave at least one element for the second chunk
+        let len_1 = mutator.rng.usize(1..len - start);
+        let start_2 = mutator.rng.usize(start + len_1..len);
+        let len_2 = mutator.rng.usize(1..=len - start_2);
+        ConcreteSwapChunks {
+            start,
+            len_1,
+            middle_len: start_2 - start - len_1,
+            len_2,
+        }
+    }
+
+    #[no_coverage]
+    fn default_step(
+        &self,
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        _cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+    ) -> Option<Self::Step> {
+        self.default_random_step(mutator, value).map(
+            #[no_coverage]
+            |_| SwapChunksStep { count: 0 },
+        )
+    }
+
+    #[no_coverage]
+    fn from_step<'a>(
+        mutator: &VecMutator<T, M>,
+        value: &Vec<T>,
+        cache: &<VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        step: &'a mut Self::Step,
+        max_cplx: f64,
+    ) -> Option<Self::Concrete<'a>> {
+        if step.count >= value.len() {
+            return None;
+        }
+        step.count += 1;
+        Some(Self::random(mutator, value, cache, &(), max_cplx))
+    }
+
+    #[no_coverage]
+    fn apply<'a>(
+        mutation: Self::Concrete<'a>,
+        mutator: &VecMutator<T, M>,
+        value: &mut Vec<T>,
+        cache: &mut <VecMutator<T, M> as Mutator<Vec<T>>>::Cache,
+        _max_cplx: f64,
+    ) -> (Self::Revert, f64) {
+        let cplx = mutator.complexity(value, cache);
+        let ConcreteSwapChunks {
+            start,
+            len_1,
+            middle_len,
+            len_2,
+        } = mutation;
+        // chunk_1 + middle + chunk_2 -> middle + chunk_2 + chunk_1 -> chunk_2 + middle + chunk_1
+        let slice = &mut value[start..start + len_1 + middle_len + len_2];
+        slice.rotate_left(len_1);
+        slice[..middle_len + len_2].rotate_right(len_2);
+        (
+            RevertSwapChunks {
+                start,
+                len_1,
+                middle_len,
+                len_2,
+            },
+            cplx,
+        )
+    }
+}
@@ -1,10 +1,16 @@
 use super::arbitrary;
+use super::crossover;
+use super::duplicate_chunk;
 use super::insert_element;
 use super::insert_many_elements;
+use super::insert_token;
 use super::mutate_element;
 use super::only_choose_length;
 use super::remove;
 use super::remove_and_insert_element;
+use super::reverse_range;
+use super::rotate;
+use super::swap_chunks;
 use super::swap_elements;
 use super::VecMutator;
 use crate::mutators::mutations::{Mutation, RevertMutation};
@@ -12,9 +18,9 @@ use crate::mutators::vose_alias::VoseAlias;
 use crate::Mutator;
 
 pub struct WeightedMutation<M> {
-    mutation: M,
-    random_weight: f64,
-    ordered_weight: f64,
+    pub(super) mutation: M,
+    pub(super) random_weight: f64,
+    pub(super) ordered_weight: f64,
 }
 macro_rules! impl_vec_mutation {
     ($(($i:ident,$t:ty)),*) => {
@@ -22,7 +28,7 @@ macro_rules! impl_vec_mutation {
             $($i($t),)*
         }
         pub struct VectorMutation {
-            mutations: Vec<WeightedMutation<InnerVectorMutation>>,
+            pub(super) mutations: Vec<WeightedMutation<InnerVectorMutation>>,
         }
         pub enum VectorMutationInnerStep<T, M>
         where
@@ -311,9 +317,15 @@ impl_vec_mutation! {
     (InsertElement, insert_element::InsertElement),
     (SwapElements, swap_elements::SwapElements),
     (InsertManyElements, insert_many_elements::InsertManyElements),
+    (InsertToken, insert_token::InsertToken),
     (RemoveAndInsertElement, remove_and_insert_element::RemoveAndInsertElement),
     (OnlyChooseLength, only_choose_length::OnlyChooseLength),
-    (Arbitrary, arbitrary::Arbitrary)
+    (Arbitrary, arbitrary::Arbitrary),
+    (Crossover, crossover::Crossover),
+    (DuplicateChunk, duplicate_chunk::DuplicateChunk),
+    (Rotate, rotate::Rotate),
+    (ReverseRange, reverse_range::ReverseRange),
+    (SwapChunks, swap_chunks::SwapChunks)
 }
 
 // ====== Default Vector Mutations =====
@@ -361,6 +373,26 @@ impl Default for VectorMutation {
                     random_weight: 20.,
                     ordered_weight: 500.,
                 },
+                WeightedMutation {
+                    mutation: InnerVectorMutation::DuplicateChunk(duplicate_chunk::DuplicateChunk),
+                    random_weight: 10.,
+                    ordered_weight: 10.,
+                },
+                WeightedMutation {
+                    mutation: InnerVectorMutation::Rotate(rotate::Rotate),
+                    random_weight: 5.,
+                    ordered_weight: 20.,
+                },
+                WeightedMutation {
+                    mutation: InnerVectorMutation::ReverseRange(reverse_range::ReverseRange),
+                    random_weight: 5.,
+                    ordered_weight: 20.,
+                },
+                WeightedMutation {
+                    mutation: InnerVectorMutation::SwapChunks(swap_chunks::SwapChunks),
+                    random_weight: 5.,
+                    ordered_weight: 10.,
+                },
                 WeightedMutation {
                     mutation: InnerVectorMutation::InsertManyElements(insert_many_elements::InsertManyElements {
                         nbr_added_elements: 2,
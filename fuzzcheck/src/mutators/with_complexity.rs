@@ -0,0 +1,218 @@
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/** Wrap a mutator and override the complexity that it gives to the values it generates.
+
+The fuzzer prefers the test cases with a low complexity and stops exploring values that exceed the maximum
+complexity. But the default complexity of a value, such as `8.0` for any `u8` or a measure of the length for
+a vector, does not always reflect how expensive the value is for the tested function. `WithComplexity` lets you
+bias the exploration toward or away from some values without writing a new mutator. The generation and mutation
+of the values are delegated to the wrapped mutator.
+
+The complexity function receives the value and the complexity given to it by the wrapped mutator, and returns
+the new complexity:
+```
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::mutators::with_complexity::WithComplexity;
+
+// zero bytes are twice as expensive as the other bytes
+let m = WithComplexity::new(
+    VecMutator::new(U8Mutator::default(), 0..=10),
+    |v: &Vec<u8>, cplx: f64| cplx + 8.0 * v.iter().filter(|&&b| b == 0).count() as f64,
+)
+// the wrapped mutator gives a complexity between 1.0 and 81.0 to vectors of up to 10 bytes
+.with_complexity_bounds(1.0, 161.0);
+```
+
+The complexity function must follow a few rules for the fuzzer to work well:
+* it must be deterministic and only depend on the value and the complexity given by the wrapped mutator
+* it must return a value within the bounds given by [`min_complexity`](Mutator::min_complexity) and
+[`max_complexity`](Mutator::max_complexity). By default, these are the bounds of the wrapped mutator, so a
+function that can return larger or smaller complexities should be combined with
+[`with_complexity_bounds`](WithComplexity::with_complexity_bounds).
+* the maximum complexity given to the wrapped mutator is not adjusted, because the complexity function cannot
+be inverted. A function that returns much larger complexities than the default ones may therefore produce values
+that exceed the maximum complexity of the fuzz test, which the fuzzer will then discard.
+*/
+pub struct WithComplexity<T, M, F>
+where
+    T: Clone,
+    M: Mutator<T>,
+    F: Fn(&T, f64) -> f64,
+{
+    mutator: M,
+    complexity: F,
+    min_complexity: f64,
+    max_complexity: f64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M, F> WithComplexity<T, M, F>
+where
+    T: Clone,
+    M: Mutator<T>,
+    F: Fn(&T, f64) -> f64,
+{
+    /// Create a mutator that delegates to `mutator` but gives the complexity `complexity(value, default_cplx)`
+    /// to each value, where `default_cplx` is the complexity given by `mutator`.
+    ///
+    /// The minimum and maximum complexities are the ones of `mutator`.
+    #[no_coverage]
+    pub fn new(mutator: M, complexity: F) -> Self {
+        let min_complexity = mutator.min_complexity();
+        let max_complexity = mutator.max_complexity();
+        Self {
+            mutator,
+            complexity,
+            min_complexity,
+            max_complexity,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the minimum and maximum complexities that the complexity function can return.
+    #[no_coverage]
+    pub fn with_complexity_bounds(self, min_complexity: f64, max_complexity: f64) -> Self {
+        assert!(min_complexity <= max_complexity);
+        Self {
+            min_complexity,
+            max_complexity,
+            ..self
+        }
+    }
+}
+
+impl<T, M, F> Mutator<T> for WithComplexity<T, M, F>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    F: Fn(&T, f64) -> f64,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn max_complexity(&self) -> f64 {
+        self.max_complexity
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn min_complexity(&self) -> f64 {
+        self.min_complexity
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        (self.complexity)(value, self.mutator.complexity(value, cache))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let (value, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        let cplx = (self.complexity)(&value, cplx);
+        Some((value, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let (value, cplx) = self.mutator.random_arbitrary(max_cplx);
+        let cplx = (self.complexity)(&value, cplx);
+        (value, cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self.mutator.ordered_mutate(value, cache, step, max_cplx)?;
+        let cplx = (self.complexity)(value, cplx);
+        Some((token, cplx))
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let (token, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+        let cplx = (self.complexity)(value, cplx);
+        (token, cplx)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        other: &T,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self
+            .mutator
+            .crossover_mutate(value, cache, other, other_cache, max_cplx)?;
+        let cplx = (self.complexity)(value, cplx);
+        Some((token, cplx))
+    }
+
+    #[doc(hidden)]
+    type RecursingPartIndex = M::RecursingPartIndex;
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn default_recursing_part_index(&self, value: &T, cache: &Self::Cache) -> Self::RecursingPartIndex {
+        self.mutator.default_recursing_part_index(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[no_coverage]
+    fn recursing_part<'a, V, N>(&self, parent: &N, value: &'a T, index: &mut Self::RecursingPartIndex) -> Option<&'a V>
+    where
+        V: Clone + 'static,
+        N: Mutator<V>,
+    {
+        self.mutator.recursing_part::<V, N>(parent, value, index)
+    }
+}
@@ -0,0 +1,276 @@
+//! The state of the fuzzer that is saved to the file given by `--resume`, and from which a fuzz test can resume.
+//!
+//! The file starts with the magic bytes `fuzzcheck-state\n` followed by a version number. A file written by a
+//! different version of the format is rejected with an error instead of being misinterpreted. The version must
+//! be incremented whenever the layout below changes.
+//!
+//! After the version, all integers are little-endian `u64`, and all byte strings are preceded by their length:
+//! 1. the total number of runs of the test function
+//! 2. the time elapsed since the start of the fuzz test, in milliseconds
+//! 3. the fingerprint of the fuzz target, which is a hash of its executable and of the names of its pools
+//! 4. the seed of the random number generator of the fuzzer
+//! 5. the number of test cases, followed by the index in the pool storage, the number of pools containing it,
+//!    the generation, the number of mutations, and the serialized content of each test case
+//! 6. the number of files of the output corpus, followed by the folder, the index of the test case, and the hash
+//!    of each file
+//! 7. `1` followed by the state of the pools, or `0` if one of the pools cannot save its state
+//!
+//! The state of the pools is written by [`Pool::save_state`](crate::Pool::save_state), with the functions of this
+//! module. It refers to the test cases by their index in the pool storage. It contains the coverage reached by the
+//! fuzzer, in the form of the counters that each test case is the best one for, and the seeds of the random number
+//! generators of the pools.
+//!
+//! The random number generators of the mutators are not saved. The mutators are created again when the fuzz test
+//! resumes, so their random choices differ from those of a fuzz test that was not interrupted.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::PoolStorageIndex;
+
+const MAGIC: &[u8] = b"fuzzcheck-state\n";
+const VERSION: u64 = 4;
+
+/// The part of the fuzzer’s state that is needed to resume a fuzz test
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SavedState {
+    pub total_number_of_runs: usize,
+    pub elapsed_time: Duration,
+    /// A hash of the executable that saved the state and of the names of its pools. The state of the pools is only
+    /// valid for the same fuzz target, because it refers to the counters of its sensors.
+    pub fingerprint: String,
+    /// The seed of the random number generator of the fuzzer, given by [`fastrand::get_seed`]
+    pub seed: u64,
+    /// The test cases of the pool storage
+    pub inputs: Vec<SavedInput>,
+    /// The files of the output corpus, given by the folder of their pool, the index of their test case, and their
+    /// hash
+    pub corpus: Vec<(PathBuf, usize, String)>,
+    /// The state of the pools, if they can all save it
+    pub pools: Option<Vec<u8>>,
+}
+
+/// A test case of the pool storage
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SavedInput {
+    /// The index of the test case in the pool storage
+    pub key: usize,
+    /// The number of pools containing the test case
+    pub ref_count: usize,
+    pub generation: usize,
+    /// The number of times the test case was mutated, from which its mutation step is re-derived
+    pub nbr_mutations: usize,
+    /// The test case, encoded by the serializer
+    pub content: Vec<u8>,
+}
+
+impl SavedState {
+    #[no_coverage]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        write_u64(&mut bytes, VERSION);
+        write_u64(&mut bytes, self.total_number_of_runs as u64);
+        write_u64(&mut bytes, self.elapsed_time.as_millis() as u64);
+        write_bytes(&mut bytes, self.fingerprint.as_bytes());
+        write_u64(&mut bytes, self.seed);
+        write_u64(&mut bytes, self.inputs.len() as u64);
+        for input in self.inputs.iter() {
+            write_u64(&mut bytes, input.key as u64);
+            write_u64(&mut bytes, input.ref_count as u64);
+            write_u64(&mut bytes, input.generation as u64);
+            write_u64(&mut bytes, input.nbr_mutations as u64);
+            write_bytes(&mut bytes, &input.content);
+        }
+        write_u64(&mut bytes, self.corpus.len() as u64);
+        for (folder, key, hash) in self.corpus.iter() {
+            write_bytes(&mut bytes, folder.to_string_lossy().as_bytes());
+            write_u64(&mut bytes, *key as u64);
+            write_bytes(&mut bytes, hash.as_bytes());
+        }
+        if let Some(pools) = &self.pools {
+            write_u64(&mut bytes, 1);
+            write_bytes(&mut bytes, pools);
+        } else {
+            write_u64(&mut bytes, 0);
+        }
+        bytes
+    }
+
+    #[no_coverage]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader::new(bytes);
+        if reader.take(MAGIC.len()) != Some(MAGIC) {
+            return Err("it is not a fuzzcheck state file".to_owned());
+        }
+        let version = reader.u64()?;
+        if version != VERSION {
+            return Err(format!(
+                "it was written with version {} of the state file format, but only version {} is supported",
+                version, VERSION
+            ));
+        }
+        let total_number_of_runs = reader.usize()?;
+        let elapsed_time = Duration::from_millis(reader.u64()?);
+        let fingerprint = reader.string()?;
+        let seed = reader.u64()?;
+        let nbr_inputs = reader.u64()?;
+        let mut inputs = vec![];
+        for _ in 0..nbr_inputs {
+            inputs.push(SavedInput {
+                key: reader.usize()?,
+                ref_count: reader.usize()?,
+                generation: reader.usize()?,
+                nbr_mutations: reader.usize()?,
+                content: reader.bytes()?.to_vec(),
+            });
+        }
+        let nbr_files = reader.u64()?;
+        let mut corpus = vec![];
+        for _ in 0..nbr_files {
+            let folder = PathBuf::from(reader.string()?);
+            let key = reader.usize()?;
+            let hash = reader.string()?;
+            corpus.push((folder, key, hash));
+        }
+        let pools = match reader.u64()? {
+            0 => None,
+            1 => Some(reader.bytes()?.to_vec()),
+            _ => return Err("it is corrupted".to_owned()),
+        };
+        Ok(Self {
+            total_number_of_runs,
+            elapsed_time,
+            fingerprint,
+            seed,
+            inputs,
+            corpus,
+            pools,
+        })
+    }
+}
+
+#[no_coverage]
+pub(crate) fn write_u64(bytes: &mut Vec<u8>, x: u64) {
+    bytes.extend(x.to_le_bytes());
+}
+#[no_coverage]
+pub(crate) fn write_f64(bytes: &mut Vec<u8>, x: f64) {
+    write_u64(bytes, x.to_bits());
+}
+#[no_coverage]
+pub(crate) fn write_bytes(bytes: &mut Vec<u8>, x: &[u8]) {
+    write_u64(bytes, x.len() as u64);
+    bytes.extend(x);
+}
+#[no_coverage]
+pub(crate) fn write_index(bytes: &mut Vec<u8>, index: PoolStorageIndex) {
+    write_u64(bytes, index.0 as u64);
+}
+
+/// Read the values written by the functions of this module
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+impl<'a> Reader<'a> {
+    #[no_coverage]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+    #[no_coverage]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+    #[no_coverage]
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(taken)
+    }
+    #[no_coverage]
+    pub fn u64(&mut self) -> Result<u64, String> {
+        let bytes = self.take(8).ok_or_else(
+            #[no_coverage]
+            || "it is truncated".to_owned(),
+        )?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+    #[no_coverage]
+    pub fn usize(&mut self) -> Result<usize, String> {
+        Ok(self.u64()? as usize)
+    }
+    #[no_coverage]
+    pub fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_bits(self.u64()?))
+    }
+    #[no_coverage]
+    pub fn bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = self.usize()?;
+        self.take(len).ok_or_else(
+            #[no_coverage]
+            || "it is truncated".to_owned(),
+        )
+    }
+    #[no_coverage]
+    pub fn string(&mut self) -> Result<String, String> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(
+            #[no_coverage]
+            |_| "it contains invalid UTF-8".to_owned(),
+        )
+    }
+    /// Read an index written by [`write_index`] and give the index of the same test case in the new pool storage
+    #[no_coverage]
+    pub fn index(
+        &mut self,
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<PoolStorageIndex, String> {
+        let index = PoolStorageIndex(self.usize()?);
+        new_index(index).ok_or_else(
+            #[no_coverage]
+            || "it refers to a test case that is not in the pool storage".to_owned(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SavedInput, SavedState};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    #[no_coverage]
+    fn test_saved_state() {
+        let input = |key: usize, content: &[u8]| SavedInput {
+            key,
+            ref_count: 2,
+            generation: 7,
+            nbr_mutations: key * 10,
+            content: content.to_vec(),
+        };
+        let mut state = SavedState {
+            total_number_of_runs: 12345,
+            elapsed_time: Duration::from_millis(6789),
+            fingerprint: "0123456789abcdef".to_owned(),
+            seed: 0x0123_4567_89ab_cdef,
+            inputs: vec![input(0, b"abc"), input(3, b""), input(4, &[0, 1, 2, 3])],
+            corpus: vec![(PathBuf::from("test_failures/12/1.0000"), 3, "89ab".to_owned())],
+            pools: Some(vec![5, 6, 7]),
+        };
+        let bytes = state.to_bytes();
+        assert_eq!(SavedState::from_bytes(&bytes), Ok(state.clone()));
+
+        assert!(SavedState::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(SavedState::from_bytes(b"not a state file").is_err());
+
+        let mut other_version = bytes;
+        other_version[super::MAGIC.len()] = 3;
+        let error = SavedState::from_bytes(&other_version).unwrap_err();
+        assert!(error.contains("version 3"));
+
+        state.pools = None;
+        assert_eq!(SavedState::from_bytes(&state.to_bytes()), Ok(state));
+    }
+}
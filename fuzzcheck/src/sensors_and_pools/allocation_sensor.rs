@@ -0,0 +1,254 @@
+//! A global allocator counting the memory allocated by the program, and a sensor observing how much memory each run
+//! of the test function allocated.
+use crate::traits::{SaveToStatsFolder, Sensor};
+use std::alloc::{GlobalAlloc, Layout};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of bytes allocated since the last reset, regardless of whether they were deallocated
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// The number of bytes currently allocated
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// The highest value of `LIVE_BYTES` since the last reset
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// The value of `LIVE_BYTES` at the last reset
+static LIVE_BYTES_AT_RESET: AtomicUsize = AtomicUsize::new(0);
+
+/**
+A global allocator that wraps another one and counts the bytes allocated by the program.
+
+It must be installed by the fuzz target for the [`AllocationSensor`] and the `--malloc-limit` option to work:
+```
+use fuzzcheck::sensors_and_pools::CountingAllocator;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+# fn main() {}
+```
+The allocations of all the threads of the program are counted, so the test function should not run concurrently
+with other threads that allocate a lot of memory.
+*/
+pub struct CountingAllocator<A> {
+    allocator: A,
+}
+
+impl<A> CountingAllocator<A> {
+    #[no_coverage]
+    pub const fn new(allocator: A) -> Self {
+        Self { allocator }
+    }
+}
+
+#[no_coverage]
+fn record_allocation(size: usize) {
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_LIVE_BYTES.fetch_max(live_bytes, Ordering::Relaxed);
+}
+
+#[no_coverage]
+fn record_deallocation(size: usize) {
+    LIVE_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl<A> GlobalAlloc for CountingAllocator<A>
+where
+    A: GlobalAlloc,
+{
+    #[no_coverage]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.allocator.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    #[no_coverage]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.allocator.dealloc(ptr, layout);
+        record_deallocation(layout.size());
+    }
+
+    #[no_coverage]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.allocator.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    #[no_coverage]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.allocator.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_deallocation(layout.size());
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The memory allocated during a run of the test function
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Allocations {
+    /// The total number of bytes allocated, including the ones that were deallocated afterwards
+    pub total_bytes: usize,
+    /// The highest number of bytes that were allocated at the same time, not counting the ones that were already
+    /// allocated before the run
+    pub peak_live_bytes: usize,
+}
+
+/// Start counting the allocations of a new run of the test function
+#[no_coverage]
+pub(crate) fn reset_allocation_counters() {
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    LIVE_BYTES_AT_RESET.store(live_bytes, Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live_bytes, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+}
+
+/// The allocations made since the last call to [`reset_allocation_counters`]
+#[no_coverage]
+pub(crate) fn allocation_counters() -> Allocations {
+    Allocations {
+        total_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        peak_live_bytes: PEAK_LIVE_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(LIVE_BYTES_AT_RESET.load(Ordering::Relaxed)),
+    }
+}
+
+/// Overwrite the counters with the allocations made by a run of the test function in a child process,
+/// such that they are observed as if the test function had run in this process
+#[no_coverage]
+pub(crate) fn set_allocation_counters(allocations: Allocations) {
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    LIVE_BYTES_AT_RESET.store(live_bytes, Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live_bytes + allocations.peak_live_bytes, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(allocations.total_bytes, Ordering::Relaxed);
+}
+
+/// Whether the fuzz target installed a [`CountingAllocator`] as its global allocator
+#[no_coverage]
+pub(crate) fn counting_allocator_is_installed() -> bool {
+    // the fuzzer itself always holds some memory, which would have been counted
+    LIVE_BYTES.load(Ordering::Relaxed) > 0
+}
+
+/**
+A sensor observing the memory allocated by each run of the test function.
+
+It requires the fuzz target to install a [`CountingAllocator`] as its global allocator. Otherwise, all of its
+observations are zero.
+
+The observations are two counters, which is compatible with a
+[`MaximiseEachCounterPool`](crate::sensors_and_pools::MaximiseEachCounterPool):
+* the counter [`TOTAL_BYTES`](AllocationSensor::TOTAL_BYTES) is the total number of bytes allocated by the test
+  function, including the ones it deallocated before returning
+* the counter [`PEAK_LIVE_BYTES`](AllocationSensor::PEAK_LIVE_BYTES) is the highest number of bytes that the test
+  function held at the same time
+
+It is typically added to the other sensors with
+[`SensorAndPoolBuilder::find_test_cases_allocating_the_most_memory`](crate::builder::SensorAndPoolBuilder::find_test_cases_allocating_the_most_memory).
+*/
+#[derive(Default)]
+pub struct AllocationSensor {
+    allocations: Allocations,
+}
+
+impl AllocationSensor {
+    /// The index of the counter of the total number of bytes allocated
+    pub const TOTAL_BYTES: usize = 0;
+    /// The index of the counter of the peak number of bytes allocated at the same time
+    pub const PEAK_LIVE_BYTES: usize = 1;
+}
+
+impl Sensor for AllocationSensor {
+    type Observations = [(usize, u64); 2];
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        reset_allocation_counters();
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {
+        self.allocations = allocation_counters();
+    }
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        [
+            (Self::TOTAL_BYTES, self.allocations.total_bytes as u64),
+            (Self::PEAK_LIVE_BYTES, self.allocations.peak_live_bytes as u64),
+        ]
+    }
+}
+
+impl SaveToStatsFolder for AllocationSensor {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{allocation_counters, CountingAllocator};
+    use super::{record_allocation, record_deallocation, reset_allocation_counters, set_allocation_counters};
+    use super::{Allocations, LIVE_BYTES};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::Ordering;
+
+    // the counters are global and the tests run concurrently, so they are all checked in a single test,
+    // without installing the allocator
+    #[test]
+    #[no_coverage]
+    fn test_allocation_counters() {
+        record_allocation(1000);
+        reset_allocation_counters();
+        assert_eq!(allocation_counters(), Allocations::default());
+
+        record_allocation(100);
+        record_allocation(50);
+        record_deallocation(100);
+        record_allocation(20);
+        // memory allocated before the reset is freed during the run
+        record_deallocation(1000);
+        assert_eq!(
+            allocation_counters(),
+            Allocations {
+                total_bytes: 170,
+                peak_live_bytes: 150
+            }
+        );
+
+        let allocator = CountingAllocator::new(System);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        reset_allocation_counters();
+        unsafe {
+            let ptr = allocator.alloc(layout);
+            let ptr = allocator.realloc(ptr, layout, 256);
+            allocator.dealloc(ptr, Layout::from_size_align(256, 8).unwrap());
+        }
+        assert_eq!(
+            allocation_counters(),
+            Allocations {
+                total_bytes: 320,
+                peak_live_bytes: 256
+            }
+        );
+
+        let child_allocations = Allocations {
+            total_bytes: 5000,
+            peak_live_bytes: 4000,
+        };
+        set_allocation_counters(child_allocations);
+        assert_eq!(allocation_counters(), child_allocations);
+        record_deallocation(70);
+        assert_eq!(LIVE_BYTES.load(Ordering::Relaxed), 0);
+    }
+}
@@ -20,6 +20,7 @@
 //! After the test function is run, both sensors will collect data and feed them to their respective pool.
 use std::{fmt::Display, marker::PhantomData, path::PathBuf};
 
+use crate::saved_state::{write_bytes, write_u64, Reader};
 use crate::{
     traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder, Sensor, SensorAndPool, Stats},
     CSVField, PoolStorageIndex, ToCSV,
@@ -110,6 +111,35 @@ where
     fn weight(&self) -> f64 {
         self.p1_weight() + self.p2_weight()
     }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.p1_number_times_chosen_since_last_progress as u64);
+        write_u64(&mut state, self.p2_number_times_chosen_since_last_progress as u64);
+        write_bytes(&mut state, &self.p1.save_state()?);
+        write_bytes(&mut state, &self.p2.save_state()?);
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let p1_number_times_chosen = reader.usize()?;
+        let p2_number_times_chosen = reader.usize()?;
+        self.p1.restore_state(reader.bytes()?, new_index)?;
+        self.p2.restore_state(reader.bytes()?, new_index)?;
+        self.p1_number_times_chosen_since_last_progress = p1_number_times_chosen;
+        self.p2_number_times_chosen_since_last_progress = p2_number_times_chosen;
+        self.rng.seed(seed);
+        Ok(())
+    }
 }
 
 impl<P1, P2, SensorMarker> SaveToStatsFolder for AndPool<P1, P2, SensorMarker>
@@ -359,4 +389,33 @@ impl SensorAndPool for AndSensorAndPool {
             self.sap1.get_random_index()
         }
     }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.sap1_number_times_chosen_since_last_progress as u64);
+        write_u64(&mut state, self.sap2_number_times_chosen_since_last_progress as u64);
+        write_bytes(&mut state, &self.sap1.save_state()?);
+        write_bytes(&mut state, &self.sap2.save_state()?);
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let sap1_number_times_chosen = reader.usize()?;
+        let sap2_number_times_chosen = reader.usize()?;
+        self.sap1.restore_state(reader.bytes()?, new_index)?;
+        self.sap2.restore_state(reader.bytes()?, new_index)?;
+        self.sap1_number_times_chosen_since_last_progress = sap1_number_times_chosen;
+        self.sap2_number_times_chosen_since_last_progress = sap2_number_times_chosen;
+        self.rng.seed(seed);
+        Ok(())
+    }
 }
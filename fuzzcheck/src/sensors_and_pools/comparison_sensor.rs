@@ -0,0 +1,395 @@
+//! A sensor observing the distance between the operands of the comparisons made by the test function,
+//! and a pool keeping the test cases that minimise these distances.
+//!
+//! The comparisons are only instrumented when the fuzz test is compiled by `cargo fuzzcheck` with
+//! `--trace-compares`. Otherwise, the sensor never observes anything.
+use crate::saved_state::{write_bytes, write_u64, Reader};
+use crate::sensors_and_pools::{MaximiseEachCounterPool, RecencyBias};
+use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder, Sensor, Stats};
+use crate::{CSVField, PoolStorageIndex, ToCSV};
+use nu_ansi_term::Color;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// The number of slots of the table of distances. Each comparison is assigned to a slot by hashing its address.
+const NBR_SLOTS: usize = 1 << 16;
+
+/// For each slot, the lowest distance observed since the sensor started recording, plus one, or 0 if no
+/// comparison of that slot was made
+static mut DISTANCES: [u8; NBR_SLOTS] = [0; NBR_SLOTS];
+
+/// Whether the comparison hooks record the distances between their operands
+///
+/// It is also cleared while a hook is running, such that the comparisons made by the hook itself, if fuzzcheck is
+/// instrumented too, are not recorded and do not recurse into the hooks.
+static mut RECORDING: bool = false;
+
+extern "C" {
+    #[link_name = "llvm.returnaddress"]
+    fn return_address(level: i32) -> *const u8;
+}
+
+/// The distance between the two operands of a comparison, between 0 and [`ComparisonSensor::MAX_DISTANCE`]
+///
+/// It is the sum of their hamming distance and of the number of bits of their absolute difference, such that it
+/// decreases both when the operands share more bits and when they are numerically closer.
+#[no_coverage]
+#[inline(always)]
+fn distance(a: u64, b: u64) -> u8 {
+    let hamming = (a ^ b).count_ones();
+    let absolute = u64::BITS - a.abs_diff(b).leading_zeros();
+    (hamming + absolute) as u8
+}
+
+#[no_coverage]
+#[inline(always)]
+unsafe fn record(pc: usize, a: u64, b: u64) {
+    if !RECORDING {
+        return;
+    }
+    RECORDING = false;
+    let slot = (pc ^ (pc >> 16)) % NBR_SLOTS;
+    let d = distance(a, b) + 1;
+    let lowest = &mut DISTANCES[slot];
+    if *lowest == 0 || d < *lowest {
+        *lowest = d;
+    }
+    RECORDING = true;
+}
+
+// With the `libfuzzer` feature, the hooks are not exported, because libFuzzer defines its own
+macro_rules! trace_cmp_hooks {
+    ($($name:literal => $hook:ident($ty:ty)),* $(,)?) => {
+        $(
+            #[cfg_attr(not(feature = "libfuzzer"), export_name = $name)]
+            #[cfg_attr(feature = "libfuzzer", allow(dead_code))]
+            #[no_coverage]
+            extern "C" fn $hook(a: $ty, b: $ty) {
+                unsafe { record(return_address(0) as usize, a as u64, b as u64) }
+            }
+        )*
+    };
+}
+
+trace_cmp_hooks! {
+    "__sanitizer_cov_trace_cmp1" => trace_cmp1(u8),
+    "__sanitizer_cov_trace_cmp2" => trace_cmp2(u16),
+    "__sanitizer_cov_trace_cmp4" => trace_cmp4(u32),
+    "__sanitizer_cov_trace_cmp8" => trace_cmp8(u64),
+    "__sanitizer_cov_trace_const_cmp1" => trace_const_cmp1(u8),
+    "__sanitizer_cov_trace_const_cmp2" => trace_const_cmp2(u16),
+    "__sanitizer_cov_trace_const_cmp4" => trace_const_cmp4(u32),
+    "__sanitizer_cov_trace_const_cmp8" => trace_const_cmp8(u64),
+}
+
+/// `cases[0]` is the number of cases, `cases[1]` is the bit width of `value`, and the values of the cases follow.
+/// Each case is recorded as a separate comparison.
+#[cfg_attr(not(feature = "libfuzzer"), export_name = "__sanitizer_cov_trace_switch")]
+#[cfg_attr(feature = "libfuzzer", allow(dead_code))]
+#[no_coverage]
+extern "C" fn trace_switch(value: u64, cases: *const u64) {
+    unsafe {
+        let pc = return_address(0) as usize;
+        let nbr_cases = *cases as usize;
+        let cases = std::slice::from_raw_parts(cases.add(2), nbr_cases);
+        for (i, &case) in cases.iter().enumerate() {
+            record(pc.wrapping_add(i), value, case);
+        }
+    }
+}
+
+/// The boolean flags are only used to prevent the sanitizer coverage pass from inserting the `trace-pc-guard` callbacks
+#[cfg_attr(not(feature = "libfuzzer"), export_name = "__sanitizer_cov_bool_flag_init")]
+#[cfg_attr(feature = "libfuzzer", allow(dead_code))]
+#[no_coverage]
+extern "C" fn bool_flag_init(_start: *const bool, _stop: *const bool) {}
+
+/// A sensor observing, for each comparison made by the test function, the lowest distance between its operands.
+///
+/// The comparisons are instrumented only if the fuzz test is launched with `cargo fuzzcheck --trace-compares`.
+/// Its observations are a vector of `(slot, distance)`, sorted by slot, where `slot` identifies the comparison and
+/// `distance` is between 0, when the operands are equal, and [`ComparisonSensor::MAX_DISTANCE`]. Different
+/// comparisons may share the same slot.
+///
+/// It is meant to be used with a [`ComparisonDistancePool`], alongside the default sensor and pool, for example with
+/// [`find_test_cases_solving_comparisons`](crate::builder::SensorAndPoolBuilder::find_test_cases_solving_comparisons).
+/// The distances are stored in a global table, so a fuzz test should not use more than one `ComparisonSensor`.
+#[derive(Default)]
+pub struct ComparisonSensor {
+    _private: (),
+}
+
+impl ComparisonSensor {
+    /// The highest distance between the operands of a comparison
+    pub const MAX_DISTANCE: u64 = 2 * u64::BITS as u64;
+    /// The number of slots that the comparisons are assigned to
+    pub const NBR_SLOTS: usize = NBR_SLOTS;
+
+    #[no_coverage]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Sensor for ComparisonSensor {
+    type Observations = Vec<(usize, u64)>;
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        unsafe {
+            DISTANCES.fill(0);
+            RECORDING = true;
+        }
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {
+        unsafe {
+            RECORDING = false;
+        }
+    }
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        unsafe {
+            DISTANCES
+                .iter()
+                .enumerate()
+                .filter(
+                    #[no_coverage]
+                    |(_, &d)| d != 0,
+                )
+                .map(
+                    #[no_coverage]
+                    |(slot, &d)| (slot, d as u64 - 1),
+                )
+                .collect()
+        }
+    }
+}
+
+impl SaveToStatsFolder for ComparisonSensor {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+/// The statistics of a [ComparisonDistancePool]
+#[derive(Clone)]
+pub struct ComparisonDistancePoolStats {
+    name: String,
+    size: usize,
+    nbr_comparisons: usize,
+    nbr_solved: usize,
+}
+
+impl Display for ComparisonDistancePoolStats {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Color::LightCyan.paint(format!(
+                "{}({} solved: {}/{})",
+                self.name, self.size, self.nbr_solved, self.nbr_comparisons
+            ))
+        )
+    }
+}
+
+impl ToCSV for ComparisonDistancePoolStats {
+    #[no_coverage]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::String(format!("{}-count", self.name)),
+            CSVField::String(format!("{}-comparisons", self.name)),
+            CSVField::String(format!("{}-solved", self.name)),
+        ]
+    }
+    #[no_coverage]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::Integer(self.size as isize),
+            CSVField::Integer(self.nbr_comparisons as isize),
+            CSVField::Integer(self.nbr_solved as isize),
+        ]
+    }
+}
+impl Stats for ComparisonDistancePoolStats {}
+
+/// A pool that keeps, for each comparison, the simplest test case minimising the distance between its operands.
+///
+/// Mutating these test cases brings the operands ever closer, until they are equal, which is how the checks
+/// against magic numbers are solved. A comparison is counted as solved once its operands were equal.
+///
+/// It is [compatible with](crate::CompatibleWithObservations) the [`ComparisonSensor`], and any other sensor whose
+/// observations are given by an iterator of `(usize, u64)`, where the second element is a distance lower than
+/// or equal to [`ComparisonSensor::MAX_DISTANCE`].
+pub struct ComparisonDistancePool {
+    pool: MaximiseEachCounterPool,
+    lowest_distances: Vec<Option<u64>>,
+    stats: ComparisonDistancePoolStats,
+}
+
+impl ComparisonDistancePool {
+    #[no_coverage]
+    pub fn new(name: &str) -> Self {
+        Self {
+            pool: MaximiseEachCounterPool::new(name, NBR_SLOTS),
+            lowest_distances: vec![None; NBR_SLOTS],
+            stats: ComparisonDistancePoolStats {
+                name: name.to_string(),
+                size: 0,
+                nbr_comparisons: 0,
+                nbr_solved: 0,
+            },
+        }
+    }
+
+    /// Choose the recently added test cases more often than the others.
+    ///
+    /// By default, the pool has no recency bias. See [`RecencyBias`] for more details.
+    #[no_coverage]
+    pub fn with_recency_bias(self, recency_bias: RecencyBias) -> Self {
+        Self {
+            pool: self.pool.with_recency_bias(recency_bias),
+            ..self
+        }
+    }
+}
+
+impl Pool for ComparisonDistancePool {
+    type Stats = ComparisonDistancePoolStats;
+
+    #[no_coverage]
+    fn stats(&self) -> Self::Stats {
+        self.stats.clone()
+    }
+
+    #[no_coverage]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        self.pool.get_random_index()
+    }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_bytes(&mut state, &self.pool.save_state()?);
+        write_u64(&mut state, self.stats.nbr_comparisons as u64);
+        for (slot, lowest) in self.lowest_distances.iter().enumerate() {
+            if let Some(lowest) = lowest {
+                write_u64(&mut state, slot as u64);
+                write_u64(&mut state, *lowest);
+            }
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        self.pool.restore_state(reader.bytes()?, new_index)?;
+        let nbr_comparisons = reader.usize()?;
+        let mut lowest_distances = vec![None; NBR_SLOTS];
+        let mut nbr_solved = 0;
+        for _ in 0..nbr_comparisons {
+            let slot = reader.usize()?;
+            let lowest = reader.u64()?;
+            if slot >= NBR_SLOTS {
+                return Err("it is corrupted".to_owned());
+            }
+            if lowest == 0 {
+                nbr_solved += 1;
+            }
+            lowest_distances[slot] = Some(lowest);
+        }
+        self.lowest_distances = lowest_distances;
+        self.stats.size = self.pool.nbr_inputs();
+        self.stats.nbr_comparisons = nbr_comparisons;
+        self.stats.nbr_solved = nbr_solved;
+        Ok(())
+    }
+}
+
+impl SaveToStatsFolder for ComparisonDistancePool {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+impl<O> CompatibleWithObservations<O> for ComparisonDistancePool
+where
+    for<'a> &'a O: IntoIterator<Item = &'a (usize, u64)>,
+{
+    #[no_coverage]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &O, complexity: f64) -> Vec<CorpusDelta> {
+        // the inner pool maximises its counters, so the distances are turned into a closeness greater than 0
+        let closeness = observations
+            .into_iter()
+            .map(
+                #[no_coverage]
+                |&(slot, distance)| (slot, ComparisonSensor::MAX_DISTANCE + 1 - distance),
+            )
+            .collect::<Vec<_>>();
+        let deltas = <MaximiseEachCounterPool as CompatibleWithObservations<Vec<(usize, u64)>>>::process(
+            &mut self.pool,
+            input_id,
+            &closeness,
+            complexity,
+        );
+        if !deltas.is_empty() {
+            for &(slot, distance) in observations.into_iter() {
+                let lowest = &mut self.lowest_distances[slot];
+                match *lowest {
+                    Some(lowest) if lowest <= distance => continue,
+                    Some(_) => {}
+                    None => self.stats.nbr_comparisons += 1,
+                }
+                if distance == 0 {
+                    self.stats.nbr_solved += 1;
+                }
+                *lowest = Some(distance);
+            }
+            self.stats.size = self.pool.nbr_inputs();
+        }
+        deltas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distance, ComparisonDistancePool, ComparisonSensor};
+    use crate::traits::{CompatibleWithObservations, Pool};
+    use crate::PoolStorageIndex;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance(42, 42), 0);
+        assert!(distance(0x1234_5678, 0x1234_5679) < distance(0x1234_5678, 0x1234_0000));
+        assert!(distance(u64::MAX, 0) as u64 <= ComparisonSensor::MAX_DISTANCE);
+    }
+
+    #[test]
+    fn test_pool_keeps_closest_test_cases() {
+        let mut pool = ComparisonDistancePool::new("cmp");
+        assert!(!pool.process(PoolStorageIndex::mock(0), &vec![(3, 10)], 1.0).is_empty());
+        // farther from the target: not interesting
+        assert!(pool.process(PoolStorageIndex::mock(1), &vec![(3, 12)], 1.0).is_empty());
+        // closer: replaces the first test case
+        let deltas = pool.process(PoolStorageIndex::mock(2), &vec![(3, 0), (7, 5)], 1.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].remove, vec![PoolStorageIndex::mock(0)]);
+
+        let stats = pool.stats();
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.nbr_comparisons, 2);
+        assert_eq!(stats.nbr_solved, 1);
+        assert_eq!(pool.get_random_index(), Some(PoolStorageIndex::mock(2)));
+    }
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use crate::sensors_and_pools::WrapperSensor;
+use crate::traits::{SaveToStatsFolder, Sensor};
+
+/// Whether the last run of the test function returned `true`. It is set by the fuzzer before it stops recording.
+pub(crate) static mut TEST_RETURNED_TRUE: bool = false;
+
+/// A sensor that only gives the observations of the wrapped sensor when the test function returned `true`.
+///
+/// When the test function returns `false` or panics, the observations are replaced by their default
+/// value (e.g. an empty list of counters), so that the pools do not add the test case to the corpus.
+/// This is useful for property tests that fail easily, to prevent the corpus from growing around the
+/// code that is only reached by failing test cases.
+///
+/// The failing test cases are still saved as artifacts and added to the pool of test failures,
+/// regardless of this sensor, because the test failures are recorded by a separate sensor.
+///
+/// ```
+/// # #![feature(no_coverage)]
+/// use fuzzcheck::sensors_and_pools::{ArrayOfCounters, ConditionalSensor};
+/// # static mut COUNTERS: [u64; 2] = [0; 2];
+/// let sensor = ConditionalSensor::new(ArrayOfCounters::new(unsafe { &mut COUNTERS }));
+/// ```
+pub struct ConditionalSensor<S>
+where
+    S: Sensor,
+    S::Observations: Default,
+{
+    sensor: S,
+    test_returned_true: bool,
+}
+
+impl<S> ConditionalSensor<S>
+where
+    S: Sensor,
+    S::Observations: Default,
+{
+    #[no_coverage]
+    pub fn new(sensor: S) -> Self {
+        Self {
+            sensor,
+            test_returned_true: false,
+        }
+    }
+}
+
+impl<S> SaveToStatsFolder for ConditionalSensor<S>
+where
+    S: Sensor,
+    S::Observations: Default,
+{
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        self.sensor.save_to_stats_folder()
+    }
+}
+
+impl<S> Sensor for ConditionalSensor<S>
+where
+    S: Sensor,
+    S::Observations: Default,
+{
+    type Observations = S::Observations;
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        self.test_returned_true = false;
+        self.sensor.start_recording();
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {
+        self.sensor.stop_recording();
+        self.test_returned_true = unsafe { TEST_RETURNED_TRUE };
+    }
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        // the wrapped sensor is always asked for its observations, in case it resets its state when doing so
+        let observations = self.sensor.get_observations();
+        if self.test_returned_true {
+            observations
+        } else {
+            <_>::default()
+        }
+    }
+}
+
+impl<S> WrapperSensor for ConditionalSensor<S>
+where
+    S: Sensor,
+    S::Observations: Default,
+{
+    type Wrapped = S;
+    #[no_coverage]
+    fn wrapped(&self) -> &S {
+        &self.sensor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct OneObservation;
+    impl Sensor for OneObservation {
+        type Observations = Vec<(usize, u64)>;
+        #[no_coverage]
+        fn start_recording(&mut self) {}
+        #[no_coverage]
+        fn stop_recording(&mut self) {}
+        #[no_coverage]
+        fn get_observations(&mut self) -> Self::Observations {
+            vec![(0, 1)]
+        }
+    }
+    impl SaveToStatsFolder for OneObservation {
+        #[no_coverage]
+        fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+            vec![]
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_conditional_sensor() {
+        let mut sensor = ConditionalSensor::new(OneObservation);
+        for test_returned_true in [true, false, true] {
+            sensor.start_recording();
+            unsafe {
+                TEST_RETURNED_TRUE = test_returned_true;
+            }
+            sensor.stop_recording();
+            let expected = if test_returned_true { vec![(0, 1)] } else { vec![] };
+            assert_eq!(sensor.get_observations(), expected);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! A sensor measuring how long the test function takes to run, and a pool using it to mutate the fastest test
+//! cases of another pool more often.
+use crate::saved_state::{write_bytes, write_index, write_u64, Reader};
+use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder, Sensor, Stats};
+use crate::{CSVField, PoolStorageIndex, ToCSV};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// A sensor observing the wall-clock time taken by each run of the test function.
+///
+/// It is meant to be combined with another sensor in an [`AndSensor`](crate::sensors_and_pools::AndSensor), for
+/// a [`FastestToExecutePool`].
+#[derive(Default)]
+pub struct ExecutionTimeSensor {
+    start: Option<Instant>,
+    execution_time: Duration,
+}
+
+impl Sensor for ExecutionTimeSensor {
+    type Observations = Duration;
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        self.start = Some(Instant::now());
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {
+        if let Some(start) = self.start.take() {
+            self.execution_time = start.elapsed();
+        }
+    }
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        self.execution_time
+    }
+}
+
+impl SaveToStatsFolder for ExecutionTimeSensor {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+/**
+A pool that wraps another pool and chooses its fastest test cases more often.
+
+When the corpus accumulates test cases that are very slow to run, mutating them as often as the others makes the
+fuzzer much slower. So, to choose a test case, this pool asks the wrapped pool for a few candidates and returns the
+one that ran the fastest. Otherwise, the wrapped pool decides which test cases to keep, as usual.
+
+The observations of the pool are those of the wrapped pool paired with the execution time given by an
+[`ExecutionTimeSensor`]:
+```
+use fuzzcheck::sensors_and_pools::{AndSensor, ExecutionTimeSensor, FastestToExecutePool};
+# use fuzzcheck::sensors_and_pools::{NoopSensor, UniqueValuesPool};
+# let (sensor, pool) = (NoopSensor, UniqueValuesPool::<u8>::new("a", 0));
+let sensor = AndSensor(sensor, ExecutionTimeSensor::default());
+let pool = FastestToExecutePool::new(pool).with_nbr_candidates(3);
+```
+*/
+pub struct FastestToExecutePool<P> {
+    pool: P,
+    nbr_candidates: usize,
+    execution_times: HashMap<PoolStorageIndex, Duration>,
+}
+
+impl<P> FastestToExecutePool<P> {
+    /// Wrap the given pool. By default, the fastest of two candidates is chosen.
+    #[no_coverage]
+    pub fn new(pool: P) -> Self {
+        Self {
+            pool,
+            nbr_candidates: 2,
+            execution_times: HashMap::new(),
+        }
+    }
+
+    /// Choose the fastest of `nbr_candidates` test cases given by the wrapped pool. A higher number favours the
+    /// fast test cases more strongly.
+    #[no_coverage]
+    pub fn with_nbr_candidates(self, nbr_candidates: usize) -> Self {
+        assert!(
+            nbr_candidates > 0,
+            "the number of candidates of a FastestToExecutePool must be strictly positive"
+        );
+        Self { nbr_candidates, ..self }
+    }
+
+    #[no_coverage]
+    fn execution_time(&self, idx: PoolStorageIndex) -> Duration {
+        self.execution_times.get(&idx).copied().unwrap_or(Duration::MAX)
+    }
+}
+
+#[derive(Clone)]
+pub struct FastestToExecutePoolStats<S> {
+    stats: S,
+    average_execution_time: Duration,
+}
+impl<S> Display for FastestToExecutePoolStats<S>
+where
+    S: Display,
+{
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} exec({}µs)", self.stats, self.average_execution_time.as_micros())
+    }
+}
+impl<S> ToCSV for FastestToExecutePoolStats<S>
+where
+    S: ToCSV,
+{
+    #[no_coverage]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        let mut headers = self.stats.csv_headers();
+        headers.push(CSVField::String("average_execution_time_us".to_string()));
+        headers
+    }
+    #[no_coverage]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        let mut record = self.stats.to_csv_record();
+        record.push(CSVField::Integer(self.average_execution_time.as_micros() as isize));
+        record
+    }
+}
+impl<S> Stats for FastestToExecutePoolStats<S> where S: Stats {}
+
+impl<P> Pool for FastestToExecutePool<P>
+where
+    P: Pool,
+{
+    type Stats = FastestToExecutePoolStats<P::Stats>;
+
+    #[no_coverage]
+    fn stats(&self) -> Self::Stats {
+        let total = self.execution_times.values().sum::<Duration>();
+        FastestToExecutePoolStats {
+            stats: self.pool.stats(),
+            average_execution_time: total / std::cmp::max(self.execution_times.len(), 1) as u32,
+        }
+    }
+
+    #[no_coverage]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        let mut fastest = self.pool.get_random_index()?;
+        for _ in 1..self.nbr_candidates {
+            if let Some(candidate) = self.pool.get_random_index() {
+                if self.execution_time(candidate) < self.execution_time(fastest) {
+                    fastest = candidate;
+                }
+            }
+        }
+        Some(fastest)
+    }
+
+    #[no_coverage]
+    fn weight(&self) -> f64 {
+        self.pool.weight()
+    }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_bytes(&mut state, &self.pool.save_state()?);
+        write_u64(&mut state, self.execution_times.len() as u64);
+        for (idx, execution_time) in self.execution_times.iter() {
+            write_index(&mut state, *idx);
+            write_u64(&mut state, execution_time.as_nanos() as u64);
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        self.pool.restore_state(reader.bytes()?, new_index)?;
+        let mut execution_times = HashMap::new();
+        for _ in 0..reader.u64()? {
+            let idx = reader.index(new_index)?;
+            execution_times.insert(idx, Duration::from_nanos(reader.u64()?));
+        }
+        if !reader.is_empty() {
+            return Err("it is corrupted".to_owned());
+        }
+        self.execution_times = execution_times;
+        Ok(())
+    }
+}
+
+impl<P> SaveToStatsFolder for FastestToExecutePool<P>
+where
+    P: SaveToStatsFolder,
+{
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        self.pool.save_to_stats_folder()
+    }
+}
+
+impl<P, O> CompatibleWithObservations<(O, Duration)> for FastestToExecutePool<P>
+where
+    P: CompatibleWithObservations<O>,
+{
+    #[no_coverage]
+    fn process(
+        &mut self,
+        input_id: PoolStorageIndex,
+        observations: &(O, Duration),
+        complexity: f64,
+    ) -> Vec<CorpusDelta> {
+        let deltas = self.pool.process(input_id, &observations.0, complexity);
+        for delta in deltas.iter() {
+            for removed in delta.remove.iter() {
+                self.execution_times.remove(removed);
+            }
+        }
+        if deltas.iter().any(
+            #[no_coverage]
+            |delta| delta.add,
+        ) {
+            self.execution_times.insert(input_id, observations.1);
+        }
+        deltas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FastestToExecutePool;
+    use crate::sensors_and_pools::MaximiseEachCounterPool;
+    use crate::traits::{CompatibleWithObservations, Pool};
+    use crate::PoolStorageIndex;
+    use std::time::Duration;
+
+    #[test]
+    #[no_coverage]
+    fn test_fastest_to_execute_pool() {
+        let mut pool = FastestToExecutePool::new(MaximiseEachCounterPool::new("c", 2)).with_nbr_candidates(20);
+        let slow = PoolStorageIndex::mock(0);
+        let fast = PoolStorageIndex::mock(1);
+        assert_eq!(pool.get_random_index(), None);
+        assert!(!pool
+            .process(slow, &(vec![(0, 1)], Duration::from_secs(1)), 1.0)
+            .is_empty());
+        assert!(!pool
+            .process(fast, &(vec![(1, 1)], Duration::from_millis(1)), 1.0)
+            .is_empty());
+
+        let nbr_fast = (0..1000).filter(|_| pool.get_random_index() == Some(fast)).count();
+        assert!(nbr_fast > 990, "{}", nbr_fast);
+
+        // the slow test case is replaced by a faster one
+        let other = PoolStorageIndex::mock(2);
+        assert!(!pool
+            .process(other, &(vec![(0, 2)], Duration::from_millis(2)), 1.0)
+            .is_empty());
+        assert_eq!(pool.execution_times.len(), 2);
+        assert!(!pool.execution_times.contains_key(&slow));
+
+        let state = pool.save_state().unwrap();
+        let mut restored = FastestToExecutePool::new(MaximiseEachCounterPool::new("c", 2));
+        restored.restore_state(&state, &Some).unwrap();
+        assert_eq!(restored.execution_times, pool.execution_times);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::data_structures::{Slab, SlabKey};
 use crate::fenwick_tree::FenwickTree;
+use crate::saved_state::{write_f64, write_index, write_u64, Reader};
+use crate::sensors_and_pools::RecencyBias;
 use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
 use crate::PoolStorageIndex;
 use crate::{CSVField, CompatibleWithObservations, ToCSV};
@@ -52,6 +54,8 @@ struct Input {
     idx: PoolStorageIndex,
     score: f64,
     number_times_chosen: usize,
+    /// The value of the pool’s `nbr_added_inputs` when this input was added
+    added_at: usize,
 }
 
 /// A pool that tries to find test cases maximizing the value of each counter of a sensor.
@@ -67,6 +71,9 @@ pub struct MaximiseEachCounterPool {
     best_input_for_counter: Vec<Option<SlabKey<Input>>>,
     ranked_inputs: FenwickTree,
     stats: MaximiseEachCounterPoolStats,
+    /// The total number of inputs that were ever added to the pool
+    nbr_added_inputs: usize,
+    recency_bias: Option<RecencyBias>,
     rng: fastrand::Rng,
 }
 impl Debug for MaximiseEachCounterPool {
@@ -97,9 +104,39 @@ impl MaximiseEachCounterPool {
                 size: 0,
                 total_counts: 0,
             },
+            nbr_added_inputs: 0,
+            recency_bias: None,
             rng: fastrand::Rng::new(),
         }
     }
+
+    /// Choose the recently added test cases more often than the others.
+    ///
+    /// By default, the pool has no recency bias. See [`RecencyBias`] for more details.
+    #[no_coverage]
+    pub fn with_recency_bias(self, recency_bias: RecencyBias) -> Self {
+        Self {
+            recency_bias: Some(recency_bias),
+            ..self
+        }
+    }
+
+    /// The number of test cases in the pool
+    #[no_coverage]
+    pub(crate) fn nbr_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// The weight of the input in the `ranked_inputs` Fenwick tree
+    #[no_coverage]
+    fn rank(&self, input: &Input) -> f64 {
+        let rank = input.score / (input.number_times_chosen as f64);
+        if let Some(recency_bias) = &self.recency_bias {
+            rank * recency_bias.factor(self.nbr_added_inputs - input.added_at)
+        } else {
+            rank
+        }
+    }
 }
 
 impl Pool for MaximiseEachCounterPool {
@@ -116,14 +153,100 @@ impl Pool for MaximiseEachCounterPool {
 
         let key = self.inputs.get_nth_key(choice);
 
-        let input = &mut self.inputs[key];
-        let old_rank = input.score / (input.number_times_chosen as f64);
-        input.number_times_chosen += 1;
-        let new_rank = input.score / (input.number_times_chosen as f64);
+        let old_rank = self.rank(&self.inputs[key]);
+        self.inputs[key].number_times_chosen += 1;
+        let new_rank = self.rank(&self.inputs[key]);
 
         let delta = new_rank - old_rank;
         self.ranked_inputs.update(choice, delta);
-        Some(input.idx)
+        Some(self.inputs[key].idx)
+    }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.highest_counts.len() as u64);
+        write_u64(&mut state, self.nbr_added_inputs as u64);
+        write_u64(&mut state, self.inputs.len() as u64);
+        for key in self.inputs.keys() {
+            let input = &self.inputs[key];
+            write_index(&mut state, input.idx);
+            write_f64(&mut state, input.cplx);
+            write_u64(&mut state, input.number_times_chosen as u64);
+            write_u64(&mut state, input.added_at as u64);
+            write_u64(&mut state, input.best_for_counters.len() as u64);
+            for &counter in input.best_for_counters.iter() {
+                write_u64(&mut state, counter as u64);
+                write_u64(&mut state, self.highest_counts[counter]);
+            }
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let size = reader.usize()?;
+        if size != self.highest_counts.len() {
+            return Err(format!(
+                "the pool {} was saved with {} counters, but it now has {}",
+                self.name,
+                size,
+                self.highest_counts.len()
+            ));
+        }
+        let nbr_added_inputs = reader.usize()?;
+        let nbr_inputs = reader.usize()?;
+        let mut complexities = vec![0.0; size];
+        let mut highest_counts = vec![0; size];
+        let mut best_input_for_counter = vec![None; size];
+        let mut inputs = Slab::new();
+        for _ in 0..nbr_inputs {
+            let idx = reader.index(new_index)?;
+            let cplx = reader.f64()?;
+            let number_times_chosen = reader.usize()?.max(1);
+            let added_at = reader.usize()?;
+            if added_at >= nbr_added_inputs {
+                return Err("it is corrupted".to_owned());
+            }
+            let key = inputs.insert(Input {
+                best_for_counters: AHashSet::new(),
+                cplx,
+                idx,
+                score: 0.0,
+                number_times_chosen,
+                added_at,
+            });
+            for _ in 0..reader.usize()? {
+                let counter = reader.usize()?;
+                let count = reader.u64()?;
+                if counter >= size || best_input_for_counter[counter].is_some() {
+                    return Err("it is corrupted".to_owned());
+                }
+                complexities[counter] = cplx;
+                highest_counts[counter] = count;
+                best_input_for_counter[counter] = Some(key);
+                inputs[key].best_for_counters.insert(counter);
+            }
+            inputs[key].score = inputs[key].best_for_counters.len() as f64;
+        }
+        if !reader.is_empty() {
+            return Err("it is corrupted".to_owned());
+        }
+        self.complexities = complexities;
+        self.highest_counts = highest_counts;
+        self.best_input_for_counter = best_input_for_counter;
+        self.inputs = inputs;
+        self.nbr_added_inputs = nbr_added_inputs;
+        self.update_stats();
+        self.rng.seed(seed);
+        Ok(())
     }
 }
 
@@ -143,10 +266,7 @@ impl MaximiseEachCounterPool {
             .keys()
             .map(
                 #[no_coverage]
-                |key| {
-                    let input = &inputs[key];
-                    input.score / (input.number_times_chosen as f64)
-                },
+                |key| self.rank(&inputs[key]),
             )
             .collect();
         self.ranked_inputs = FenwickTree::new(ranked_inputs);
@@ -191,7 +311,9 @@ where
             idx: input_id,
             score: highest_for_counters.len() as f64,
             number_times_chosen: 1,
+            added_at: self.nbr_added_inputs,
         };
+        self.nbr_added_inputs += 1;
         let input_key = self.inputs.insert(input);
 
         let mut removed_keys = vec![];
@@ -239,6 +361,7 @@ mod tests {
     use std::collections::HashMap;
 
     use super::MaximiseEachCounterPool;
+    use crate::sensors_and_pools::RecencyBias;
     use crate::traits::CompatibleWithObservations;
     use crate::traits::Pool;
     use crate::PoolStorageIndex;
@@ -331,4 +454,19 @@ mod tests {
         }
         println!("{:?}", map);
     }
+
+    #[test]
+    fn test_recency_bias() {
+        let mut pool = MaximiseEachCounterPool::new("c", 5).with_recency_bias(RecencyBias::new(20.0, 1));
+        let _ = pool.process(PoolStorageIndex::mock(0), &[(1, 1)], 1.0);
+        let _ = pool.process(PoolStorageIndex::mock(1), &[(2, 1)], 1.0);
+
+        let mut map = HashMap::new();
+        for _ in 0..1000 {
+            let index = pool.get_random_index().unwrap();
+            *map.entry(index).or_insert(0) += 1;
+        }
+        // the most recent input is chosen more often, even though both have the same score
+        assert!(map[&PoolStorageIndex::mock(1)] > map[&PoolStorageIndex::mock(0)]);
+    }
 }
@@ -0,0 +1,221 @@
+use crate::saved_state::{write_f64, write_index, write_u64, Reader};
+use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder, Stats};
+use crate::{CSVField, PoolStorageIndex, ToCSV};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+
+struct Input {
+    input_id: PoolStorageIndex,
+    execution_time: Duration,
+    complexity: f64,
+}
+
+/**
+A pool that keeps the `size` test cases that took the longest to run, as observed by an
+[`ExecutionTimeSensor`](crate::sensors_and_pools::ExecutionTimeSensor).
+
+Mutating the slowest test cases steers the fuzzer towards test cases that are slower and slower to run, which
+reveals algorithmic complexity issues, such as an accidentally quadratic parser, that do not cause any crash.
+The test cases of the pool are saved in the folder of the output corpus named after the pool.
+
+It is typically added to the other pools with
+[`SensorAndPoolBuilder::find_slowest_test_cases`](crate::builder::SensorAndPoolBuilder::find_slowest_test_cases).
+*/
+pub struct MaximiseExecutionTimePool {
+    name: String,
+    size: usize,
+    /// The test cases, from the slowest to the fastest
+    inputs: Vec<Input>,
+    rng: fastrand::Rng,
+}
+
+impl MaximiseExecutionTimePool {
+    #[no_coverage]
+    pub fn new(name: &str, size: usize) -> Self {
+        assert!(
+            size > 0,
+            "the size of a MaximiseExecutionTimePool must be strictly positive"
+        );
+        Self {
+            name: name.to_string(),
+            size,
+            inputs: vec![],
+            rng: fastrand::Rng::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MaximiseExecutionTimePoolStats {
+    name: String,
+    slowest: Duration,
+}
+impl Display for MaximiseExecutionTimePoolStats {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({}ms)", self.name, self.slowest.as_millis())
+    }
+}
+impl ToCSV for MaximiseExecutionTimePoolStats {
+    #[no_coverage]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        vec![CSVField::String(format!("{}_ms", self.name))]
+    }
+    #[no_coverage]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        vec![CSVField::Integer(self.slowest.as_millis() as isize)]
+    }
+}
+impl Stats for MaximiseExecutionTimePoolStats {}
+
+impl Pool for MaximiseExecutionTimePool {
+    type Stats = MaximiseExecutionTimePoolStats;
+
+    #[no_coverage]
+    fn stats(&self) -> Self::Stats {
+        MaximiseExecutionTimePoolStats {
+            name: self.name.clone(),
+            slowest: self
+                .inputs
+                .first()
+                .map(
+                    #[no_coverage]
+                    |input| input.execution_time,
+                )
+                .unwrap_or_default(),
+        }
+    }
+
+    #[no_coverage]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        if self.inputs.is_empty() {
+            None
+        } else {
+            Some(self.inputs[self.rng.usize(..self.inputs.len())].input_id)
+        }
+    }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.inputs.len() as u64);
+        for input in self.inputs.iter() {
+            write_index(&mut state, input.input_id);
+            write_u64(&mut state, input.execution_time.as_nanos() as u64);
+            write_f64(&mut state, input.complexity);
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let mut inputs = vec![];
+        for _ in 0..reader.u64()? {
+            inputs.push(Input {
+                input_id: reader.index(new_index)?,
+                execution_time: Duration::from_nanos(reader.u64()?),
+                complexity: reader.f64()?,
+            });
+        }
+        if !reader.is_empty() || inputs.len() > self.size {
+            return Err("it is corrupted".to_owned());
+        }
+        self.inputs = inputs;
+        self.rng.seed(seed);
+        Ok(())
+    }
+}
+
+impl SaveToStatsFolder for MaximiseExecutionTimePool {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+impl CompatibleWithObservations<Duration> for MaximiseExecutionTimePool {
+    #[no_coverage]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &Duration, complexity: f64) -> Vec<CorpusDelta> {
+        let execution_time = *observations;
+        if self.inputs.len() == self.size
+            && !matches!(self.inputs.last(), Some(fastest) if execution_time > fastest.execution_time)
+        {
+            return vec![];
+        }
+        let position = self.inputs.partition_point(
+            #[no_coverage]
+            |input| input.execution_time >= execution_time,
+        );
+        self.inputs.insert(
+            position,
+            Input {
+                input_id,
+                execution_time,
+                complexity,
+            },
+        );
+        let remove = if self.inputs.len() > self.size {
+            self.inputs.pop().map(
+                #[no_coverage]
+                |input| input.input_id,
+            )
+        } else {
+            None
+        };
+        vec![CorpusDelta {
+            path: PathBuf::new().join(&self.name),
+            add: true,
+            remove: remove.into_iter().collect(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaximiseExecutionTimePool;
+    use crate::traits::{CompatibleWithObservations, Pool};
+    use crate::PoolStorageIndex;
+    use std::time::Duration;
+
+    #[test]
+    #[no_coverage]
+    fn test_maximise_execution_time_pool() {
+        let mut pool = MaximiseExecutionTimePool::new("slowest", 2);
+        assert_eq!(pool.get_random_index(), None);
+        let mut process = |idx: usize, millis: u64| {
+            let deltas = pool.process(PoolStorageIndex::mock(idx), &Duration::from_millis(millis), 1.0);
+            deltas.first().map(|delta| delta.remove.clone())
+        };
+        assert_eq!(process(0, 10), Some(vec![]));
+        assert_eq!(process(1, 30), Some(vec![]));
+        // the pool is full and the test case is faster than all of its test cases
+        assert_eq!(process(2, 5), None);
+        assert_eq!(process(3, 20), Some(vec![PoolStorageIndex::mock(0)]));
+        assert_eq!(process(4, 40), Some(vec![PoolStorageIndex::mock(3)]));
+
+        assert_eq!(pool.stats().slowest, Duration::from_millis(40));
+        for _ in 0..10 {
+            let idx = pool.get_random_index().unwrap();
+            assert!(idx == PoolStorageIndex::mock(1) || idx == PoolStorageIndex::mock(4));
+        }
+
+        let state = pool.save_state().unwrap();
+        let mut restored = MaximiseExecutionTimePool::new("slowest", 2);
+        restored.restore_state(&state, &Some).unwrap();
+        assert_eq!(restored.stats().slowest, Duration::from_millis(40));
+        // the restored pool makes the same random choices as the original one
+        for _ in 0..10 {
+            assert_eq!(restored.get_random_index(), pool.get_random_index());
+        }
+        let mut smaller = MaximiseExecutionTimePool::new("slowest", 1);
+        assert!(smaller.restore_state(&state, &Some).is_err());
+    }
+}
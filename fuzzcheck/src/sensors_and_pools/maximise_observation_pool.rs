@@ -1,9 +1,11 @@
+use crate::saved_state::{write_f64, write_index, write_u64, Reader};
 use crate::traits::Stats;
 use crate::CompatibleWithObservations;
 use crate::{
     traits::{CorpusDelta, Pool, SaveToStatsFolder},
     CSVField, PoolStorageIndex, ToCSV,
 };
+use std::any::Any;
 use std::fmt::Display;
 use std::{fmt::Debug, path::PathBuf};
 
@@ -86,6 +88,54 @@ where
             None
         }
     }
+
+    /// The state can only be saved if the observations are `u64` or `usize`
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        if let Some((best, input)) = &self.current_best {
+            let best = best as &dyn Any;
+            let best = if let Some(best) = best.downcast_ref::<u64>() {
+                *best
+            } else {
+                *best.downcast_ref::<usize>()? as u64
+            };
+            write_u64(&mut state, 1);
+            write_u64(&mut state, best);
+            write_index(&mut state, input.input_id);
+            write_f64(&mut state, input.complexity);
+        } else {
+            write_u64(&mut state, 0);
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        if reader.u64()? == 0 {
+            self.current_best = None;
+            return Ok(());
+        }
+        let saved_best = reader.u64()?;
+        let mut best = T::default();
+        let best_any = &mut best as &mut dyn Any;
+        if let Some(best) = best_any.downcast_mut::<u64>() {
+            *best = saved_best;
+        } else if let Some(best) = best_any.downcast_mut::<usize>() {
+            *best = saved_best as usize;
+        } else {
+            return Err(format!("the pool {} cannot restore its state", self.name));
+        }
+        let input_id = reader.index(new_index)?;
+        let complexity = reader.f64()?;
+        self.current_best = Some((best, Input { input_id, complexity }));
+        Ok(())
+    }
 }
 impl<T> SaveToStatsFolder for MaximiseObservationPool<T> {
     #[no_coverage]
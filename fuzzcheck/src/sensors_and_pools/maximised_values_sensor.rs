@@ -0,0 +1,193 @@
+//! The [`maximise!`](crate::maximise) macro, which lets the test function report values that the fuzzer should try
+//! to maximise, and the sensor observing them.
+use crate::traits::{SaveToStatsFolder, Sensor};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The maximum number of distinct calls to [`maximise!`](crate::maximise) in a fuzz target. The values given to the
+/// other calls are ignored.
+pub const MAX_MAXIMISED_VALUES: usize = 256;
+
+/// The location of each call to `maximise!` that was reached, with the highest value it was given during the
+/// current run of the test function
+static mut MAXIMISED_VALUES: Vec<(String, u64)> = Vec::new();
+
+/// The index of the given call to `maximise!` in `MAXIMISED_VALUES`, or `MAX_MAXIMISED_VALUES` if there are too many
+#[no_coverage]
+fn maximised_value_index(location: &str) -> usize {
+    let values = unsafe { &mut MAXIMISED_VALUES };
+    if let Some(index) = values.iter().position(
+        #[no_coverage]
+        |(l, _)| l == location,
+    ) {
+        index
+    } else if values.len() < MAX_MAXIMISED_VALUES {
+        values.push((location.to_owned(), 0));
+        values.len() - 1
+    } else {
+        MAX_MAXIMISED_VALUES
+    }
+}
+
+/// A call to [`maximise!`](crate::maximise), which caches its index in the list of maximised values.
+#[doc(hidden)]
+pub struct MaximisedValue {
+    location: &'static str,
+    index: AtomicUsize,
+}
+
+impl MaximisedValue {
+    #[no_coverage]
+    pub const fn new(location: &'static str) -> Self {
+        Self {
+            location,
+            index: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    #[no_coverage]
+    pub fn record(&self, value: u64) {
+        let mut index = self.index.load(Ordering::Relaxed);
+        if index == usize::MAX {
+            index = maximised_value_index(self.location);
+            self.index.store(index, Ordering::Relaxed);
+        }
+        if let Some((_, highest)) = unsafe { MAXIMISED_VALUES.get_mut(index) } {
+            *highest = std::cmp::max(*highest, value);
+        }
+    }
+}
+
+/// Forget the values given to `maximise!` during the previous run of the test function
+#[no_coverage]
+pub(crate) fn reset_maximised_values() {
+    for (_, value) in unsafe { MAXIMISED_VALUES.iter_mut() } {
+        *value = 0;
+    }
+}
+
+/// The non-zero values given to `maximise!` during the last run of the test function, with the location of the call
+#[no_coverage]
+pub(crate) fn maximised_values() -> Vec<(String, u64)> {
+    unsafe { MAXIMISED_VALUES.iter() }
+        .filter(
+            #[no_coverage]
+            |(_, value)| *value != 0,
+        )
+        .cloned()
+        .collect()
+}
+
+/// Record the values given to `maximise!` by a run of the test function in a child process, such that they are
+/// observed as if the test function had run in this process
+#[no_coverage]
+pub(crate) fn set_maximised_values(values: &[(String, u64)]) {
+    for (location, value) in values {
+        let index = maximised_value_index(location);
+        if let Some((_, highest)) = unsafe { MAXIMISED_VALUES.get_mut(index) } {
+            *highest = *value;
+        }
+    }
+}
+
+/**
+Ask the fuzzer to find test cases that make the given value as large as possible.
+
+The value is converted to a `u64` with `as`, so it can be any integer or floating point number. If the macro is
+called several times during a single run of the test function, the largest value is kept. Each call site of the
+macro is a different value to maximise.
+```
+fn test_function(xs: &[u8]) {
+    let nbr_zeros = xs.iter().filter(|&&x| x == 0).count();
+    // try to find test cases with more and more zeros
+    fuzzcheck::maximise!(nbr_zeros);
+}
+```
+The values are only observed if the sensor and pool of the fuzz test were augmented with
+[`SensorAndPoolBuilder::find_test_cases_maximising_values`](crate::builder::SensorAndPoolBuilder::find_test_cases_maximising_values).
+The macro must be called from the thread running the test function, and a fuzz target can contain at most
+[`MAX_MAXIMISED_VALUES`](crate::sensors_and_pools::MAX_MAXIMISED_VALUES) call sites.
+*/
+#[macro_export]
+macro_rules! maximise {
+    ($value:expr) => {{
+        static MAXIMISED_VALUE: $crate::sensors_and_pools::MaximisedValue =
+            $crate::sensors_and_pools::MaximisedValue::new(concat!(file!(), ":", line!(), ":", column!()));
+        MAXIMISED_VALUE.record(($value) as u64);
+    }};
+}
+
+/**
+A sensor observing the values given to the [`maximise!`](crate::maximise) macro by each run of the test function.
+
+Its observations are a list of `(index, value)` pairs, one for each call site of the macro that was given a non-zero
+value, which is compatible with a [`MaximiseEachCounterPool`](crate::sensors_and_pools::MaximiseEachCounterPool) of
+size [`MAX_MAXIMISED_VALUES`](crate::sensors_and_pools::MAX_MAXIMISED_VALUES).
+*/
+#[derive(Default)]
+pub struct MaximisedValuesSensor;
+
+impl Sensor for MaximisedValuesSensor {
+    type Observations = Vec<(usize, u64)>;
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        reset_maximised_values();
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {}
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        unsafe { MAXIMISED_VALUES.iter() }
+            .map(
+                #[no_coverage]
+                |(_, value)| *value,
+            )
+            .enumerate()
+            .filter(
+                #[no_coverage]
+                |&(_, value)| value != 0,
+            )
+            .collect()
+    }
+}
+
+impl SaveToStatsFolder for MaximisedValuesSensor {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{maximised_values, set_maximised_values, MaximisedValuesSensor};
+    use crate::traits::Sensor;
+
+    // the maximised values are global, so they are all checked in a single test
+    #[test]
+    #[no_coverage]
+    fn test_maximise_macro() {
+        let mut sensor = MaximisedValuesSensor;
+        let run = |sensor: &mut MaximisedValuesSensor, x: u64| {
+            sensor.start_recording();
+            for i in 0..x {
+                crate::maximise!(i);
+            }
+            crate::maximise!(x * 2);
+            sensor.stop_recording();
+            sensor.get_observations()
+        };
+        assert_eq!(run(&mut sensor, 3), vec![(0, 2), (1, 6)]);
+        assert_eq!(run(&mut sensor, 1), vec![(1, 2)]);
+        assert_eq!(run(&mut sensor, 0), vec![]);
+
+        // a call site that was only reached in a child process
+        sensor.start_recording();
+        set_maximised_values(&[("child.rs:1:1".to_owned(), 7)]);
+        assert_eq!(sensor.get_observations(), vec![(2, 7)]);
+        assert_eq!(maximised_values(), vec![("child.rs:1:1".to_owned(), 7)]);
+    }
+}
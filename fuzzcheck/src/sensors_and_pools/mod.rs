@@ -2,14 +2,22 @@
 Types implementing the [Sensor](crate::Sensor) and [Pool](crate::Pool) traits.
 */
 
+mod allocation_sensor;
 mod and_sensor_and_pool;
 mod array_of_counters;
+mod comparison_sensor;
+mod conditional_sensor;
+mod fastest_to_execute_pool;
 mod map_sensor;
 mod maximise_each_counter_pool;
+mod maximise_execution_time_pool;
 mod maximise_observation_pool;
+mod maximised_values_sensor;
 mod most_n_diverse_pool;
 mod noop_sensor;
+mod shared_memory_sensor;
 mod simplest_to_activate_counter_pool;
+mod target_counter_pool;
 mod test_failure_pool;
 mod unique_values_pool;
 mod unit_pool;
@@ -17,24 +25,42 @@ mod unit_pool;
 #[doc(inline)]
 pub use crate::code_coverage_sensor::CodeCoverageSensor;
 #[doc(inline)]
+pub use allocation_sensor::{AllocationSensor, CountingAllocator};
+#[doc(inline)]
 pub use and_sensor_and_pool::{AndPool, AndSensor, AndSensorAndPool, DifferentObservations, SameObservations};
 #[doc(inline)]
 pub use array_of_counters::ArrayOfCounters;
 #[doc(inline)]
+pub use comparison_sensor::{ComparisonDistancePool, ComparisonSensor};
+#[doc(inline)]
+pub use conditional_sensor::ConditionalSensor;
+#[doc(inline)]
+pub use fastest_to_execute_pool::{ExecutionTimeSensor, FastestToExecutePool};
+#[doc(inline)]
 pub use map_sensor::MapSensor;
 #[doc(inline)]
 pub use map_sensor::WrapperSensor;
 #[doc(inline)]
 pub use maximise_each_counter_pool::MaximiseEachCounterPool;
 #[doc(inline)]
+pub use maximise_execution_time_pool::MaximiseExecutionTimePool;
+#[doc(inline)]
 pub use maximise_observation_pool::MaximiseObservationPool;
+#[doc(hidden)]
+pub use maximised_values_sensor::MaximisedValue;
+#[doc(inline)]
+pub use maximised_values_sensor::{MaximisedValuesSensor, MAX_MAXIMISED_VALUES};
 #[doc(inline)]
 pub use most_n_diverse_pool::MostNDiversePool;
 #[doc(inline)]
 pub use noop_sensor::NoopSensor;
 #[doc(inline)]
+pub use shared_memory_sensor::SharedMemorySensor;
+#[doc(inline)]
 pub use simplest_to_activate_counter_pool::SimplestToActivateCounterPool;
 #[doc(inline)]
+pub use target_counter_pool::TargetCounterPool;
+#[doc(inline)]
 pub use test_failure_pool::TestFailure;
 #[doc(inline)]
 pub use test_failure_pool::TestFailurePool;
@@ -45,8 +71,53 @@ pub use unique_values_pool::UniqueValuesPool;
 #[doc(inline)]
 pub use unit_pool::UnitPool;
 
+pub(crate) use allocation_sensor::{
+    allocation_counters, counting_allocator_is_installed, reset_allocation_counters, set_allocation_counters,
+    Allocations,
+};
+pub(crate) use conditional_sensor::TEST_RETURNED_TRUE;
+pub(crate) use maximised_values_sensor::{maximised_values, reset_maximised_values, set_maximised_values};
 pub(crate) use test_failure_pool::TEST_FAILURE;
 
+/// A scheduling option for pools, giving more weight to the test cases that were recently added to them.
+///
+/// New test cases often lead to unexplored parts of the code, so it is worth mutating them more
+/// often than the others for a while. The weight of a test case that was added to the pool `age`
+/// insertions ago is multiplied by:
+/// ```text
+/// 1 + boost * 0.5^(age / half_life)
+/// ```
+/// So a test case that was just added is `1 + boost` times more likely to be chosen, and this extra
+/// weight is halved after every `half_life` insertions, until the choice is the same as without
+/// the bias.
+///
+/// The age of a test case is measured in number of insertions into the pool, not in wall-clock time.
+/// Therefore the bias does not make the fuzzer less reproducible.
+#[derive(Clone, Copy, Debug)]
+pub struct RecencyBias {
+    boost: f64,
+    half_life: usize,
+}
+
+impl RecencyBias {
+    /// Create a recency bias. `boost` must be positive and `half_life` must be strictly positive.
+    #[no_coverage]
+    pub fn new(boost: f64, half_life: usize) -> Self {
+        assert!(boost >= 0.0, "the boost of a RecencyBias must be positive");
+        assert!(
+            half_life > 0,
+            "the half-life of a RecencyBias must be strictly positive"
+        );
+        Self { boost, half_life }
+    }
+
+    /// The factor by which the weight of a test case that was added `age` insertions ago is multiplied
+    #[no_coverage]
+    pub(crate) fn factor(&self, age: usize) -> f64 {
+        1.0 + self.boost * 0.5_f64.powf(age as f64 / self.half_life as f64)
+    }
+}
+
 /// Each pool has an associated `Stats` type. They're not very interesting, but I don't want to completely hide them, so I have gathered them here.
 pub mod stats {
     use crate::traits::Stats;
@@ -56,15 +127,23 @@ pub mod stats {
     #[doc(inline)]
     pub use super::and_sensor_and_pool::AndPoolStats;
     #[doc(inline)]
+    pub use super::comparison_sensor::ComparisonDistancePoolStats;
+    #[doc(inline)]
+    pub use super::fastest_to_execute_pool::FastestToExecutePoolStats;
+    #[doc(inline)]
     pub use super::maximise_each_counter_pool::MaximiseEachCounterPoolStats;
     #[doc(inline)]
+    pub use super::maximise_execution_time_pool::MaximiseExecutionTimePoolStats;
+    #[doc(inline)]
     pub use super::most_n_diverse_pool::MostNDiversePoolStats;
     #[doc(inline)]
     pub use super::simplest_to_activate_counter_pool::UniqueCoveragePoolStats;
     #[doc(inline)]
+    pub use super::target_counter_pool::TargetCounterPoolStats;
+    #[doc(inline)]
     pub use super::test_failure_pool::TestFailurePoolStats;
-    // #[doc(inline)]
-    // pub use super::unique_values_pool::UniqueValuesPoolStats;
+    #[doc(inline)]
+    pub use super::unique_values_pool::UniqueValuesPoolStats;
 
     /// An empty type that can be used for [`Pool::Stats`](crate::Pool::Stats)
     #[derive(Clone, Copy)]
@@ -4,6 +4,7 @@ use std::{
     path::PathBuf,
 };
 
+use crate::saved_state::{write_f64, write_index, write_u64, Reader};
 use crate::{bitset::FixedBitSet, traits::SaveToStatsFolder};
 use crate::{traits::Stats, CompatibleWithObservations};
 
@@ -77,6 +78,75 @@ impl Pool for MostNDiversePool {
         let input = &self.inputs[choice];
         Some(input.pool_idx)
     }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.nbr_counters as u64);
+        write_u64(&mut state, self.inputs.len() as u64);
+        for input in self.inputs.iter() {
+            write_index(&mut state, input.pool_idx);
+            write_f64(&mut state, input.cplx);
+            write_u64(&mut state, input.counters.count_ones() as u64);
+            for counter in input.counters.ones() {
+                write_u64(&mut state, counter as u64);
+            }
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let nbr_counters = reader.usize()?;
+        if nbr_counters != self.nbr_counters {
+            return Err(format!(
+                "the pool {} was saved with {} counters, but it now has {}",
+                self.name, nbr_counters, self.nbr_counters
+            ));
+        }
+        let nbr_inputs = reader.usize()?;
+        if nbr_inputs > self.max_len {
+            return Err(format!(
+                "the pool {} was saved with {} test cases, but it can now contain at most {}",
+                self.name, nbr_inputs, self.max_len
+            ));
+        }
+        let mut inputs = vec![];
+        for _ in 0..nbr_inputs {
+            let pool_idx = reader.index(new_index)?;
+            let cplx = reader.f64()?;
+            let mut counters = FixedBitSet::with_capacity(self.nbr_counters + 1);
+            for _ in 0..reader.usize()? {
+                let counter = reader.usize()?;
+                if counter > self.nbr_counters {
+                    return Err("it is corrupted".to_owned());
+                }
+                counters.insert(counter);
+            }
+            // the unique counters are computed by `recompute_state_from_inputs_vec`
+            inputs.push(Input {
+                nbr_unique_counters: 0,
+                unique_counters: FixedBitSet::new(),
+                counters,
+                pool_idx,
+                cplx,
+            });
+        }
+        if !reader.is_empty() {
+            return Err("it is corrupted".to_owned());
+        }
+        self.inputs = inputs;
+        self.recompute_state_from_inputs_vec();
+        self.rng.seed(seed);
+        Ok(())
+    }
 }
 impl SaveToStatsFolder for MostNDiversePool {
     #[no_coverage]
@@ -0,0 +1,184 @@
+//! A sensor reading the code coverage of a target that runs in another process, through a bitmap of 8-bit
+//! counters in System V shared memory, like AFL.
+use crate::traits::{SaveToStatsFolder, Sensor};
+use std::io::{self, Result};
+use std::path::PathBuf;
+
+/**
+A variant of [`CodeCoverageSensor`](crate::sensors_and_pools::CodeCoverageSensor) observing the code coverage of a
+target running in a child process, such as a program written in C that is executed by the test function.
+
+The sensor creates a bitmap of 8-bit counters in a System V shared memory segment. The child process finds the
+segment through the environment variable [`ENV_VAR`](SharedMemorySensor::ENV_VAR), which is `__AFL_SHM_ID`, and
+increments the counters of the edges it executes. So a program compiled with the instrumentation of AFL, such as
+`afl-clang-fast`, reports its code coverage to the sensor without any change. The map of the target should have
+the same size as the sensor, which is 65536 counters by default for AFL.
+
+The counters are cleared before each run of the test function, and its observations are the indices and values
+of the non-zero counters, like those of `CodeCoverageSensor`. They are thus compatible with the same pools:
+```no_run
+use fuzzcheck::sensors_and_pools::{SharedMemorySensor, SimplestToActivateCounterPool};
+use fuzzcheck::ByteSerializer;
+use std::process::{Command, Stdio};
+use std::io::Write;
+
+let sensor = SharedMemorySensor::new(1 << 16).unwrap();
+let pool = SimplestToActivateCounterPool::new("shm_coverage", sensor.map_size());
+let shm_id = sensor.shm_id().to_string();
+
+let test = move |input: &Vec<u8>| {
+    let mut child = Command::new("./target")
+        .env(SharedMemorySensor::ENV_VAR, &shm_id)
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait().unwrap().success()
+};
+let result = fuzzcheck::fuzz_test(test)
+    .default_mutator()
+    .serializer(ByteSerializer::new("bin"))
+    .sensor_and_pool(sensor, pool)
+    .arguments_from_cargo_fuzzcheck()
+    .launch();
+```
+The segment is inherited by the processes forked from the fuzzer, so the sensor also works with `--fork`.
+*/
+pub struct SharedMemorySensor {
+    shm_id: i32,
+    map: *mut u8,
+    map_size: usize,
+}
+
+impl SharedMemorySensor {
+    /// The environment variable containing the identifier of the shared memory segment, as read by AFL targets
+    pub const ENV_VAR: &'static str = "__AFL_SHM_ID";
+
+    /// Create a shared memory segment of `map_size` 8-bit counters
+    #[no_coverage]
+    pub fn new(map_size: usize) -> Result<Self> {
+        if map_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the map of a SharedMemorySensor cannot be empty",
+            ));
+        }
+        let shm_id = unsafe { libc::shmget(libc::IPC_PRIVATE, map_size, libc::IPC_CREAT | libc::IPC_EXCL | 0o600) };
+        if shm_id < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let map = unsafe { libc::shmat(shm_id, std::ptr::null(), 0) };
+        if map as isize == -1 {
+            let error = io::Error::last_os_error();
+            unsafe { libc::shmctl(shm_id, libc::IPC_RMID, std::ptr::null_mut()) };
+            return Err(error);
+        }
+        // On Linux, the segment can still be attached after it is marked for removal, and it is destroyed once
+        // the last process detaches from it, even if the fuzzer is killed. Elsewhere, it is removed on drop.
+        #[cfg(target_os = "linux")]
+        unsafe {
+            libc::shmctl(shm_id, libc::IPC_RMID, std::ptr::null_mut());
+        }
+        Ok(Self {
+            shm_id,
+            map: map as *mut u8,
+            map_size,
+        })
+    }
+
+    /// The identifier of the shared memory segment, which should be given to the child process in the environment
+    /// variable [`ENV_VAR`](SharedMemorySensor::ENV_VAR)
+    #[no_coverage]
+    pub fn shm_id(&self) -> i32 {
+        self.shm_id
+    }
+
+    /// The number of counters of the map
+    #[no_coverage]
+    pub fn map_size(&self) -> usize {
+        self.map_size
+    }
+
+    #[no_coverage]
+    fn map(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.map, self.map_size) }
+    }
+}
+
+impl Drop for SharedMemorySensor {
+    #[no_coverage]
+    fn drop(&mut self) {
+        unsafe {
+            libc::shmdt(self.map as *const libc::c_void);
+            #[cfg(not(target_os = "linux"))]
+            libc::shmctl(self.shm_id, libc::IPC_RMID, std::ptr::null_mut());
+        }
+    }
+}
+
+impl Sensor for SharedMemorySensor {
+    type Observations = Vec<(usize, u64)>;
+
+    #[no_coverage]
+    fn start_recording(&mut self) {
+        self.map().fill(0);
+    }
+
+    #[no_coverage]
+    fn stop_recording(&mut self) {}
+
+    #[no_coverage]
+    fn get_observations(&mut self) -> Self::Observations {
+        let mut observations = vec![];
+        // most of the map is empty, so it is scanned eight counters at a time
+        for (i, word) in self.map().chunks(8).enumerate() {
+            if let Ok(word) = <[u8; 8]>::try_from(word) {
+                if u64::from_ne_bytes(word) == 0 {
+                    continue;
+                }
+            }
+            for (j, &counter) in word.iter().enumerate() {
+                if counter != 0 {
+                    observations.push((i * 8 + j, counter as u64));
+                }
+            }
+        }
+        observations
+    }
+}
+
+impl SaveToStatsFolder for SharedMemorySensor {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedMemorySensor;
+    use crate::traits::Sensor;
+
+    #[test]
+    #[no_coverage]
+    fn test_shared_memory_sensor() {
+        let mut sensor = SharedMemorySensor::new(20).unwrap();
+        // attach the segment a second time, as a target process would
+        let target_map = unsafe { libc::shmat(sensor.shm_id(), std::ptr::null(), 0) } as *mut u8;
+        assert_ne!(target_map as isize, -1);
+
+        unsafe {
+            *target_map.add(3) = 1;
+            sensor.start_recording();
+            assert!((0..20).all(|i| *target_map.add(i) == 0));
+            *target_map = 2;
+            *target_map.add(9) = 255;
+            *target_map.add(19) = 7;
+        }
+        sensor.stop_recording();
+        assert_eq!(sensor.get_observations(), vec![(0, 2), (9, 255), (19, 7)]);
+
+        unsafe { libc::shmdt(target_map as *const libc::c_void) };
+        assert!(SharedMemorySensor::new(0).is_err());
+    }
+}
@@ -28,6 +28,8 @@
 
 use crate::data_structures::{Slab, SlabKey};
 use crate::fenwick_tree::FenwickTree;
+use crate::saved_state::{write_f64, write_index, write_u64, Reader};
+use crate::sensors_and_pools::RecencyBias;
 use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
 use crate::PoolStorageIndex;
 use crate::{CSVField, CompatibleWithObservations, ToCSV};
@@ -99,6 +101,8 @@ struct Input {
     ///
     /// This is used to prioritise new inputs over old ones.
     number_times_chosen: usize,
+    /// The value of the pool’s `nbr_added_inputs` when this input was added
+    added_at: usize,
 }
 
 /**
@@ -150,6 +154,10 @@ pub struct SimplestToActivateCounterPool {
     pub total_score: f64,
     pub ranked_inputs: FenwickTree,
 
+    /// The total number of inputs that were ever added to the pool
+    nbr_added_inputs: usize,
+    recency_bias: Option<RecencyBias>,
+
     rng: Rng,
 }
 
@@ -167,10 +175,35 @@ impl SimplestToActivateCounterPool {
             total_score: 0.0,
             ranked_inputs: FenwickTree::new(vec![]),
 
+            nbr_added_inputs: 0,
+            recency_bias: None,
+
             rng: fastrand::Rng::new(),
         }
     }
 
+    /// Choose the recently added test cases more often than the others.
+    ///
+    /// By default, the pool has no recency bias. See [`RecencyBias`] for more details.
+    #[no_coverage]
+    pub fn with_recency_bias(self, recency_bias: RecencyBias) -> Self {
+        Self {
+            recency_bias: Some(recency_bias),
+            ..self
+        }
+    }
+
+    /// The weight of the input in the `ranked_inputs` Fenwick tree
+    #[no_coverage]
+    fn rank(&self, input: &Input) -> f64 {
+        let rank = input.score / (input.number_times_chosen as f64);
+        if let Some(recency_bias) = &self.recency_bias {
+            rank * recency_bias.factor(self.nbr_added_inputs - input.added_at)
+        } else {
+            rank
+        }
+    }
+
     #[no_coverage]
     pub fn score(&self) -> f64 {
         self.total_score
@@ -200,7 +233,9 @@ impl SimplestToActivateCounterPool {
             data,
             complexity,
             number_times_chosen: 1,
+            added_at: self.nbr_added_inputs,
         };
+        self.nbr_added_inputs += 1;
         let element_key = self.slab_inputs.insert(element);
 
         let mut to_delete: AHashSet<SlabKey<Input>> = AHashSet::with_hasher(ahash::RandomState::with_seeds(0, 0, 0, 0));
@@ -347,10 +382,7 @@ impl SimplestToActivateCounterPool {
             .keys()
             .map(
                 #[no_coverage]
-                |key| {
-                    let input = &slab[key];
-                    input.score / (input.number_times_chosen as f64)
-                },
+                |key| self.rank(&slab[key]),
             )
             .collect();
         self.ranked_inputs = FenwickTree::new(ranked_inputs);
@@ -475,14 +507,140 @@ impl Pool for SimplestToActivateCounterPool {
         let choice = self.ranked_inputs.sample(&self.rng)?;
         let key = self.slab_inputs.get_nth_key(choice);
 
-        let input = &mut self.slab_inputs[key];
-        let old_rank = input.score / (input.number_times_chosen as f64);
-        input.number_times_chosen += 1;
-        let new_rank = input.score / (input.number_times_chosen as f64);
+        let old_rank = self.rank(&self.slab_inputs[key]);
+        self.slab_inputs[key].number_times_chosen += 1;
+        let new_rank = self.rank(&self.slab_inputs[key]);
 
         let delta = new_rank - old_rank;
         self.ranked_inputs.update(choice, delta);
-        Some(input.data)
+        Some(self.slab_inputs[key].data)
+    }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.least_complexity_for_counter.len() as u64);
+        write_u64(&mut state, self.nbr_added_inputs as u64);
+        write_u64(&mut state, self.slab_inputs.len() as u64);
+        for key in self.slab_inputs.keys() {
+            let input = &self.slab_inputs[key];
+            write_index(&mut state, input.data);
+            write_f64(&mut state, input.complexity);
+            write_u64(&mut state, input.number_times_chosen as u64);
+            write_u64(&mut state, input.added_at as u64);
+            write_u64(&mut state, input.all_counters.len() as u64);
+            for counter in input.all_counters.iter() {
+                write_u64(&mut state, counter.0 as u64);
+            }
+            write_u64(&mut state, input.least_complex_for_counters.len() as u64);
+            for counter in input.least_complex_for_counters.iter() {
+                write_u64(&mut state, counter.0 as u64);
+            }
+        }
+        Some(state)
+    }
+
+    /// Rebuild the analysed counters and the scores from the counters of each input
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let nbr_counters = reader.usize()?;
+        if nbr_counters != self.least_complexity_for_counter.len() {
+            return Err(format!(
+                "the pool {} was saved with {} counters, but it now has {}",
+                self.name,
+                nbr_counters,
+                self.least_complexity_for_counter.len()
+            ));
+        }
+        #[no_coverage]
+        fn counter(reader: &mut Reader, nbr_counters: usize) -> Result<CounterIdx, String> {
+            let counter = reader.usize()?;
+            if counter < nbr_counters {
+                Ok(CounterIdx::new(counter))
+            } else {
+                Err("it refers to a counter that does not exist".to_owned())
+            }
+        }
+        let nbr_added_inputs = reader.usize()?;
+        let nbr_inputs = reader.usize()?;
+        let mut least_complexity_for_counter = vec![f64::INFINITY; nbr_counters];
+        let mut analysed_counters: AHashMap<CounterIdx, AnalysedCounter> =
+            AHashMap::with_hasher(ahash::RandomState::with_seeds(0, 0, 0, 0));
+        let mut slab_inputs = Slab::new();
+        for _ in 0..nbr_inputs {
+            let data = reader.index(new_index)?;
+            let complexity = reader.f64()?;
+            let number_times_chosen = reader.usize()?.max(1);
+            let added_at = reader.usize()?;
+            if added_at >= nbr_added_inputs {
+                return Err("it is corrupted".to_owned());
+            }
+            let key = slab_inputs.insert(Input {
+                least_complex_for_counters: AHashSet::with_hasher(ahash::RandomState::with_seeds(0, 0, 0, 0)),
+                all_counters: vec![],
+                score: 0.0,
+                data,
+                complexity,
+                number_times_chosen,
+                added_at,
+            });
+            for _ in 0..reader.usize()? {
+                let counter = counter(&mut reader, nbr_counters)?;
+                slab_inputs[key].all_counters.push(counter);
+                analysed_counters
+                    .entry(counter)
+                    .or_insert_with(
+                        #[no_coverage]
+                        || AnalysedCounter::new(counter, vec![], key, complexity),
+                    )
+                    .inputs
+                    .push(key);
+            }
+            for _ in 0..reader.usize()? {
+                let counter = counter(&mut reader, nbr_counters)?;
+                let analysed_counter = analysed_counters.get_mut(&counter).ok_or_else(
+                    #[no_coverage]
+                    || "it is corrupted".to_owned(),
+                )?;
+                analysed_counter.least_complex_input = key;
+                analysed_counter.least_complexity = complexity;
+                least_complexity_for_counter[counter.0] = complexity;
+                slab_inputs[key].least_complex_for_counters.insert(counter);
+            }
+        }
+        if !reader.is_empty() {
+            return Err("it is corrupted".to_owned());
+        }
+        for analysed_counter in analysed_counters.values_mut() {
+            analysed_counter.score = Self::score_of_counter(analysed_counter.inputs.len());
+        }
+        for key in slab_inputs.keys() {
+            let input = &mut slab_inputs[key];
+            input.score = input
+                .all_counters
+                .iter()
+                .map(
+                    #[no_coverage]
+                    |counter| analysed_counters[counter].score,
+                )
+                .sum();
+        }
+        self.least_complexity_for_counter = least_complexity_for_counter;
+        self.analysed_counters = analysed_counters;
+        self.slab_inputs = slab_inputs;
+        self.nbr_added_inputs = nbr_added_inputs;
+        if nbr_inputs > 0 {
+            self.update_self_stats();
+        }
+        self.rng.seed(seed);
+        Ok(())
     }
 }
 
@@ -781,6 +939,43 @@ mod tests {
         }
     }
 
+    #[test]
+    #[no_coverage]
+    fn test_save_and_restore_state() {
+        let mut pool = SimplestToActivateCounterPool::new("cov", 16);
+        let inputs = [
+            (2.0, vec![], vec![edge_f(0, 1), edge_f(0, 2)]),
+            (3.0, vec![edge_f(0, 1)], vec![edge_f(0, 3)]),
+            (1.0, vec![edge_f(0, 2), edge_f(0, 3)], vec![]),
+        ];
+        for (i, (cplx, existing_counters, new_counters)) in inputs.into_iter().enumerate() {
+            let analysis_result = AnalysisResult {
+                existing_counters,
+                new_counters,
+            };
+            let _ = pool.add(PoolStorageIndex::mock(i), cplx, analysis_result);
+        }
+        let state = pool.save_state().unwrap();
+
+        // the test cases are stored at other indices in the new pool storage
+        let new_index = |idx: PoolStorageIndex| Some(PoolStorageIndex::mock(idx.0 + 10));
+        let mut restored = SimplestToActivateCounterPool::new("cov", 16);
+        restored.restore_state(&state, &new_index).unwrap();
+        restored.sanity_check();
+        assert_eq!(restored.slab_inputs.len(), pool.slab_inputs.len());
+        assert!((restored.score() - pool.score()).abs() < 0.01);
+        assert_eq!(restored.least_complexity_for_counter, pool.least_complexity_for_counter);
+        assert!(restored
+            .slab_inputs
+            .keys()
+            .all(|key| restored.slab_inputs[key].data.0 >= 10));
+
+        let mut other_size = SimplestToActivateCounterPool::new("cov", 32);
+        assert!(other_size.restore_state(&state, &new_index).is_err());
+        let mut truncated = SimplestToActivateCounterPool::new("cov", 16);
+        assert!(truncated.restore_state(&state[..state.len() - 1], &new_index).is_err());
+    }
+
     #[derive(Clone, Copy, Debug)]
     pub struct VoidMutator {}
 
@@ -0,0 +1,262 @@
+use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
+use crate::{CSVField, CompatibleWithObservations, PoolStorageIndex, ToCSV};
+use fastrand::Rng;
+use nu_ansi_term::Color;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// The statistics of a [TargetCounterPool]
+#[derive(Clone)]
+pub struct TargetCounterPoolStats {
+    name: String,
+    size: usize,
+    /// The smallest distance to the target reached by a test case, if any
+    closest: Option<u64>,
+}
+
+impl Display for TargetCounterPoolStats {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.closest {
+            Some(0) => write!(
+                f,
+                "{}",
+                Color::Green.paint(format!("{}({} reached)", self.name, self.size))
+            ),
+            Some(closest) => write!(
+                f,
+                "{}",
+                Color::LightYellow.paint(format!("{}({} dist: {})", self.name, self.size, closest))
+            ),
+            None => write!(f, "{}", Color::LightYellow.paint(format!("{}(none)", self.name))),
+        }
+    }
+}
+
+impl ToCSV for TargetCounterPoolStats {
+    #[no_coverage]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::String(format!("{}-count", self.name)),
+            CSVField::String(format!("{}-distance", self.name)),
+        ]
+    }
+    #[no_coverage]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::Integer(self.size as isize),
+            if let Some(closest) = self.closest {
+                CSVField::Integer(closest as isize)
+            } else {
+                CSVField::String("none".to_owned())
+            },
+        ]
+    }
+}
+impl Stats for TargetCounterPoolStats {}
+
+#[derive(Clone, Copy)]
+struct Input {
+    input_id: PoolStorageIndex,
+    complexity: f64,
+}
+
+/// A pool that directs the fuzzer towards a single target counter.
+///
+/// Each counter of the sensor is given a distance to the target, and the distance of a test case is
+/// the smallest distance of the counters it activates. The pool only keeps the test cases that are the
+/// closest to the target so far: for each counter at that distance, it keeps the simplest test case activating it.
+/// Once the target is reached, the pool keeps the simplest test case activating it.
+///
+/// The distances are usually given by [`CodeCoverageSensor::distances_to_counter`](crate::sensors_and_pools::CodeCoverageSensor::distances_to_counter),
+/// but any other measure can be used. Counters whose distance is `None` are ignored.
+///
+/// It is [compatible with](crate::CompatibleWithObservations) the following sensors:
+/// * [`CodeCoverageSensor`](crate::sensors_and_pools::CodeCoverageSensor)
+/// * any other sensor whose [observations](crate::Sensor::Observations) are given by an iterator of `(usize, u64)`
+///
+/// See the `find_test_cases_reaching_counter` method of the [`SensorAndPoolBuilder`](crate::builder::SensorAndPoolBuilder)
+/// to learn how to choose the target counter.
+pub struct TargetCounterPool {
+    name: String,
+    distances: Vec<Option<u64>>,
+    closest: Option<u64>,
+    /// The simplest test case activating each counter that is at the `closest` distance
+    best_input_for_counter: BTreeMap<usize, Input>,
+    rng: Rng,
+}
+
+impl TargetCounterPool {
+    /// Create a new pool from the distance between each counter of the sensor and the target counter.
+    #[no_coverage]
+    pub fn new(name: &str, distances: Vec<Option<u64>>) -> Self {
+        Self {
+            name: name.to_string(),
+            distances,
+            closest: None,
+            best_input_for_counter: BTreeMap::new(),
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// The test cases of the pool, without duplicates
+    #[no_coverage]
+    fn input_ids(&self) -> Vec<PoolStorageIndex> {
+        let mut ids = vec![];
+        for input in self.best_input_for_counter.values() {
+            if !ids.contains(&input.input_id) {
+                ids.push(input.input_id);
+            }
+        }
+        ids
+    }
+}
+
+impl Pool for TargetCounterPool {
+    type Stats = TargetCounterPoolStats;
+
+    #[no_coverage]
+    fn stats(&self) -> Self::Stats {
+        TargetCounterPoolStats {
+            name: self.name.clone(),
+            size: self.input_ids().len(),
+            closest: self.closest,
+        }
+    }
+
+    #[no_coverage]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        if self.best_input_for_counter.is_empty() {
+            return None;
+        }
+        let choice = self.rng.usize(..self.best_input_for_counter.len());
+        self.best_input_for_counter.values().nth(choice).map(
+            #[no_coverage]
+            |input| input.input_id,
+        )
+    }
+}
+
+impl SaveToStatsFolder for TargetCounterPool {
+    #[no_coverage]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+impl<O> CompatibleWithObservations<O> for TargetCounterPool
+where
+    for<'a> &'a O: IntoIterator<Item = &'a (usize, u64)>,
+{
+    #[no_coverage]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &O, complexity: f64) -> Vec<CorpusDelta> {
+        let mut distance = None;
+        let mut closest_counters = vec![];
+        for &(index, _) in observations.into_iter() {
+            let counter_distance = if let Some(d) = self.distances[index] {
+                d
+            } else {
+                continue;
+            };
+            match distance {
+                Some(d) if counter_distance > d => {}
+                Some(d) if counter_distance == d => closest_counters.push(index),
+                _ => {
+                    distance = Some(counter_distance);
+                    closest_counters = vec![index];
+                }
+            }
+        }
+        let distance = if let Some(distance) = distance {
+            distance
+        } else {
+            return vec![];
+        };
+        let previous_ids = self.input_ids();
+        match self.closest {
+            Some(closest) if distance > closest => return vec![],
+            Some(closest) if distance == closest => {}
+            _ => {
+                self.closest = Some(distance);
+                self.best_input_for_counter.clear();
+            }
+        }
+        let new = Input { input_id, complexity };
+        let mut add = false;
+        for counter in closest_counters {
+            let best = self.best_input_for_counter.entry(counter).or_insert(new);
+            if best.input_id == input_id {
+                add = true;
+            } else if complexity < best.complexity {
+                *best = new;
+                add = true;
+            }
+        }
+        if !add {
+            return vec![];
+        }
+        let ids = self.input_ids();
+        let remove = previous_ids
+            .into_iter()
+            .filter(
+                #[no_coverage]
+                |id| !ids.contains(id),
+            )
+            .collect();
+        vec![CorpusDelta {
+            path: PathBuf::new().join(&self.name),
+            add: true,
+            remove,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[no_coverage]
+    fn test_target_counter_pool() {
+        let distances = vec![Some(10), Some(5), Some(5), Some(0), None];
+        let mut pool = TargetCounterPool::new("target", distances);
+        assert!(pool.get_random_index().is_none());
+
+        // counters without a distance are ignored
+        assert!(run(&mut pool, 0, vec![(4, 1)], 1.0).is_empty());
+
+        let deltas = run(&mut pool, 1, vec![(0, 1), (4, 1)], 1.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(pool.stats().closest, Some(10));
+
+        // a closer test case replaces all the others
+        let deltas = run(&mut pool, 2, vec![(0, 1), (1, 1)], 2.0);
+        assert_eq!(deltas[0].remove, vec![PoolStorageIndex::mock(1)]);
+        assert_eq!(pool.stats().closest, Some(5));
+
+        // a farther test case is not interesting
+        assert!(run(&mut pool, 3, vec![(0, 1)], 0.5).is_empty());
+        // a test case at the same distance is kept if it activates another counter at that distance
+        let deltas = run(&mut pool, 4, vec![(2, 1)], 3.0);
+        assert!(deltas[0].remove.is_empty());
+        assert_eq!(pool.stats().size, 2);
+        // or if it is simpler
+        let deltas = run(&mut pool, 5, vec![(1, 1), (2, 1)], 1.0);
+        assert_eq!(
+            deltas[0].remove,
+            vec![PoolStorageIndex::mock(2), PoolStorageIndex::mock(4)]
+        );
+        assert_eq!(pool.stats().size, 1);
+
+        run(&mut pool, 6, vec![(3, 1)], 10.0);
+        let stats = pool.stats();
+        assert_eq!((stats.size, stats.closest), (1, Some(0)));
+        assert_eq!(pool.get_random_index(), Some(PoolStorageIndex::mock(6)));
+    }
+
+    #[no_coverage]
+    fn run(pool: &mut TargetCounterPool, idx: usize, observations: Vec<(usize, u64)>, cplx: f64) -> Vec<CorpusDelta> {
+        pool.process(PoolStorageIndex::mock(idx), &observations, cplx)
+    }
+}
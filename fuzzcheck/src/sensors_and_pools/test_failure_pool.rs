@@ -1,21 +1,108 @@
+use crate::saved_state::{write_bytes, write_f64, write_index, write_u64, Reader};
 use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder, Sensor, Stats};
 use crate::PoolStorageIndex;
 use crate::{CSVField, ToCSV};
 use nu_ansi_term::Color;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
-const NBR_ARTIFACTS_PER_ERROR_AND_CPLX: usize = 8;
+/// The number of frames at the top of the backtrace of a panic that are part of its failure signature
+const NBR_SIGNATURE_FRAMES: usize = 4;
+
+/// The prefixes of the names of the frames of the panic machinery and of the fuzzer, which are at the top of the
+/// backtrace of every panic and are skipped before taking the frames of the failure signature
+const IGNORED_FRAME_PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "<std::",
+    "<core::",
+    "<alloc::",
+    "rust_begin_unwind",
+    "rust_panic",
+    "__rust",
+    "fuzzcheck::",
+    "<fuzzcheck::",
+];
 
 pub(crate) static mut TEST_FAILURE: Option<TestFailure> = None;
 
 /// A type describing a test failure.
 ///
 /// It is uniquely identifiable through `self.id` and displayable through `self.display`.
+///
+/// The `id` only depends on the location of the failure, while the `signature` also depends on the panic message
+/// and on the functions at the top of the backtrace of the panic. Test cases with the same signature are
+/// duplicates of each other, and only a few of them are saved by the [`TestFailurePool`].
 #[derive(Debug, Clone)]
 pub struct TestFailure {
     pub display: String,
     pub id: u64,
+    pub signature: u64,
+}
+
+impl TestFailure {
+    /// Compute the failure signature of a panic from its message and its backtrace, as displayed by
+    /// [`Backtrace`](std::backtrace::Backtrace).
+    ///
+    /// The frames of the panic machinery and of the fuzzer at the top of the backtrace are skipped, and the
+    /// names of the next few functions are hashed along with the message.
+    #[no_coverage]
+    pub(crate) fn panic_signature(message: &str, backtrace: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        message.hash(&mut hasher);
+        let frames = backtrace
+            .lines()
+            .filter_map(
+                #[no_coverage]
+                |line| {
+                    // the lines naming a function are of the form `  12: name`, and are followed by its location
+                    let (index, name) = line.trim_start().split_once(": ")?;
+                    if index.is_empty()
+                        || !index.bytes().all(
+                            #[no_coverage]
+                            |b| b.is_ascii_digit(),
+                        )
+                    {
+                        return None;
+                    }
+                    Some(strip_symbol_hash(name))
+                },
+            )
+            .skip_while(
+                #[no_coverage]
+                |name| {
+                    IGNORED_FRAME_PREFIXES.iter().any(
+                        #[no_coverage]
+                        |prefix| name.starts_with(prefix),
+                    )
+                },
+            )
+            .take(NBR_SIGNATURE_FRAMES);
+        for frame in frames {
+            frame.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+/// Remove the hash that may end the name of a symbol, e.g. `::h0123456789abcdef`, which can change between
+/// compilations of the same code
+#[no_coverage]
+fn strip_symbol_hash(name: &str) -> &str {
+    if let Some((prefix, hash)) = name.rsplit_once("::h") {
+        if hash.len() == 16
+            && hash.bytes().all(
+                #[no_coverage]
+                |b| b.is_ascii_hexdigit(),
+            )
+        {
+            return prefix;
+        }
+    }
+    name
 }
 
 /// A sensor that records test failures.
@@ -82,6 +169,7 @@ impl Stats for TestFailurePoolStats {}
 
 struct TestFailureList {
     error: TestFailure,
+    /// Sorted by decreasing complexity
     inputs: Vec<TestFailureListForError>,
 }
 
@@ -92,22 +180,38 @@ struct TestFailureListForError {
 
 /// A pool that saves failing test cases.
 ///
-/// It categorizes the test cases by their failure information and sort them by complexity.
+/// It categorizes the test cases by their failure signature and sort them by complexity. At most
+/// `max_artifacts_per_signature` test cases are saved for each signature: once this limit is reached, a new
+/// failing test case is only saved if it is simpler than the most complex saved one, which is then removed.
 pub struct TestFailurePool {
     name: String,
+    max_artifacts_per_signature: usize,
     inputs: Vec<TestFailureList>,
     rng: fastrand::Rng,
 }
 
 impl TestFailurePool {
     #[no_coverage]
-    pub(crate) fn new(name: &str) -> Self {
+    pub(crate) fn new(name: &str, max_artifacts_per_signature: usize) -> Self {
         Self {
             name: name.to_string(),
+            max_artifacts_per_signature,
             inputs: vec![],
             rng: fastrand::Rng::new(),
         }
     }
+
+    /// The folder of the output corpus where the failing test cases of the given list and complexity are saved.
+    ///
+    /// It is named after the first error of the list, such that all its test cases can be found from their complexity.
+    #[no_coverage]
+    fn path(&self, list_index: usize, cplx: f64) -> PathBuf {
+        let mut path = PathBuf::new();
+        path.push(&self.name);
+        path.push(format!("{}", self.inputs[list_index].error.id));
+        path.push(format!("{:.4}", cplx));
+        path
+    }
 }
 
 impl Pool for TestFailurePool {
@@ -135,6 +239,61 @@ impl Pool for TestFailurePool {
         let input_choice = self.rng.usize(0..least_complexity.inputs.len());
         Some(least_complexity.inputs[input_choice])
     }
+
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let mut state = vec![];
+        write_u64(&mut state, self.rng.get_seed());
+        write_u64(&mut state, self.inputs.len() as u64);
+        for list in self.inputs.iter() {
+            write_bytes(&mut state, list.error.display.as_bytes());
+            write_u64(&mut state, list.error.id);
+            write_u64(&mut state, list.error.signature);
+            write_u64(&mut state, list.inputs.len() as u64);
+            for list_for_cplx in list.inputs.iter() {
+                write_f64(&mut state, list_for_cplx.cplx);
+                write_u64(&mut state, list_for_cplx.inputs.len() as u64);
+                for &input in list_for_cplx.inputs.iter() {
+                    write_index(&mut state, input);
+                }
+            }
+        }
+        Some(state)
+    }
+
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let mut reader = Reader::new(state);
+        let seed = reader.u64()?;
+        let mut inputs = vec![];
+        for _ in 0..reader.usize()? {
+            let error = TestFailure {
+                display: reader.string()?,
+                id: reader.u64()?,
+                signature: reader.u64()?,
+            };
+            let mut list = vec![];
+            for _ in 0..reader.usize()? {
+                let cplx = reader.f64()?;
+                let mut inputs = vec![];
+                for _ in 0..reader.usize()? {
+                    inputs.push(reader.index(new_index)?);
+                }
+                list.push(TestFailureListForError { cplx, inputs });
+            }
+            inputs.push(TestFailureList { error, inputs: list });
+        }
+        if !reader.is_empty() {
+            return Err("it is corrupted".to_owned());
+        }
+        self.inputs = inputs;
+        self.rng.seed(seed);
+        Ok(())
+    }
 }
 impl SaveToStatsFolder for TestFailurePool {
     #[no_coverage]
@@ -151,79 +310,167 @@ impl CompatibleWithObservations<Option<TestFailure>> for TestFailurePool {
         observations: &Option<TestFailure>,
         complexity: f64,
     ) -> Vec<CorpusDelta> {
-        let error = observations;
+        let error = if let Some(error) = observations {
+            error
+        } else {
+            return vec![];
+        };
 
-        enum PositionOfNewInput {
-            NewError,
-            ExistingErrorNewCplx(usize),
-            ExistingErrorAndCplx(usize),
-        }
+        let list_index = if let Some(list_index) = self.inputs.iter().position(
+            #[no_coverage]
+            |xs| xs.error.signature == error.signature,
+        ) {
+            list_index
+        } else {
+            // a new failure signature we haven't seen before
+            self.inputs.push(TestFailureList {
+                error: error.clone(),
+                inputs: vec![],
+            });
+            self.inputs.len() - 1
+        };
 
-        let mut is_interesting = None;
-        if let Some(error) = error {
-            if let Some(list_index) = self.inputs.iter().position(
-                #[no_coverage]
-                |xs| xs.error.id == error.id,
-            ) {
-                let list = &self.inputs[list_index];
-                if let Some(least_complex) = list.inputs.last() {
-                    if least_complex.cplx > complexity {
-                        is_interesting = Some(PositionOfNewInput::ExistingErrorNewCplx(list_index));
-                    } else if least_complex.cplx == complexity {
-                        if least_complex.inputs.len() < NBR_ARTIFACTS_PER_ERROR_AND_CPLX
-                            && !self.inputs.iter().any(
-                                #[no_coverage]
-                                |xs| xs.error.display == error.display,
-                            )
-                        {
-                            is_interesting = Some(PositionOfNewInput::ExistingErrorAndCplx(list_index));
-                        }
-                    }
-                } else {
-                    is_interesting = Some(PositionOfNewInput::ExistingErrorNewCplx(list_index));
-                }
-            } else {
-                // a new error we haven't seen before
-                is_interesting = Some(PositionOfNewInput::NewError);
+        let list = &self.inputs[list_index];
+        let nbr_saved = list.inputs.iter().fold(
+            0,
+            #[no_coverage]
+            |acc, xs| acc + xs.inputs.len(),
+        );
+        let is_full = nbr_saved >= self.max_artifacts_per_signature;
+        if is_full {
+            // the list is not empty, because there is at least one test case per signature
+            let most_complex = &list.inputs[0];
+            if most_complex.cplx <= complexity {
+                return vec![];
             }
-            if let Some(position) = is_interesting {
-                let mut path = PathBuf::new();
-                path.push(&self.name);
-                path.push(format!("{}", error.id));
-                path.push(format!("{:.4}", complexity));
-
-                match position {
-                    PositionOfNewInput::NewError => {
-                        self.inputs.push(TestFailureList {
-                            error: error.clone(),
-                            inputs: vec![TestFailureListForError {
-                                cplx: complexity,
-                                inputs: vec![input_idx],
-                            }],
-                        });
-                    }
-                    PositionOfNewInput::ExistingErrorNewCplx(error_idx) => {
-                        // TODO: handle event
-                        self.inputs[error_idx].inputs.push(TestFailureListForError {
-                            cplx: complexity,
-                            inputs: vec![input_idx],
-                        });
-                    }
-                    PositionOfNewInput::ExistingErrorAndCplx(error_idx) => {
-                        // NOTE: the complexity must be the last one
-                        // TODO: handle event
-                        self.inputs[error_idx].inputs.last_mut().unwrap().inputs.push(input_idx);
-                    }
-                };
-
-                let delta = CorpusDelta {
-                    path,
-                    add: true,
-                    remove: vec![],
-                };
-                return vec![delta];
+        }
+
+        let mut deltas = vec![];
+        if is_full {
+            let most_complex = &mut self.inputs[list_index].inputs[0];
+            let removed = most_complex.inputs.pop().unwrap();
+            let removed_cplx = most_complex.cplx;
+            if most_complex.inputs.is_empty() {
+                self.inputs[list_index].inputs.remove(0);
             }
+            deltas.push(CorpusDelta {
+                path: self.path(list_index, removed_cplx),
+                add: false,
+                remove: vec![removed],
+            });
         }
-        vec![]
+
+        let list = &mut self.inputs[list_index];
+        if let Some(same_cplx) = list.inputs.iter_mut().find(
+            #[no_coverage]
+            |xs| xs.cplx == complexity,
+        ) {
+            same_cplx.inputs.push(input_idx);
+        } else {
+            let position = list.inputs.partition_point(
+                #[no_coverage]
+                |xs| xs.cplx > complexity,
+            );
+            list.inputs.insert(
+                position,
+                TestFailureListForError {
+                    cplx: complexity,
+                    inputs: vec![input_idx],
+                },
+            );
+        }
+
+        deltas.push(CorpusDelta {
+            path: self.path(list_index, complexity),
+            add: true,
+            remove: vec![],
+        });
+        deltas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[no_coverage]
+    fn test_test_failure_pool_keeps_the_simplest_inputs_per_signature() {
+        let mut pool = TestFailurePool::new("test_failures", 2);
+
+        assert_eq!(summary(&run(&mut pool, 0, 1, 10.0)), vec![(true, vec![])]);
+        assert_eq!(summary(&run(&mut pool, 1, 1, 10.0)), vec![(true, vec![])]);
+        // the limit is reached and the input is not simpler than the saved ones
+        assert!(run(&mut pool, 2, 1, 10.0).is_empty());
+        // a simpler input replaces one of the most complex ones
+        assert_eq!(
+            summary(&run(&mut pool, 3, 1, 5.0)),
+            vec![(false, vec![PoolStorageIndex::mock(1)]), (true, vec![])]
+        );
+        assert_eq!(
+            summary(&run(&mut pool, 4, 1, 2.0)),
+            vec![(false, vec![PoolStorageIndex::mock(0)]), (true, vec![])]
+        );
+        // another signature has its own limit
+        assert_eq!(summary(&run(&mut pool, 5, 2, 10.0)), vec![(true, vec![])]);
+        assert_eq!(pool.stats().count, 2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_panic_signature_skips_the_panic_machinery() {
+        let signature =
+            TestFailure::panic_signature("oops", &backtrace("std::panicking::rust_panic_with_hook", "parse"));
+        assert_eq!(
+            signature,
+            TestFailure::panic_signature(
+                "oops",
+                &backtrace("fuzzcheck::fuzzer::Fuzzer::test_and_process_input", "parse")
+            )
+        );
+        assert_ne!(
+            signature,
+            TestFailure::panic_signature("oh no", &backtrace("rust_begin_unwind", "parse"))
+        );
+        assert_ne!(
+            signature,
+            TestFailure::panic_signature("oops", &backtrace("rust_begin_unwind", "print"))
+        );
+    }
+
+    /// A backtrace as displayed by `std::backtrace::Backtrace`, panicking in `my_crate::{function}`
+    #[no_coverage]
+    fn backtrace(machinery: &str, function: &str) -> String {
+        [
+            "   0: std::backtrace::Backtrace::force_capture".to_owned(),
+            "             at /rustc/library/std/src/backtrace.rs:315:9".to_owned(),
+            format!("   1: {}", machinery),
+            "   2: core::panicking::panic_fmt".to_owned(),
+            format!("   3: my_crate::{}::h0123456789abcdef", function),
+            "             at ./src/lib.rs:12:5".to_owned(),
+            "   4: my_crate::fuzz_test".to_owned(),
+        ]
+        .join("\n")
+    }
+
+    #[no_coverage]
+    fn run(pool: &mut TestFailurePool, idx: usize, signature: u64, cplx: f64) -> Vec<CorpusDelta> {
+        let failure = TestFailure {
+            display: "failure".to_owned(),
+            id: 0,
+            signature,
+        };
+        pool.process(PoolStorageIndex::mock(idx), &Some(failure), cplx)
+    }
+
+    #[no_coverage]
+    fn summary(deltas: &[CorpusDelta]) -> Vec<(bool, Vec<PoolStorageIndex>)> {
+        deltas
+            .iter()
+            .map(
+                #[no_coverage]
+                |delta| (delta.add, delta.remove.clone()),
+            )
+            .collect()
     }
 }
@@ -2,26 +2,42 @@ use crate::data_structures::{Slab, SlabKey};
 use crate::fenwick_tree::FenwickTree;
 use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
 use crate::PoolStorageIndex;
-use crate::{CompatibleWithObservations, ToCSV};
+use crate::{CSVField, CompatibleWithObservations, ToCSV};
 use ahash::{AHashMap, AHashSet};
+use std::cell::Cell;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::path::Path;
 
+/// The statistics of a [`UniqueValuesPool`]
 #[derive(Clone, Default)]
 pub struct UniqueValuesPoolStats {
     pub name: String,
+    /// The number of inputs in the pool
     pub size: usize,
+    /// The number of different `(counter, value)` pairs observed so far
+    pub unique_values: usize,
+    /// The number of `(counter, value)` pairs that were observed for the first time since
+    /// the previous statistics were reported
+    pub new_unique_values: usize,
 }
 impl ToCSV for UniqueValuesPoolStats {
     #[no_coverage]
-    fn csv_headers(&self) -> Vec<crate::CSVField> {
-        vec![]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::String(format!("{}-size", self.name)),
+            CSVField::String(format!("{}-unique-values", self.name)),
+            CSVField::String(format!("{}-new-unique-values", self.name)),
+        ]
     }
 
     #[no_coverage]
-    fn to_csv_record(&self) -> Vec<crate::CSVField> {
-        vec![]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::Integer(self.size as isize),
+            CSVField::Integer(self.unique_values as isize),
+            CSVField::Integer(self.new_unique_values as isize),
+        ]
     }
 }
 
@@ -31,7 +47,10 @@ impl Display for UniqueValuesPoolStats {
         write!(
             f,
             "{}",
-            nu_ansi_term::Color::Blue.paint(format!("{}({})", self.name, self.size))
+            nu_ansi_term::Color::Blue.paint(format!(
+                "{}({} values: {} (+{}))",
+                self.name, self.size, self.unique_values, self.new_unique_values
+            ))
         )
     }
 }
@@ -58,7 +77,9 @@ where
     inputs: Slab<Input<T>>,
     best_input_for_value: Vec<AHashMap<T, SlabKey<Input<T>>>>,
     ranked_inputs: FenwickTree,
-    stats: UniqueValuesPoolStats,
+    nbr_unique_values: usize,
+    /// The number of unique values when the statistics were last reported
+    nbr_reported_unique_values: Cell<usize>,
     rng: fastrand::Rng,
 }
 impl<T> Debug for UniqueValuesPool<T>
@@ -89,10 +110,8 @@ where
             inputs: Slab::new(),
             best_input_for_value: vec![AHashMap::new(); size],
             ranked_inputs: FenwickTree::new(vec![]),
-            stats: UniqueValuesPoolStats {
-                name: name.to_string(),
-                size: 0,
-            },
+            nbr_unique_values: 0,
+            nbr_reported_unique_values: Cell::new(0),
             rng: fastrand::Rng::new(),
         }
     }
@@ -106,7 +125,14 @@ where
 
     #[no_coverage]
     fn stats(&self) -> Self::Stats {
-        self.stats.clone()
+        let new_unique_values = self.nbr_unique_values - self.nbr_reported_unique_values.get();
+        self.nbr_reported_unique_values.set(self.nbr_unique_values);
+        UniqueValuesPoolStats {
+            name: self.name.clone(),
+            size: self.inputs.len(),
+            unique_values: self.nbr_unique_values,
+            new_unique_values,
+        }
     }
 
     #[no_coverage]
@@ -140,7 +166,7 @@ where
     T: Hash + Eq + Clone,
 {
     #[no_coverage]
-    fn update_stats(&mut self) {
+    fn update_ranked_inputs(&mut self) {
         let inputs = &self.inputs;
 
         let ranked_inputs = self
@@ -155,8 +181,6 @@ where
             )
             .collect();
         self.ranked_inputs = FenwickTree::new(ranked_inputs);
-
-        self.stats.size = self.inputs.len();
     }
 }
 
@@ -198,7 +222,9 @@ where
         let mut removed_keys = vec![];
 
         for (counter, id) in &new_observations {
-            self.complexities[*counter].insert(id.clone(), cplx);
+            if self.complexities[*counter].insert(id.clone(), cplx).is_none() {
+                self.nbr_unique_values += 1;
+            }
 
             let previous_best_key = self.best_input_for_value[*counter].get_mut(id);
             if let Some(previous_best_key) = previous_best_key {
@@ -224,7 +250,7 @@ where
                 |k| self.inputs[k].data,
             )
             .collect();
-        self.update_stats();
+        self.update_ranked_inputs();
         return vec![CorpusDelta {
             path: Path::new(&self.name).to_path_buf(),
             add: true,
@@ -232,3 +258,30 @@ where
         }];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[no_coverage]
+    fn test_unique_values_pool_stats() {
+        let mut pool = UniqueValuesPool::<u64>::new("unique", 4);
+        assert_eq!(pool.stats().unique_values, 0);
+
+        run(&mut pool, 0, vec![(0, 1), (1, 2)]);
+        let stats = pool.stats();
+        assert_eq!((stats.size, stats.unique_values, stats.new_unique_values), (1, 2, 2));
+        assert_eq!(pool.stats().new_unique_values, 0);
+
+        run(&mut pool, 1, vec![(0, 3), (2, 2)]);
+        run(&mut pool, 2, vec![(3, 1)]);
+        let stats = pool.stats();
+        assert_eq!((stats.size, stats.unique_values, stats.new_unique_values), (3, 5, 3));
+    }
+
+    #[no_coverage]
+    fn run(pool: &mut UniqueValuesPool<u64>, idx: usize, observations: Vec<(usize, u64)>) {
+        pool.process(PoolStorageIndex::mock(idx), &observations, 1.0);
+    }
+}
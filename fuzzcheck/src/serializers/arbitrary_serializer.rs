@@ -0,0 +1,49 @@
+use std::marker::PhantomData;
+
+use crate::mutators::arbitrary_adapter::ArbitraryValue;
+
+/// A serializer for the values generated by [`ArbitraryAdapterMutator`](crate::mutators::arbitrary_adapter::ArbitraryAdapterMutator),
+/// which copies their bytes from/to the files.
+///
+/// The files have the same content as the ones of a `cargo fuzz` corpus, so such a corpus can be given to
+/// fuzzcheck as its input corpus. The extension is customizable.
+/// ```
+/// use fuzzcheck::ArbitrarySerializer;
+///
+/// let ser = ArbitrarySerializer::<(u8, String)>::new("bin");
+/// ```
+#[doc(cfg(feature = "arbitrary_mutator"))]
+pub struct ArbitrarySerializer<T> {
+    ext: &'static str,
+    phantom: PhantomData<T>,
+}
+
+impl<T> ArbitrarySerializer<T> {
+    #[no_coverage]
+    pub fn new(ext: &'static str) -> Self {
+        Self {
+            ext,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> crate::traits::Serializer for ArbitrarySerializer<T>
+where
+    T: for<'a> arbitrary::Arbitrary<'a>,
+{
+    type Value = ArbitraryValue<T>;
+
+    #[no_coverage]
+    fn extension(&self) -> &str {
+        self.ext
+    }
+    #[no_coverage]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        Some(ArbitraryValue::new(data.to_vec()))
+    }
+    #[no_coverage]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        value.bytes().to_vec()
+    }
+}
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+/// A serializer that uses `serde` and `bincode` to encode the test inputs (of
+/// arbitrary type `T: Serializable + for<'e> Deserializable<'e>`) in a compact
+/// binary format.
+///
+/// The files have the `bin` extension. They are much smaller and faster to decode
+/// than the json files of [`SerdeSerializer`](crate::SerdeSerializer), which helps
+/// when replaying large corpora of complex values, but they are not human-readable.
+/// ```
+/// use fuzzcheck::BincodeSerializer;
+///
+/// let ser = BincodeSerializer::<Vec<(u8, String)>>::default();
+/// ```
+#[doc(cfg(feature = "bincode_serializer"))]
+pub struct BincodeSerializer<S> {
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for BincodeSerializer<S> {
+    #[no_coverage]
+    fn default() -> Self {
+        Self { phantom: PhantomData }
+    }
+}
+
+impl<S> crate::traits::Serializer for BincodeSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[no_coverage]
+    fn extension(&self) -> &str {
+        "bin"
+    }
+    #[no_coverage]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        bincode::deserialize(data).ok()
+    }
+    #[no_coverage]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        bincode::serialize(value).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BincodeSerializer;
+    use crate::Serializer;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Request {
+        id: u32,
+        name: String,
+        values: Vec<u64>,
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_bincode_serializer_round_trip() {
+        let ser = BincodeSerializer::<Request>::default();
+        assert_eq!(ser.extension(), "bin");
+
+        let request = Request {
+            id: 42,
+            name: "αβγ".to_owned(),
+            values: vec![0, 1, u64::MAX],
+        };
+        let data = ser.to_data(&request);
+        assert_eq!(ser.from_data(&data), Some(request));
+
+        // a truncated value is rejected
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+        assert_eq!(ser.from_data(&[]), None);
+
+        // a name that is not valid UTF-8 is rejected
+        let mut data = ser.to_data(&Request {
+            id: 1,
+            name: "abc".to_owned(),
+            values: vec![],
+        });
+        // the name starts after the id (4 bytes) and its length (8 bytes)
+        data[12] = 0xff;
+        assert_eq!(ser.from_data(&data), None);
+    }
+}
@@ -1,20 +1,44 @@
 //! Types implementing the [Serializer] trait.
 //!
-//! There are currently three implementations:
+//! There are currently seven implementations:
 //!
 //! * SerdeSerializer uses the `serde` and `serde_json` crate to serialize
 //! the test inputs (of arbitrary Serializable type) to a `.json` file.
 //!
+//! * BincodeSerializer uses the `serde` and `bincode` crates to encode the test
+//! inputs (of arbitrary Serializable type) to compact binary `.bin` files.
+//!
+//! * ProstSerializer uses the `prost` crate to encode the test inputs (of any
+//! `prost::Message` type) to binary Protocol Buffers `.pb` files.
+//!
 //! * [ByteSerializer] encodes and decodes values of type `Vec<u8>` by simply
 //! copy/pasting the bytes from/to the files. The extension is customizable.
 //!
 //! * [StringSerializer] encodes and decodes values of any type implementing
 //! `FromStr` and `ToString` into utf-8 encoded text files.
+//!
+//! * [MultiFormatSerializer] combines several serializers of the same type and
+//! decodes each file with the serializer matching its extension.
+//!
+//! * ArbitrarySerializer copies the bytes from which the values generated by the
+//! `ArbitraryAdapterMutator` are decoded, like the corpus files of `cargo fuzz`.
 
+#[cfg(feature = "arbitrary_mutator")]
+mod arbitrary_serializer;
+#[cfg(feature = "bincode_serializer")]
+mod bincode_serializer;
+#[cfg(feature = "prost_serializer")]
+mod prost_serializer;
 #[cfg(feature = "serde_json_serializer")]
 mod serde_serializer;
 use std::{marker::PhantomData, str::FromStr};
 
+#[cfg(feature = "arbitrary_mutator")]
+pub use arbitrary_serializer::ArbitrarySerializer;
+#[cfg(feature = "bincode_serializer")]
+pub use bincode_serializer::BincodeSerializer;
+#[cfg(feature = "prost_serializer")]
+pub use prost_serializer::ProstSerializer;
 #[cfg(feature = "serde_json_serializer")]
 pub use serde_serializer::SerdeSerializer;
 
@@ -109,3 +133,72 @@ where
         value.to_string().into_bytes()
     }
 }
+
+/**
+A serializer that reads files in several formats, choosing the format of each file based on its extension.
+
+New files are always written with the primary serializer. This makes it possible to migrate a corpus from
+one format to another: the corpus can temporarily contain files in both formats, and the old files are
+progressively replaced by files in the new format.
+
+```
+use fuzzcheck::{ByteSerializer, MultiFormatSerializer};
+
+// write `.bin` files, but also read the `.dat` files that are still in the corpus
+let ser = MultiFormatSerializer::new(ByteSerializer::new("bin"))
+    .with_format(ByteSerializer::new("dat"));
+```
+
+Files whose extension does not match any serializer are read with the primary serializer.
+*/
+pub struct MultiFormatSerializer<T> {
+    primary: Box<dyn Serializer<Value = T>>,
+    others: Vec<Box<dyn Serializer<Value = T>>>,
+}
+
+impl<T> MultiFormatSerializer<T> {
+    /// Create a serializer that reads and writes files with the `primary` serializer.
+    #[no_coverage]
+    pub fn new(primary: impl Serializer<Value = T> + 'static) -> Self {
+        Self {
+            primary: Box::new(primary),
+            others: vec![],
+        }
+    }
+
+    /// Also read the files whose extension is the one of `serializer`, using that serializer.
+    #[no_coverage]
+    pub fn with_format(mut self, serializer: impl Serializer<Value = T> + 'static) -> Self {
+        self.others.push(Box::new(serializer));
+        self
+    }
+}
+
+impl<T> Serializer for MultiFormatSerializer<T> {
+    type Value = T;
+
+    #[no_coverage]
+    fn extension(&self) -> &str {
+        self.primary.extension()
+    }
+    #[no_coverage]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.primary.from_data(data)
+    }
+    #[no_coverage]
+    fn from_file(&self, extension: &str, data: &[u8]) -> Option<Self::Value> {
+        if self.primary.extension() == extension {
+            return self.primary.from_file(extension, data);
+        }
+        for serializer in self.others.iter() {
+            if serializer.extension() == extension {
+                return serializer.from_file(extension, data);
+            }
+        }
+        self.primary.from_file(extension, data)
+    }
+    #[no_coverage]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        self.primary.to_data(value)
+    }
+}
@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+
+/// A serializer that uses `prost` to encode the test inputs (of any
+/// type `M: prost::Message + Default`) as binary Protocol Buffers messages.
+///
+/// The files have the `pb` extension. Files that cannot be decoded into a valid
+/// message of type `M` are rejected.
+/// ```ignore
+/// use fuzzcheck::ProstSerializer;
+///
+/// // where `Request` is a message type generated by prost-build
+/// let ser = ProstSerializer::<Request>::default();
+/// ```
+#[doc(cfg(feature = "prost_serializer"))]
+pub struct ProstSerializer<M> {
+    phantom: PhantomData<M>,
+}
+
+impl<M> Default for ProstSerializer<M> {
+    #[no_coverage]
+    fn default() -> Self {
+        Self { phantom: PhantomData }
+    }
+}
+
+impl<M> crate::traits::Serializer for ProstSerializer<M>
+where
+    M: prost::Message + Default,
+{
+    type Value = M;
+
+    #[no_coverage]
+    fn extension(&self) -> &str {
+        "pb"
+    }
+    #[no_coverage]
+    fn from_data(&self, data: &[u8]) -> Option<M> {
+        M::decode(data).ok()
+    }
+    #[no_coverage]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        value.encode_to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProstSerializer;
+    use crate::Serializer;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Request {
+        #[prost(uint32, tag = "1")]
+        id: u32,
+        #[prost(string, tag = "2")]
+        name: String,
+        #[prost(uint64, repeated, tag = "3")]
+        values: Vec<u64>,
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_prost_serializer_round_trip() {
+        let ser = ProstSerializer::<Request>::default();
+        assert_eq!(ser.extension(), "pb");
+
+        let request = Request {
+            id: 42,
+            name: "αβγ".to_owned(),
+            values: vec![0, 1, u64::MAX],
+        };
+        let data = ser.to_data(&request);
+        assert_eq!(ser.from_data(&data), Some(request));
+
+        // the default message is encoded as an empty buffer
+        let data = ser.to_data(&Request::default());
+        assert!(data.is_empty());
+        assert_eq!(ser.from_data(&data), Some(Request::default()));
+
+        // a truncated message is rejected
+        let data = ser.to_data(&Request {
+            id: 1,
+            name: "abc".to_owned(),
+            values: vec![],
+        });
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+    }
+}
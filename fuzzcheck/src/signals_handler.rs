@@ -2,10 +2,11 @@
 // ! recover from crashes.
 
 use std::ptr;
+use std::time::Duration;
 
 use libc::{
-    sigaction, sigemptyset, SA_NODEFER, SA_ONSTACK, SA_SIGINFO, SIGABRT, SIGBUS, SIGFPE, SIGINT, SIGSEGV, SIGTERM,
-    SIGTRAP, SIG_DFL,
+    sigaction, sigemptyset, SA_NODEFER, SA_ONSTACK, SA_SIGINFO, SIGABRT, SIGALRM, SIGBUS, SIGFPE, SIGINT, SIGSEGV,
+    SIGTERM, SIGTRAP, SIG_DFL,
 };
 
 static mut SIGNAL_HANDLER: Option<Box<dyn Fn(libc::c_int) -> !>> = None;
@@ -52,7 +53,9 @@ where
     sa.sa_flags = SA_NODEFER | SA_SIGINFO | SA_ONSTACK;
     sa.sa_sigaction = os_handler as usize;
 
-    let signals = [SIGINT, SIGTERM, SIGSEGV, SIGBUS, SIGABRT, SIGFPE, SIGABRT, SIGTRAP];
+    let signals = [
+        SIGINT, SIGTERM, SIGSEGV, SIGBUS, SIGABRT, SIGFPE, SIGABRT, SIGTRAP, SIGALRM,
+    ];
     for sig in signals {
         if sigaction(sig as i32, &mut sa as *mut sigaction, ptr::null_mut()) < 0 {
             panic!("Could not set up signal handler");
@@ -66,9 +69,28 @@ pub(crate) unsafe fn reset_signal_handlers() {
     sigemptyset(&mut sa.sa_mask as *mut libc::sigset_t);
     sa.sa_sigaction = SIG_DFL;
 
-    for &signal in &[SIGINT, SIGTERM, SIGSEGV, SIGBUS, SIGABRT, SIGFPE, SIGABRT, SIGTRAP] {
+    for &signal in &[
+        SIGINT, SIGTERM, SIGSEGV, SIGBUS, SIGABRT, SIGFPE, SIGABRT, SIGTRAP, SIGALRM,
+    ] {
         if sigaction(signal, &mut sa as *mut sigaction, ptr::null_mut()) < 0 {
             panic!("Could not set up signal handler");
         }
     }
 }
+
+/// Deliver a `SIGALRM` signal to the process after the given duration of wall-clock time,
+/// or cancel the pending one if `duration` is `None`.
+#[no_coverage]
+pub(crate) unsafe fn set_alarm(duration: Option<Duration>) {
+    let duration = duration.unwrap_or_default();
+    let timer = libc::itimerval {
+        it_interval: libc::timeval { tv_sec: 0, tv_usec: 0 },
+        it_value: libc::timeval {
+            tv_sec: duration.as_secs() as libc::time_t,
+            tv_usec: duration.subsec_micros() as libc::suseconds_t,
+        },
+    };
+    if libc::setitimer(libc::ITIMER_REAL, &timer, ptr::null_mut()) < 0 {
+        panic!("Could not set up the timer for detecting hangs");
+    }
+}
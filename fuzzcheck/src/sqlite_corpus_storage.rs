@@ -0,0 +1,236 @@
+//! A [`CorpusStorage`] keeping the whole output corpus in a single SQLite database file.
+//!
+//! It is available with the `sqlite` feature.
+
+use std::io::{self, Result};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::world::CorpusStorage;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS corpus (
+    path TEXT PRIMARY KEY NOT NULL,
+    pool TEXT NOT NULL,
+    content BLOB NOT NULL,
+    complexity REAL,
+    discovered_at_ms INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS corpus_pool ON corpus (pool);
+";
+
+/// A test case of a [`SqliteCorpusStorage`] and its metadata
+#[doc(cfg(feature = "sqlite"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqliteCorpusEntry {
+    /// The path of the test case, relative to the root of the corpus
+    pub file: PathBuf,
+    /// The folder of the pool that added the test case to the corpus
+    pub pool: PathBuf,
+    /// The complexity of the test case, if it was known when it was added
+    pub complexity: Option<f64>,
+    /// When the test case was first added to the corpus
+    pub discovered_at: SystemTime,
+}
+
+/**
+Store the test cases of the output corpus, along with their complexity, the pool that added them, and the time at
+which they were discovered, in a single SQLite database file.
+
+A long fuzzing campaign can save hundreds of thousands of small files to the output corpus, which is slow to list,
+copy, and back up. With this storage, the corpus is a single file that is safe to copy while the fuzzer is stopped.
+Adding or removing a test case is a transaction, so the corpus is never half-written, even if the fuzzer is killed.
+
+The storage is selected with [`corpus_storage`](crate::builder::FuzzerBuilder5::corpus_storage):
+```no_run
+# fn parse(message: &Vec<u8>) {}
+use fuzzcheck::SqliteCorpusStorage;
+
+let storage = SqliteCorpusStorage::open("fuzz/parse/corpus.sqlite").unwrap();
+let result = fuzzcheck::fuzz_test(parse)
+    .default_options()
+    .corpus_storage(storage)
+    .launch();
+```
+*/
+#[doc(cfg(feature = "sqlite"))]
+pub struct SqliteCorpusStorage {
+    connection: Connection,
+}
+
+#[no_coverage]
+fn sqlite_error(error: rusqlite::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+#[no_coverage]
+fn path_to_sql(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(
+        #[no_coverage]
+        || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the path {} is not valid UTF-8", path.display()),
+            )
+        },
+    )
+}
+
+impl SqliteCorpusStorage {
+    /// Open the database at the given path, creating it if it does not exist
+    #[no_coverage]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(folder) = path.parent() {
+            if !folder.as_os_str().is_empty() && !folder.is_dir() {
+                std::fs::create_dir_all(folder)?;
+            }
+        }
+        let connection = Connection::open(path).map_err(sqlite_error)?;
+        // the write-ahead log makes each addition to the corpus much cheaper than a full synchronisation
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(sqlite_error)?;
+        connection
+            .pragma_update(None, "synchronous", "NORMAL")
+            .map_err(sqlite_error)?;
+        connection.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(Self { connection })
+    }
+
+    /// All the test cases of the corpus and their metadata, sorted by path
+    #[no_coverage]
+    pub fn entries(&self) -> Result<Vec<SqliteCorpusEntry>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT path, pool, complexity, discovered_at_ms FROM corpus ORDER BY path")
+            .map_err(sqlite_error)?;
+        let rows = statement
+            .query_map(
+                [],
+                #[no_coverage]
+                |row| {
+                    let file: String = row.get(0)?;
+                    let pool: String = row.get(1)?;
+                    let discovered_at_ms: i64 = row.get(3)?;
+                    Ok(SqliteCorpusEntry {
+                        file: PathBuf::from(file),
+                        pool: PathBuf::from(pool),
+                        complexity: row.get(2)?,
+                        discovered_at: SystemTime::UNIX_EPOCH + Duration::from_millis(discovered_at_ms as u64),
+                    })
+                },
+            )
+            .map_err(sqlite_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(sqlite_error)
+    }
+}
+
+impl CorpusStorage for SqliteCorpusStorage {
+    #[no_coverage]
+    fn add(&mut self, file: &Path, content: &[u8], cplx: Option<f64>) -> Result<()> {
+        let pool = file.parent().unwrap_or_else(
+            #[no_coverage]
+            || Path::new(""),
+        );
+        let discovered_at_ms = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+        // a test case that is added again keeps the time at which it was first discovered
+        self.connection
+            .execute(
+                "INSERT INTO corpus (path, pool, content, complexity, discovered_at_ms) VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT (path) DO UPDATE SET content = excluded.content, complexity = excluded.complexity",
+                params![path_to_sql(file)?, path_to_sql(pool)?, content, cplx, discovered_at_ms],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    #[no_coverage]
+    fn remove(&mut self, file: &Path) -> Result<()> {
+        self.connection
+            .execute("DELETE FROM corpus WHERE path = ?1", params![path_to_sql(file)?])
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    #[no_coverage]
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT path FROM corpus ORDER BY path")
+            .map_err(sqlite_error)?;
+        let rows = statement
+            .query_map(
+                [],
+                #[no_coverage]
+                |row| row.get::<_, String>(0).map(PathBuf::from),
+            )
+            .map_err(sqlite_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(sqlite_error)
+    }
+
+    #[no_coverage]
+    fn read(&self, file: &Path) -> Result<Vec<u8>> {
+        let content: Option<Vec<u8>> = self
+            .connection
+            .query_row(
+                "SELECT content FROM corpus WHERE path = ?1",
+                params![path_to_sql(file)?],
+                #[no_coverage]
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        content.ok_or_else(
+            #[no_coverage]
+            || {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} is not in the corpus", file.display()),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteCorpusStorage;
+    use crate::world::CorpusStorage;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    #[no_coverage]
+    fn test_sqlite_corpus_storage() {
+        let folder = std::env::temp_dir().join(format!("fuzzcheck-sqlite-storage-{}", std::process::id()));
+        let database = folder.join("corpus.sqlite");
+        {
+            let mut storage = SqliteCorpusStorage::open(&database).unwrap();
+            storage.add(Path::new("coverage/b.json"), b"b", Some(2.0)).unwrap();
+            storage.add(Path::new("a.json"), b"a", None).unwrap();
+            let discovered_at = storage.entries().unwrap()[1].discovered_at;
+            storage.add(Path::new("coverage/b.json"), b"bb", Some(3.0)).unwrap();
+            storage.remove(Path::new("a.json")).unwrap();
+            storage.remove(Path::new("missing.json")).unwrap();
+
+            let entries = storage.entries().unwrap();
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].file, PathBuf::from("coverage/b.json"));
+            assert_eq!(entries[0].pool, PathBuf::from("coverage"));
+            assert_eq!(entries[0].complexity, Some(3.0));
+            assert_eq!(entries[0].discovered_at, discovered_at);
+        }
+        // the corpus is still there when the database is opened again
+        let storage = SqliteCorpusStorage::open(&database).unwrap();
+        assert_eq!(storage.list().unwrap(), vec![PathBuf::from("coverage/b.json")]);
+        assert_eq!(storage.read(Path::new("coverage/b.json")).unwrap(), b"bb");
+        assert!(storage.read(Path::new("a.json")).is_err());
+        drop(storage);
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+}
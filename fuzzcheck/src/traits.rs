@@ -238,6 +238,32 @@ pub trait Mutator<Value: Clone>: 'static {
     /// the given [`UnmutateToken`](Mutator::UnmutateToken).
     fn unmutate(&self, value: &mut Value, cache: &mut Self::Cache, t: Self::UnmutateToken);
 
+    /// Mutates a value (and optionally its cache) by combining it with another value, such as
+    /// by replacing a part of `value` with a part of `other`.
+    ///
+    /// The fuzzer occasionally calls this method with two test cases of its pool. Both were validated
+    /// by this mutator, and `other_cache` is the cache of `other`.
+    ///
+    /// The mutated value should be within the given `max_cplx`. Returns `None` if the mutator does not
+    /// support crossover or if it could not combine the two values, in which case the fuzzer
+    /// falls back to [`ordered_mutate`](Mutator::ordered_mutate). Otherwise, return the
+    /// [`UnmutateToken`](Mutator::UnmutateToken) that describes how to undo the mutation, as well as
+    /// the new complexity of the value.
+    ///
+    /// The default implementation returns `None`.
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut Value,
+        cache: &mut Self::Cache,
+        other: &Value,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let _ = (value, cache, other, other_cache, max_cplx);
+        None
+    }
+
     type RecursingPartIndex: Clone;
 
     fn default_recursing_part_index(&self, value: &Value, cache: &Self::Cache) -> Self::RecursingPartIndex;
@@ -272,6 +298,21 @@ pub trait Serializer {
     /// This method can fail by returning `None`
     fn from_data(&self, data: &[u8]) -> Option<Self::Value>;
 
+    /// Deserialize the content of a file with the given extension into the value.
+    ///
+    /// This is the method used by the fuzzer to read the files of the input corpus, the artifacts, and
+    /// the input files given on the command line. The `extension` does not contain the leading dot, and is
+    /// empty if the file has no extension.
+    ///
+    /// The default implementation ignores the extension and calls [`from_data`](Serializer::from_data), so
+    /// existing serializers do not need to implement it. Serializers that understand several formats, such
+    /// as [`MultiFormatSerializer`](crate::MultiFormatSerializer), override it.
+    #[no_coverage]
+    fn from_file(&self, extension: &str, data: &[u8]) -> Option<Self::Value> {
+        let _ = extension;
+        self.from_data(data)
+    }
+
     /// Serialize the value into bytes
     ///
     /// This method should never fail.
@@ -493,6 +534,20 @@ where
         self.wrapped_mutator().random_mutate(value, cache, max_cplx)
     }
 
+    #[doc(hidden)]
+    #[no_coverage]
+    fn crossover_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        other: &T,
+        other_cache: &Self::Cache,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        self.wrapped_mutator()
+            .crossover_mutate(value, cache, other, other_cache, max_cplx)
+    }
+
     #[doc(hidden)]
     type RecursingPartIndex = W::RecursingPartIndex;
     #[doc(hidden)]
@@ -620,6 +675,21 @@ pub trait SensorAndPool: SaveToStatsFolder {
     fn stop_recording(&mut self);
     fn process(&mut self, input_id: PoolStorageIndex, cplx: f64) -> Vec<CorpusDelta>;
     fn get_random_index(&mut self) -> Option<PoolStorageIndex>;
+    /// See [`Pool::save_state`]
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+    /// See [`Pool::restore_state`]
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let _ = (state, new_index);
+        Err("the pool cannot restore its state".to_owned())
+    }
 }
 impl<A, B> SaveToStatsFolder for (A, B)
 where
@@ -660,6 +730,18 @@ where
     fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
         self.1.get_random_index()
     }
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        self.1.save_state()
+    }
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        self.1.restore_state(state, new_index)
+    }
 }
 
 pub enum CSVField {
@@ -750,6 +832,31 @@ pub trait Pool: SaveToStatsFolder {
     fn weight(&self) -> f64 {
         1.0
     }
+
+    /// Serialize the internal state of the pool, such that a fuzz test resumed with `--resume` can
+    /// [restore](Pool::restore_state) it instead of running the test function on all its test cases again.
+    ///
+    /// The default implementation returns `None`, which means that the pool cannot save its state. The test cases
+    /// are then tested again when the fuzz test resumes.
+    #[no_coverage]
+    fn save_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restore the state saved by [`save_state`](Pool::save_state) in a previous run of the same fuzz target.
+    ///
+    /// The test cases are given new indices when the fuzz test resumes. `new_index` maps the index of a test case
+    /// in the saved state to its new index, and returns `None` if it is unknown. An error is returned if the
+    /// state is invalid.
+    #[no_coverage]
+    fn restore_state(
+        &mut self,
+        state: &[u8],
+        new_index: &dyn Fn(PoolStorageIndex) -> Option<PoolStorageIndex>,
+    ) -> Result<(), String> {
+        let _ = (state, new_index);
+        Err("the pool cannot restore its state".to_owned())
+    }
 }
 
 pub trait PoolExt: Pool + Sized {
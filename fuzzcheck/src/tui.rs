@@ -0,0 +1,152 @@
+//! A live progress display for the terminal, enabled by the `tui` feature.
+//!
+//! Instead of printing a new line for each event of the fuzzer, a small panel is redrawn in place, showing
+//! the number of iterations, the iterations per second and a sparkline of their recent history, the
+//! statistics of the pools (e.g. the size of their corpus and the number of coverage counters hit), the
+//! last event, and the most recent artifact.
+//!
+//! The panel is only used when the standard output is a terminal. Anything else that the fuzzer prints, such
+//! as a test failure, is printed below the panel, which is then drawn again after it.
+
+use crate::traits::Stats;
+use fuzzcheck_common::{FuzzerEvent, FuzzerStats};
+use nu_ansi_term::Color;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The number of samples of the iterations per second shown by the sparkline
+const NBR_THROUGHPUT_SAMPLES: usize = 60;
+
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+pub(crate) struct Tui {
+    throughput: VecDeque<usize>,
+    last_artifact: Option<PathBuf>,
+    /// The number of lines of the panel currently on screen, which are erased before it is drawn again
+    nbr_lines_drawn: usize,
+}
+
+impl Tui {
+    /// Create the display if the standard output is a terminal
+    #[no_coverage]
+    pub(crate) fn new_if_terminal() -> Option<Self> {
+        if unsafe { libc::isatty(libc::STDOUT_FILENO) } == 1 {
+            Some(Self {
+                throughput: VecDeque::with_capacity(NBR_THROUGHPUT_SAMPLES),
+                last_artifact: None,
+                nbr_lines_drawn: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Keep the panel currently on screen, so that the text printed after it is not erased by the next draw
+    #[no_coverage]
+    pub(crate) fn detach(&mut self) {
+        self.nbr_lines_drawn = 0;
+    }
+
+    #[no_coverage]
+    pub(crate) fn set_last_artifact(&mut self, path: PathBuf) {
+        self.last_artifact = Some(path);
+    }
+
+    /// Redraw the panel with the given statistics
+    #[no_coverage]
+    pub(crate) fn draw(
+        &mut self,
+        time_since_start: Duration,
+        event: &FuzzerEvent,
+        fuzzer_stats: &FuzzerStats,
+        pool_stats: &dyn Stats,
+    ) {
+        if self.throughput.len() == NBR_THROUGHPUT_SAMPLES {
+            self.throughput.pop_front();
+        }
+        self.throughput.push_back(fuzzer_stats.exec_per_s);
+
+        let event = match event {
+            FuzzerEvent::Pulse => "pulse".to_owned(),
+            FuzzerEvent::Replace(add, remove) => format!("corpus +{} -{}", add, remove),
+            FuzzerEvent::Slow => "slow test case".to_owned(),
+            _ => String::new(),
+        };
+        let artifact = self.last_artifact.as_ref().map_or_else(
+            #[no_coverage]
+            || "none".to_owned(),
+            #[no_coverage]
+            |path| path.display().to_string(),
+        );
+        let lines = [
+            (
+                "time",
+                format!(
+                    "{}s   iterations {}   iter/s {}",
+                    time_since_start.as_secs(),
+                    fuzzer_stats.total_number_of_runs,
+                    fuzzer_stats.exec_per_s
+                ),
+            ),
+            ("pools", format!("{}", pool_stats)),
+            ("iter/s", self.sparkline()),
+            ("event", event),
+            ("artifact", artifact),
+        ];
+
+        let width = terminal_width();
+        let mut out = String::new();
+        if self.nbr_lines_drawn > 0 {
+            // move the cursor to the first line of the panel
+            out.push_str(&format!("\x1b[{}F", self.nbr_lines_drawn));
+        }
+        for (title, content) in lines.iter() {
+            // the lines are truncated so that they do not wrap, which would break the count of the lines to erase
+            let content = content.chars().take(width.saturating_sub(11)).collect::<String>();
+            out.push_str(&format!(
+                "\x1b[2K{} {}\n",
+                Color::Yellow.paint(format!("{:>9}", title)),
+                content
+            ));
+        }
+        self.nbr_lines_drawn = lines.len();
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    #[no_coverage]
+    fn sparkline(&self) -> String {
+        let max = self.throughput.iter().copied().max().unwrap_or(0);
+        self.throughput
+            .iter()
+            .map(
+                #[no_coverage]
+                |&x| {
+                    if max == 0 {
+                        SPARKLINE_BARS[0]
+                    } else {
+                        SPARKLINE_BARS[x * (SPARKLINE_BARS.len() - 1) / max]
+                    }
+                },
+            )
+            .collect()
+    }
+}
+
+/// The number of columns of the terminal, or 80 if it cannot be determined
+#[no_coverage]
+fn terminal_width() -> usize {
+    let mut size = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) } != 0 {
+        return 80;
+    }
+    let columns = unsafe { size.assume_init() }.ws_col as usize;
+    if columns == 0 {
+        80
+    } else {
+        columns
+    }
+}
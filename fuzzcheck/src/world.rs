@@ -1,29 +1,306 @@
+use crate::code_coverage_sensor::write_json_string;
+use crate::data_structures::SlowInput;
 use crate::fuzzer::PoolStorageIndex;
 use crate::fuzzer::TerminationStatus;
+use crate::saved_state::SavedState;
 use crate::traits::CorpusDelta;
 use crate::traits::SaveToStatsFolder;
 use crate::traits::Stats;
+#[cfg(feature = "tui")]
+use crate::tui::Tui;
 use crate::CSVField;
 use crate::ToCSV;
 use fuzzcheck_common::arg::Arguments;
 use fuzzcheck_common::arg::FuzzerCommand;
+use fuzzcheck_common::arg::StatsFormat;
+use fuzzcheck_common::arg::DEBUG_VALUE_EXTENSION;
+use fuzzcheck_common::ensemble::{EnsembleClient, TestCaseMessage, MAX_MESSAGE_LEN};
+use fuzzcheck_common::results::CampaignResults;
 use fuzzcheck_common::{FuzzerEvent, FuzzerStats};
 use nu_ansi_term::Color;
 use std::cell::RefCell;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write as _;
 use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::io::{self, Result};
+use std::os::unix::io::AsRawFd;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
 use std::time::SystemTime;
 
+/// The name of the file written at the root of the output corpus once all its files have been written.
+///
+/// It is removed before the first file of the output corpus is written or removed, and written again when
+/// the fuzzer stops. If it is missing when the corpus is read, the previous fuzzer was probably killed while
+/// it was writing to the corpus.
+const CORPUS_COMPLETE_MARKER: &str = ".corpus-complete";
+
+/// The name of the file written at the root of the output corpus when the fuzzer first modifies it, and never
+/// removed. The missing marker of a complete corpus is only reported for the folders written by fuzzcheck, so
+/// that a corpus written by hand or by another tool is not reported as incomplete.
+const CORPUS_WRITTEN_MARKER: &str = ".fuzzcheck-corpus";
+
+/// The prefix of the subfolders of the output corpus where the workers launched by `cargo fuzzcheck --jobs`
+/// write their test cases, followed by the index of the worker
+const WORKER_FOLDER_PREFIX: &str = "worker-";
+
+/// The name of the file that the workers launched by `cargo fuzzcheck --jobs` lock while they write to the
+/// output corpus or the artifacts folder that they share
+const LOCK_FILE: &str = ".lock";
+
+/// The subfolder of the artifacts folder where the test cases that exceed the `--hang-threshold` are saved
+const HANGS_FOLDER: &str = "hangs";
+
+/// The subfolder of the artifacts folder where the test cases that exceed the `--max-memory` are saved
+const OOM_FOLDER: &str = "oom";
+
+/// Write `content` to a temporary sibling of `path`, which is then renamed to `path`.
+///
+/// The rename is atomic if both files are on the same file system, so the file at `path` is never half-written.
+/// The name of the temporary file contains the process id, so that several fuzzers writing the same file
+/// do not write to the same temporary file.
+#[no_coverage]
+fn write_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Take an exclusive lock on the lock file of the folder, waiting for the other processes to release it.
+///
+/// The lock is released when the returned file is dropped.
+#[no_coverage]
+fn lock_folder(folder: &Path) -> Result<File> {
+    if !folder.is_dir() {
+        fs::create_dir_all(folder)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(folder.join(LOCK_FILE))?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(file)
+}
+
+/// The folder of the output corpus where the fuzzer writes its test cases, which is the subfolder of the worker
+/// if the fuzzer is a worker of `cargo fuzzcheck --jobs`
+#[no_coverage]
+fn own_output_corpus_folder(corpus: &Path, worker: Option<usize>) -> PathBuf {
+    if let Some(worker) = worker {
+        corpus.join(format!("{}{}", WORKER_FOLDER_PREFIX, worker))
+    } else {
+        corpus.to_path_buf()
+    }
+}
+
+/// The storage of the test cases of the output corpus.
+///
+/// A test case is identified by its path relative to the root of the corpus, made of the folder of the pool
+/// that added it, its name, and the extension of the serializer, such as `coverage/8a3f1b2c.json`.
+///
+/// The fuzzer only adds and removes test cases. Listing and reading them back lets other tools, and the tests
+/// of fuzzcheck itself, inspect a corpus regardless of where it is stored.
+pub trait CorpusStorage {
+    /// Save a test case, replacing the one with the same path, if any.
+    ///
+    /// Its complexity is given if it is known, but only the storages that keep metadata about the test cases use it.
+    fn add(&mut self, file: &Path, content: &[u8], cplx: Option<f64>) -> Result<()>;
+    /// Remove a test case. Removing a test case that is not in the storage is not an error.
+    fn remove(&mut self, file: &Path) -> Result<()>;
+    /// The paths of all the test cases of the storage, sorted
+    fn list(&self) -> Result<Vec<PathBuf>>;
+    /// The content of a test case
+    fn read(&self, file: &Path) -> Result<Vec<u8>>;
+    /// The location of a test case on the file system, if the storage is backed by files
+    #[no_coverage]
+    fn location(&self, _file: &Path) -> Option<PathBuf> {
+        None
+    }
+    /// Called once all the test cases of the corpus have been written, typically when the fuzzer stops
+    #[no_coverage]
+    fn mark_complete(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The default storage of the output corpus, with one file per test case in the `--out-corpus` folder.
+///
+/// The files are written atomically, and the marker of a complete corpus is removed before the first change
+/// and written again by [`mark_complete`](CorpusStorage::mark_complete). The workers of `cargo fuzzcheck --jobs`
+/// each write to their own subfolder of the corpus, and lock the whole corpus while they modify it.
+pub struct FileSystemCorpusStorage {
+    folder: PathBuf,
+    /// The corpus shared by the workers of `cargo fuzzcheck --jobs`, if the fuzzer is one of them
+    shared_corpus: Option<PathBuf>,
+    /// Whether the corpus was modified since the marker of a complete corpus was last written
+    modified: bool,
+}
+
+impl FileSystemCorpusStorage {
+    #[no_coverage]
+    pub fn new(corpus: &Path, worker: Option<usize>) -> Self {
+        Self {
+            folder: own_output_corpus_folder(corpus, worker),
+            shared_corpus: worker.map(
+                #[no_coverage]
+                |_| corpus.to_path_buf(),
+            ),
+            modified: false,
+        }
+    }
+
+    /// Lock the shared output corpus while the fuzzer is a worker of `cargo fuzzcheck --jobs` writing to it
+    #[no_coverage]
+    fn lock(&self) -> Result<Option<File>> {
+        match &self.shared_corpus {
+            Some(corpus) => Ok(Some(lock_folder(corpus)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the marker of a complete corpus before the corpus is first modified, and mark the corpus as written
+    /// by fuzzcheck
+    #[no_coverage]
+    fn start_modifying(&mut self) -> Result<()> {
+        if self.modified {
+            return Ok(());
+        }
+        match fs::remove_file(self.folder.join(CORPUS_COMPLETE_MARKER)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let written_marker = self.folder.join(CORPUS_WRITTEN_MARKER);
+        if !written_marker.exists() {
+            if !self.folder.is_dir() {
+                fs::create_dir_all(&self.folder)?;
+            }
+            write_atomically(&written_marker, &[])?;
+        }
+        self.modified = true;
+        Ok(())
+    }
+}
+
+impl CorpusStorage for FileSystemCorpusStorage {
+    #[no_coverage]
+    fn add(&mut self, file: &Path, content: &[u8], _cplx: Option<f64>) -> Result<()> {
+        let path = self.folder.join(file);
+        if let Some(folder) = path.parent() {
+            if !folder.is_dir() {
+                fs::create_dir_all(folder)?;
+            }
+        }
+        let _lock = self.lock()?;
+        self.start_modifying()?;
+        write_atomically(&path, content)
+    }
+
+    #[no_coverage]
+    fn remove(&mut self, file: &Path) -> Result<()> {
+        let _lock = self.lock()?;
+        self.start_modifying()?;
+        let _ = fs::remove_file(self.folder.join(file));
+        Ok(())
+    }
+
+    #[no_coverage]
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![];
+        World::list_input_corpus_rec(&self.folder, &mut paths)?;
+        let mut files = paths
+            .into_iter()
+            .filter_map(
+                #[no_coverage]
+                |path| path.strip_prefix(&self.folder).ok().map(Path::to_path_buf),
+            )
+            .collect::<Vec<_>>();
+        files.sort();
+        Ok(files)
+    }
+
+    #[no_coverage]
+    fn read(&self, file: &Path) -> Result<Vec<u8>> {
+        fs::read(self.folder.join(file))
+    }
+
+    #[no_coverage]
+    fn location(&self, file: &Path) -> Option<PathBuf> {
+        Some(self.folder.join(file))
+    }
+
+    #[no_coverage]
+    fn mark_complete(&mut self) -> Result<()> {
+        if !self.folder.is_dir() {
+            fs::create_dir_all(&self.folder)?;
+        }
+        write_atomically(&self.folder.join(CORPUS_COMPLETE_MARKER), &[])?;
+        self.modified = false;
+        Ok(())
+    }
+}
+
+/// A storage of the output corpus that keeps the test cases in memory, such that fuzzing does not write to the
+/// disk. It is meant for tests and benchmarks.
+#[derive(Default)]
+pub struct InMemoryCorpusStorage {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl InMemoryCorpusStorage {
+    #[no_coverage]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CorpusStorage for InMemoryCorpusStorage {
+    #[no_coverage]
+    fn add(&mut self, file: &Path, content: &[u8], _cplx: Option<f64>) -> Result<()> {
+        self.files.insert(file.to_path_buf(), content.to_vec());
+        Ok(())
+    }
+
+    #[no_coverage]
+    fn remove(&mut self, file: &Path) -> Result<()> {
+        self.files.remove(file);
+        Ok(())
+    }
+
+    #[no_coverage]
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        Ok(self.files.keys().cloned().collect())
+    }
+
+    #[no_coverage]
+    fn read(&self, file: &Path) -> Result<Vec<u8>> {
+        self.files.get(file).cloned().ok_or_else(
+            #[no_coverage]
+            || {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} is not in the corpus", file.display()),
+                )
+            },
+        )
+    }
+}
+
 impl ToCSV for FuzzerStats {
     #[no_coverage]
     fn csv_headers(&self) -> Vec<CSVField> {
@@ -49,6 +326,24 @@ pub struct World {
     pub corpus: HashMap<(PathBuf, PoolStorageIndex), String>,
     pub stats: Option<RefCell<File>>,
     pub stats_folder: Option<PathBuf>,
+    /// The files of the input corpus that have not been read yet
+    corpus_to_read: Vec<PathBuf>,
+    /// The same files as `corpus_to_read`, for fast lookup
+    corpus_to_read_set: HashSet<PathBuf>,
+    /// The number of files of the input corpus when it was listed
+    input_corpus_size: usize,
+    /// Where the test cases of the output corpus are saved, if there is an output corpus
+    corpus_storage: Option<RefCell<Box<dyn CorpusStorage>>>,
+    /// The files written by the other workers of `cargo fuzzcheck --jobs` that were already read or
+    /// are waiting to be read
+    synced_files: HashSet<PathBuf>,
+    /// The connection to the broker of the ensemble of fuzzers given by `--ensemble`, if any
+    ensemble: Option<EnsembleClient>,
+    /// The hashes of the test cases received from the ensemble, which are not sent back to it
+    ensemble_hashes: HashSet<String>,
+    /// The live progress display, if the `tui` feature is enabled and the standard output is a terminal
+    #[cfg(feature = "tui")]
+    tui: Option<RefCell<Tui>>,
 }
 
 impl World {
@@ -60,12 +355,23 @@ impl World {
             let name = format!("{}", duration_since_epoch.as_millis());
             let stats_folder = stats_folder.join(name);
             std::fs::create_dir_all(&stats_folder)?;
-            let path = stats_folder.join("events").with_extension("csv");
+            let extension = match settings.stats_format {
+                StatsFormat::Csv => "csv",
+                StatsFormat::Json => "jsonl",
+            };
+            let path = stats_folder.join("events").with_extension(extension);
             let file = OpenOptions::new().create_new(true).append(true).open(path)?;
             (Some(RefCell::new(file)), Some(stats_folder))
         } else {
             (None, None)
         };
+        let corpus_storage = settings.corpus_out.as_ref().map(
+            #[no_coverage]
+            |corpus| {
+                let storage = FileSystemCorpusStorage::new(corpus, settings.worker);
+                RefCell::new(Box::new(storage) as Box<dyn CorpusStorage>)
+            },
+        );
         Ok(Self {
             settings,
             initial_instant: std::time::Instant::now(),
@@ -73,11 +379,20 @@ impl World {
             corpus: HashMap::new(),
             stats,
             stats_folder,
+            corpus_to_read: vec![],
+            corpus_to_read_set: HashSet::new(),
+            input_corpus_size: 0,
+            corpus_storage,
+            synced_files: HashSet::new(),
+            ensemble: None,
+            ensemble_hashes: HashSet::new(),
+            #[cfg(feature = "tui")]
+            tui: Tui::new_if_terminal().map(RefCell::new),
         })
     }
 
     #[no_coverage]
-    fn hash(&self, input: &[u8]) -> String {
+    pub(crate) fn hash(&self, input: &[u8]) -> String {
         let mut hasher = DefaultHasher::new();
         input.hash(&mut hasher);
         let hash = hasher.finish();
@@ -85,8 +400,14 @@ impl World {
         hash
     }
 
+    /// Append a row to the CSV file of events of the stats folder.
+    ///
+    /// Nothing is written if the events are written as JSON lines, with `--stats-format json`.
     #[no_coverage]
     pub fn append_stats_file(&self, fields: &[CSVField]) -> Result<()> {
+        if self.settings.stats_format != StatsFormat::Csv {
+            return Ok(());
+        }
         if let Some(stats) = &self.stats {
             let mut stats = stats.try_borrow_mut().unwrap();
             stats.write_all(&CSVField::to_bytes(fields))?;
@@ -99,6 +420,7 @@ impl World {
         &mut self,
         idx: PoolStorageIndex,
         content: Vec<u8>,
+        cplx: f64,
         deltas: &[CorpusDelta],
         extension: &str,
     ) -> Result<()> {
@@ -112,47 +434,270 @@ impl World {
             if *add {
                 let hash = self.hash(&content);
                 let _old = self.corpus.insert((path.to_path_buf(), idx), hash.clone());
-                self.add_to_output_corpus(path, hash.clone(), content.clone(), extension)?;
+                self.add_to_output_corpus(path, hash.clone(), content.clone(), Some(cplx), extension)?;
             }
         }
+        if deltas.iter().any(
+            #[no_coverage]
+            |delta| delta.add,
+        ) {
+            self.send_to_ensemble(content, extension);
+        }
 
         Ok(())
     }
 
+    /// Connect to the broker of the ensemble of fuzzers at the given address, see [`fuzzcheck_common::ensemble`]
     #[no_coverage]
-    pub fn add_to_output_corpus(&self, path: &Path, name: String, content: Vec<u8>, extension: &str) -> Result<()> {
-        if self.settings.corpus_out.is_none() {
-            return Ok(());
+    pub(crate) fn connect_to_ensemble(&mut self, address: &str) -> Result<()> {
+        self.ensemble = Some(EnsembleClient::connect(address)?);
+        Ok(())
+    }
+
+    /// Send a test case that was added to the corpus to the other fuzzers of the ensemble, unless it was
+    /// received from them.
+    ///
+    /// The fuzzer continues alone if the connection to the broker is lost.
+    #[no_coverage]
+    fn send_to_ensemble(&mut self, content: Vec<u8>, extension: &str) {
+        if self.ensemble.is_none() || content.len() > MAX_MESSAGE_LEN {
+            return;
         }
-        let folder = self.settings.corpus_out.as_ref().unwrap().join(path);
+        let hash = self.hash(&content);
+        if self.ensemble_hashes.contains(&hash) {
+            return;
+        }
+        let message = TestCaseMessage {
+            extension: extension.to_owned(),
+            content,
+        };
+        if let Some(Err(e)) = self.ensemble.as_mut().map(
+            #[no_coverage]
+            |ensemble| ensemble.send(&message),
+        ) {
+            println!(
+                "{}",
+                Color::Yellow.paint(format!(
+                    "The connection to the ensemble was lost, the fuzzer continues alone: {}",
+                    e
+                ))
+            );
+            self.ensemble = None;
+        }
+    }
 
-        if !folder.is_dir() {
-            std::fs::create_dir_all(&folder)?;
+    /// The test cases sent by the other fuzzers of the ensemble since the last call, with a path made of their
+    /// hash and the extension of their serializer, such as `ensemble/8a3f1b2c.json`
+    #[no_coverage]
+    pub(crate) fn receive_from_ensemble(&mut self) -> Vec<(PathBuf, Vec<u8>)> {
+        let messages = if let Some(ensemble) = &mut self.ensemble {
+            ensemble.receive()
+        } else {
+            return vec![];
+        };
+        let mut files = vec![];
+        for message in messages {
+            let hash = self.hash(&message.content);
+            let path = Path::new("ensemble").join(&hash).with_extension(&message.extension);
+            self.ensemble_hashes.insert(hash);
+            files.push((path, message.content));
         }
+        files
+    }
 
-        let path = folder.join(name).with_extension(extension);
-        fs::write(path, content)?;
+    /// Save the test cases of the output corpus in the given storage instead of the `--out-corpus` folder
+    #[no_coverage]
+    pub(crate) fn set_corpus_storage(&mut self, storage: Box<dyn CorpusStorage>) {
+        self.corpus_storage = Some(RefCell::new(storage));
+    }
+
+    /// The folder where the test cases of the output corpus are written, if there is an output corpus.
+    ///
+    /// It is the subfolder of the worker in the output corpus if the fuzzer is a worker of `cargo fuzzcheck --jobs`.
+    #[no_coverage]
+    fn output_corpus_folder(&self) -> Option<PathBuf> {
+        let corpus = self.settings.corpus_out.as_ref()?;
+        Some(own_output_corpus_folder(corpus, self.settings.worker))
+    }
+
+    #[no_coverage]
+    pub fn add_to_output_corpus(
+        &self,
+        path: &Path,
+        name: String,
+        content: Vec<u8>,
+        cplx: Option<f64>,
+        extension: &str,
+    ) -> Result<()> {
+        self.add_file_to_output_corpus(&path.join(name).with_extension(extension), content, cplx)
+    }
 
+    /// Add the test case to the output corpus, at the given path relative to the output corpus
+    #[no_coverage]
+    pub(crate) fn add_file_to_output_corpus(&self, file: &Path, content: Vec<u8>, cplx: Option<f64>) -> Result<()> {
+        if let Some(storage) = &self.corpus_storage {
+            storage.borrow_mut().add(file, &content, cplx)?;
+        }
         Ok(())
     }
 
+    /// The path of the file of the output corpus with the given folder, name, and extension, if the output corpus
+    /// is stored in files
+    #[no_coverage]
+    pub(crate) fn output_corpus_file(&self, path: &Path, name: &str, extension: &str) -> Option<PathBuf> {
+        let storage = self.corpus_storage.as_ref()?;
+        storage.borrow().location(&path.join(name).with_extension(extension))
+    }
+
     #[no_coverage]
     pub fn remove_from_output_corpus(&self, path: &Path, name: String, extension: &str) -> Result<()> {
-        if self.settings.corpus_out.is_none() {
+        self.remove_file_from_output_corpus(&path.join(name).with_extension(extension))
+    }
+
+    /// Remove the test case at the given path, relative to the output corpus, from the output corpus
+    #[no_coverage]
+    pub(crate) fn remove_file_from_output_corpus(&self, file: &Path) -> Result<()> {
+        let storage = if let Some(storage) = &self.corpus_storage {
+            storage
+        } else {
             return Ok(());
+        };
+        // the output corpus may be the same folder as the input corpus, which is still being read
+        if let Some(location) = storage.borrow().location(file) {
+            if self.corpus_to_read_set.contains(&location) {
+                return Ok(());
+            }
         }
-        let corpus = self.settings.corpus_out.as_ref().unwrap().as_path().join(path);
-
-        let path = corpus.join(name).with_extension(extension);
-        let _ = fs::remove_file(path);
+        storage.borrow_mut().remove(file)
+    }
 
+    /// Write the marker of a complete corpus to the output corpus, if any.
+    ///
+    /// It must only be called once all the files of the output corpus have been written.
+    #[no_coverage]
+    pub(crate) fn mark_output_corpus_complete(&self) -> Result<()> {
+        if let Some(storage) = &self.corpus_storage {
+            storage.borrow_mut().mark_complete()?;
+        }
         Ok(())
     }
 
+    /// Append the event, as a line of JSON, to the file of events of the stats folder, if the events are written
+    /// as JSON lines.
+    ///
+    /// The line contains the name of the event, the time since the start of the fuzzer and a Unix timestamp in
+    /// milliseconds, and, if given, the number of iterations, the iterations per second, and the statistics of
+    /// the pools, keyed by their CSV headers.
+    #[no_coverage]
+    fn append_json_event(
+        &self,
+        event: &FuzzerEvent,
+        time_since_start: Duration,
+        stats: Option<(&FuzzerStats, &dyn Stats)>,
+    ) -> Result<()> {
+        let file = match &self.stats {
+            Some(file) if self.settings.stats_format == StatsFormat::Json => file,
+            _ => return Ok(()),
+        };
+        let (name, details) = match event {
+            FuzzerEvent::Start => ("start", None),
+            FuzzerEvent::Stop => ("stop", None),
+            FuzzerEvent::End => ("end", None),
+            FuzzerEvent::CrashNoInput => ("crash_no_input", None),
+            FuzzerEvent::Pulse => ("pulse", None),
+            FuzzerEvent::Done => ("done", None),
+            FuzzerEvent::Replace(add, remove) => (
+                "corpus_delta",
+                Some(format!("\"added\": {}, \"removed\": {}", add, remove)),
+            ),
+            FuzzerEvent::DidReadCorpus => ("did_read_corpus", None),
+            FuzzerEvent::CaughtSignal(signal) => ("caught_signal", Some(format!("\"signal\": {}", signal))),
+            FuzzerEvent::TestFailure => ("test_failure", None),
+            FuzzerEvent::Slow => ("slow", None),
+            FuzzerEvent::Hang => ("hang", None),
+            FuzzerEvent::OutOfMemory => ("out_of_memory", None),
+            FuzzerEvent::Stats => ("stats", None),
+            FuzzerEvent::None => return Ok(()),
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut line = String::new();
+        write!(
+            line,
+            "{{\"event\": \"{}\", \"time_ms\": {}, \"timestamp_ms\": {}",
+            name,
+            time_since_start.as_millis(),
+            timestamp
+        )
+        .unwrap();
+        if let Some(details) = details {
+            write!(line, ", {}", details).unwrap();
+        }
+        if let Some((fuzzer_stats, pool_stats)) = stats {
+            write!(
+                line,
+                ", \"nbr_iter\": {}, \"iter_per_s\": {}, \"pools\": {{",
+                fuzzer_stats.total_number_of_runs, fuzzer_stats.exec_per_s
+            )
+            .unwrap();
+            let headers = pool_stats.csv_headers();
+            let record = pool_stats.to_csv_record();
+            for (i, (header, field)) in headers.iter().zip(record.iter()).enumerate() {
+                if i != 0 {
+                    line.push_str(", ");
+                }
+                match header {
+                    CSVField::String(header) => write_json_string(&mut line, header),
+                    CSVField::Integer(header) => write!(line, "\"{}\"", header).unwrap(),
+                    CSVField::Float(header) => write!(line, "\"{}\"", header).unwrap(),
+                }
+                line.push_str(": ");
+                match field {
+                    CSVField::Integer(n) => write!(line, "{}", n).unwrap(),
+                    CSVField::Float(f) if f.is_finite() => write!(line, "{}", f).unwrap(),
+                    // NaN and infinities cannot be represented in JSON
+                    CSVField::Float(_) => line.push_str("null"),
+                    CSVField::String(s) => write_json_string(&mut line, s),
+                }
+            }
+            line.push('}');
+        }
+        line.push_str("}\n");
+        file.try_borrow_mut().unwrap().write_all(line.as_bytes())
+    }
+
     #[no_coverage]
     pub(crate) fn report_event(&self, event: FuzzerEvent, stats: Option<(&FuzzerStats, &dyn Stats)>) {
         // println uses a lock, which may mess up the signal handling
         let time_since_start = self.initial_instant.elapsed();
+        self.append_json_event(&event, time_since_start, stats)
+            .expect("cannot write to stats file");
+        // the periodic records of the statistics are only written to the stats folder
+        if let FuzzerEvent::Stats = event {
+            if let Some((fuzzer_stats, pool_stats)) = stats {
+                self.append_stats_record(time_since_start, fuzzer_stats, pool_stats);
+            }
+            return;
+        }
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &self.tui {
+            match (event, stats) {
+                (
+                    FuzzerEvent::Pulse | FuzzerEvent::Replace(_, _) | FuzzerEvent::Slow,
+                    Some((fuzzer_stats, pool_stats)),
+                ) => {
+                    tui.borrow_mut()
+                        .draw(time_since_start, &event, fuzzer_stats, pool_stats);
+                    self.append_stats_record(time_since_start, fuzzer_stats, pool_stats);
+                    return;
+                }
+                (FuzzerEvent::None, _) => {}
+                // the other events print a message below the panel, which must not be erased
+                _ => tui.borrow_mut().detach(),
+            }
+        }
         let time_since_start_display = {
             let time_since_start_millis = time_since_start.as_millis();
             if time_since_start_millis > 10_000 {
@@ -206,8 +751,17 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
             FuzzerEvent::TestFailure => {
                 println!("\n================ TEST FAILED ================");
             }
+            FuzzerEvent::Slow => {
+                print!("{} ", Color::Yellow.paint("SLOW"));
+            }
+            FuzzerEvent::Hang => {
+                println!("\n================ HANG DETECTED ================");
+            }
+            FuzzerEvent::OutOfMemory => {
+                println!("\n================ OUT OF MEMORY ================");
+            }
             FuzzerEvent::Replace(_, _) => {}
-            FuzzerEvent::None => return,
+            FuzzerEvent::Stats | FuzzerEvent::None => return,
         };
         if let Some((fuzzer_stats, pool_stats)) = stats {
             print!(
@@ -221,14 +775,19 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
             );
 
             println!();
-            let mut stats_fields = vec![CSVField::Integer(time_since_start.as_millis() as isize)];
-            stats_fields.extend(fuzzer_stats.to_csv_record());
-            stats_fields.extend(pool_stats.to_csv_record());
-            self.append_stats_file(&stats_fields)
-                .expect("cannot write to stats file");
+            self.append_stats_record(time_since_start, fuzzer_stats, pool_stats);
         }
     }
 
+    #[no_coverage]
+    fn append_stats_record(&self, time_since_start: Duration, fuzzer_stats: &FuzzerStats, pool_stats: &dyn Stats) {
+        let mut stats_fields = vec![CSVField::Integer(time_since_start.as_millis() as isize)];
+        stats_fields.extend(fuzzer_stats.to_csv_record());
+        stats_fields.extend(pool_stats.to_csv_record());
+        self.append_stats_file(&stats_fields)
+            .expect("cannot write to stats file");
+    }
+
     // #[no_coverage]
     // pub fn set_start_instant(&mut self) {
     //     self.initial_instant = Instant::now();
@@ -241,23 +800,158 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
     pub fn elapsed_time_since_start(&self) -> Duration {
         self.initial_instant.elapsed()
     }
+    /// Pretend that the fuzz test started `elapsed_time` ago, when it resumes from a saved state
+    #[no_coverage]
+    pub(crate) fn set_elapsed_time_since_start(&mut self, elapsed_time: Duration) {
+        self.initial_instant = Instant::now().checked_sub(elapsed_time).unwrap_or(self.initial_instant);
+    }
     #[no_coverage]
     pub fn elapsed_time_since_last_checkpoint(&self) -> usize {
         self.checkpoint_instant.elapsed().as_micros() as usize
     }
+    /// The peak resident set size of the process since it started, in megabytes
+    #[no_coverage]
+    pub(crate) fn peak_memory_usage(&self) -> usize {
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+            return 0;
+        }
+        let max_rss = unsafe { usage.assume_init() }.ru_maxrss as usize;
+        // ru_maxrss is in bytes on macOS and in kilobytes elsewhere
+        if cfg!(target_os = "macos") {
+            max_rss / (1024 * 1024)
+        } else {
+            max_rss / 1024
+        }
+    }
 
+    /// List the files of the input corpus, so that they can then be read lazily, in batches,
+    /// with [`read_input_corpus_batch`](World::read_input_corpus_batch).
+    ///
+    /// Only the paths of the files are kept in memory. The files are read in the order of their paths.
     #[no_coverage]
-    pub fn read_input_corpus(&self) -> Result<Vec<Vec<u8>>> {
+    pub fn start_reading_input_corpus(&mut self) -> Result<()> {
+        self.corpus_to_read.clear();
+        self.corpus_to_read_set.clear();
         if self.settings.corpus_in.is_none() {
-            return Result::Ok(vec![]);
+            return Ok(());
         }
-        let corpus = self.settings.corpus_in.as_ref().unwrap().as_path();
-        let mut values = vec![];
-        self.read_input_corpus_rec(corpus, &mut values)?;
-        Ok(values)
+        let corpus = self.settings.corpus_in.clone().unwrap();
+        let mut paths = vec![];
+        Self::list_input_corpus_rec(&corpus, &mut paths)?;
+        if Self::is_corpus_incomplete(&corpus)? {
+            println!(
+                "WARNING: the input corpus {} was not marked as complete. The fuzzer that wrote it may have \
+                been interrupted, in which case some of its test cases are missing.",
+                corpus.display()
+            );
+        }
+        // the order of the entries given by `fs::read_dir` depends on the platform and file system,
+        // so the files are sorted to always read the same corpus in the same order
+        paths.sort();
+        // the files are popped from the end of the list
+        paths.reverse();
+        self.corpus_to_read_set = paths.iter().cloned().collect();
+        self.input_corpus_size = paths.len();
+        if self.settings.worker.is_some() {
+            // the files of the other workers that are already in the input corpus are not read again
+            self.synced_files.extend(paths.iter().cloned());
+        }
+        self.corpus_to_read = paths;
+        Ok(())
+    }
+
+    /// Whether the corpus contains the leftovers of interrupted writes, or the corpus or one of the subfolders of
+    /// the workers of `cargo fuzzcheck --jobs` was written by fuzzcheck but does not contain the marker of a
+    /// complete corpus
+    #[no_coverage]
+    fn is_corpus_incomplete(corpus: &Path) -> Result<bool> {
+        if Self::contains_interrupted_writes(corpus)? {
+            return Ok(true);
+        }
+        let mut folders = Self::worker_folders(corpus)?;
+        folders.push(corpus.to_path_buf());
+        Ok(folders.iter().any(
+            #[no_coverage]
+            |folder| folder.join(CORPUS_WRITTEN_MARKER).exists() && !folder.join(CORPUS_COMPLETE_MARKER).exists(),
+        ))
     }
+
+    /// Whether the folder or one of its subfolders contains a temporary file left by [`write_atomically`]
     #[no_coverage]
-    fn read_input_corpus_rec(&self, corpus: &Path, values: &mut Vec<Vec<u8>>) -> Result<()> {
+    fn contains_interrupted_writes(folder: &Path) -> Result<bool> {
+        if !folder.is_dir() {
+            return Ok(false);
+        }
+        for entry in fs::read_dir(folder)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if Self::contains_interrupted_writes(&path)? {
+                    return Ok(true);
+                }
+            } else if path.extension() == Some("tmp".as_ref()) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// The subfolders of the corpus where the workers of `cargo fuzzcheck --jobs` write their test cases
+    #[no_coverage]
+    fn worker_folders(corpus: &Path) -> Result<Vec<PathBuf>> {
+        let mut folders = vec![];
+        if !corpus.is_dir() {
+            return Ok(folders);
+        }
+        for entry in fs::read_dir(corpus)? {
+            let path = entry?.path();
+            let is_worker_folder = path
+                .file_name()
+                .and_then(
+                    #[no_coverage]
+                    |name| name.to_str(),
+                )
+                .map_or(
+                    false,
+                    #[no_coverage]
+                    |name| name.starts_with(WORKER_FOLDER_PREFIX),
+                );
+            if is_worker_folder && path.is_dir() {
+                folders.push(path);
+            }
+        }
+        Ok(folders)
+    }
+
+    /// Add the test cases that the other workers of `cargo fuzzcheck --jobs` wrote to the output corpus since the
+    /// last synchronization to the files of the input corpus that have not been read yet.
+    ///
+    /// Returns the number of added files, which is always 0 if the fuzzer is not a worker.
+    #[no_coverage]
+    pub(crate) fn sync_with_other_workers(&mut self) -> Result<usize> {
+        let (corpus, own_folder) = match (&self.settings.corpus_out, self.output_corpus_folder()) {
+            (Some(corpus), Some(own_folder)) if self.settings.worker.is_some() => (corpus.clone(), own_folder),
+            _ => return Ok(0),
+        };
+        let _lock = lock_folder(&corpus)?;
+        let mut paths = vec![];
+        for folder in Self::worker_folders(&corpus)? {
+            if folder != own_folder {
+                Self::list_input_corpus_rec(&folder, &mut paths)?;
+            }
+        }
+        let mut nbr_added_files = 0;
+        for path in paths {
+            if self.synced_files.insert(path.clone()) {
+                self.corpus_to_read_set.insert(path.clone());
+                self.corpus_to_read.push(path);
+                nbr_added_files += 1;
+            }
+        }
+        Ok(nbr_added_files)
+    }
+    #[no_coverage]
+    fn list_input_corpus_rec(corpus: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
         if !corpus.exists() {
             return Ok(());
         }
@@ -271,15 +965,79 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
             let entry = entry?;
             let path = entry.path();
             if path.is_dir() {
-                self.read_input_corpus_rec(&path, values)?;
+                Self::list_input_corpus_rec(&path, paths)?;
+            } else if path.file_name() == Some(CORPUS_COMPLETE_MARKER.as_ref())
+                || path.file_name() == Some(CORPUS_WRITTEN_MARKER.as_ref())
+                || path.file_name() == Some(LOCK_FILE.as_ref())
+                || path.extension() == Some("tmp".as_ref())
+                || path.extension() == Some(DEBUG_VALUE_EXTENSION.as_ref())
+            {
+                // the markers of the corpus, the lock file, the leftovers of interrupted writes, and the
+                // pretty-printed values of the failing test cases are not test cases
+                continue;
             } else {
-                let data = fs::read(path)?;
-                values.push(data);
+                paths.push(path);
             }
         }
         Ok(())
     }
 
+    /// Read the path and content of all the files in `folder` and its subfolders, sorted by path.
+    ///
+    /// An empty list is returned if the folder does not exist.
+    #[no_coverage]
+    pub(crate) fn read_corpus_folder(&self, folder: &Path) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut paths = vec![];
+        Self::list_input_corpus_rec(folder, &mut paths)?;
+        paths.sort();
+        let mut files = vec![];
+        for path in paths {
+            let data = fs::read(&path)?;
+            files.push((path, data));
+        }
+        Ok(files)
+    }
+
+    /// Read the path and content of at most `max_len` of the files of the input corpus that have not been read yet.
+    ///
+    /// Files that were deleted since the call to [`start_reading_input_corpus`](World::start_reading_input_corpus)
+    /// are skipped.
+    #[no_coverage]
+    pub fn read_input_corpus_batch(&mut self, max_len: usize) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut values = vec![];
+        while values.len() < max_len {
+            if let Some(file) = self.read_next_input_corpus_file()? {
+                values.push(file);
+            } else {
+                break;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Read the next file of the input corpus that has not been read yet, and return its path and content.
+    ///
+    /// Files that were deleted since the call to [`start_reading_input_corpus`](World::start_reading_input_corpus)
+    /// are skipped.
+    #[no_coverage]
+    pub fn read_next_input_corpus_file(&mut self) -> Result<Option<(PathBuf, Vec<u8>)>> {
+        while let Some(path) = self.corpus_to_read.pop() {
+            self.corpus_to_read_set.remove(&path);
+            match fs::read(&path) {
+                Ok(data) => return Ok(Some((path, data))),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns true if some files of the input corpus have not been read yet
+    #[no_coverage]
+    pub fn is_reading_input_corpus(&self) -> bool {
+        !self.corpus_to_read.is_empty()
+    }
+
     #[no_coverage]
     pub fn read_input_file(&self, file: &Path) -> Result<Vec<u8>> {
         let data = fs::read(file)?;
@@ -287,15 +1045,70 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
     }
 
     #[no_coverage]
-    pub fn save_artifact(&mut self, content: Vec<u8>, cplx: f64, extension: &str) -> Result<()> {
-        let artifacts_folder = self.settings.artifacts_folder.as_ref();
-        if artifacts_folder.is_none() {
-            return Ok(());
+    pub fn save_artifact(&mut self, content: Vec<u8>, cplx: f64, extension: &str) -> Result<Option<PathBuf>> {
+        self.save_artifact_in(None, content, cplx, extension, "Failing test case found")
+    }
+
+    /// Save a test case that did not return within the `--hang-threshold` to the `hangs` subfolder of the
+    /// artifacts folder, so that hangs are not mixed with the other test failures.
+    ///
+    /// When minifying or reading a test case, it is saved to the artifacts folder itself instead, which is
+    /// where `cargo fuzzcheck` looks for the minified test cases.
+    #[no_coverage]
+    pub(crate) fn save_hang_artifact(
+        &mut self,
+        content: Vec<u8>,
+        cplx: f64,
+        extension: &str,
+    ) -> Result<Option<PathBuf>> {
+        let subfolder = self.crash_kind_subfolder(HANGS_FOLDER);
+        self.save_artifact_in(subfolder, content, cplx, extension, "Hanging test case found")
+    }
+
+    /// Save a test case that made the memory usage exceed the `--max-memory` to the `oom` subfolder of the
+    /// artifacts folder, with the same exception as [`save_hang_artifact`](World::save_hang_artifact).
+    #[no_coverage]
+    pub(crate) fn save_oom_artifact(
+        &mut self,
+        content: Vec<u8>,
+        cplx: f64,
+        extension: &str,
+    ) -> Result<Option<PathBuf>> {
+        let subfolder = self.crash_kind_subfolder(OOM_FOLDER);
+        self.save_artifact_in(subfolder, content, cplx, extension, "Out-of-memory test case found")
+    }
+
+    #[no_coverage]
+    fn crash_kind_subfolder(&self, subfolder: &'static str) -> Option<&'static str> {
+        if let FuzzerCommand::MinifyInput { .. } | FuzzerCommand::Read { .. } = self.settings.command {
+            None
+        } else {
+            Some(subfolder)
         }
-        let artifacts_folder = artifacts_folder.unwrap().as_path();
+    }
+
+    #[no_coverage]
+    fn save_artifact_in(
+        &mut self,
+        subfolder: Option<&str>,
+        content: Vec<u8>,
+        cplx: f64,
+        extension: &str,
+        description: &str,
+    ) -> Result<Option<PathBuf>> {
+        let artifacts_root = if let Some(folder) = &self.settings.artifacts_folder {
+            folder.as_path()
+        } else {
+            return Ok(None);
+        };
+        let artifacts_folder = if let Some(subfolder) = subfolder {
+            artifacts_root.join(subfolder)
+        } else {
+            artifacts_root.to_path_buf()
+        };
 
         if !artifacts_folder.is_dir() {
-            std::fs::create_dir_all(artifacts_folder)?;
+            std::fs::create_dir_all(&artifacts_folder)?;
         }
 
         let mut hasher = DefaultHasher::new();
@@ -309,10 +1122,54 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
         };
 
         let path = artifacts_folder.join(&name).with_extension(extension);
-        println!("Failing test case found. Saving at {:?}", path);
-        fs::write(path, &content)?;
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &self.tui {
+            let mut tui = tui.borrow_mut();
+            tui.set_last_artifact(path.clone());
+            tui.detach();
+        }
+        println!("{}. Saving at {:?}", description, path);
+        // the artifacts folder is shared by the workers of `cargo fuzzcheck --jobs`
+        let _lock = if self.settings.worker.is_some() {
+            Some(lock_folder(artifacts_root)?)
+        } else {
+            None
+        };
+        write_atomically(&path, &content)?;
+
+        Result::Ok(Some(path))
+    }
 
-        Result::Ok(())
+    /// Print the value of a failing test case, pretty-printed with `Debug`, and write it next to the artifact
+    /// where the test case was saved, if any, in a file with the extension `.debug`
+    #[no_coverage]
+    pub(crate) fn describe_failing_test_case(&self, description: &str, artifact: Option<&Path>) -> Result<()> {
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &self.tui {
+            tui.borrow_mut().detach();
+        }
+        eprintln!("\n================ FAILING TEST CASE ================\n{}", description);
+        if let Some(artifact) = artifact {
+            write_atomically(&artifact.with_extension(DEBUG_VALUE_EXTENSION), description.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Print the test cases that took the longest to run, from the slowest to the fastest
+    #[no_coverage]
+    pub(crate) fn report_slowest_inputs(&self, inputs: &[SlowInput]) {
+        if inputs.is_empty() {
+            return;
+        }
+        println!("\n================ SLOWEST INPUTS ================");
+        for input in inputs {
+            println!(
+                "{:>10.3}ms  cplx: {:<8.2} {}",
+                input.duration.as_secs_f64() * 1000.0,
+                input.cplx,
+                input.name
+            );
+        }
     }
 
     #[no_coverage]
@@ -321,6 +1178,85 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
         std::process::exit(TerminationStatus::Success as i32);
     }
 
+    /// The number of files of the input corpus when the fuzzer started reading it
+    #[no_coverage]
+    pub(crate) fn input_corpus_size(&self) -> usize {
+        self.input_corpus_size
+    }
+
+    /// The number of test cases of the corpus, which are the files of the output corpus if there is one
+    #[no_coverage]
+    pub(crate) fn corpus_size(&self) -> usize {
+        self.corpus.len()
+    }
+
+    /// The files of the output corpus, given by the folder of their pool, the index of their test case, and their hash
+    #[no_coverage]
+    pub(crate) fn corpus_files(&self) -> impl Iterator<Item = (&Path, PoolStorageIndex, &str)> {
+        self.corpus.iter().map(
+            #[no_coverage]
+            |((folder, idx), hash)| (folder.as_path(), *idx, hash.as_str()),
+        )
+    }
+
+    /// Register a file of the output corpus that was added by a previous run of the fuzz test, when the pools are
+    /// restored from its saved state
+    #[no_coverage]
+    pub(crate) fn restore_corpus_file(&mut self, folder: PathBuf, idx: PoolStorageIndex, hash: String) {
+        self.corpus.insert((folder, idx), hash);
+    }
+
+    /// Write the outcome of the fuzzing campaign to the file given by `--results-file`, if any
+    #[no_coverage]
+    pub(crate) fn write_results_file(&self, results: &CampaignResults) -> Result<()> {
+        if let Some(path) = &self.settings.results_file {
+            if let Some(parent) = path.parent().filter(
+                #[no_coverage]
+                |parent| !parent.as_os_str().is_empty(),
+            ) {
+                fs::create_dir_all(parent)?;
+            }
+            write_atomically(path, results.to_json().as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read the state saved in the file given by `--resume`, if it exists
+    #[no_coverage]
+    pub(crate) fn read_saved_state(&self) -> Result<Option<SavedState>> {
+        let path = if let Some(path) = &self.settings.resume {
+            path
+        } else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read(path)?;
+        let state = SavedState::from_bytes(&content).map_err(
+            #[no_coverage]
+            |e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("the state file {} cannot be read because {}", path.display(), e),
+                )
+            },
+        )?;
+        Ok(Some(state))
+    }
+
+    /// Write the state to the file given by `--resume`, if any.
+    ///
+    /// The state is written atomically, so that the previous state is kept intact if the process is
+    /// killed while writing.
+    #[no_coverage]
+    pub(crate) fn save_state(&self, state: &SavedState) -> Result<()> {
+        if let Some(path) = &self.settings.resume {
+            write_atomically(path, &state.to_bytes())?;
+        }
+        Ok(())
+    }
+
     #[no_coverage]
     pub fn write_stats_content(&self, contents: Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
         if let Some(stats_folder) = &self.stats_folder {
@@ -345,3 +1281,112 @@ impl SaveToStatsFolder for World {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        write_atomically, CorpusStorage, FileSystemCorpusStorage, InMemoryCorpusStorage, World, CORPUS_COMPLETE_MARKER,
+        CORPUS_WRITTEN_MARKER,
+    };
+    use std::path::{Path, PathBuf};
+
+    #[no_coverage]
+    fn check_storage(storage: &mut dyn CorpusStorage) {
+        storage.add(Path::new("coverage/b.json"), b"b", Some(2.0)).unwrap();
+        storage.add(Path::new("a.json"), b"a", None).unwrap();
+        storage.add(Path::new("coverage/b.json"), b"bb", Some(3.0)).unwrap();
+        assert_eq!(
+            storage.list().unwrap(),
+            vec![PathBuf::from("a.json"), PathBuf::from("coverage/b.json")]
+        );
+        assert_eq!(storage.read(Path::new("coverage/b.json")).unwrap(), b"bb");
+
+        storage.remove(Path::new("a.json")).unwrap();
+        storage.remove(Path::new("missing.json")).unwrap();
+        assert_eq!(storage.list().unwrap(), vec![PathBuf::from("coverage/b.json")]);
+        assert!(storage.read(Path::new("a.json")).is_err());
+        storage.mark_complete().unwrap();
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_in_memory_corpus_storage() {
+        check_storage(&mut InMemoryCorpusStorage::new());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_file_system_corpus_storage() {
+        let corpus = std::env::temp_dir().join(format!("fuzzcheck-corpus-storage-{}", std::process::id()));
+        let mut storage = FileSystemCorpusStorage::new(&corpus, Some(1));
+        check_storage(&mut storage);
+        assert_eq!(
+            storage.location(Path::new("coverage/b.json")),
+            Some(corpus.join("worker-1/coverage/b.json"))
+        );
+        // the marker of a complete corpus is not a test case
+        assert!(corpus.join("worker-1").join(CORPUS_COMPLETE_MARKER).exists());
+        assert_eq!(storage.list().unwrap(), vec![PathBuf::from("coverage/b.json")]);
+        std::fs::remove_dir_all(&corpus).unwrap();
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_write_atomically() {
+        let folder = std::env::temp_dir().join(format!("fuzzcheck-write-atomically-{}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        let path = folder.join("a.json");
+        write_atomically(&path, b"abc").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"abc");
+        write_atomically(&path, b"d").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"d");
+        // the temporary file was renamed
+        assert_eq!(std::fs::read_dir(&folder).unwrap().count(), 1);
+        assert!(write_atomically(&folder.join("missing/a.json"), b"abc").is_err());
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    #[no_coverage]
+    fn test_corpus_markers() {
+        let corpus = std::env::temp_dir().join(format!("fuzzcheck-corpus-markers-{}", std::process::id()));
+
+        // a corpus that was not written by fuzzcheck is not reported as incomplete
+        std::fs::create_dir_all(&corpus).unwrap();
+        std::fs::write(corpus.join("seed"), b"abc").unwrap();
+        assert!(!World::is_corpus_incomplete(&corpus).unwrap());
+
+        let mut storage = FileSystemCorpusStorage::new(&corpus, None);
+        storage.mark_complete().unwrap();
+        assert!(corpus.join(CORPUS_COMPLETE_MARKER).exists());
+        assert!(!World::is_corpus_incomplete(&corpus).unwrap());
+
+        // the marker is removed when the corpus is modified, and written again when the fuzzer stops
+        let mut storage = FileSystemCorpusStorage::new(&corpus, None);
+        storage.add(Path::new("a.json"), b"a", None).unwrap();
+        assert!(!corpus.join(CORPUS_COMPLETE_MARKER).exists());
+        assert!(corpus.join(CORPUS_WRITTEN_MARKER).exists());
+        assert!(World::is_corpus_incomplete(&corpus).unwrap());
+        storage.remove(Path::new("a.json")).unwrap();
+        assert!(!corpus.join(CORPUS_COMPLETE_MARKER).exists());
+        storage.mark_complete().unwrap();
+        assert!(!World::is_corpus_incomplete(&corpus).unwrap());
+        assert_eq!(storage.list().unwrap(), vec![PathBuf::from("seed")]);
+
+        // the leftover of an interrupted write is reported even if the corpus is marked as complete
+        std::fs::write(corpus.join("b.json.1234.tmp"), b"b").unwrap();
+        assert!(World::is_corpus_incomplete(&corpus).unwrap());
+        std::fs::remove_dir_all(&corpus).unwrap();
+
+        // the subfolders of the workers are checked separately
+        let mut worker_1 = FileSystemCorpusStorage::new(&corpus, Some(1));
+        let mut worker_2 = FileSystemCorpusStorage::new(&corpus, Some(2));
+        worker_1.add(Path::new("a.json"), b"a", None).unwrap();
+        worker_2.add(Path::new("b.json"), b"b", None).unwrap();
+        worker_1.mark_complete().unwrap();
+        assert!(World::is_corpus_incomplete(&corpus).unwrap());
+        worker_2.mark_complete().unwrap();
+        assert!(!World::is_corpus_incomplete(&corpus).unwrap());
+        std::fs::remove_dir_all(&corpus).unwrap();
+    }
+}
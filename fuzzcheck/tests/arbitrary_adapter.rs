@@ -0,0 +1,38 @@
+#![cfg(feature = "arbitrary_mutator")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use fuzzcheck::mutators::arbitrary_adapter::{ArbitraryAdapterMutator, ArbitraryValue};
+use fuzzcheck::{ArbitrarySerializer, Mutator, Serializer};
+
+#[test]
+fn test_arbitrary_adapter_mutator() {
+    let m = ArbitraryAdapterMutator::<(u8, Vec<u16>)>::new(0..=64);
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_arbitrary_adapter_mutator_decodes_bytes() {
+    let m = ArbitraryAdapterMutator::<(u8, String)>::new(0..=32);
+    let decode = |bytes: &[u8]| <(u8, String)>::arbitrary_take_rest(Unstructured::new(bytes)).unwrap();
+
+    let mut step = m.default_arbitrary_step();
+    let (mut value, _) = m.ordered_arbitrary(&mut step, 1000.0).unwrap();
+    let mut cache = m.validate_value(&value).unwrap();
+    for _ in 0..1000 {
+        let original = value.clone();
+        let (token, cplx) = m.random_mutate(&mut value, &mut cache, 1000.0);
+        assert_eq!(value.value(), &decode(value.bytes()));
+        assert_eq!(cplx, m.complexity(&value, &m.validate_value(&value).unwrap()));
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+        let _ = m.random_mutate(&mut value, &mut cache, 1000.0);
+    }
+}
+
+#[test]
+fn test_arbitrary_serializer() {
+    let ser = ArbitrarySerializer::<(u8, String)>::new("bin");
+    let value = ArbitraryValue::<(u8, String)>::new(b"\x07abc".to_vec());
+    assert_eq!(ser.to_data(&value), b"\x07abc".to_vec());
+    assert_eq!(ser.from_data(b"\x07abc"), Some(value));
+}
@@ -0,0 +1,31 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use fuzzcheck::mutators::btreemap::BTreeMapMutator;
+use fuzzcheck::mutators::btreeset::BTreeSetMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::DefaultMutator;
+
+// the complexities are not consistent because parsing a map or set from the corpus removes the duplicate keys
+
+#[test]
+fn test_btreemap_mutator() {
+    test_mutator(
+        BTreeMap::<u8, bool>::default_mutator(),
+        100.0,
+        100.0,
+        false,
+        false,
+        100,
+        100,
+    );
+    let m = BTreeMapMutator::new(U8Mutator::default(), U8Mutator::default(), 0..=5);
+    test_mutator(m, 100.0, 100.0, false, false, 100, 100);
+}
+
+#[test]
+fn test_btreeset_mutator() {
+    test_mutator(BTreeSet::<u16>::default_mutator(), 100.0, 100.0, false, false, 100, 100);
+    let m = BTreeSetMutator::new(U8Mutator::default(), 1..=3);
+    test_mutator(m, 100.0, 100.0, false, false, 100, 100);
+}
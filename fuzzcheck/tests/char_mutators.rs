@@ -1,5 +1,31 @@
-use fuzzcheck::mutators::char::CharWithinRangeMutator;
+use fuzzcheck::mutators::char::{CharMutator, CharWithinRangeMutator, CharWithinRangesMutator};
+use fuzzcheck::mutators::char_set::{CharSetMutator, StringFromCharSetMutator};
+use fuzzcheck::mutators::numeric_string::{NumericStringMutator, NUMERIC_EDGE_CASES};
 use fuzzcheck::mutators::testing_utilities::*;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_default_char_mutator() {
+    test_mutator(CharMutator::default(), 100.0, 100.0, true, true, 500, 500);
+    test_mutator(CharMutator::default(), 1.0, 1.0, true, true, 100, 100);
+
+    let m = CharMutator::default();
+    let mut step = m.default_arbitrary_step();
+    let chars = (0..2000)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(chars[0], 'a');
+    assert!(chars.contains(&'\0'));
+    assert!(chars.contains(&'\u{10ffff}'));
+    // all the ranges are visited early, including combining marks and emoji
+    assert!(chars
+        .iter()
+        .any(|c| ('\u{300}'..='\u{36f}').contains(c) && *c != '\u{300}'));
+    assert!(chars
+        .iter()
+        .any(|c| ('\u{1f300}'..='\u{1faff}').contains(c) && *c != '\u{1f600}'));
+    assert!(chars.iter().filter(|c| c.is_ascii()).count() > 100);
+}
 
 #[test]
 fn other_test_char_mutator() {
@@ -33,3 +59,84 @@ fn other_test_char_mutator() {
         100,
     );
 }
+
+#[test]
+fn test_char_within_ranges_mutator() {
+    // overlapping ranges, and a range containing the surrogates
+    let m = CharWithinRangesMutator::new(['a'..='f', 'd'..='h', '\u{d7fe}'..='\u{e001}']);
+    assert!(m.validate_value(&'i').is_none());
+    assert!(m.validate_value(&'\u{e000}').is_some());
+    let mut step = m.default_arbitrary_step();
+    let mut chars: Vec<char> = vec![];
+    while let Some((c, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        chars.push(c);
+    }
+    chars.sort_unstable();
+    assert_eq!(
+        chars,
+        "abcdefgh\u{d7fe}\u{d7ff}\u{e000}\u{e001}".chars().collect::<Vec<_>>()
+    );
+
+    let mut value = 'c';
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut mutated = vec![];
+    while let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, 100.0) {
+        mutated.push(value);
+        m.unmutate(&mut value, &mut cache, token);
+    }
+    assert_eq!(mutated.len(), 11);
+    assert!(!mutated.contains(&'c'));
+    for _ in 0..100 {
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 100.0);
+        assert_ne!(token, value);
+    }
+
+    test_mutator(m, 100.0, 100.0, true, true, 100, 100);
+    test_mutator(
+        CharWithinRangesMutator::new(['x'..='x']),
+        100.0,
+        100.0,
+        true,
+        true,
+        100,
+        100,
+    );
+}
+
+#[test]
+fn test_char_set_mutators() {
+    let hex_digits = ['0'..='9', 'a'..='f'];
+    let m = CharSetMutator::new(&hex_digits);
+    assert!(m.validate_value(&'g').is_none());
+    let mut step = m.default_arbitrary_step();
+    let mut digits: Vec<char> = vec![];
+    while let Some((c, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        digits.push(c);
+    }
+    digits.sort_unstable();
+    assert_eq!(digits, "0123456789abcdef".chars().collect::<Vec<_>>());
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+
+    let m = StringFromCharSetMutator::new(&hex_digits, 1..=8);
+    assert!(m.validate_value(&"12ab".to_owned()).is_some());
+    assert!(m.validate_value(&"12AB".to_owned()).is_none());
+    assert!(m.validate_value(&"".to_owned()).is_none());
+    for _ in 0..100 {
+        let (s, _): (String, f64) = m.random_arbitrary(100.0);
+        assert!(!s.is_empty() && s.len() <= 8 && s.chars().all(|c| c.is_ascii_hexdigit() && !c.is_uppercase()));
+    }
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_numeric_string_mutator() {
+    let m = NumericStringMutator::new();
+    assert!(m.validate_value(&"not a number ✓".to_owned()).is_some());
+    let mut step = m.default_arbitrary_step();
+    for &expected in NUMERIC_EDGE_CASES {
+        let (s, _): (String, f64) = m.ordered_arbitrary(&mut step, 1000.0).unwrap();
+        assert_eq!(s, expected);
+    }
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashSet, ops::RangeBounds};
 
-use fuzzcheck::mutators::integer_within_range::I8WithinRangeMutator;
+use fuzzcheck::mutators::integer_within_range::{I8WithinRangeMutator, IntegerWithinRangeMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
 use fuzzcheck::Mutator;
 
 fn test_arbitrary_for_int_range_mutator(range: impl RangeBounds<i8> + IntoIterator<Item = i8> + Clone) {
@@ -27,3 +28,38 @@ fn test_arbitrary_constrained_signed_integer_8() {
     test_arbitrary_for_int_range_mutator(-128..=127);
     test_arbitrary_for_int_range_mutator(-100..50);
 }
+
+#[test]
+fn test_integer_within_range_stays_within_range() {
+    let m = IntegerWithinRangeMutator::<u16>::new(1024..=1100);
+    assert!(m.validate_value(&80).is_none());
+    assert!(m.validate_value(&1100).is_some());
+
+    let mut value = 1050;
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut mutated = HashSet::new();
+    while let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, 100.0) {
+        assert!((1024..=1100).contains(&value), "{}", value);
+        assert!(mutated.insert(value));
+        m.unmutate(&mut value, &mut cache, token);
+    }
+    // every other value of the range, but not the current one
+    assert_eq!(mutated.len(), 76);
+    assert!(!mutated.contains(&1050));
+    for _ in 0..1000 {
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 100.0);
+        assert_ne!(token, value);
+        assert!((1024..=1100).contains(&value), "{}", value);
+    }
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+
+    let m = IntegerWithinRangeMutator::<i64>::new(..=-5);
+    for _ in 0..1000 {
+        assert!(m.random_arbitrary(100.0).0 <= -5);
+    }
+    let m = IntegerWithinRangeMutator::<usize>::new(3..4);
+    let mut step = 0;
+    assert_eq!(m.ordered_arbitrary(&mut step, 100.0).unwrap().0, 3);
+    assert!(m.ordered_arbitrary(&mut step, 100.0).is_none());
+}
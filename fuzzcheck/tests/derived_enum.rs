@@ -2,6 +2,7 @@
 
 use fuzzcheck::mutators::testing_utilities::test_mutator;
 use fuzzcheck::DefaultMutator;
+use fuzzcheck::Mutator;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
 enum SampleEnum {
@@ -17,3 +18,61 @@ fn test_derived_struct() {
     let mutator = <Vec<SampleEnum>>::default_mutator();
     test_mutator(mutator, 1000., 1000., false, true, 100, 100);
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+enum SampleBasicEnum {
+    A,
+    B,
+    C,
+    D,
+}
+
+#[test]
+fn test_derived_enum_excluded_variants() {
+    let mutator = SampleEnum::default_mutator().exclude_variants(&[1]);
+    for _ in 0..100 {
+        let (x, _) = mutator.random_arbitrary(1000.);
+        assert_ne!(x, SampleEnum::B);
+    }
+    let mut step = mutator.default_arbitrary_step();
+    for _ in 0..1000 {
+        if let Some((x, _)) = mutator.ordered_arbitrary(&mut step, 1000.) {
+            assert_ne!(x, SampleEnum::B);
+        } else {
+            break;
+        }
+    }
+    // values of the excluded variants can still come from the corpus
+    assert!(mutator.validate_value(&SampleEnum::B).is_some());
+
+    let mutator = SampleBasicEnum::default_mutator().only_variants(&[0, 2]);
+    let mut step = <_ as Mutator<SampleBasicEnum>>::default_arbitrary_step(&mutator);
+    let mut values: Vec<SampleBasicEnum> = vec![];
+    while let Some((x, _)) = mutator.ordered_arbitrary(&mut step, 1000.) {
+        values.push(x);
+    }
+    assert_eq!(values, vec![SampleBasicEnum::A, SampleBasicEnum::C]);
+
+    let mut value = SampleBasicEnum::D;
+    let mut cache = mutator.validate_value(&value).unwrap();
+    let mut step = mutator.default_mutation_step(&value, &cache);
+    let mut values = vec![];
+    while let Some((token, _)) = mutator.ordered_mutate(&mut value, &mut cache, &mut step, 1000.) {
+        values.push(value.clone());
+        mutator.unmutate(&mut value, &mut cache, token);
+    }
+    assert_eq!(values, vec![SampleBasicEnum::A, SampleBasicEnum::C]);
+}
+
+#[cfg(feature = "field_complexities")]
+#[test]
+fn test_derived_enum_field_complexities() {
+    let mutator = SampleEnum::default_mutator();
+    assert_eq!(mutator.field_complexities(&SampleEnum::A(7)), Some(vec![("0", 16.)]));
+    assert_eq!(mutator.field_complexities(&SampleEnum::B), Some(vec![]));
+    let complexities = mutator
+        .field_complexities(&SampleEnum::C { x: true, y: false })
+        .unwrap();
+    let names = complexities.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    assert_eq!(names, vec!["x", "y"]);
+}
@@ -1,7 +1,8 @@
 #![feature(no_coverage)]
+#![feature(trivial_bounds)]
 
 use fuzzcheck::mutators::testing_utilities::test_mutator;
-use fuzzcheck::DefaultMutator;
+use fuzzcheck::{DefaultMutator, Mutator};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
 struct SampleStruct<T, U> {
@@ -16,3 +17,67 @@ fn test_derived_struct() {
     let mutator = <Vec<SampleStruct<u8, u8>>>::default_mutator();
     test_mutator(mutator, 1000., 1000., false, true, 100, 100);
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+struct ManyFields {
+    a: u8,
+    b: bool,
+    c: u8,
+    d: bool,
+    e: u8,
+    f: bool,
+    g: u8,
+    h: bool,
+    i: u8,
+    j: bool,
+    k: u8,
+    l: bool,
+    m: Option<u8>,
+}
+
+type Tuple12 = (u8, bool, u8, bool, u8, bool, u8, bool, u8, bool, u8, bool);
+
+#[test]
+fn test_derived_struct_with_many_fields() {
+    let mutator = ManyFields::default_mutator();
+    test_mutator(mutator, 1000., 1000., false, true, 100, 100);
+    // the tuple mutators implement `Mutator<T>` for any `T` with that tuple structure
+    let mutator = Tuple12::default_mutator();
+    test_mutator::<Tuple12, _>(mutator, 1000., 1000., false, true, 100, 100);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+struct BoundedFields {
+    #[field_range(1..=64)]
+    ttl: u8,
+    #[field_range(-3..=3)]
+    offset: i32,
+    #[field_range('a'..='f')]
+    letter: char,
+    #[field_range(2..=4)]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn test_derived_struct_with_field_ranges() {
+    let mutator = BoundedFields::default_mutator();
+    for _ in 0..1000 {
+        let (x, _) = mutator.random_arbitrary(1000.);
+        assert!((1..=64).contains(&x.ttl), "{:?}", x);
+        assert!((-3..=3).contains(&x.offset), "{:?}", x);
+        assert!(('a'..='f').contains(&x.letter), "{:?}", x);
+        assert!((2..=4).contains(&x.payload.len()), "{:?}", x);
+    }
+    test_mutator(mutator, 1000., 1000., false, true, 100, 100);
+}
+
+#[cfg(feature = "field_complexities")]
+#[test]
+fn test_derived_struct_field_complexities() {
+    let mutator = SampleStruct::<u16, Vec<u8>>::default_mutator();
+    let value = SampleStruct { x: 1, y: vec![1, 2, 3] };
+    let complexities = mutator.field_complexities(&value).unwrap();
+    assert_eq!(complexities[0], ("x", 16.));
+    assert_eq!(complexities[1].0, "y");
+    assert!(complexities[1].1 > 24.);
+}
@@ -20,6 +20,14 @@ pub enum Z {
     D(bool),
 }
 
+#[derive(Clone, DefaultMutator)]
+pub enum U {
+    A,
+    B(()),
+    C(u8, ()),
+    D(u8),
+}
+
 #[test]
 #[no_coverage]
 fn test_compile() {
@@ -37,3 +45,17 @@ fn test_compile() {
     let m = Z::default_mutator();
     let (_value, _): (Z, _) = m.random_arbitrary(10.0);
 }
+
+#[test]
+#[no_coverage]
+fn test_unit_fields_have_no_complexity() {
+    let m = <()>::default_mutator();
+    let mut step = m.default_arbitrary_step();
+    assert_eq!(m.ordered_arbitrary(&mut step, 10.0), Some(((), 0.0)));
+    assert_eq!(m.ordered_arbitrary(&mut step, 10.0), None);
+
+    let m = U::default_mutator();
+    let cplx = |value: &U| m.complexity(value, &m.validate_value(value).unwrap());
+    assert_eq!(cplx(&U::A), cplx(&U::B(())));
+    assert_eq!(cplx(&U::C(7, ())), cplx(&U::D(7)));
+}
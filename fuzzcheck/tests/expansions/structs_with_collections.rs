@@ -0,0 +1,28 @@
+use std::collections::{LinkedList, VecDeque};
+
+use fuzzcheck::DefaultMutator;
+
+#[derive(Clone, Debug, PartialEq, DefaultMutator)]
+pub struct X {
+    queue: VecDeque<u8>,
+    list: LinkedList<bool>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fuzzcheck::Mutator;
+    #[test]
+    #[no_coverage]
+    fn test_compile() {
+        let m = X::default_mutator();
+        for _ in 0..100 {
+            let (mut x, _) = m.random_arbitrary(100.0);
+            let original = x.clone();
+            let mut cache = m.validate_value(&x).unwrap();
+            let (token, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, original);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+#![feature(no_coverage)]
+
+use fuzzcheck::mutators::fixup::FixupMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[no_coverage]
+fn sort(xs: &mut Vec<u8>) {
+    xs.sort_unstable();
+}
+
+#[test]
+fn test_fixup_mutator() {
+    let m = FixupMutator::new(<Vec<u8>>::default_mutator(), sort);
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_fixup_mutator_values_are_fixed() {
+    let m = FixupMutator::new(<Vec<u8>>::default_mutator(), sort);
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(100.0);
+        assert!(x.windows(2).all(|w| w[0] <= w[1]));
+        let mut cache = m.validate_value(&x).unwrap();
+        let original = x.clone();
+        for _ in 0..10 {
+            let (token, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+            assert!(x.windows(2).all(|w| w[0] <= w[1]));
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, original);
+        }
+    }
+    // values that are not fixed are rejected
+    assert!(m.validate_value(&vec![2, 1]).is_none());
+    assert!(m.validate_value(&vec![1, 2]).is_some());
+}
@@ -0,0 +1,93 @@
+use fuzzcheck::mutators::float::{F32Mutator, F64Mutator, F64WithinRangeMutator};
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_float_arbitraries_start_with_special_values() {
+    let m = f64::default_mutator();
+    let mut step = m.default_arbitrary_step();
+    let mut values = vec![];
+    for _ in 0..100 {
+        let (value, _) = m.ordered_arbitrary(&mut step, 64.0).unwrap();
+        values.push(value.to_bits());
+    }
+    for special in [
+        0.0,
+        -0.0,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::from_bits(1),
+        f64::MAX,
+    ] {
+        assert!(values[..40].contains(&special.to_bits()), "{:?}", special);
+    }
+    let mut deduplicated = values.clone();
+    deduplicated.sort_unstable();
+    deduplicated.dedup();
+    assert_eq!(deduplicated.len(), values.len());
+
+    let m = F32Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    assert!(m.ordered_arbitrary(&mut step, 31.0).is_none());
+    let (zero, _) = m.ordered_arbitrary(&mut step, 32.0).unwrap();
+    assert_eq!(zero.to_bits(), 0.0_f32.to_bits());
+}
+
+#[test]
+fn test_float_mutations_are_undone() {
+    let m = F64Mutator::default();
+    for original in [1.5, -0.0, f64::NAN, f64::MIN_POSITIVE] {
+        let mut value = original;
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for i in 0..200 {
+            let (token, _) = m.ordered_mutate(&mut value, &mut cache, &mut step, 64.0).unwrap();
+            if i == 0 {
+                // the first mutation moves to the next representable value
+                assert_eq!(value.to_bits(), original.to_bits() + 1);
+            }
+            assert_ne!(value.to_bits(), original.to_bits());
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value.to_bits(), original.to_bits());
+
+            let (token, _) = m.random_mutate(&mut value, &mut cache, 64.0);
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value.to_bits(), original.to_bits());
+        }
+    }
+}
+
+#[test]
+fn test_float_within_range_stays_within_range() {
+    let m = F64WithinRangeMutator::new(-2.5..=10.0);
+    let mut step = m.default_arbitrary_step();
+    let (start, _) = m.ordered_arbitrary(&mut step, 64.0).unwrap();
+    assert_eq!(start.to_bits(), (-2.5_f64).to_bits());
+    for _ in 0..1000 {
+        let (value, _) = m.ordered_arbitrary(&mut step, 64.0).unwrap();
+        assert!((-2.5..=10.0).contains(&value), "{}", value);
+    }
+    for _ in 0..100 {
+        let (mut value, _) = m.random_arbitrary(64.0);
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for _ in 0..100 {
+            let original = value;
+            let (token, _) = m.ordered_mutate(&mut value, &mut cache, &mut step, 64.0).unwrap();
+            assert!(m.validate_value(&value).is_some(), "{}", value);
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value.to_bits(), original.to_bits());
+            let (_, _) = m.random_mutate(&mut value, &mut cache, 64.0);
+            assert!(m.validate_value(&value).is_some(), "{}", value);
+        }
+    }
+    assert!(m.validate_value(&f64::NAN).is_none());
+    assert!(m.validate_value(&10.5).is_none());
+
+    // the range can span all finite values
+    let m = F64WithinRangeMutator::new(f64::MIN..=f64::MAX);
+    for _ in 0..100 {
+        let (value, _) = m.random_arbitrary(64.0);
+        assert!(value.is_finite());
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "serde_json_serializer")]
+
+// None of these tests have a corpus, so `cargo test` checks that the attribute accepts their signature and that
+// a missing corpus is not a test failure.
+
+#[fuzzcheck::fuzz_test]
+fn borrowed_argument(xs: &[u8]) {
+    let _ = xs.iter().sum::<u8>();
+}
+
+#[fuzzcheck::fuzz_test]
+fn owned_argument(x: Option<u16>) -> bool {
+    x.map_or(true, |x| x.checked_mul(2).is_some() || x > u16::MAX / 2)
+}
+
+#[fuzzcheck::fuzz_test]
+fn mutable_argument(xs: &mut Vec<bool>) {
+    xs.push(true);
+}
+
+#[fuzzcheck::fuzz_test]
+fn several_arguments(x: u8, s: &str, xs: &mut Vec<u8>) -> Result<(), String> {
+    xs.push(x);
+    if s.len() < 10_000 {
+        Ok(())
+    } else {
+        Err(s.to_owned())
+    }
+}
@@ -6,7 +6,7 @@ use std::rc::{Rc, Weak};
 
 use fuzzcheck::mutators::grammar::*;
 use fuzzcheck::mutators::testing_utilities::test_mutator;
-// use fuzzcheck::{DefaultMutator, Mutator};
+use fuzzcheck::Mutator;
 
 #[no_coverage]
 fn text() -> Rc<Grammar> {
@@ -182,3 +182,151 @@ fn test_grammar_based_ast_mutator() {
     let mutator = grammar_based_ast_mutator(markdown());
     test_mutator(mutator, 500., 500., false, false, 50, 50);
 }
+
+#[test]
+fn test_optional_grammar_toggles_presence() {
+    let mutator = grammar_based_ast_mutator(concatenation([literal('a'), optional(literal('b'))]));
+
+    let mut step = mutator.default_arbitrary_step();
+    let (mut value, _) = mutator.ordered_arbitrary(&mut step, 100.).unwrap();
+    let mut cache = mutator.validate_value(&value).unwrap();
+    let mut mutation_step = mutator.default_mutation_step(&value, &cache);
+
+    let original = value.to_string();
+    assert!(original == "a" || original == "ab");
+
+    let mut saw_toggle = false;
+    for _ in 0..100 {
+        let (token, _) = if let Some(x) = mutator.ordered_mutate(&mut value, &mut cache, &mut mutation_step, 100.) {
+            x
+        } else {
+            break;
+        };
+        let mutated = value.to_string();
+        assert!(mutated == "a" || mutated == "ab");
+        if mutated != original {
+            saw_toggle = true;
+        }
+        mutator.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value.to_string(), original);
+    }
+    assert!(saw_toggle);
+
+    // the optional rule nested in a concatenation makes the complexities inconsistent
+    test_mutator(mutator, 100., 100., false, false, 50, 50);
+}
+
+#[test]
+fn test_regex_grammar_with_anchors_and_empty_branches() {
+    let mutator = grammar_based_ast_mutator(regex("^(ab|)c{2,3}$"));
+    let expected = regex::Regex::new("^(ab)?c{2,3}$").unwrap();
+
+    let mut step = mutator.default_arbitrary_step();
+    for _ in 0..20 {
+        if let Some((value, _)) = mutator.ordered_arbitrary(&mut step, 100.) {
+            assert!(expected.is_match(&value.to_string()), "{}", value.to_string());
+        } else {
+            break;
+        }
+    }
+    for _ in 0..100 {
+        let (value, _) = mutator.random_arbitrary(100.);
+        assert!(expected.is_match(&value.to_string()), "{}", value.to_string());
+    }
+
+    // the alternation and the repetition nested in a concatenation make the complexities inconsistent
+    test_mutator(mutator, 100., 100., false, false, 50, 50);
+}
+
+#[test]
+fn test_mutually_recursive_grammar() {
+    // expr := term ('+' term)?
+    // term := [0-9] | '(' expr ')' | '-' term
+    let expr = Grammar::recursive(|expr| {
+        let term = Grammar::recursive(|term| {
+            alternation([
+                literal_range('0'..='9'),
+                concatenation([literal('('), Grammar::recurse(expr), literal(')')]),
+                concatenation([literal('-'), Grammar::recurse(term)]),
+            ])
+        });
+        concatenation([term.clone(), optional(concatenation([literal('+'), term]))])
+    });
+    let mutator = grammar_based_ast_mutator(expr);
+    let mut saw_nested_expr = false;
+    for _ in 0..200 {
+        let (value, _) = mutator.random_arbitrary(100.);
+        let s = value.to_string();
+        let mut depth = 0i32;
+        for c in s.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '0'..='9' | '+' | '-' => {}
+                _ => panic!("unexpected character in {:?}", s),
+            }
+            assert!(depth >= 0, "{:?}", s);
+        }
+        assert_eq!(depth, 0, "{:?}", s);
+        saw_nested_expr |= s.contains('(');
+    }
+    assert!(saw_nested_expr);
+    // the recursive rules nested in concatenations make the complexities inconsistent
+    test_mutator(mutator, 100., 100., false, false, 50, 50);
+
+    // the rule given to `recursive` may be shared with other rules
+    let digit = literal_range('0'..='9');
+    let rule = recursive(|_| digit.clone());
+    let mutator = grammar_based_ast_mutator(concatenation([rule, digit]));
+    let (value, _) = mutator.random_arbitrary(100.);
+    assert_eq!(value.to_string().len(), 2);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Expr {
+    Digit(u8),
+    Neg(Box<Expr>),
+}
+
+fn expr_from_ast(ast: &AST) -> Expr {
+    let s = ast.to_string();
+    let nbr_negations = s.len() - 1;
+    let mut expr = Expr::Digit(s.as_bytes()[nbr_negations] - b'0');
+    for _ in 0..nbr_negations {
+        expr = Expr::Neg(Box::new(expr));
+    }
+    expr
+}
+
+fn ast_from_expr(expr: &Expr) -> Option<AST> {
+    let mut negations = vec![];
+    let mut expr = expr;
+    while let Expr::Neg(inner) = expr {
+        negations.push(AST::Token('-'));
+        expr = inner;
+    }
+    match expr {
+        Expr::Digit(d) if *d < 10 => Some(AST::Sequence(vec![
+            AST::Sequence(negations),
+            AST::Token((b'0' + d) as char),
+        ])),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_grammar_based_map_mutator() {
+    let grammar = concatenation([repetition(literal('-'), 0..=3), literal_range('0'..='9')]);
+    let mutator = grammar_based_map_mutator(grammar, expr_from_ast, ast_from_expr);
+
+    let expr = Expr::Neg(Box::new(Expr::Digit(7)));
+    assert!(mutator.validate_value(&expr).is_some());
+    assert!(mutator.validate_value(&Expr::Digit(12)).is_none());
+
+    for _ in 0..100 {
+        let (expr, _) = mutator.random_arbitrary(100.);
+        assert!(ast_from_expr(&expr).is_some(), "{:?}", expr);
+    }
+    // the repetition nested in a concatenation makes the complexities inconsistent
+    test_mutator(mutator, 100., 100., false, false, 50, 50);
+}
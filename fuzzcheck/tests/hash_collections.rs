@@ -0,0 +1,47 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use fuzzcheck::mutators::hashmap::HashMapMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+/// `HashMap` and `HashSet` do not implement `Hash`, so they cannot be given to `test_mutator`.
+/// Instead, check that the mutations are undone by `unmutate` and respect the maximum complexity.
+fn check_mutate_unmutate<T, M>(m: M)
+where
+    T: Clone + Debug + PartialEq,
+    M: Mutator<T>,
+{
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let (mut value, _) = if let Some(x) = m.ordered_arbitrary(&mut step, 100.0) {
+            x
+        } else {
+            break;
+        };
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut mutation_step = m.default_mutation_step(&value, &cache);
+        for _ in 0..100 {
+            let original = value.clone();
+            let (token, cplx) = if let Some(x) = m.ordered_mutate(&mut value, &mut cache, &mut mutation_step, 100.0) {
+                x
+            } else {
+                break;
+            };
+            assert!(cplx <= 100.0);
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, original);
+        }
+    }
+}
+
+#[test]
+fn test_hashmap_mutator() {
+    check_mutate_unmutate(HashMap::<u8, bool>::default_mutator());
+    check_mutate_unmutate(HashMapMutator::new(U8Mutator::default(), U8Mutator::default()));
+}
+
+#[test]
+fn test_hashset_mutator() {
+    check_mutate_unmutate(HashSet::<u16>::default_mutator());
+}
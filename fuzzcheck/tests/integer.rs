@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use fuzzcheck::mutators::decimal::DecimalMutator;
+use fuzzcheck::mutators::integer::{I16Mutator, I8Mutator, U32Mutator, U8Mutator};
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_ordered_arbitrary_starts_with_boundary_values() {
+    let m = I16Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    let first = (0..6)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(first, vec![0, 1, i16::MAX, i16::MAX - 1, i16::MIN, i16::MIN + 1]);
+
+    let m = U32Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    let first = (0..4)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(first, vec![0, 1, u32::MAX, u32::MAX - 1]);
+}
+
+#[test]
+fn test_ordered_arbitrary_integer_8_is_exhaustive() {
+    let m = U8Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    let mut all_generated = HashSet::new();
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        let is_new = all_generated.insert(x);
+        assert!(is_new);
+    }
+    assert_eq!(all_generated.len(), 256);
+
+    let m = I8Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    let mut all_generated = HashSet::new();
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        let is_new = all_generated.insert(x);
+        assert!(is_new);
+    }
+    assert_eq!(all_generated.len(), 256);
+}
+
+#[test]
+fn test_decimal_mutator() {
+    let m = DecimalMutator::new(4);
+    assert!(m.validate_value(&(12345, 4)).is_some());
+    assert!(m.validate_value(&(12345, 5)).is_none());
+    let mut step = m.default_arbitrary_step();
+    let first = (0..10)
+        .map(|_| m.ordered_arbitrary(&mut step, 1000.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        first,
+        vec![
+            (0, 0),
+            (1, 0),
+            (-1, 0),
+            (i64::MAX, 0),
+            (i64::MIN, 0),
+            (0, 4),
+            (1, 4),
+            (-1, 4),
+            (i64::MAX, 4),
+            (i64::MIN, 4)
+        ]
+    );
+    assert_eq!(m.ordered_arbitrary(&mut step, 1000.0).unwrap().0, (10, 1));
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
@@ -0,0 +1,38 @@
+use fuzzcheck::mutators::json::JSON_MAX_DEPTH;
+use fuzzcheck::{DefaultMutator, Mutator};
+use serde_json::Value;
+
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Array(xs) => 1 + xs.iter().map(depth).max().unwrap_or(0),
+        Value::Object(map) => 1 + map.values().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[test]
+fn test_json_value_mutator() {
+    let m = Value::default_mutator();
+    let mut step = m.default_arbitrary_step();
+
+    // the scalar values and empty containers are generated first
+    let (first, _) = m.ordered_arbitrary(&mut step, 100.0).unwrap();
+    assert_eq!(depth(&first), 0);
+
+    for _ in 0..200 {
+        let (mut x, _) = m.random_arbitrary(1000.0);
+        assert!(depth(&x) <= JSON_MAX_DEPTH);
+        let original = x.clone();
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut mutation_step = m.default_mutation_step(&x, &cache);
+        for _ in 0..20 {
+            if let Some((token, _)) = m.ordered_mutate(&mut x, &mut cache, &mut mutation_step, 1000.0) {
+                assert!(depth(&x) <= JSON_MAX_DEPTH);
+                m.unmutate(&mut x, &mut cache, token);
+                assert_eq!(x, original);
+            } else {
+                break;
+            }
+        }
+    }
+}
@@ -5,7 +5,11 @@ mod char_mutators;
 mod constrained_integer;
 mod derived_recursive_struct;
 mod expansions;
+mod fixup;
 #[cfg(feature = "regex_grammar")]
 mod grammar_based_mutators;
+mod integer;
+#[cfg(feature = "serde_json")]
+mod json_value;
 mod option;
 mod vector;
@@ -0,0 +1,15 @@
+#![cfg(feature = "libfuzzer")]
+
+fn parse(bytes: &[u8]) -> bool {
+    bytes.len() < 1000 || bytes[0] != 0
+}
+
+fuzzcheck::libfuzzer_target!(parse);
+
+#[test]
+fn test_libfuzzer_entry_point() {
+    // libFuzzer may pass a null pointer for the empty test case
+    assert_eq!(LLVMFuzzerTestOneInput(std::ptr::null(), 0), 0);
+    let bytes = [1, 2, 3];
+    assert_eq!(LLVMFuzzerTestOneInput(bytes.as_ptr(), bytes.len()), 0);
+}
@@ -1,7 +1,35 @@
 use fuzzcheck::mutators::{integer::U8Mutator, option::OptionMutator};
+use fuzzcheck::Mutator;
 
 #[test]
 fn test_option() {
     let m = OptionMutator::new(U8Mutator::default());
     fuzzcheck::mutators::testing_utilities::test_mutator(m, 100.0, 100.0, false, true, 500, 500);
 }
+
+#[test]
+fn test_option_none_probability() {
+    let m = OptionMutator::new(U8Mutator::default()).with_none_probability(0.1);
+    let mut nones = 0;
+    for _ in 0..10_000 {
+        let (x, _) = m.random_arbitrary(10.0);
+        if x.is_none() {
+            nones += 1;
+        }
+    }
+    assert!((500..1500).contains(&nones), "{}", nones);
+
+    let m = OptionMutator::new(U8Mutator::default()).with_none_probability(0.0);
+    let mut step = m.default_arbitrary_step();
+    let mut count = 0;
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 10.0) {
+        assert!(x.is_some());
+        count += 1;
+    }
+    assert_eq!(count, 256);
+
+    let m = OptionMutator::new(U8Mutator::default()).with_none_probability(1.0);
+    for _ in 0..100 {
+        assert_eq!(m.random_arbitrary(10.0).0, None);
+    }
+}
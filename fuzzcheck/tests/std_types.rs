@@ -0,0 +1,64 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_duration_mutator() {
+    test_mutator(Duration::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+
+    let m = Duration::default_mutator();
+    let mut step = m.default_arbitrary_step();
+    let mut values = vec![];
+    for _ in 0..20 {
+        values.push(m.ordered_arbitrary(&mut step, 100.0).unwrap().0);
+    }
+    for special in [Duration::ZERO, Duration::from_nanos(999_999_999), Duration::MAX] {
+        assert!(values.contains(&special), "{:?}", special);
+    }
+}
+
+#[test]
+fn test_system_time_mutator() {
+    test_mutator(SystemTime::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+
+    let m = SystemTime::default_mutator();
+    let before_epoch = UNIX_EPOCH - Duration::from_millis(1500);
+    assert!(m.validate_value(&before_epoch).is_some());
+    for _ in 0..1000 {
+        let (t, _) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&t).unwrap();
+        let mut mutated = t;
+        let (token, _) = m.random_mutate(&mut mutated, &mut cache, 100.0);
+        m.unmutate(&mut mutated, &mut cache, token);
+        assert_eq!(mutated, t);
+    }
+}
+
+#[test]
+fn test_ip_addr_mutators() {
+    test_mutator(Ipv4Addr::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+    test_mutator(Ipv6Addr::default_mutator(), 200.0, 200.0, false, true, 100, 100);
+    test_mutator(IpAddr::default_mutator(), 200.0, 200.0, false, true, 100, 100);
+
+    let m = IpAddr::default_mutator();
+    let mut step = m.default_arbitrary_step();
+    let mut values = vec![];
+    for _ in 0..40 {
+        values.push(m.ordered_arbitrary(&mut step, 200.0).unwrap().0);
+    }
+    for special in [IpAddr::from(Ipv4Addr::LOCALHOST), IpAddr::from(Ipv6Addr::LOCALHOST)] {
+        assert!(values.contains(&special), "{:?}", special);
+    }
+}
+
+#[test]
+fn test_socket_addr_mutator() {
+    test_mutator(SocketAddr::default_mutator(), 200.0, 200.0, false, true, 100, 100);
+
+    let m = SocketAddr::default_mutator();
+    assert!(m.validate_value(&"127.0.0.1:8080".parse().unwrap()).is_some());
+    assert!(m.validate_value(&"[::1]:443".parse().unwrap()).is_some());
+    assert!(m.validate_value(&"[fe80::1%2]:443".parse().unwrap()).is_none());
+}
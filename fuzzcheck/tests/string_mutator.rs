@@ -0,0 +1,51 @@
+use fuzzcheck::mutators::string::StringMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_string_mutator() {
+    test_mutator(StringMutator::default(), 1000.0, 1000.0, false, true, 100, 100);
+    test_mutator(StringMutator::new(2..=10), 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_string_mutator_length_and_complexity() {
+    let m = StringMutator::new(0..=16);
+    for _ in 0..1000 {
+        let (mut s, cplx) = m.random_arbitrary(1000.0);
+        assert!(s.len() <= 16);
+        assert!((cplx - (1.0 + 8.0 * s.len() as f64)).abs() < 1e-9);
+        let mut cache = m.validate_value(&s).unwrap();
+        let mut step = m.default_mutation_step(&s, &cache);
+        for _ in 0..20 {
+            let before = s.clone();
+            if let Some((token, cplx)) = m.ordered_mutate(&mut s, &mut cache, &mut step, 1000.0) {
+                assert!(s.len() <= 16);
+                assert!((cplx - (1.0 + 8.0 * s.len() as f64)).abs() < 1e-9);
+                m.unmutate(&mut s, &mut cache, token);
+                assert_eq!(s, before);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_string_mutator_mutations() {
+    let m = StringMutator::new(0..=64);
+    let original = "paypal".to_owned();
+    let mut found_confusable = false;
+    let mut found_toggled_case = false;
+    let mut found_duplicate = false;
+    for _ in 0..1000 {
+        let mut s = original.clone();
+        let mut cache = m.validate_value(&s).unwrap();
+        m.random_mutate(&mut s, &mut cache, 1000.0);
+        found_confusable |= s.chars().count() == 6 && s.contains(['а', 'р', 'у', 'ｐ']);
+        found_toggled_case |= s.to_lowercase() == original && s != original;
+        // inserting a character only adds one
+        found_duplicate |= s.len() >= 8 && s.chars().all(|c| "payl".contains(c));
+    }
+    assert!(found_confusable);
+    assert!(found_toggled_case);
+    assert!(found_duplicate);
+}
@@ -1,4 +1,7 @@
+use fuzzcheck::mutators::cached_complexity::CachedComplexityMutator;
+use fuzzcheck::mutators::with_complexity::WithComplexity;
 use fuzzcheck::mutators::{integer::U8Mutator, vector::VecMutator};
+use fuzzcheck::{DefaultMutator, Mutator};
 #[test]
 fn test_vector_mutator() {
     // let m = VecMutator::new(U8Mutator::default(), 0..=10);
@@ -13,6 +16,72 @@ fn test_vector_mutator() {
     fuzzcheck::mutators::testing_utilities::test_mutator(m, 500.0, 500.0, false, true, 100, 200);
 }
 
+#[test]
+fn test_vector_mutator_with_min_len() {
+    let m = VecMutator::new(U8Mutator::default(), 0..=usize::MAX).with_min_len(16);
+    assert!(m.min_complexity() >= 1.0 + 16.0 * 8.0);
+
+    assert!(m.validate_value(&vec![0; 15]).is_none());
+    assert!(m.validate_value(&vec![0; 16]).is_some());
+
+    let mut step = m.default_arbitrary_step();
+    let (first, _) = m.ordered_arbitrary(&mut step, 4096.0).unwrap();
+    assert_eq!(first.len(), 16);
+    for _ in 0..100 {
+        let (x, _) = m.ordered_arbitrary(&mut step, 4096.0).unwrap();
+        assert!(x.len() >= 16);
+    }
+
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 4096.0, 4096.0, false, true, 100, 200);
+}
+
+#[test]
+fn test_vector_mutator_within_len_range() {
+    let m = VecMutator::new(U8Mutator::default(), 2..=4);
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let (mut x, _) = m.ordered_arbitrary(&mut step, 4096.0).unwrap();
+        assert!((2..=4).contains(&x.len()), "{}", x.len());
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut mutation_step = m.default_mutation_step(&x, &cache);
+        for _ in 0..100 {
+            if let Some((token, _)) = m.ordered_mutate(&mut x, &mut cache, &mut mutation_step, 4096.0) {
+                assert!((2..=4).contains(&x.len()), "{}", x.len());
+                m.unmutate(&mut x, &mut cache, token);
+            }
+            let (token, _) = m.random_mutate(&mut x, &mut cache, 4096.0);
+            assert!((2..=4).contains(&x.len()), "{}", x.len());
+            m.unmutate(&mut x, &mut cache, token);
+        }
+    }
+    // the length of vectors of unit elements is chosen directly
+    let m = VecMutator::new(<()>::default_mutator(), 1..=3);
+    let mut step = m.default_arbitrary_step();
+    let mut lengths = vec![];
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 4096.0) {
+        lengths.push(x.len());
+    }
+    assert_eq!(lengths, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_vector_mutator_with_length_complexity() {
+    let m = VecMutator::new(U8Mutator::default(), 0..=usize::MAX);
+    let m_len = VecMutator::new(U8Mutator::default(), 0..=usize::MAX).with_length_complexity(2.0);
+    let value = vec![0u8; 10];
+    let cplx = m.complexity(&value, &m.validate_value(&value).unwrap());
+    let cplx_len = m_len.complexity(&value, &m_len.validate_value(&value).unwrap());
+    assert_eq!(cplx_len, cplx + 20.0);
+
+    for _ in 0..100 {
+        let (x, cplx) = m_len.random_arbitrary(100.0);
+        assert!(cplx <= 100.0, "{}", cplx);
+        assert!(x.len() <= 10);
+    }
+
+    fuzzcheck::mutators::testing_utilities::test_mutator(m_len, 500.0, 500.0, false, true, 100, 100);
+}
+
 // #[test]
 // fn test_vector_explore() {
 //     // let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=5), 0..=5);
@@ -83,3 +152,114 @@ fn test_vector_mutator() {
 //         }
 //     }
 // }
+
+#[test]
+fn test_cached_complexity_mutator() {
+    let m = CachedComplexityMutator::new(VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=10), 0..=10));
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_with_complexity_mutator() {
+    let m = WithComplexity::new(
+        VecMutator::new(U8Mutator::default(), 0..=10),
+        |v: &Vec<u8>, cplx: f64| cplx + 8.0 * v.iter().filter(|&&b| b == 0).count() as f64,
+    )
+    .with_complexity_bounds(1.0, 161.0);
+    assert_eq!(m.max_complexity(), 161.0);
+
+    let value = vec![0, 1, 0];
+    let cache = m.validate_value(&value).unwrap();
+    assert_eq!(m.complexity(&value, &cache), 1.0 + 3.0 * 8.0 + 2.0 * 8.0);
+
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_vector_mutator_with_dictionary() {
+    let token = b"\x7fELF".to_vec();
+    let m = VecMutator::new(U8Mutator::default(), 0..=usize::MAX).with_dictionary(vec![token.clone()]);
+
+    let mut value = vec![];
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut inserted_token = false;
+    while let Some((unmutate_token, cplx)) = m.ordered_mutate(&mut value, &mut cache, &mut step, 4096.0) {
+        if value == token {
+            inserted_token = true;
+            assert_eq!(cplx, m.complexity(&value, &m.validate_value(&value).unwrap()));
+        }
+        m.unmutate(&mut value, &mut cache, unmutate_token);
+        assert!(value.is_empty());
+        if inserted_token {
+            break;
+        }
+    }
+    assert!(inserted_token);
+
+    fuzzcheck::mutators::testing_utilities::test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_vector_mutator_crossover() {
+    let m = VecMutator::new(U8Mutator::default(), 0..=8);
+    let original = vec![1, 2, 3];
+    let other = vec![4, 5, 6, 7, 8];
+    let other_cache = m.validate_value(&other).unwrap();
+
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut crossed_over = false;
+    for _ in 0..1000 {
+        if let Some((unmutate_token, cplx)) = m.crossover_mutate(&mut value, &mut cache, &other, &other_cache, 4096.0) {
+            crossed_over = true;
+            assert!(value.len() <= 8);
+            assert!(value.iter().any(|x| other.contains(x)));
+            assert_eq!(cplx, m.complexity(&value, &m.validate_value(&value).unwrap()));
+            m.unmutate(&mut value, &mut cache, unmutate_token);
+        }
+        assert_eq!(value, original);
+    }
+    assert!(crossed_over);
+    // nothing can be taken from an empty vector
+    let empty: Vec<u8> = vec![];
+    let empty_cache = m.validate_value(&empty).unwrap();
+    assert!(m
+        .crossover_mutate(&mut value, &mut cache, &empty, &empty_cache, 4096.0)
+        .is_none());
+}
+
+#[test]
+fn test_vector_mutator_reorders_and_duplicates_chunks() {
+    let m = VecMutator::new(U8Mutator::default(), 0..=16);
+    let original = (0..8).collect::<Vec<u8>>();
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let (mut rotated, mut reversed, mut duplicated) = (false, false, false);
+    for _ in 0..10_000 {
+        let (token, cplx) = m.random_mutate(&mut value, &mut cache, 4096.0);
+        assert!(value.len() <= 16);
+        assert_eq!(cplx, m.complexity(&value, &m.validate_value(&value).unwrap()));
+        if value.len() == original.len() && value != original {
+            let mut doubled = value.clone();
+            doubled.extend(value.iter());
+            rotated |= doubled.windows(8).any(|w| w == original.as_slice()) && value[0] != 0;
+            let start = value.iter().zip(original.iter()).position(|(a, b)| a != b).unwrap();
+            let end = 8 - value
+                .iter()
+                .rev()
+                .zip(original.iter().rev())
+                .position(|(a, b)| a != b)
+                .unwrap();
+            reversed |= end - start > 3 && value[start..end].iter().rev().eq(original[start..end].iter());
+        } else if value.len() > original.len() + 1 {
+            let mut deduplicated = value.clone();
+            deduplicated.sort_unstable();
+            deduplicated.dedup();
+            duplicated |= deduplicated == original;
+        }
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    assert!(rotated && reversed && duplicated);
+}
@@ -6,6 +6,8 @@ use std::{
     time::Duration,
 };
 
+use crate::config::{parse_config, ConfigValue};
+
 pub const MAX_INPUT_CPLX_FLAG: &str = "max-cplx";
 pub const INPUT_FILE_FLAG: &str = "input-file";
 pub const IN_CORPUS_FLAG: &str = "in-corpus";
@@ -15,25 +17,69 @@ pub const NO_OUT_CORPUS_FLAG: &str = "no-out-corpus";
 pub const ARTIFACTS_FLAG: &str = "artifacts";
 pub const NO_ARTIFACTS_FLAG: &str = "no-artifacts";
 pub const STATS_FLAG: &str = "stats";
+pub const STATS_FORMAT_FLAG: &str = "stats-format";
+pub const STATS_INTERVAL_FLAG: &str = "stats-interval";
 pub const NO_STATS_FLAG: &str = "no-stats";
 pub const COMMAND_FLAG: &str = "command";
+pub const WRITE_COVERAGE_FLAG: &str = "write-coverage";
+pub const ASSERT_COVERAGE_FLAG: &str = "assert-coverage";
+pub const COVERAGE_MAP_FLAG: &str = "coverage-map";
+pub const COVERAGE_REPORT_FLAG: &str = "coverage-report";
+pub const CONFIG_FLAG: &str = "config";
+pub const DUMP_CONFIG_FLAG: &str = "dump-config";
 
 pub const MAX_DURATION_FLAG: &str = "stop-after-duration";
+pub const MAX_WALL_DURATION_FLAG: &str = "max-duration";
 pub const MAX_ITERATIONS_FLAG: &str = "stop-after-iterations";
 pub const STOP_AFTER_FIRST_FAILURE_FLAG: &str = "stop-after-first-failure";
+pub const IGNORE_PANIC_FLAG: &str = "ignore-panic";
+pub const SLOW_THRESHOLD_FLAG: &str = "slow-threshold";
+pub const REPORT_SLOW_UNITS_FLAG: &str = "report-slow-units";
+pub const HANG_THRESHOLD_FLAG: &str = "hang-threshold";
+pub const MAX_MEMORY_FLAG: &str = "max-memory";
+pub const MALLOC_LIMIT_FLAG: &str = "malloc-limit";
+pub const RESUME_FLAG: &str = "resume";
+pub const SAVE_STATE_FLAG: &str = "save-state";
+pub const RESULTS_FILE_FLAG: &str = "results-file";
+pub const RUNS_PER_INPUT_FLAG: &str = "runs-per-input";
+pub const MAX_ARTIFACTS_PER_SIGNATURE_FLAG: &str = "max-artifacts-per-signature";
+pub const SANITIZER_FLAG: &str = "sanitizer";
+pub const TRACE_COMPARES_FLAG: &str = "trace-compares";
+pub const TARGET_FLAG: &str = "target";
+pub const RUSTFLAGS_FLAG: &str = "rustflags";
+pub const MERGE_FROM_FLAG: &str = "merge-from";
+pub const STRICT_FLAG: &str = "strict";
+pub const POST_MINIFY_CHECK_FLAG: &str = "post-minify-check";
+pub const BOOTSTRAP_COUNT_FLAG: &str = "bootstrap-count";
+pub const DICTIONARY_FLAG: &str = "dictionary";
+pub const WORKER_FLAG: &str = "worker";
+pub const ENSEMBLE_FLAG: &str = "ensemble";
+pub const FORK_FLAG: &str = "fork";
 
 pub const COMMAND_FUZZ: &str = "fuzz";
 pub const COMMAND_MINIFY_INPUT: &str = "minify";
 pub const COMMAND_READ: &str = "read";
+pub const COMMAND_BISECT_CORPUS: &str = "bisect-corpus";
+pub const COMMAND_MERGE: &str = "merge";
+pub const COMMAND_RUN_CORPUS: &str = "run-corpus";
+pub const COMMAND_MINIFY_CORPUS: &str = "minify-corpus";
+
+/// The extension of the file saved next to a failing test case, which contains its value pretty-printed with `Debug`
+/// when the fuzz test was built with `debug_failing_test_cases()`
+pub const DEBUG_VALUE_EXTENSION: &str = "debug";
 
 #[derive(Clone)]
 pub struct DefaultArguments {
     pub max_input_cplx: f64,
+    pub bootstrap_count: usize,
 }
 impl Default for DefaultArguments {
     #[no_coverage]
     fn default() -> Self {
-        Self { max_input_cplx: 4096.0 }
+        Self {
+            max_input_cplx: 4096.0,
+            bootstrap_count: 100,
+        }
     }
 }
 
@@ -41,8 +87,46 @@ impl Default for DefaultArguments {
 #[derive(Debug, Clone)]
 pub enum FuzzerCommand {
     Fuzz,
-    Read { input_file: PathBuf },
-    MinifyInput { input_file: PathBuf },
+    Read {
+        input_file: PathBuf,
+    },
+    MinifyInput {
+        input_file: PathBuf,
+    },
+    /// Replay the input corpus and save the set of activated coverage counters to `snapshot_file`
+    WriteCoverage {
+        snapshot_file: PathBuf,
+    },
+    /// Replay the input corpus and fail if the set of activated coverage counters differs from
+    /// the one saved in `snapshot_file`
+    AssertCoverage {
+        snapshot_file: PathBuf,
+    },
+    /// Replay the input corpus and save, for each instrumented function, the number of inputs
+    /// activating it and its total hit count, as JSON, to `output_file`
+    CoverageMap {
+        output_file: PathBuf,
+    },
+    /// Replay the input corpus and save the hit count of each line and region of the source files to
+    /// `output_file`, in the lcov format, or as JSON if its extension is `json`
+    CoverageReport {
+        output_file: PathBuf,
+    },
+    /// Load the files of the input corpus one by one, without running the test function, and report
+    /// the first file whose loading panics
+    BisectCorpus,
+    /// Replay the test cases of `corpora`, from the simplest to the most complex, and add to the output corpus
+    /// the ones that activate coverage counters that were not activated before
+    Merge {
+        corpora: Vec<PathBuf>,
+    },
+    /// Run the test function once on each test case of the input corpus and of the artifacts folder, report
+    /// the ones that fail, and exit with a non-zero status if there is any
+    RunCorpus,
+    /// Replay the input corpus from the simplest to the most complex test case, remove the test cases that do not
+    /// activate any coverage counter that a simpler one did not activate, and report the number of counters that
+    /// each remaining test case is the only one to activate
+    MinifyCorpus,
 }
 impl Default for FuzzerCommand {
     fn default() -> Self {
@@ -50,6 +134,71 @@ impl Default for FuzzerCommand {
     }
 }
 
+/// A sanitizer that the fuzz test can be compiled with, to detect memory errors or data races
+/// that do not cause a panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sanitizer {
+    Address,
+    Memory,
+    Thread,
+}
+impl Sanitizer {
+    /// The name of the sanitizer, as given to `--sanitizer` and to `rustc -Zsanitizer`
+    #[no_coverage]
+    pub fn name(self) -> &'static str {
+        match self {
+            Sanitizer::Address => "address",
+            Sanitizer::Memory => "memory",
+            Sanitizer::Thread => "thread",
+        }
+    }
+    /// The environment variable holding the runtime options of the sanitizer
+    #[no_coverage]
+    pub fn options_env_var(self) -> &'static str {
+        match self {
+            Sanitizer::Address => "ASAN_OPTIONS",
+            Sanitizer::Memory => "MSAN_OPTIONS",
+            Sanitizer::Thread => "TSAN_OPTIONS",
+        }
+    }
+    #[no_coverage]
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "address" => Some(Sanitizer::Address),
+            "memory" => Some(Sanitizer::Memory),
+            "thread" => Some(Sanitizer::Thread),
+            _ => None,
+        }
+    }
+}
+
+/// The format of the file of events written to the stats folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// A CSV file, `events.csv`, with a row for each event that carries statistics
+    Csv,
+    /// A JSON lines file, `events.jsonl`, with an object for each event, including its name and a timestamp
+    Json,
+}
+impl StatsFormat {
+    /// The name of the format, as given to `--stats-format`
+    #[no_coverage]
+    pub fn name(self) -> &'static str {
+        match self {
+            StatsFormat::Csv => "csv",
+            StatsFormat::Json => "json",
+        }
+    }
+    #[no_coverage]
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(StatsFormat::Csv),
+            "json" => Some(StatsFormat::Json),
+            _ => None,
+        }
+    }
+}
+
 /// Various arguments given to the fuzzer, typically provided by the `cargo fuzzcheck` command line tool.
 #[derive(Debug, Clone)]
 pub struct Arguments {
@@ -62,6 +211,77 @@ pub struct Arguments {
     pub corpus_out: Option<PathBuf>,
     pub artifacts_folder: Option<PathBuf>,
     pub stats_folder: Option<PathBuf>,
+    /// The format of the events written to the stats folder
+    pub stats_format: StatsFormat,
+    /// The number of iterations between two records of the statistics written to the stats folder, in addition
+    /// to the ones written when the corpus changes
+    pub stats_interval: Option<usize>,
+    /// Regular expressions matched against the message of the panics of the test function.
+    /// A panic whose message matches any of them is not considered a test failure.
+    pub ignored_panics: Vec<String>,
+    /// The test cases that take longer than this duration to run are saved to the `slow` folder of the output corpus,
+    /// but are not considered test failures
+    pub slow_threshold: Option<Duration>,
+    /// The test cases that take longer than this duration to run are interrupted and considered test failures.
+    /// They are saved to the `hangs` folder of the artifacts folder.
+    pub hang_threshold: Option<Duration>,
+    /// The maximum resident set size of the fuzzer, in megabytes. A test case whose run makes the
+    /// memory usage exceed it is treated as a test failure and saved to the `oom` folder of the
+    /// artifacts folder.
+    pub max_memory: Option<usize>,
+    /// The maximum number of megabytes that the test function may hold at the same time during a single run. A test
+    /// case exceeding it is treated as a test failure. It requires the fuzz target to install a `CountingAllocator`
+    /// as its global allocator.
+    pub malloc_limit: Option<usize>,
+    /// The file where the state of the fuzzer is saved periodically and when it stops, and from
+    /// which it resumes if it exists
+    pub resume: Option<PathBuf>,
+    /// The file where the outcome of the fuzzing campaign is written as JSON when the fuzzer stops, see
+    /// [`CampaignResults`](crate::results::CampaignResults)
+    pub results_file: Option<PathBuf>,
+    /// A dictionary file in the format of libFuzzer and AFL, whose tokens can be inserted into the
+    /// generated vectors and strings. It is read by the fuzz test when it creates its mutator, with
+    /// [`dictionary_from_cargo_fuzzcheck`](https://docs.rs/fuzzcheck/latest/fuzzcheck/builder/fn.dictionary_from_cargo_fuzzcheck.html).
+    pub dictionary: Option<PathBuf>,
+    /// The index of the fuzzer among the workers launched by `cargo fuzzcheck --jobs`, if any.
+    /// A worker writes its test cases to its own subfolder of the output corpus, and periodically
+    /// reads the test cases that the other workers wrote to theirs.
+    pub worker: Option<usize>,
+    /// The address of the broker of an ensemble of fuzzers, such as `192.168.1.10:7878`. The fuzzer sends the
+    /// test cases that it adds to its corpus to the broker, and tests the ones found by the other fuzzers of the
+    /// ensemble, see [`ensemble`](crate::ensemble).
+    pub ensemble: Option<String>,
+    /// Run the test function in a child process, which tests this many test cases before it is replaced by a new
+    /// one. A crash of the child process, such as a segmentation fault in unsafe code, is saved as an artifact and
+    /// does not stop the fuzzer.
+    pub fork: Option<usize>,
+    /// The number of times each test case is run while the sensor is recording, such that the
+    /// observations of the sensor cover all of these runs
+    pub runs_per_input: u32,
+    /// The maximum number of failing test cases saved for each failure signature, which is made of the panic
+    /// message and of the top frames of the backtrace. The simplest ones are kept.
+    pub max_artifacts_per_signature: usize,
+    /// The number of test cases tested before the fuzzer starts mutating them. The test cases of the input
+    /// corpus that are tested first count towards it, and the rest are arbitrary test cases.
+    pub bootstrap_count: usize,
+    /// The sanitizer that the fuzz test is compiled with, if any. It is only used by `cargo fuzzcheck`.
+    pub sanitizer: Option<Sanitizer>,
+    /// Whether the fuzz test is compiled with the instrumentation of its comparisons, which is needed by the
+    /// `ComparisonSensor`. It is only used by `cargo fuzzcheck`.
+    pub trace_compares: bool,
+    /// The target triple that the fuzz test is compiled for, such as `aarch64-apple-darwin`. It is only used by
+    /// `cargo fuzzcheck`, which compiles for the host triple given by `rustc -vV` if it is `None`.
+    pub target: Option<String>,
+    /// Additional flags given to `rustc` when compiling the fuzz test and its dependencies, such as
+    /// `-Zsanitizer=address`. Each value may contain several flags separated by whitespace. It is only used by
+    /// `cargo fuzzcheck`, which appends them to the flags it uses.
+    pub rustflags: Vec<String>,
+    /// Whether the warnings about the configuration of the fuzzer, such as a maximum complexity lower than
+    /// the minimum complexity of the mutator, stop the fuzzer instead
+    pub strict: bool,
+    /// Whether the command `minify-corpus` replays the kept test cases and fails if they do not activate the
+    /// same coverage counters as the whole input corpus
+    pub post_minify_check: bool,
 }
 
 /// The command line argument parser used by the fuzz target and `cargo fuzzcheck`
@@ -78,7 +298,15 @@ pub fn options_parser() -> Options {
             "the action to be performed (default: fuzz). --{} is required when using `{}`",
             INPUT_FILE_FLAG, COMMAND_MINIFY_INPUT
         ),
-        &format!("<{} | {}>", COMMAND_FUZZ, COMMAND_MINIFY_INPUT),
+        &format!(
+            "<{} | {} | {} | {} | {} | {}>",
+            COMMAND_FUZZ,
+            COMMAND_MINIFY_INPUT,
+            COMMAND_BISECT_CORPUS,
+            COMMAND_MERGE,
+            COMMAND_RUN_CORPUS,
+            COMMAND_MINIFY_CORPUS
+        ),
     );
     options.optopt(
         "",
@@ -86,11 +314,150 @@ pub fn options_parser() -> Options {
         "maximum duration of the fuzz test, in seconds",
         "N",
     );
+    options.optopt(
+        "",
+        MAX_WALL_DURATION_FLAG,
+        "maximum duration of the fuzz test, with a unit: s, m, h, or d (e.g. 8h)",
+        "DURATION",
+    );
     options.optopt("", MAX_ITERATIONS_FLAG, "maximum number of iterations", "N");
-    options.optflag(
+    options.optflagopt(
         "",
         STOP_AFTER_FIRST_FAILURE_FLAG,
-        "stop the fuzzer after the first test failure is found",
+        "stop the fuzzer after the first test failure is found (=false to keep fuzzing and collect more failures)",
+        "BOOL",
+    );
+    options.optmulti(
+        "",
+        IGNORE_PANIC_FLAG,
+        "do not treat panics whose message matches the regular expression as test failures (can be repeated)",
+        "REGEX",
+    );
+    options.optopt(
+        "",
+        SLOW_THRESHOLD_FLAG,
+        "save the test cases that take longer than N milliseconds to run to the output corpus, without stopping",
+        "N",
+    );
+    options.optopt(
+        "",
+        REPORT_SLOW_UNITS_FLAG,
+        "same as --slow-threshold, under the name of the equivalent libFuzzer option",
+        "N",
+    );
+    options.optopt(
+        "",
+        HANG_THRESHOLD_FLAG,
+        "interrupt the test cases that take longer than N milliseconds to run and treat them as test failures",
+        "N",
+    );
+    options.optopt(
+        "",
+        MAX_MEMORY_FLAG,
+        "treat the test cases that make the memory usage of the fuzzer exceed N megabytes as test failures",
+        "N",
+    );
+    options.optopt(
+        "",
+        MALLOC_LIMIT_FLAG,
+        "treat the test cases during which the test function holds more than N megabytes of allocated memory as test failures",
+        "N",
+    );
+    options.optopt(
+        "",
+        RUNS_PER_INPUT_FLAG,
+        "run each test case N times and record the union of their observations (default: 1)",
+        "N",
+    );
+    options.optopt(
+        "",
+        MAX_ARTIFACTS_PER_SIGNATURE_FLAG,
+        "save at most N failing test cases for each panic message and backtrace (default: 1)",
+        "N",
+    );
+    options.optopt(
+        "",
+        BOOTSTRAP_COUNT_FLAG,
+        &format!(
+            "test N test cases, from the input corpus or else arbitrary, before starting to mutate them (default: {})",
+            defaults.bootstrap_count
+        ),
+        "N",
+    );
+    options.optflag(
+        "",
+        STRICT_FLAG,
+        "stop the fuzzer instead of warning when it is misconfigured, e.g. when --max-cplx is too low for the mutator",
+    );
+    options.optflag(
+        "",
+        POST_MINIFY_CHECK_FLAG,
+        "with the command minify-corpus, replay the kept test cases and fail if they activate other coverage counters than the input corpus",
+    );
+    options.optopt(
+        "",
+        RESUME_FLAG,
+        "save the state of the fuzzer to the given file periodically and when it stops, and resume from it if it exists",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        SAVE_STATE_FLAG,
+        "same as --resume DIR/fuzz_state.bin, under the name of the option of earlier versions",
+        "DIR",
+    );
+    options.optopt(
+        "",
+        RESULTS_FILE_FLAG,
+        "write the outcome of the fuzz test (failures found, iterations, coverage, corpus size) to the given JSON file when it stops",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        DICTIONARY_FLAG,
+        "a libFuzzer/AFL dictionary of tokens that the mutators of vectors and strings can insert into test cases",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        WORKER_FLAG,
+        "the index of the fuzzer among the workers sharing the output corpus (set by cargo fuzzcheck --jobs)",
+        "N",
+    );
+    options.optopt(
+        "",
+        ENSEMBLE_FLAG,
+        "share the test cases added to the corpus with the other fuzzers connected to the broker at the given address",
+        "ADDRESS",
+    );
+    options.optopt(
+        "",
+        FORK_FLAG,
+        "run the test function in a child process, forked again after every N test cases or after a crash",
+        "N",
+    );
+    options.optopt(
+        "",
+        SANITIZER_FLAG,
+        "compile the fuzz test with the given sanitizer (requires a nightly toolchain)",
+        "<address | memory | thread>",
+    );
+    options.optflag(
+        "",
+        TRACE_COMPARES_FLAG,
+        "compile the fuzz test with the instrumentation of its comparisons, needed by the ComparisonSensor",
+    );
+    options.optopt(
+        "",
+        TARGET_FLAG,
+        "compile the fuzz test for the given target triple (default: the host triple given by rustc -vV)",
+        "<TRIPLE>",
+    );
+    options.optmulti(
+        "",
+        RUSTFLAGS_FLAG,
+        "additional flags given to rustc when compiling the fuzz test and its dependencies (can be repeated)",
+        "<FLAGS>",
     );
 
     options.optopt("", IN_CORPUS_FLAG, "folder for the input corpus", "PATH");
@@ -129,7 +496,53 @@ pub fn options_parser() -> Options {
         NO_STATS_FLAG,
         format!("do not save statistics, overrides --{stats}", stats = STATS_FLAG).as_str(),
     );
+    options.optopt(
+        "",
+        STATS_FORMAT_FLAG,
+        "format of the events written to the stats folder (default: csv)",
+        "<csv | json>",
+    );
+    options.optopt(
+        "",
+        STATS_INTERVAL_FLAG,
+        "also record the statistics in the stats folder every N iterations",
+        "N",
+    );
     options.optopt("", INPUT_FILE_FLAG, "file containing a test case", "PATH");
+    options.optmulti(
+        "",
+        MERGE_FROM_FLAG,
+        format!(
+            "corpus to merge into the output corpus with the command {merge} (can be repeated)",
+            merge = COMMAND_MERGE
+        )
+        .as_str(),
+        "PATH",
+    );
+    options.optopt(
+        "",
+        WRITE_COVERAGE_FLAG,
+        "replay the input corpus and write the set of activated coverage counters to the given file",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        ASSERT_COVERAGE_FLAG,
+        "replay the input corpus and fail if the set of activated coverage counters differs from the one in the given file",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        COVERAGE_MAP_FLAG,
+        "replay the input corpus and write, for each function, the number of inputs reaching it and its total hit count to the given JSON file",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        COVERAGE_REPORT_FLAG,
+        "replay the input corpus and write the hit count of each source line to the given lcov file, or JSON file if it ends with .json",
+        "PATH",
+    );
     options.optopt(
         "",
         MAX_INPUT_CPLX_FLAG,
@@ -141,11 +554,173 @@ pub fn options_parser() -> Options {
         "N",
     );
 
+    options.optopt(
+        "",
+        CONFIG_FLAG,
+        "TOML file containing default values for the other options, which are overridden by the command line",
+        "PATH",
+    );
+    options.optopt(
+        "",
+        DUMP_CONFIG_FLAG,
+        "write the fully-resolved options to the given file, in a format that can be read by --config",
+        "PATH",
+    );
+
     options.optflag("h", "help", "print this help menu");
 
     options
 }
 
+/// Pairs of flags such that giving one of them on the command line overrides the other one
+/// when it is given in a config file.
+const OPPOSITE_FLAGS: [(&str, &str); 13] = [
+    (MAX_DURATION_FLAG, MAX_WALL_DURATION_FLAG),
+    (SLOW_THRESHOLD_FLAG, REPORT_SLOW_UNITS_FLAG),
+    (RESUME_FLAG, SAVE_STATE_FLAG),
+    (IN_CORPUS_FLAG, NO_IN_CORPUS_FLAG),
+    (OUT_CORPUS_FLAG, NO_OUT_CORPUS_FLAG),
+    (ARTIFACTS_FLAG, NO_ARTIFACTS_FLAG),
+    (STATS_FLAG, NO_STATS_FLAG),
+    (WRITE_COVERAGE_FLAG, ASSERT_COVERAGE_FLAG),
+    (WRITE_COVERAGE_FLAG, COVERAGE_MAP_FLAG),
+    (ASSERT_COVERAGE_FLAG, COVERAGE_MAP_FLAG),
+    (WRITE_COVERAGE_FLAG, COVERAGE_REPORT_FLAG),
+    (ASSERT_COVERAGE_FLAG, COVERAGE_REPORT_FLAG),
+    (COVERAGE_MAP_FLAG, COVERAGE_REPORT_FLAG),
+];
+
+/// Parse the command line arguments, using the file given by `--config`, if any, as a base layer.
+///
+/// The precedence of the options is: command line > config file > environment > default value.
+/// The only environment input is the `FUZZCHECK_ARGS` variable, which is the command line of the
+/// fuzz test binary itself, so it is parsed by this same function.
+/// The keys of the config file are the names of the command line flags, for example:
+/// ```toml
+/// max-cplx = 4096
+/// in-corpus = "fuzz/target1/corpus"
+/// stop-after-first-failure = true
+/// ```
+#[no_coverage]
+pub fn parse_arguments_with_config<S: AsRef<str>>(parser: &Options, args: &[S]) -> Result<Matches, ArgumentsError> {
+    parse_arguments_with_defaults(parser, args, None)
+}
+
+/// Parse the command line arguments like [`parse_arguments_with_config`], with the options of `defaults`, if any,
+/// as a layer below the file given by `--config`.
+///
+/// `defaults` contains the path of the file that the options were read from, which is used in the error
+/// messages, and the options themselves. It is used by `cargo fuzzcheck` for the options of the fuzz test
+/// given in the `fuzz/fuzzcheck.toml` file of the fuzz project.
+#[no_coverage]
+pub fn parse_arguments_with_defaults<S: AsRef<str>>(
+    parser: &Options,
+    args: &[S],
+    defaults: Option<(PathBuf, Vec<(String, ConfigValue)>)>,
+) -> Result<Matches, ArgumentsError> {
+    let mut all_args = args
+        .iter()
+        .map(
+            #[no_coverage]
+            |x| x.as_ref().to_owned(),
+        )
+        .collect::<Vec<_>>();
+    let matches = parser.parse(&all_args)?;
+    if let Some(config_path) = matches.opt_str(CONFIG_FLAG) {
+        let content = std::fs::read_to_string(&config_path).map_err(
+            #[no_coverage]
+            |e| ArgumentsError::Validation(format!("The config file {} could not be read: {}", config_path, e)),
+        )?;
+        let config = parse_config(&content).map_err(
+            #[no_coverage]
+            |e| ArgumentsError::Validation(format!("The config file {} is invalid: {}", config_path, e)),
+        )?;
+        let mut config_args = config_to_arguments(&matches, &config_path, config)?;
+        config_args.extend(all_args);
+        all_args = config_args;
+    }
+    if let Some((defaults_path, defaults)) = defaults {
+        let matches = parser.parse(&all_args)?;
+        let mut defaults_args = config_to_arguments(&matches, &defaults_path.display().to_string(), defaults)?;
+        defaults_args.extend(all_args);
+        all_args = defaults_args;
+    }
+    Ok(parser.parse(all_args)?)
+}
+
+/// The command line arguments corresponding to the options of a config file, except for the ones overridden by
+/// the arguments that were already parsed into `matches`
+#[no_coverage]
+fn config_to_arguments(
+    matches: &Matches,
+    config_path: &str,
+    config: Vec<(String, ConfigValue)>,
+) -> Result<Vec<String>, ArgumentsError> {
+    let mut all_args = vec![];
+    for (key, value) in config {
+        if !matches.opt_defined(&key) || key == CONFIG_FLAG || key == DUMP_CONFIG_FLAG {
+            return Err(ArgumentsError::Validation(format!(
+                "The config file {} contains the unknown option {}.",
+                config_path, key
+            )));
+        }
+        let overridden = matches.opt_present(&key)
+            || OPPOSITE_FLAGS.iter().any(
+                #[no_coverage]
+                |(a, b)| (key == *a && matches.opt_present(b)) || (key == *b && matches.opt_present(a)),
+            );
+        if overridden {
+            continue;
+        }
+        match value {
+            ConfigValue::Bool(true) => all_args.push(format!("--{}", key)),
+            // it must override a `true` given by a lower layer
+            ConfigValue::Bool(false) if key == STOP_AFTER_FIRST_FAILURE_FLAG => {
+                all_args.push(format!("--{}=false", key))
+            }
+            ConfigValue::Bool(false) => {}
+            ConfigValue::String(value) | ConfigValue::Number(value) => {
+                all_args.push(format!("--{}", key));
+                all_args.push(value);
+            }
+            ConfigValue::Array(values) => {
+                for value in values {
+                    match value {
+                        ConfigValue::String(value) | ConfigValue::Number(value) => {
+                            all_args.push(format!("--{}", key));
+                            all_args.push(value);
+                        }
+                        _ => {
+                            return Err(ArgumentsError::Validation(format!(
+                                "The config file {} contains an invalid value for the option {}.",
+                                config_path, key
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(all_args)
+}
+
+/// Parse a positive duration written as a number followed by a unit: `s`, `m`, `h`, or `d` (e.g. `8h`)
+#[no_coverage]
+fn parse_duration_with_unit(duration: &str) -> Option<Duration> {
+    let (number, unit_seconds) = [('s', 1), ('m', 60), ('h', 60 * 60), ('d', 24 * 60 * 60)]
+        .iter()
+        .find_map(
+            #[no_coverage]
+            |&(unit, unit_seconds)| Some((duration.strip_suffix(unit)?, unit_seconds)),
+        )?;
+    let seconds = number.parse::<u64>().ok()?.checked_mul(unit_seconds)?;
+    if seconds > 0 {
+        Some(Duration::from_secs(seconds))
+    } else {
+        None
+    }
+}
+
 impl Arguments {
     /// Create an `Arguments` from the parsed result of [`options_parser()`].
     ///
@@ -172,12 +747,25 @@ impl Arguments {
 
         let command = command.as_str();
 
-        if !matches!(command, COMMAND_FUZZ | COMMAND_READ | COMMAND_MINIFY_INPUT) {
+        if !matches!(
+            command,
+            COMMAND_FUZZ
+                | COMMAND_READ
+                | COMMAND_MINIFY_INPUT
+                | COMMAND_BISECT_CORPUS
+                | COMMAND_MERGE
+                | COMMAND_RUN_CORPUS
+                | COMMAND_MINIFY_CORPUS
+        ) {
             return Err(ArgumentsError::Validation(format!(
-                r#"The command {c} is not supported. It can either be ‘{fuzz}’ or ‘{minify}’."#,
+                r#"The command {c} is not supported. It can either be ‘{fuzz}’, ‘{minify}’, ‘{bisect}’, ‘{merge}’, ‘{run_corpus}’, or ‘{minify_corpus}’."#,
                 c = &matches.free[0],
                 fuzz = COMMAND_FUZZ,
                 minify = COMMAND_MINIFY_INPUT,
+                bisect = COMMAND_BISECT_CORPUS,
+                merge = COMMAND_MERGE,
+                run_corpus = COMMAND_RUN_CORPUS,
+                minify_corpus = COMMAND_MINIFY_CORPUS,
             )));
         }
 
@@ -241,10 +829,191 @@ impl Arguments {
             |x| x.parse::<PathBuf>().ok(),
         );
 
+        let write_coverage: Option<PathBuf> = matches.opt_str(WRITE_COVERAGE_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+        let assert_coverage: Option<PathBuf> = matches.opt_str(ASSERT_COVERAGE_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+
+        let coverage_map: Option<PathBuf> = matches.opt_str(COVERAGE_MAP_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+        let coverage_report: Option<PathBuf> = matches.opt_str(COVERAGE_REPORT_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+
+        let resume: Option<PathBuf> = if let Some(folder) = matches.opt_str(SAVE_STATE_FLAG) {
+            if matches.opt_present(RESUME_FLAG) {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} and --{} cannot be given together.",
+                    RESUME_FLAG, SAVE_STATE_FLAG
+                )));
+            }
+            Some(PathBuf::from(folder).join("fuzz_state.bin"))
+        } else {
+            matches.opt_str(RESUME_FLAG).and_then(
+                #[no_coverage]
+                |x| x.parse::<PathBuf>().ok(),
+            )
+        };
+
+        let results_file: Option<PathBuf> = matches.opt_str(RESULTS_FILE_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+
+        let dictionary: Option<PathBuf> = matches.opt_str(DICTIONARY_FLAG).and_then(
+            #[no_coverage]
+            |x| x.parse::<PathBuf>().ok(),
+        );
+
+        let merge_from: Vec<PathBuf> = matches
+            .opt_strs(MERGE_FROM_FLAG)
+            .into_iter()
+            .map(
+                #[no_coverage]
+                PathBuf::from,
+            )
+            .collect();
+
         // verify all the right options are here
 
+        let nbr_coverage_options = [&write_coverage, &assert_coverage, &coverage_map, &coverage_report]
+            .iter()
+            .filter(
+                #[no_coverage]
+                |x| x.is_some(),
+            )
+            .count();
+        if nbr_coverage_options > 0 {
+            if command != COMMAND_FUZZ {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{}, --{}, --{}, and --{} cannot be used with the command {}.",
+                    WRITE_COVERAGE_FLAG, ASSERT_COVERAGE_FLAG, COVERAGE_MAP_FLAG, COVERAGE_REPORT_FLAG, command
+                )));
+            }
+            if nbr_coverage_options > 1 {
+                return Err(ArgumentsError::Validation(format!(
+                    "Only one of --{}, --{}, --{}, and --{} can be given.",
+                    WRITE_COVERAGE_FLAG, ASSERT_COVERAGE_FLAG, COVERAGE_MAP_FLAG, COVERAGE_REPORT_FLAG
+                )));
+            }
+        }
+
+        if resume.is_some() && (command != COMMAND_FUZZ || nbr_coverage_options > 0) {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used when fuzzing.",
+                RESUME_FLAG
+            )));
+        }
+        if results_file.is_some() && (command != COMMAND_FUZZ || nbr_coverage_options > 0) {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used when fuzzing.",
+                RESULTS_FILE_FLAG
+            )));
+        }
+
+        let worker = if let Some(worker) = matches.opt_str(WORKER_FLAG) {
+            match worker.parse::<usize>() {
+                Ok(worker) => Some(worker),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a non-negative number.",
+                        WORKER_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+        if worker.is_some() && (command != COMMAND_FUZZ || nbr_coverage_options > 0) {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used when fuzzing.",
+                WORKER_FLAG
+            )));
+        }
+
+        let fork = if let Some(fork) = matches.opt_str(FORK_FLAG) {
+            match fork.parse::<usize>() {
+                Ok(fork) if fork > 0 => Some(fork),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number.",
+                        FORK_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+        if fork.is_some() && (command != COMMAND_FUZZ || nbr_coverage_options > 0) {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used when fuzzing.",
+                FORK_FLAG
+            )));
+        }
+
+        let ensemble = matches.opt_str(ENSEMBLE_FLAG);
+        if ensemble.is_some() && (command != COMMAND_FUZZ || nbr_coverage_options > 0) {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used when fuzzing.",
+                ENSEMBLE_FLAG
+            )));
+        }
+
+        if command == COMMAND_MERGE {
+            if merge_from.is_empty() {
+                return Err(ArgumentsError::Validation(format!(
+                    "At least one corpus must be given to the command {} with --{}.",
+                    COMMAND_MERGE, MERGE_FROM_FLAG
+                )));
+            }
+            if no_out_corpus.is_some() {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} cannot be used with the command {}, which writes to the output corpus.",
+                    NO_OUT_CORPUS_FLAG, COMMAND_MERGE
+                )));
+            }
+        } else if !merge_from.is_empty() {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used with the command {}.",
+                MERGE_FROM_FLAG, COMMAND_MERGE
+            )));
+        }
+
+        if command == COMMAND_MINIFY_CORPUS && no_in_corpus.is_some() {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} cannot be used with the command {}, which reads the input corpus.",
+                NO_IN_CORPUS_FLAG, COMMAND_MINIFY_CORPUS
+            )));
+        }
+        let post_minify_check = matches.opt_present(POST_MINIFY_CHECK_FLAG);
+        if post_minify_check && command != COMMAND_MINIFY_CORPUS {
+            return Err(ArgumentsError::Validation(format!(
+                "--{} can only be used with the command {}.",
+                POST_MINIFY_CHECK_FLAG, COMMAND_MINIFY_CORPUS
+            )));
+        }
+
         let command = match command {
-            COMMAND_FUZZ => FuzzerCommand::Fuzz,
+            COMMAND_FUZZ => {
+                if let Some(snapshot_file) = write_coverage {
+                    FuzzerCommand::WriteCoverage { snapshot_file }
+                } else if let Some(snapshot_file) = assert_coverage {
+                    FuzzerCommand::AssertCoverage { snapshot_file }
+                } else if let Some(output_file) = coverage_map {
+                    FuzzerCommand::CoverageMap { output_file }
+                } else if let Some(output_file) = coverage_report {
+                    FuzzerCommand::CoverageReport { output_file }
+                } else {
+                    FuzzerCommand::Fuzz
+                }
+            }
             COMMAND_READ => {
                 let input_file = input_file.unwrap_or_else(
                     #[no_coverage]
@@ -269,10 +1038,30 @@ impl Arguments {
                 );
                 FuzzerCommand::MinifyInput { input_file }
             }
+            COMMAND_BISECT_CORPUS => FuzzerCommand::BisectCorpus,
+            COMMAND_MERGE => FuzzerCommand::Merge { corpora: merge_from },
+            COMMAND_RUN_CORPUS => FuzzerCommand::RunCorpus,
+            COMMAND_MINIFY_CORPUS => FuzzerCommand::MinifyCorpus,
             _ => unreachable!(),
         };
 
-        let maximum_duration = {
+        let maximum_duration = if let Some(duration) = matches.opt_str(MAX_WALL_DURATION_FLAG) {
+            if matches.opt_present(MAX_DURATION_FLAG) {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} and --{} cannot be given together.",
+                    MAX_DURATION_FLAG, MAX_WALL_DURATION_FLAG
+                )));
+            }
+            parse_duration_with_unit(&duration).ok_or_else(
+                #[no_coverage]
+                || {
+                    ArgumentsError::Validation(format!(
+                        "--{} must be a positive number followed by a unit: s, m, h, or d (e.g. 8h), but it is {}.",
+                        MAX_WALL_DURATION_FLAG, duration
+                    ))
+                },
+            )?
+        } else {
             let seconds = matches
                 .opt_str(MAX_DURATION_FLAG)
                 .and_then(
@@ -289,10 +1078,165 @@ impl Arguments {
                 |x| x.parse::<usize>().ok(),
             )
             .unwrap_or(usize::MAX);
-        let stop_after_first_failure = matches.opt_present(STOP_AFTER_FIRST_FAILURE_FLAG);
+        let stop_after_first_failure = match matches.opt_str(STOP_AFTER_FIRST_FAILURE_FLAG).as_deref() {
+            None => matches.opt_present(STOP_AFTER_FIRST_FAILURE_FLAG),
+            Some("true") => true,
+            Some("false") => false,
+            Some(value) => {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} must be given without a value, or with =true or =false, but it is {}.",
+                    STOP_AFTER_FIRST_FAILURE_FLAG, value
+                )))
+            }
+        };
+        let strict = matches.opt_present(STRICT_FLAG);
+        let trace_compares = matches.opt_present(TRACE_COMPARES_FLAG);
+        let target = matches.opt_str(TARGET_FLAG);
+        let rustflags = matches.opt_strs(RUSTFLAGS_FLAG);
+        let ignored_panics = matches.opt_strs(IGNORE_PANIC_FLAG);
+        #[no_coverage]
+        fn threshold(matches: &Matches, flag: &str) -> Result<Option<Duration>, ArgumentsError> {
+            if let Some(millis) = matches.opt_str(flag) {
+                match millis.parse::<u64>() {
+                    Ok(millis) if millis > 0 => Ok(Some(Duration::from_millis(millis))),
+                    _ => Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number of milliseconds.",
+                        flag
+                    ))),
+                }
+            } else {
+                Ok(None)
+            }
+        }
+        let runs_per_input = if let Some(runs) = matches.opt_str(RUNS_PER_INPUT_FLAG) {
+            match runs.parse::<u32>() {
+                Ok(runs) if runs > 0 => runs,
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number.",
+                        RUNS_PER_INPUT_FLAG
+                    )))
+                }
+            }
+        } else {
+            1
+        };
+        let max_artifacts_per_signature = if let Some(max) = matches.opt_str(MAX_ARTIFACTS_PER_SIGNATURE_FLAG) {
+            match max.parse::<usize>() {
+                Ok(max) if max > 0 => max,
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number.",
+                        MAX_ARTIFACTS_PER_SIGNATURE_FLAG
+                    )))
+                }
+            }
+        } else {
+            1
+        };
+        let bootstrap_count = if let Some(count) = matches.opt_str(BOOTSTRAP_COUNT_FLAG) {
+            match count.parse::<usize>() {
+                Ok(count) => Some(count),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a non-negative number.",
+                        BOOTSTRAP_COUNT_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+        let slow_threshold = if matches.opt_present(REPORT_SLOW_UNITS_FLAG) {
+            if matches.opt_present(SLOW_THRESHOLD_FLAG) {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} and --{} cannot be given together.",
+                    SLOW_THRESHOLD_FLAG, REPORT_SLOW_UNITS_FLAG
+                )));
+            }
+            threshold(matches, REPORT_SLOW_UNITS_FLAG)?
+        } else {
+            threshold(matches, SLOW_THRESHOLD_FLAG)?
+        };
+        let hang_threshold = threshold(matches, HANG_THRESHOLD_FLAG)?;
+        if let (Some(slow), Some(hang)) = (slow_threshold, hang_threshold) {
+            if slow >= hang {
+                return Err(ArgumentsError::Validation(format!(
+                    "--{} must be smaller than --{}.",
+                    SLOW_THRESHOLD_FLAG, HANG_THRESHOLD_FLAG
+                )));
+            }
+        }
+        let max_memory = if let Some(megabytes) = matches.opt_str(MAX_MEMORY_FLAG) {
+            match megabytes.parse::<usize>() {
+                Ok(megabytes) if megabytes > 0 => Some(megabytes),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number of megabytes.",
+                        MAX_MEMORY_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+        let malloc_limit = if let Some(megabytes) = matches.opt_str(MALLOC_LIMIT_FLAG) {
+            match megabytes.parse::<usize>() {
+                Ok(megabytes) if megabytes > 0 => Some(megabytes),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number of megabytes.",
+                        MALLOC_LIMIT_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+        let sanitizer = if let Some(name) = matches.opt_str(SANITIZER_FLAG) {
+            Some(Sanitizer::from_name(&name).ok_or_else(
+                #[no_coverage]
+                || {
+                    ArgumentsError::Validation(format!(
+                        "The sanitizer {} is not supported. It can be ‘address’, ‘memory’, or ‘thread’.",
+                        name
+                    ))
+                },
+            )?)
+        } else {
+            None
+        };
+
+        let stats_format = if let Some(name) = matches.opt_str(STATS_FORMAT_FLAG) {
+            StatsFormat::from_name(&name).ok_or_else(
+                #[no_coverage]
+                || {
+                    ArgumentsError::Validation(format!(
+                        "The stats format {} is not supported. It can be ‘csv’ or ‘json’.",
+                        name
+                    ))
+                },
+            )?
+        } else {
+            StatsFormat::Csv
+        };
+        let stats_interval = if let Some(iterations) = matches.opt_str(STATS_INTERVAL_FLAG) {
+            match iterations.parse::<usize>() {
+                Ok(iterations) if iterations > 0 => Some(iterations),
+                _ => {
+                    return Err(ArgumentsError::Validation(format!(
+                        "--{} must be a positive number of iterations.",
+                        STATS_INTERVAL_FLAG
+                    )))
+                }
+            }
+        } else {
+            None
+        };
 
         let defaults = DefaultArguments::default();
         let max_input_cplx: f64 = max_input_cplx.unwrap_or(defaults.max_input_cplx as f64);
+        let bootstrap_count = bootstrap_count.unwrap_or(defaults.bootstrap_count);
 
         let corpus_in: Option<PathBuf> = if no_in_corpus.is_some() { None } else { corpus_in };
         let corpus_out: Option<PathBuf> = if no_out_corpus.is_some() { None } else { corpus_out };
@@ -310,10 +1254,208 @@ impl Arguments {
             corpus_out,
             artifacts_folder,
             stats_folder,
+            stats_format,
+            stats_interval,
+            ignored_panics,
+            slow_threshold,
+            hang_threshold,
+            max_memory,
+            malloc_limit,
+            resume,
+            results_file,
+            dictionary,
+            worker,
+            ensemble,
+            fork,
+            runs_per_input,
+            max_artifacts_per_signature,
+            bootstrap_count,
+            sanitizer,
+            trace_compares,
+            target,
+            rustflags,
+            strict,
+            post_minify_check,
         })
     }
 }
 
+impl Arguments {
+    /// Serialize the arguments as a TOML config file that can be given to `--config`.
+    #[no_coverage]
+    pub fn to_toml(&self) -> String {
+        #[no_coverage]
+        fn string(x: &std::path::Path) -> String {
+            ConfigValue::String(x.display().to_string()).to_string()
+        }
+        let mut s = String::new();
+        match &self.command {
+            FuzzerCommand::Fuzz => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_FUZZ));
+            }
+            FuzzerCommand::Read { input_file } => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_READ));
+                s.push_str(&format!("{} = {}\n", INPUT_FILE_FLAG, string(input_file)));
+            }
+            FuzzerCommand::MinifyInput { input_file } => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_MINIFY_INPUT));
+                s.push_str(&format!("{} = {}\n", INPUT_FILE_FLAG, string(input_file)));
+            }
+            FuzzerCommand::WriteCoverage { snapshot_file } => {
+                s.push_str(&format!("{} = {}\n", WRITE_COVERAGE_FLAG, string(snapshot_file)));
+            }
+            FuzzerCommand::AssertCoverage { snapshot_file } => {
+                s.push_str(&format!("{} = {}\n", ASSERT_COVERAGE_FLAG, string(snapshot_file)));
+            }
+            FuzzerCommand::CoverageMap { output_file } => {
+                s.push_str(&format!("{} = {}\n", COVERAGE_MAP_FLAG, string(output_file)));
+            }
+            FuzzerCommand::CoverageReport { output_file } => {
+                s.push_str(&format!("{} = {}\n", COVERAGE_REPORT_FLAG, string(output_file)));
+            }
+            FuzzerCommand::BisectCorpus => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_BISECT_CORPUS));
+            }
+            FuzzerCommand::Merge { corpora } => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_MERGE));
+                let corpora = ConfigValue::Array(
+                    corpora
+                        .iter()
+                        .map(
+                            #[no_coverage]
+                            |x| ConfigValue::String(x.display().to_string()),
+                        )
+                        .collect(),
+                );
+                s.push_str(&format!("{} = {}\n", MERGE_FROM_FLAG, corpora));
+            }
+            FuzzerCommand::RunCorpus => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_RUN_CORPUS));
+            }
+            FuzzerCommand::MinifyCorpus => {
+                s.push_str(&format!("{} = \"{}\"\n", COMMAND_FLAG, COMMAND_MINIFY_CORPUS));
+            }
+        }
+        s.push_str(&format!("{} = {}\n", MAX_INPUT_CPLX_FLAG, self.max_input_cplx as usize));
+        if self.maximum_duration.as_secs() != u64::MAX {
+            s.push_str(&format!(
+                "{} = {}\n",
+                MAX_DURATION_FLAG,
+                self.maximum_duration.as_secs()
+            ));
+        }
+        if self.maximum_iterations != usize::MAX {
+            s.push_str(&format!("{} = {}\n", MAX_ITERATIONS_FLAG, self.maximum_iterations));
+        }
+        s.push_str(&format!(
+            "{} = {}\n",
+            STOP_AFTER_FIRST_FAILURE_FLAG, self.stop_after_first_failure
+        ));
+        if !self.ignored_panics.is_empty() {
+            let patterns = ConfigValue::Array(
+                self.ignored_panics
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |x| ConfigValue::String(x.clone()),
+                    )
+                    .collect(),
+            );
+            s.push_str(&format!("{} = {}\n", IGNORE_PANIC_FLAG, patterns));
+        }
+        for (threshold, flag) in [
+            (self.slow_threshold, SLOW_THRESHOLD_FLAG),
+            (self.hang_threshold, HANG_THRESHOLD_FLAG),
+        ] {
+            if let Some(threshold) = threshold {
+                s.push_str(&format!("{} = {}\n", flag, threshold.as_millis()));
+            }
+        }
+        if let Some(max_memory) = self.max_memory {
+            s.push_str(&format!("{} = {}\n", MAX_MEMORY_FLAG, max_memory));
+        }
+        if let Some(malloc_limit) = self.malloc_limit {
+            s.push_str(&format!("{} = {}\n", MALLOC_LIMIT_FLAG, malloc_limit));
+        }
+        if self.runs_per_input != 1 {
+            s.push_str(&format!("{} = {}\n", RUNS_PER_INPUT_FLAG, self.runs_per_input));
+        }
+        if self.max_artifacts_per_signature != 1 {
+            s.push_str(&format!(
+                "{} = {}\n",
+                MAX_ARTIFACTS_PER_SIGNATURE_FLAG, self.max_artifacts_per_signature
+            ));
+        }
+        s.push_str(&format!("{} = {}\n", BOOTSTRAP_COUNT_FLAG, self.bootstrap_count));
+        if let Some(resume) = &self.resume {
+            s.push_str(&format!("{} = {}\n", RESUME_FLAG, string(resume)));
+        }
+        if let Some(results_file) = &self.results_file {
+            s.push_str(&format!("{} = {}\n", RESULTS_FILE_FLAG, string(results_file)));
+        }
+        if let Some(dictionary) = &self.dictionary {
+            s.push_str(&format!("{} = {}\n", DICTIONARY_FLAG, string(dictionary)));
+        }
+        if let Some(worker) = self.worker {
+            s.push_str(&format!("{} = {}\n", WORKER_FLAG, worker));
+        }
+        if let Some(ensemble) = &self.ensemble {
+            s.push_str(&format!(
+                "{} = {}\n",
+                ENSEMBLE_FLAG,
+                ConfigValue::String(ensemble.clone())
+            ));
+        }
+        if let Some(fork) = self.fork {
+            s.push_str(&format!("{} = {}\n", FORK_FLAG, fork));
+        }
+        if let Some(sanitizer) = self.sanitizer {
+            s.push_str(&format!("{} = \"{}\"\n", SANITIZER_FLAG, sanitizer.name()));
+        }
+        if self.trace_compares {
+            s.push_str(&format!("{} = true\n", TRACE_COMPARES_FLAG));
+        }
+        if let Some(target) = &self.target {
+            s.push_str(&format!("{} = \"{}\"\n", TARGET_FLAG, target));
+        }
+        if !self.rustflags.is_empty() {
+            let rustflags = ConfigValue::Array(
+                self.rustflags
+                    .iter()
+                    .map(
+                        #[no_coverage]
+                        |x| ConfigValue::String(x.clone()),
+                    )
+                    .collect(),
+            );
+            s.push_str(&format!("{} = {}\n", RUSTFLAGS_FLAG, rustflags));
+        }
+        if self.strict {
+            s.push_str(&format!("{} = true\n", STRICT_FLAG));
+        }
+        if self.post_minify_check {
+            s.push_str(&format!("{} = true\n", POST_MINIFY_CHECK_FLAG));
+        }
+        s.push_str(&format!("{} = \"{}\"\n", STATS_FORMAT_FLAG, self.stats_format.name()));
+        if let Some(stats_interval) = self.stats_interval {
+            s.push_str(&format!("{} = {}\n", STATS_INTERVAL_FLAG, stats_interval));
+        }
+        for (folder, flag, no_flag) in [
+            (&self.corpus_in, IN_CORPUS_FLAG, NO_IN_CORPUS_FLAG),
+            (&self.corpus_out, OUT_CORPUS_FLAG, NO_OUT_CORPUS_FLAG),
+            (&self.artifacts_folder, ARTIFACTS_FLAG, NO_ARTIFACTS_FLAG),
+            (&self.stats_folder, STATS_FLAG, NO_STATS_FLAG),
+        ] {
+            if let Some(folder) = folder {
+                s.push_str(&format!("{} = {}\n", flag, string(folder)));
+            } else {
+                s.push_str(&format!("{} = true\n", no_flag));
+            }
+        }
+        s
+    }
+}
+
 /// The “help” output of cargo-fuzzcheck
 #[no_coverage]
 pub fn help(parser: &Options) -> String {
@@ -321,6 +1463,11 @@ pub fn help(parser: &Options) -> String {
         r##"
 USAGE:
     cargo-fuzzcheck <FUZZ_TEST> [OPTIONS]
+    cargo-fuzzcheck tmin <FUZZ_TEST> <FILE> [OPTIONS]
+    cargo-fuzzcheck merge <FUZZ_TEST> --into <DIR> <CORPUS>... [OPTIONS]
+    cargo-fuzzcheck run-corpus <FUZZ_TEST> [OPTIONS]
+    cargo-fuzzcheck coverage <FUZZ_TEST> <FILE> [OPTIONS]
+    cargo-fuzzcheck broker <ADDRESS>
 
 FUZZ_TEST:
     The fuzz test is the exact path to the #[test] function that launches
@@ -358,12 +1505,398 @@ cargo-fuzzcheck target1 {minify} --{input_file} "artifacts/crash.json"
     artifacts/crash.minified/ and name them {{complexity}}-{{hash}}.json. 
     For example, artifacts/crash.minified/4213--8cd7777109b57b8c.json
     is a minified input of complexity 42.13.
+
+cargo-fuzzcheck tmin target1 "artifacts/crash.json" --runs 100000 --time 600
+    Same as the previous command, but stop as soon as the fuzz test, which
+    is launched again after each reduction of the input, runs 100000 test
+    cases without finding a simpler failing one, or after 10 minutes at
+    most. The path and complexity of the simplest failing test case found
+    are then printed. Without --runs or --time, the minification goes on
+    until it is interrupted with Ctrl-C.
+
+cargo-fuzzcheck target1 --{write_coverage} coverage.txt
+    Replay the corpus of “target1” and save the set of coverage counters
+    that it activates to the file coverage.txt.
+
+cargo-fuzzcheck target1 --{assert_coverage} coverage.txt
+    Replay the corpus of “target1” and fail with a diff if the set of 
+    coverage counters that it activates is different from the one saved 
+    in coverage.txt.
+
+cargo-fuzzcheck target1 --{in_corpus} corpus/ --{write_coverage} before.txt
+cargo-fuzzcheck target1 --{in_corpus} reduced_corpus/ --{assert_coverage} before.txt
+    Check that reduced_corpus/, a copy of corpus/ from which some files
+    were removed by hand or by an external tool, still activates exactly
+    the same coverage counters as the original corpus. The second command
+    fails and lists the counters that are no longer activated (prefixed 
+    with -) if the reduction lost some coverage.
+
+cargo-fuzzcheck target1 --{coverage_map} coverage_map.json
+    Replay the corpus of “target1” and write to coverage_map.json, for each
+    instrumented function grouped by source file, how many inputs of the
+    corpus reach it and its total hit count over the whole corpus. The
+    output can be given to flamegraph or treemap tools to see where the
+    corpus spends its coverage and where seeds are missing.
+
+cargo-fuzzcheck target1 --{coverage_report} coverage.info
+    Replay the corpus of “target1” and write to coverage.info, in the lcov
+    format, how many times each line of the source files was run by the
+    whole corpus. The report can be turned into HTML pages with
+    “genhtml coverage.info -o coverage” or read by an editor, to see
+    which branches of the code are never reached by the corpus. If the
+    file name ends with .json, the report is written as JSON instead,
+    and also lists the hit count of each code region, which is more
+    precise than the lines when a line contains several branches. Only
+    the files observed by the coverage sensor, i.e. the ones of the
+    fuzzed crate, are reported.
+
+cargo-fuzzcheck coverage target1 coverage.info
+    Same as the previous command.
+
+cargo-fuzzcheck target1 --{command} {bisect_corpus}
+    Load the files of the corpus of “target1” one by one, without running
+    the test function, and report the first file whose decoding or
+    validation by the mutator panics, along with the panic message. This
+    helps finding corrupted corpus files and bugs in the serializer or
+    mutator. Each path is printed before its file is loaded, so if the
+    process crashes without panicking (e.g. a stack overflow), the
+    culprit is the last printed file.
+
+cargo-fuzzcheck target1 --{command} {merge} --{out_corpus} merged --{merge_from} corpus1 --{merge_from} corpus2
+    Merge the corpora of two fuzz tests of “target1”, for example run in
+    parallel on different machines, into the folder “merged”. The test
+    cases of both corpora are replayed from the simplest to the most
+    complex, and only the ones activating code coverage counters that no
+    simpler test case activated are kept, so the merged corpus is free of
+    duplicates and has the coverage of both. If “merged” already contains
+    test cases, they are replayed first and kept, and only the test cases
+    adding coverage to them are added. The number of test cases kept from
+    each corpus is printed at the end.
+
+cargo-fuzzcheck merge target1 --into merged corpus1 corpus2
+    Same as the previous command. Like “libFuzzer -merge=1”, the test cases
+    of corpus1 and corpus2 that add code coverage to “merged” are copied
+    into it.
+
+cargo-fuzzcheck target1 --{command} {run_corpus}
+    Run the test function once on each test case of the corpus and of the
+    artifacts folder of “target1”, without fuzzing, and list the files of
+    the test cases that fail. The exit status is non-zero if any test case
+    fails, so that the saved corpus and artifacts can be used as a
+    regression suite in CI.
+
+cargo-fuzzcheck run-corpus target1
+    Same as the previous command.
+
+cargo-fuzzcheck target1 --{command} {minify_corpus}
+    Replay the corpus of “target1” from the simplest to the most complex
+    test case, and remove the test cases whose code coverage counters are
+    all activated by simpler test cases. The number of counters that each
+    remaining test case is the only one to activate is printed, which shows
+    the test cases that matter most to the coverage of the corpus. With
+    --{out_corpus}, the remaining test cases are written to another folder
+    instead, and with --{no_out_corpus}, only the report is printed. With
+    --{post_minify_check}, the remaining test cases are then replayed, and
+    the exit status is non-zero if they do not activate exactly the
+    coverage counters of the whole input corpus, e.g. because the test
+    function is not deterministic. The counters that were lost or gained
+    are reported.
+
+cargo-fuzzcheck target1 --{ignore_panic} "^index out of bounds" --{ignore_panic} overflow
+    Fuzz “target1”, but do not treat the panics whose message matches one
+    of the two regular expressions as test failures. Such panics still
+    unwind normally and the test case is processed as if the test function
+    had returned true. The patterns are checked before anything else, so an
+    ignored panic never stops the fuzzer, even with --{stop_after_first_failure}.
+    Test functions that return false and crashes (e.g. stack overflows) are
+    never ignored.
+
+cargo-fuzzcheck target1 --{slow_threshold} 50 --{hang_threshold} 5000
+    Fuzz “target1” and distinguish slow test cases from hanging ones.
+    A test case that takes longer than 50ms to run is saved to the slow/
+    folder of the output corpus and fuzzing continues: it is a finding
+    worth looking at, and a seed for later runs, but not a failure. A test
+    case that takes longer than 5s is interrupted, saved to the hangs/
+    folder of the artifacts folder, apart from the other test failures,
+    and treated as a crash. The durations are wall-clock times measured
+    around the test function only. --{report_slow_units} is another name for
+    --{slow_threshold}, as in libFuzzer. The default pools do not prefer
+    slow test cases: the slow/ folder is the only place where they are
+    kept unless they are also interesting to the coverage sensor. To
+    steer the fuzzer towards slow test cases, such as inputs triggering
+    accidentally quadratic code, add a MaximiseExecutionTimePool to the
+    pools, e.g. with .find_slowest_test_cases(N) on the sensor and pool
+    builder.
+
+cargo-fuzzcheck target1 --{max_memory} 2048
+    Fuzz “target1” and treat the test cases that make the resident set
+    size of the fuzzer exceed 2048 MB as test failures, like the
+    -rss_limit_mb option of libFuzzer. The peak memory usage is checked
+    after each run of the test function, so the culprit is the test case
+    during which it first exceeded the limit. It is saved to the oom/
+    folder of the artifacts folder and reported as a crash, instead of
+    being lost when the process is killed by the system. Because the
+    peak never decreases, the fuzzer always stops after the first such
+    test case. Set the limit well above the memory used by the fuzzer
+    itself, which grows with the size of the pools.
+
+cargo-fuzzcheck target1 --{malloc_limit} 512
+    Fuzz “target1” and treat the test cases during which the test
+    function holds more than 512 MB of allocated memory at the same time
+    as test failures, like the -malloc_limit_mb option of libFuzzer.
+    Unlike --{max_memory}, the memory held by the fuzzer itself is not
+    counted, so the limit can be much lower and the fuzzer keeps going
+    after finding such a test case. The fuzz target must install a
+    CountingAllocator as its global allocator.
+
+cargo-fuzzcheck target1 --{runs_per_input} 4
+    Fuzz “target1”, running each test case 4 times in a row while the
+    coverage is recorded, such that the pools see the union of the
+    counters activated by the 4 runs (and the sum of their hit counts).
+    This is meant for targets whose behaviour intentionally varies from
+    one run to the next, for example because they branch on a counter
+    stored in a thread-local variable, and whose coverage is therefore
+    under-reported by a single run. It divides the number of test cases
+    tested per second by 4. It does not check that the target is
+    deterministic: the runs are not compared with each other. The runs
+    stop at the first one that fails, and the slow and hang thresholds
+    apply to each run separately.
+
+cargo-fuzzcheck target1 --{max_wall_duration} 8h --{stop_after_first_failure}=false
+    Fuzz “target1” for 8 hours, e.g. overnight, and keep fuzzing after
+    the test failures instead of stopping at the first one. The distinct
+    failing test cases are saved to the test_failures folder of the
+    artifacts folder, in a folder for each failure signature, and the
+    exit status is non-zero if any was found. The duration is a number
+    followed by s, m, h, or d. Giving --{stop_after_first_failure}=false
+    also overrides a `{stop_after_first_failure} = true` of a config file.
+
+cargo-fuzzcheck target1 --{max_wall_duration} 1h --{results_file} results.json
+    Fuzz “target1” for an hour, e.g. in CI, and write the outcome of the
+    campaign to results.json when the fuzzer stops, even if it crashes or
+    is interrupted: whether a test failure was found, the number of
+    distinct failures, why the fuzzer stopped, the number of iterations,
+    the statistics of the pools such as the number of coverage counters
+    hit, and the size of the corpus before and after. The exit status of
+    cargo-fuzzcheck is 0 if no test failure was found, 1 if one was found
+    or if a command replaying the corpus failed, and 2 if the arguments,
+    config files, or fuzz project are invalid or if the fuzz test does
+    not compile.
+
+cargo-fuzzcheck target1 --{max_artifacts_per_signature} 5
+    Fuzz “target1”, saving up to 5 failing test cases for each failure
+    signature instead of 1. The signature of a test failure is made of
+    its panic message and of the functions at the top of the backtrace
+    of the panic, so the test cases reaching the same panic in the same
+    way are duplicates of each other. The simplest ones are kept: when a
+    simpler failing test case is found, the most complex one with the same
+    signature is removed from the test_failures folder.
+
+cargo-fuzzcheck target1 --{bootstrap_count} 10000
+    Fuzz “target1”, testing 10000 test cases before starting to mutate
+    them. The test cases of the state given by --{resume} and of the input
+    corpus count towards these 10000: the input corpus is read until 10000
+    of its test cases were tested, and the rest are arbitrary test cases
+    generated by the mutator. So with a seed corpus of 200 files, 9800
+    arbitrary test cases are generated, and with a corpus of more than
+    10000 files, none are. A large count gives a broad initial spread of
+    test cases, which helps on targets with many disjoint code regions
+    that are easy to reach. A small count, or 0, starts the mutations
+    sooner, which helps when arbitrary test cases are rarely interesting.
+    The default is 100.
+
+cargo-fuzzcheck target1 --{resume} fuzz_state.bin
+    Fuzz “target1” and save the state of the fuzzer to fuzz_state.bin
+    every minute, when it is stopped by SIGINT or SIGTERM, and when it
+    stops by itself. If fuzz_state.bin exists, the fuzzer first resumes
+    from it: the pools and the output corpus are restored as they were,
+    without running their test cases again, and the number of iterations
+    and the elapsed time continue from the saved values, so that
+    --{max_duration} and --{max_iterations} apply to the whole campaign.
+    If the fuzz target was recompiled since the state was saved, its
+    test cases are run again to rebuild the pools instead. Running the
+    same command again after the process was killed (e.g. on a
+    preemptible machine) continues the fuzz test. The mutation steps of
+    the test cases are re-derived from the number of times each one was
+    mutated, so that their mutations continue where they stopped. The
+    random number generators of the fuzzer and of the pools are saved
+    too, but those of the mutators are not, so the resumed fuzz test
+    does not make exactly the same random choices as one that was not
+    interrupted. The states of pools that do not support it are not
+    saved either, in which case the test cases are run again. A state
+    file written by a different version of fuzzcheck’s state format is
+    rejected with an error rather than misread, in which case it should
+    be deleted or replaced by a regular corpus.
+    --{save_state} DIR is another name for --{resume} DIR/fuzz_state.bin.
+
+cargo-fuzzcheck target1 --{dictionary} http.dict
+    Fuzz “target1” with the tokens of the dictionary http.dict, written in
+    the format of libFuzzer and AFL: one token per line, such as
+    kw1="Content-Length:" or "\x7fELF", with comments starting with #.
+    The fuzz test must pass the tokens to its mutators, for example with
+    VecMutator::new(..).with_dictionary(dictionary_from_cargo_fuzzcheck()).
+    The mutators then insert the tokens into the test cases.
+
+cargo-fuzzcheck target1 --jobs 4
+    Fuzz “target1” with 4 fuzzer processes sharing the same output corpus.
+    Each process writes its test cases to its own worker-N subfolder of
+    the output corpus, so that they never overwrite or delete each
+    other’s files, and reads the test cases added by the other workers
+    every 30 seconds. The corpus files and artifacts are written while
+    holding a lock on their folder. Each worker writes its statistics
+    to a worker-N subfolder of the stats folder and, with --{resume}, its
+    state to a file whose name ends with .worker-N, as well as its
+    results with --{results_file}. The output of the
+    workers is interleaved in the terminal. The next fuzz test reads the
+    test cases of all the workers from the input corpus.
+
+cargo-fuzzcheck broker 0.0.0.0:7878
+cargo-fuzzcheck target1 --{ensemble} 192.168.1.10:7878
+    Fuzz “target1” as part of an ensemble of fuzzers, which may run on
+    different machines. The first command starts the broker of the
+    ensemble, and the second one is run on each machine, with the address
+    of the broker. Each fuzzer sends the test cases that it adds to its
+    corpus to the broker, which forwards them to the other fuzzers.
+    They test them once per second and keep the ones that are
+    interesting to their own pools. A fuzzer that joins the ensemble
+    later only receives the test cases found after it joined, so it
+    should start from a copy of the corpus of another fuzzer, given
+    with --{in_corpus}. The test cases are sent unencrypted.
+
+cargo-fuzzcheck target1 --{fork} 1000
+    Fuzz “target1” with the test function running in a child process,
+    which tests 1000 test cases before it is replaced by a new one, so
+    that memory corrupted by unsafe code or FFI does not accumulate. If
+    the child process crashes, for example with a segmentation fault or
+    an abort(), the test case is saved as an artifact, and the fuzzer
+    continues with a new child process, unless --{stop_after_first_failure}
+    is given. Only the first test case crashing with each signal is
+    saved. The test cases are sent to the child process with the
+    serializer of the fuzz test, and only the code coverage counters are
+    sent back, so the other sensors, such as the ComparisonSensor, do
+    not observe anything.
+
+cargo-fuzzcheck target1 --{sanitizer} address
+    Fuzz “target1” compiled with AddressSanitizer, to detect memory errors
+    in unsafe code or FFI that do not cause a panic. The sanitizer aborts
+    the process when it finds an error, and the failing test case is saved
+    as an artifact. The runtime options of the sanitizer are read from the
+    ASAN_OPTIONS (or MSAN_OPTIONS, TSAN_OPTIONS) environment variable, which
+    is set to "abort_on_error=1:halt_on_error=1" if it is empty.
+    Sanitizers require a nightly toolchain and a supported target, such as
+    x86_64-unknown-linux-gnu. MemorySanitizer also requires all code,
+    including the standard library, to be instrumented, for example with
+    --cargo-args "-Zbuild-std".
+
+cargo-fuzzcheck target1 --{trace_compares}
+    Fuzz “target1” compiled with the instrumentation of the comparisons of
+    the fuzz test crate, as with -sanitizer-coverage-trace-compares. The
+    fuzz test can then use a ComparisonSensor, whose pool keeps the test
+    cases that bring the operands of each comparison closer to each other,
+    such that checks against magic numbers are solved progressively.
+
+cargo-fuzzcheck target1 --{target} aarch64-unknown-linux-gnu
+    Fuzz “target1” compiled for the given target triple instead of the
+    host triple, which is read from the output of `rustc -vV` by default.
+    The build artifacts are placed in target/fuzzcheck/<TRIPLE>.
+
+cargo-fuzzcheck target1 --{rustflags} "-Zsanitizer=address -Cdebug-assertions"
+    Fuzz “target1” compiled with additional rustc flags, which are appended
+    to the ones of cargo fuzzcheck and apply to the fuzz test and all of
+    its dependencies. The option can be repeated, and be given as an array
+    of strings in the config file, for example:
+        {rustflags} = ["-Zsanitizer=address", "-Cdebug-assertions"]
+
+cargo-fuzzcheck target1 --{stats_format} json
+    Fuzz “target1” and write its events to the file events.jsonl of the
+    stats folder, as JSON lines instead of CSV rows. Each line is an
+    object with the name of the event (e.g. pulse, corpus_delta,
+    test_failure), the time since the start and a Unix timestamp in
+    milliseconds, and, when available, the number of iterations, the
+    iterations per second, and the statistics of the pools, so that
+    external dashboards can follow the progress of the fuzzer.
+
+cargo-fuzzcheck target1 --{stats} fuzz/stats --{stats_interval} 100000
+    Fuzz “target1” and record its statistics in the stats folder every
+    100000 iterations, in addition to the records written when the corpus
+    changes. Each CSV row starts with the time since the start in
+    milliseconds, followed by the number of iterations, the iterations
+    per second, and the statistics of each pool, such as the number of
+    coverage counters it has seen. Plotting these columns against the
+    time shows how the coverage grows over the fuzzing campaign.
+
+cargo-fuzzcheck target1 --{max_cplx} 4 --{strict}
+    Fuzz “target1” with test cases of complexity at most 4, and stop
+    immediately if the mutator cannot generate any value that simple.
+    Without --{strict}, the fuzzer only prints a warning suggesting a
+    greater maximum complexity, and then makes no progress beyond the
+    test cases of the input corpus. The minimum complexity of the
+    mutators of recursive types and of large structs is often higher
+    than expected.
+
+cargo-fuzzcheck target1 --{config} fuzzcheck.toml --{dump_config} resolved.toml
+    Fuzz “target1” using the options in fuzzcheck.toml as defaults. Options
+    given on the command line take precedence over the ones in the file.
+    The fully-resolved options are printed and written to resolved.toml.
+
+cargo-fuzzcheck target1
+    If the fuzz project contains a fuzz/fuzzcheck.toml file, its options
+    are used as defaults for all fuzz tests, and the ones following a
+    ["target1"] table header as defaults for “target1” only, for example:
+        {max_cplx} = 4096
+        ["target1"]
+        {in_corpus} = "fuzz/requests"
+        {rustflags} = ["-Zsanitizer=address"]
+    They are overridden by the options of the command line and of the
+    file given to --{config}.
 "#,
         fuzz = COMMAND_FUZZ,
         minify = COMMAND_MINIFY_INPUT,
+        command = COMMAND_FLAG,
+        bisect_corpus = COMMAND_BISECT_CORPUS,
+        merge = COMMAND_MERGE,
+        run_corpus = COMMAND_RUN_CORPUS,
+        minify_corpus = COMMAND_MINIFY_CORPUS,
+        merge_from = MERGE_FROM_FLAG,
         input_file = INPUT_FILE_FLAG,
         max_cplx = MAX_INPUT_CPLX_FLAG,
         out_corpus = OUT_CORPUS_FLAG,
+        no_out_corpus = NO_OUT_CORPUS_FLAG,
+        in_corpus = IN_CORPUS_FLAG,
+        write_coverage = WRITE_COVERAGE_FLAG,
+        assert_coverage = ASSERT_COVERAGE_FLAG,
+        coverage_map = COVERAGE_MAP_FLAG,
+        coverage_report = COVERAGE_REPORT_FLAG,
+        config = CONFIG_FLAG,
+        dump_config = DUMP_CONFIG_FLAG,
+        ignore_panic = IGNORE_PANIC_FLAG,
+        sanitizer = SANITIZER_FLAG,
+        trace_compares = TRACE_COMPARES_FLAG,
+        target = TARGET_FLAG,
+        rustflags = RUSTFLAGS_FLAG,
+        stop_after_first_failure = STOP_AFTER_FIRST_FAILURE_FLAG,
+        slow_threshold = SLOW_THRESHOLD_FLAG,
+        report_slow_units = REPORT_SLOW_UNITS_FLAG,
+        hang_threshold = HANG_THRESHOLD_FLAG,
+        max_memory = MAX_MEMORY_FLAG,
+        malloc_limit = MALLOC_LIMIT_FLAG,
+        resume = RESUME_FLAG,
+        save_state = SAVE_STATE_FLAG,
+        results_file = RESULTS_FILE_FLAG,
+        dictionary = DICTIONARY_FLAG,
+        ensemble = ENSEMBLE_FLAG,
+        fork = FORK_FLAG,
+        runs_per_input = RUNS_PER_INPUT_FLAG,
+        strict = STRICT_FLAG,
+        post_minify_check = POST_MINIFY_CHECK_FLAG,
+        stats_format = STATS_FORMAT_FLAG,
+        stats_interval = STATS_INTERVAL_FLAG,
+        stats = STATS_FLAG,
+        max_artifacts_per_signature = MAX_ARTIFACTS_PER_SIGNATURE_FLAG,
+        bootstrap_count = BOOTSTRAP_COUNT_FLAG,
+        max_duration = MAX_DURATION_FLAG,
+        max_wall_duration = MAX_WALL_DURATION_FLAG,
+        max_iterations = MAX_ITERATIONS_FLAG,
     )
     .as_str();
     help
@@ -0,0 +1,337 @@
+//! A minimal reader and writer for the flat TOML files given to `--config`.
+//!
+//! Only `key = value` lines are supported, where the value is a basic string,
+//! an integer or float, a boolean, or a single-line array of these. Empty lines
+//! and `#` comments are ignored.
+//!
+//! The `fuzz/fuzzcheck.toml` file of a fuzz project may also contain `["<FUZZ_TEST>"]`
+//! table headers, see [`parse_target_config`].
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    String(String),
+    /// A number, kept in its textual form so that it can be given to the command line parser
+    Number(String),
+    Bool(bool),
+    /// The values of an option that can be given multiple times
+    Array(Vec<ConfigValue>),
+}
+
+impl Display for ConfigValue {
+    #[no_coverage]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        '\r' => write!(f, "\\r")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            ConfigValue::Number(n) => write!(f, "{}", n),
+            ConfigValue::Bool(b) => write!(f, "{}", b),
+            ConfigValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Parse the content of a config file into a list of `(key, value)` pairs, in the order in which they appear.
+///
+/// On failure, the error message contains the line number of the invalid line.
+#[no_coverage]
+pub fn parse_config(content: &str) -> Result<Vec<(String, ConfigValue)>, String> {
+    let mut result: Vec<(String, ConfigValue)> = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let line_nbr = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, rest) = line.split_once('=').ok_or_else(
+            #[no_coverage]
+            || format!("line {}: expected `key = value`", line_nbr),
+        )?;
+        let key = key.trim();
+        if key.is_empty()
+            || !key.chars().all(
+                #[no_coverage]
+                |c| c.is_ascii_alphanumeric() || c == '-' || c == '_',
+            )
+        {
+            return Err(format!("line {}: invalid key `{}`", line_nbr, key));
+        }
+        if result.iter().any(
+            #[no_coverage]
+            |(k, _)| k == key,
+        ) {
+            return Err(format!("line {}: duplicate key `{}`", line_nbr, key));
+        }
+        let (value, rest) = parse_value(rest.trim()).map_err(
+            #[no_coverage]
+            |e| format!("line {}: {}", line_nbr, e),
+        )?;
+        let rest = rest.trim();
+        if !(rest.is_empty() || rest.starts_with('#')) {
+            return Err(format!("line {}: unexpected characters after the value", line_nbr));
+        }
+        result.push((key.to_owned(), value));
+    }
+    Ok(result)
+}
+
+/// Parse the content of a config file containing the options of several fuzz tests, and return the ones of `target`.
+///
+/// The options before the first table header apply to all fuzz tests. The ones following a header `["<FUZZ_TEST>"]`,
+/// where the quotes can be omitted if the name only contains letters, digits, `-` and `_`, apply to the fuzz test
+/// `<FUZZ_TEST>` only and take precedence over the former:
+/// ```toml
+/// max-cplx = 4096
+///
+/// ["tests::parse_request"]
+/// max-cplx = 1024
+/// rustflags = ["-Zsanitizer=address"]
+/// ```
+/// All the tables are checked, even though only one of them is returned.
+#[no_coverage]
+pub fn parse_target_config(content: &str, target: &str) -> Result<Vec<(String, ConfigValue)>, String> {
+    // each table is given to `parse_config` with the other lines blanked out, such that the line numbers are kept
+    let mut tables: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    let mut current_table = 0;
+    for (i, line) in content.lines().enumerate() {
+        let line_nbr = i + 1;
+        if let Some(header) = line.trim().strip_prefix('[') {
+            let header = header.trim_start();
+            let (name, rest) = if header.starts_with('"') {
+                match parse_value(header) {
+                    Ok((ConfigValue::String(name), rest)) => (name, rest),
+                    Ok(_) => unreachable!(),
+                    Err(e) => return Err(format!("line {}: {}", line_nbr, e)),
+                }
+            } else {
+                let end = header.find(']').unwrap_or(header.len());
+                (header[..end].trim().to_owned(), &header[end..])
+            };
+            if name.is_empty()
+                || !name.chars().all(
+                    #[no_coverage]
+                    |c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':',
+                )
+            {
+                return Err(format!("line {}: invalid table name `{}`", line_nbr, name));
+            }
+            let rest = rest
+                .trim_start()
+                .strip_prefix(']')
+                .ok_or_else(
+                    #[no_coverage]
+                    || format!("line {}: expected `]` after the table name", line_nbr),
+                )?
+                .trim();
+            if !(rest.is_empty() || rest.starts_with('#')) {
+                return Err(format!(
+                    "line {}: unexpected characters after the table header",
+                    line_nbr
+                ));
+            }
+            if tables.iter().any(
+                #[no_coverage]
+                |(table, _)| table.as_deref() == Some(name.as_str()),
+            ) {
+                return Err(format!("line {}: duplicate table `{}`", line_nbr, name));
+            }
+            tables.push((Some(name), "\n".repeat(i)));
+            current_table = tables.len() - 1;
+        } else {
+            tables[current_table].1.push_str(line);
+        }
+        for (_, table_content) in tables.iter_mut() {
+            table_content.push('\n');
+        }
+    }
+
+    let mut result = vec![];
+    let mut target_options = vec![];
+    for (table, table_content) in tables {
+        let options = parse_config(&table_content)?;
+        match table {
+            None => result = options,
+            Some(name) if name == target => target_options = options,
+            Some(_) => {}
+        }
+    }
+    result.retain(
+        #[no_coverage]
+        |(key, _)| {
+            !target_options.iter().any(
+                #[no_coverage]
+                |(k, _)| k == key,
+            )
+        },
+    );
+    result.extend(target_options);
+    Ok(result)
+}
+
+/// Parse a value at the start of `input` and return it along with the rest of the input
+#[no_coverage]
+fn parse_value(input: &str) -> Result<(ConfigValue, &str), String> {
+    if let Some(input) = input.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = input.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => return Ok((ConfigValue::String(value), &input[idx + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'r')) => value.push('\r'),
+                    _ => return Err("invalid escape sequence in string".to_owned()),
+                },
+                c => value.push(c),
+            }
+        }
+        Err("unterminated string".to_owned())
+    } else if let Some(mut input) = input.strip_prefix('[') {
+        let mut values = vec![];
+        loop {
+            input = input.trim_start();
+            if let Some(rest) = input.strip_prefix(']') {
+                return Ok((ConfigValue::Array(values), rest));
+            }
+            if input.starts_with('[') {
+                return Err("nested arrays are not supported".to_owned());
+            }
+            let (value, rest) = parse_value(input)?;
+            values.push(value);
+            input = rest.trim_start();
+            if let Some(rest) = input.strip_prefix(',') {
+                input = rest;
+            } else if !input.starts_with(']') {
+                return Err("expected `,` or `]` in array".to_owned());
+            }
+        }
+    } else {
+        let end = input
+            .find(
+                #[no_coverage]
+                |c: char| c.is_whitespace() || c == '#' || c == ',' || c == ']',
+            )
+            .unwrap_or(input.len());
+        let (word, rest) = input.split_at(end);
+        match word {
+            "true" => Ok((ConfigValue::Bool(true), rest)),
+            "false" => Ok((ConfigValue::Bool(false), rest)),
+            _ if word.replace('_', "").parse::<f64>().is_ok() => Ok((ConfigValue::Number(word.replace('_', "")), rest)),
+            _ => Err(format!("invalid value `{}`", word)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let content = r#"
+# a comment
+max-cplx = 4_096
+in-corpus = "fuzz/a \"b\"/corpus" # trailing comment
+stop-after-first-failure = true
+"#;
+        let config = parse_config(content).unwrap();
+        assert_eq!(
+            config,
+            vec![
+                ("max-cplx".to_owned(), ConfigValue::Number("4096".to_owned())),
+                (
+                    "in-corpus".to_owned(),
+                    ConfigValue::String("fuzz/a \"b\"/corpus".to_owned())
+                ),
+                ("stop-after-first-failure".to_owned(), ConfigValue::Bool(true)),
+            ]
+        );
+        let s = ConfigValue::String("fuzz/a \"b\"/corpus".to_owned()).to_string();
+        assert_eq!(parse_value(&s).unwrap().0, config[1].1);
+
+        assert!(parse_config("max-cplx = ").is_err());
+        assert!(parse_config("max-cplx = 1\nmax-cplx = 2").is_err());
+        assert!(parse_config("in-corpus = \"abc").is_err());
+
+        let config = parse_config(r#"ignore-panic = ["index out of bounds", "^overflow$",]"#).unwrap();
+        let array = ConfigValue::Array(vec![
+            ConfigValue::String("index out of bounds".to_owned()),
+            ConfigValue::String("^overflow$".to_owned()),
+        ]);
+        assert_eq!(config[0].1, array);
+        assert_eq!(parse_value(&array.to_string()).unwrap().0, array);
+        assert!(parse_config("ignore-panic = [\"a\" \"b\"]").is_err());
+    }
+
+    #[test]
+    fn test_parse_target_config() {
+        let content = r#"
+max-cplx = 4096
+stop-after-first-failure = true
+
+["tests::parse"] # the parser
+max-cplx = 1024
+rustflags = ["-Zsanitizer=address"]
+
+[other]
+max-cplx = 2048
+"#;
+        let config = parse_target_config(content, "tests::parse").unwrap();
+        assert_eq!(
+            config,
+            vec![
+                ("stop-after-first-failure".to_owned(), ConfigValue::Bool(true)),
+                ("max-cplx".to_owned(), ConfigValue::Number("1024".to_owned())),
+                (
+                    "rustflags".to_owned(),
+                    ConfigValue::Array(vec![ConfigValue::String("-Zsanitizer=address".to_owned())])
+                ),
+            ]
+        );
+        let config = parse_target_config(content, "other").unwrap();
+        assert_eq!(
+            config,
+            vec![
+                ("stop-after-first-failure".to_owned(), ConfigValue::Bool(true)),
+                ("max-cplx".to_owned(), ConfigValue::Number("2048".to_owned())),
+            ]
+        );
+        let config = parse_target_config(content, "tests::missing").unwrap();
+        assert_eq!(config.len(), 2);
+        assert_eq!(config[0].1, ConfigValue::Number("4096".to_owned()));
+
+        // errors in the tables of other fuzz tests are reported, with the line number of the file
+        assert_eq!(
+            parse_target_config("[a]\nmax-cplx = 1\n[b]\nmax-cplx = ", "a"),
+            Err("line 4: invalid value ``".to_owned())
+        );
+        assert!(parse_target_config("[a]\n[a]", "a").is_err());
+        assert!(parse_target_config("[\"a\"", "a").is_err());
+        assert!(parse_target_config("[a b]", "a").is_err());
+    }
+}
@@ -0,0 +1,282 @@
+//! Cooperative fuzzing by an “ensemble” of fuzzers, possibly running on different machines.
+//!
+//! Each fuzzer of the ensemble connects to a broker, started with `cargo fuzzcheck broker <ADDRESS>`, and sends
+//! it every test case that it adds to its corpus. The broker forwards the test cases to all the other fuzzers,
+//! which test them like the test cases of their input corpus and keep the ones that are interesting to their
+//! own pools.
+//!
+//! The messages exchanged over TCP are made of the length of the extension of the serializer, as a big-endian
+//! `u16`, the extension, the length of the serialized test case, as a big-endian `u32`, and the serialized
+//! test case.
+
+use std::io::{self, BufReader, Read, Result, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The maximum size of a test case sent to the ensemble. Larger test cases are not shared.
+pub const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// How long a fuzzer or the broker waits for a slow peer to accept a message before giving up on it
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A test case shared between the fuzzers of an ensemble
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCaseMessage {
+    /// The extension of the serializer of the test case, such as `json`
+    pub extension: String,
+    /// The serialized test case
+    pub content: Vec<u8>,
+}
+
+impl TestCaseMessage {
+    #[no_coverage]
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        if self.extension.len() > u16::MAX as usize || self.content.len() > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the test case is too large",
+            ));
+        }
+        let mut bytes = Vec::with_capacity(6 + self.extension.len() + self.content.len());
+        bytes.extend((self.extension.len() as u16).to_be_bytes());
+        bytes.extend(self.extension.as_bytes());
+        bytes.extend((self.content.len() as u32).to_be_bytes());
+        bytes.extend(&self.content);
+        writer.write_all(&bytes)?;
+        writer.flush()
+    }
+
+    /// Read the next message, or return `None` if the connection was closed between two messages
+    #[no_coverage]
+    pub fn read_from(reader: &mut impl Read) -> Result<Option<Self>> {
+        let mut extension_len = [0; 2];
+        // the connection may only be closed before the first byte of a message
+        loop {
+            match reader.read(&mut extension_len[..1]) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        reader.read_exact(&mut extension_len[1..])?;
+        let mut extension = vec![0; u16::from_be_bytes(extension_len) as usize];
+        reader.read_exact(&mut extension)?;
+        let extension = String::from_utf8(extension).map_err(
+            #[no_coverage]
+            |e| io::Error::new(io::ErrorKind::InvalidData, e),
+        )?;
+        let mut content_len = [0; 4];
+        reader.read_exact(&mut content_len)?;
+        let content_len = u32::from_be_bytes(content_len) as usize;
+        if content_len > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "the test case is too large"));
+        }
+        let mut content = vec![0; content_len];
+        reader.read_exact(&mut content)?;
+        Ok(Some(Self { extension, content }))
+    }
+}
+
+/// The connection of a fuzzer to the broker of its ensemble.
+///
+/// The test cases sent by the broker are read on a background thread, so that the fuzzer can collect them
+/// whenever it is ready to test them, with [`receive`](EnsembleClient::receive).
+pub struct EnsembleClient {
+    stream: TcpStream,
+    received: Receiver<TestCaseMessage>,
+}
+
+impl EnsembleClient {
+    #[no_coverage]
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (sender, received) = mpsc::channel();
+        thread::spawn(
+            #[no_coverage]
+            move || {
+                // the thread stops when the connection is closed or when the fuzzer drops the client
+                while let Ok(Some(message)) = TestCaseMessage::read_from(&mut reader) {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+            },
+        );
+        Ok(Self { stream, received })
+    }
+
+    /// Send a test case to the other fuzzers of the ensemble
+    #[no_coverage]
+    pub fn send(&mut self, message: &TestCaseMessage) -> Result<()> {
+        message.write_to(&mut self.stream)
+    }
+
+    /// The test cases sent by the other fuzzers since the last call
+    #[no_coverage]
+    pub fn receive(&mut self) -> Vec<TestCaseMessage> {
+        self.received.try_iter().collect()
+    }
+}
+
+impl Drop for EnsembleClient {
+    #[no_coverage]
+    fn drop(&mut self) {
+        // closes the connection, which also stops the background thread
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// The fuzzers connected to the broker, with the sender of the queue of test cases to write to each of them
+type Clients = Arc<Mutex<Vec<(usize, Sender<Arc<TestCaseMessage>>)>>>;
+
+/// Run the broker of an ensemble of fuzzers, listening on the given address, such as `0.0.0.0:7878`.
+///
+/// Every test case sent by a fuzzer is forwarded to all the other fuzzers currently connected. Each fuzzer has
+/// its own thread writing the test cases to its connection, so that a slow fuzzer does not delay the others.
+/// The broker does not keep the test cases, so a fuzzer that joins the ensemble later only receives the ones found after it
+/// joined, and should start from a copy of the corpus of another fuzzer. This function only returns if the
+/// broker cannot listen on the address.
+#[no_coverage]
+pub fn run_broker(address: impl ToSocketAddrs) -> Result<()> {
+    let listener = TcpListener::bind(address)?;
+    println!("The broker is listening on {}", listener.local_addr()?);
+    let clients: Clients = Arc::new(Mutex::new(vec![]));
+    for (id, stream) in listener.incoming().enumerate() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("Could not accept a connection: {}", e);
+                continue;
+            }
+        };
+        let peer = stream.peer_addr().map_or_else(
+            #[no_coverage]
+            |_| "unknown address".to_owned(),
+            #[no_coverage]
+            |addr| addr.to_string(),
+        );
+        let reader = stream.try_clone().and_then(
+            #[no_coverage]
+            |reader| {
+                stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                Ok(reader)
+            },
+        );
+        let mut reader = match reader {
+            Ok(reader) => BufReader::new(reader),
+            Err(e) => {
+                println!("Could not set up the connection with {}: {}", peer, e);
+                continue;
+            }
+        };
+        println!("Fuzzer {} joined the ensemble from {}", id, peer);
+        let (sender, messages) = mpsc::channel::<Arc<TestCaseMessage>>();
+        clients.lock().unwrap().push((id, sender));
+        thread::spawn(
+            #[no_coverage]
+            move || {
+                // stops when the fuzzer leaves the ensemble, which drops the sender of the queue
+                for message in messages {
+                    if message.write_to(&mut &stream).is_err() {
+                        // the fuzzer cannot receive the test cases, so it is disconnected, which also stops
+                        // the thread reading its messages
+                        let _ = stream.shutdown(Shutdown::Both);
+                        break;
+                    }
+                }
+            },
+        );
+        let clients = clients.clone();
+        thread::spawn(
+            #[no_coverage]
+            move || {
+                loop {
+                    match TestCaseMessage::read_from(&mut reader) {
+                        Ok(Some(message)) => {
+                            let message = Arc::new(message);
+                            for (other_id, other) in clients.lock().unwrap().iter() {
+                                if *other_id != id {
+                                    // fails only if the writing thread of that fuzzer has already stopped
+                                    let _ = other.send(message.clone());
+                                }
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            println!("Invalid message from fuzzer {}: {}", id, e);
+                            break;
+                        }
+                    }
+                }
+                println!("Fuzzer {} left the ensemble", id);
+                clients.lock().unwrap().retain(
+                    #[no_coverage]
+                    |(other_id, _)| *other_id != id,
+                );
+            },
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(extension: &str, content: &[u8]) -> TestCaseMessage {
+        TestCaseMessage {
+            extension: extension.to_owned(),
+            content: content.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_messages_round_trip() {
+        let messages = [
+            message("json", b"[1, 2, 3]"),
+            message("", b""),
+            message("bin", &[0, 255, 7]),
+        ];
+        let mut bytes = vec![];
+        for m in messages.iter() {
+            m.write_to(&mut bytes).unwrap();
+        }
+        let mut reader = bytes.as_slice();
+        for m in messages.iter() {
+            assert_eq!(TestCaseMessage::read_from(&mut reader).unwrap().as_ref(), Some(m));
+        }
+        // the connection was closed between two messages
+        assert_eq!(TestCaseMessage::read_from(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_truncated_message() {
+        let mut bytes = vec![];
+        message("json", b"[1, 2, 3]").write_to(&mut bytes).unwrap();
+        for len in 1..bytes.len() {
+            let error = TestCaseMessage::read_from(&mut &bytes[..len]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn test_message_too_large() {
+        let too_large = message("bin", &vec![0; MAX_MESSAGE_LEN + 1]);
+        let error = too_large.write_to(&mut vec![]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        let mut bytes = vec![];
+        bytes.extend(3u16.to_be_bytes());
+        bytes.extend(b"bin");
+        bytes.extend((MAX_MESSAGE_LEN as u32 + 1).to_be_bytes());
+        let error = TestCaseMessage::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -1,6 +1,9 @@
 #![feature(no_coverage)]
 
 pub mod arg;
+pub mod config;
+pub mod ensemble;
+pub mod results;
 
 #[derive(Clone, Copy, Default)]
 pub struct FuzzerStats {
@@ -21,5 +24,13 @@ pub enum FuzzerEvent {
     DidReadCorpus,
     CaughtSignal(i32),
     TestFailure,
+    /// A test case took longer than the slow threshold to run
+    Slow,
+    /// A test case took longer than the hang threshold to run and was interrupted
+    Hang,
+    /// A test case made the memory usage of the fuzzer exceed the maximum memory
+    OutOfMemory,
+    /// A periodic record of the statistics, every `--stats-interval` iterations
+    Stats,
     None,
 }
@@ -0,0 +1,160 @@
+//! The outcome of a fuzzing campaign, written as JSON to the file given by `--results-file`.
+//!
+//! The file is written by the fuzzer when it stops, including when it crashes or is interrupted, and is read
+//! by `cargo fuzzcheck` to choose its exit status. For example:
+//! ```json
+//! {
+//!   "found_test_failure": true,
+//!   "failures": 2,
+//!   "reason_for_stopping": "max_duration",
+//!   "iterations": 5318921,
+//!   "duration_secs": 28800.02,
+//!   "corpus": { "initial_size": 120, "final_size": 134, "delta": 14 },
+//!   "pool_stats": { "simplest_cov-size": 134, "simplest_cov-percent-coverage": 1620, ... }
+//! }
+//! ```
+
+use std::fmt::Write;
+use std::time::Duration;
+
+/// The key of the field telling whether the fuzzer found a test failure
+const FOUND_TEST_FAILURE_KEY: &str = "found_test_failure";
+
+/// The outcome of a fuzzing campaign
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignResults {
+    pub found_test_failure: bool,
+    /// The number of distinct test failures found, where the ones with the same signature count once
+    pub failures: usize,
+    /// Why the fuzzer stopped, e.g. `test_failure`, `max_duration`, `max_iterations`, `crash`, or `interrupted`
+    pub reason_for_stopping: &'static str,
+    /// The number of test cases that were run
+    pub iterations: usize,
+    pub duration: Duration,
+    /// The number of files of the input corpus when the fuzzer started
+    pub initial_corpus_size: usize,
+    /// The number of files of the output corpus when the fuzzer stopped
+    pub final_corpus_size: usize,
+    /// The statistics of the pools, such as the number of coverage counters they hit, by name
+    pub pool_stats: Vec<(String, f64)>,
+}
+
+/// Write `s` to `json` as a JSON string
+#[no_coverage]
+fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            '\r' => json.push_str("\\r"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+impl CampaignResults {
+    #[no_coverage]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        writeln!(json, "{{").unwrap();
+        writeln!(json, "  \"{}\": {},", FOUND_TEST_FAILURE_KEY, self.found_test_failure).unwrap();
+        writeln!(json, "  \"failures\": {},", self.failures).unwrap();
+        writeln!(json, "  \"reason_for_stopping\": \"{}\",", self.reason_for_stopping).unwrap();
+        writeln!(json, "  \"iterations\": {},", self.iterations).unwrap();
+        writeln!(json, "  \"duration_secs\": {:.2},", self.duration.as_secs_f64()).unwrap();
+        writeln!(
+            json,
+            "  \"corpus\": {{ \"initial_size\": {}, \"final_size\": {}, \"delta\": {} }},",
+            self.initial_corpus_size,
+            self.final_corpus_size,
+            self.final_corpus_size as i64 - self.initial_corpus_size as i64
+        )
+        .unwrap();
+        json.push_str("  \"pool_stats\": {");
+        for (i, (name, value)) in self.pool_stats.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            json.push(' ');
+            write_json_string(&mut json, name);
+            if value.is_finite() {
+                write!(json, ": {}", value).unwrap();
+            } else {
+                json.push_str(": null");
+            }
+        }
+        if !self.pool_stats.is_empty() {
+            json.push(' ');
+        }
+        json.push_str("}\n}\n");
+        json
+    }
+}
+
+/// Whether the content of a results file written by [`CampaignResults::to_json`] says that a test failure was found
+///
+/// Returns `None` if the content was not written by [`CampaignResults::to_json`].
+#[no_coverage]
+pub fn found_test_failure(json: &str) -> Option<bool> {
+    let key = format!("\"{}\":", FOUND_TEST_FAILURE_KEY);
+    let value = json.split_once(&key)?.1.trim_start();
+    if value.starts_with("true") {
+        Some(true)
+    } else if value.starts_with("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_to_json() {
+        let results = CampaignResults {
+            found_test_failure: true,
+            failures: 2,
+            reason_for_stopping: "max_duration",
+            iterations: 1000,
+            duration: Duration::from_millis(1500),
+            initial_corpus_size: 10,
+            final_corpus_size: 7,
+            pool_stats: vec![
+                ("cov".to_owned(), 42.0),
+                ("avg \"cplx\"".to_owned(), 3.5),
+                ("nan".to_owned(), f64::NAN),
+            ],
+        };
+        let json = results.to_json();
+        assert_eq!(
+            json,
+            r#"{
+  "found_test_failure": true,
+  "failures": 2,
+  "reason_for_stopping": "max_duration",
+  "iterations": 1000,
+  "duration_secs": 1.50,
+  "corpus": { "initial_size": 10, "final_size": 7, "delta": -3 },
+  "pool_stats": { "cov": 42, "avg \"cplx\"": 3.5, "nan": null }
+}
+"#
+        );
+        assert_eq!(found_test_failure(&json), Some(true));
+        let results = CampaignResults {
+            found_test_failure: false,
+            pool_stats: vec![],
+            ..results
+        };
+        let json = results.to_json();
+        assert!(json.contains("\"pool_stats\": {}"));
+        assert_eq!(found_test_failure(&json), Some(false));
+        assert_eq!(found_test_failure("{}"), None);
+    }
+}
@@ -1,5 +1,5 @@
 use decent_synquote_alternative as synquote;
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Literal, Span};
 
 use synquote::parser::*;
 use synquote::token_builder::*;
@@ -22,13 +22,7 @@ pub(crate) fn impl_default_mutator_for_enum(tb: &mut TokenBuilder, enu: &Enum, s
                 .iter()
                 .enumerate()
                 .map(|(j, field)| {
-                    let mut mutator = None;
-                    for attribute in field.attributes.iter() {
-                        if let Some((m, init)) = super::read_field_default_mutator_attribute(attribute.clone()) {
-                            mutator = Some((m, init));
-                        }
-                    }
-                    if let Some(m) = mutator {
+                    if let Some(m) = super::read_field_mutator_attributes(field, &cm) {
                         FieldMutator {
                             i,
                             j: Some(j),
@@ -75,6 +69,45 @@ pub(crate) fn impl_default_mutator_for_enum(tb: &mut TokenBuilder, enu: &Enum, s
         ">"
     );
 
+    let field_complexities_impl = if cfg!(feature = "field_complexities") {
+        let binding_suffix = ident!("__proc_macro__binding__");
+        ts!(
+            "/// The complexity of each field of `value`, as given by the mutator of that field.
+            ///
+            /// Returns `None` if `value` is not a valid value for this mutator.
+            #[no_coverage]
+            #[allow(non_shorthand_field_patterns, irrefutable_let_patterns)]
+            pub fn field_complexities(&self, value: &" enu.ident enu.generics.removing_bounds_and_eq_type() ") -> " cm.Option "<" cm.Vec "<(&'static str, f64)>> {
+                let mut complexities = " cm.Vec "::new();
+                match value {"
+                join_ts!(enu.items.iter().zip(field_mutators.iter()), (item, item_field_mutators),
+                    item.pattern_match(&enu.ident, Some(binding_suffix.clone())) "=> {"
+                    if item_field_mutators.is_empty() {
+                        ts!(cm.Some "(complexities)")
+                    } else {
+                        ts!(
+                            "for mutator in self.mutator.mutators() {
+                                if let " EnumSingleVariant "::" item.ident "(mutator) = mutator {
+                                    let mutators = mutator.mutators();"
+                                    join_ts!(item_field_mutators.iter().enumerate(), (j, fm),
+                                        "let cache = <" fm.mutator_stream(&cm) " as " cm.fuzzcheck_traits_Mutator "<" fm.field.ty "> >::validate_value(mutators." j ", " ident!(fm.field.safe_ident() binding_suffix) ")?;
+                                        complexities.push((" Literal::string(&fm.field.access().to_string()) ", <" fm.mutator_stream(&cm) " as " cm.fuzzcheck_traits_Mutator "<" fm.field.ty "> >::complexity(mutators." j ", " ident!(fm.field.safe_ident() binding_suffix) ", &cache)));"
+                                    )
+                                    "return " cm.Some "(complexities);
+                                }
+                            }"
+                            cm.None
+                        )
+                    }
+                    "}"
+                )
+                "}
+            }"
+        )
+    } else {
+        ts!()
+    };
+
     let params = CreateWrapperMutatorParams {
         cm: &cm,
         visibility: &enu.visibility,
@@ -110,7 +143,35 @@ pub(crate) fn impl_default_mutator_for_enum(tb: &mut TokenBuilder, enu: &Enum, s
                         , separator: ",")
                     "])
                 }
+            }
+            /// Only generate the variants whose index, in the declaration of the enum, is in `variants`.
+            ///
+            /// Values of the other variants are still accepted, for example when they come from the corpus.
+            #[no_coverage]
+            pub fn only_variants(self, variants: &[usize]) -> Self {
+                Self {
+                    mutator: self.mutator.only_mutators(variants)
+                }
+            }
+            /// Do not generate the variants whose index, in the declaration of the enum, is in `variants`.
+            ///
+            /// Values of these variants are still accepted, for example when they come from the corpus.
+            #[no_coverage]
+            pub fn exclude_variants(self, variants: &[usize]) -> Self {
+                Self {
+                    mutator: self.mutator.exclude_mutators(variants)
+                }
+            }
+            /// Generate each variant with a probability proportional to its weight in `weights`, which contains
+            /// one weight for each variant, in the order of their declaration. The variants whose weight is 0
+            /// are not generated.
+            #[no_coverage]
+            pub fn variant_weights(self, weights: &[f64]) -> Self {
+                Self {
+                    mutator: self.mutator.with_weights(weights)
+                }
             }"
+            field_complexities_impl
         ),
         default_impl: &ts!("
             #[no_coverage]
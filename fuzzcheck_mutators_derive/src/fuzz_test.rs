@@ -0,0 +1,105 @@
+use decent_synquote_alternative::parser::{AsyncOrConst, FunctionParameter, TokenParser, Ty, TyKind};
+use decent_synquote_alternative::token_builder::*;
+use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream};
+
+/// A `compile_error!` with the given message, emitted instead of the test when the function is not supported
+fn fuzz_test_error(message: &str) -> TokenStream {
+    ts!("compile_error!(" Literal::string(message) ");")
+}
+
+/// The type of the values generated by the fuzzer for a parameter of the test function, and the expression
+/// passing such a value, borrowed from `input`, as the argument of the parameter.
+///
+/// A parameter `&T` receives a borrow of a `T::Owned`, a parameter `&mut T` a mutable reference to a clone of a
+/// `T`, and a parameter `T` a clone of a `T`.
+fn fuzzed_type_and_argument(ty: &Ty, input: TokenStream) -> (TokenStream, TokenStream) {
+    match &ty.kind {
+        TyKind::Ref(ref_ty) if ref_ty.mutable => (ts!(ref_ty.rest), ts!("&mut ::std::clone::Clone::clone(" input ")")),
+        TyKind::Ref(ref_ty) => (
+            ts!("<" ref_ty.rest " as ::std::borrow::ToOwned>::Owned"),
+            ts!("::std::borrow::Borrow::borrow(" input ")"),
+        ),
+        TyKind::Other => (ts!(ty), ts!("::std::clone::Clone::clone(" input ")")),
+    }
+}
+
+pub(crate) fn make_fuzz_test(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    if !attribute.is_empty() {
+        return fuzz_test_error("The fuzz_test attribute does not take any argument.");
+    }
+    let mut parser = TokenParser::new(item);
+    let function = if let Some(function) = parser.eat_function() {
+        function
+    } else {
+        return fuzz_test_error("The fuzz_test attribute can only be used on functions.");
+    };
+    let body = if let Some(body) = parser.eat_group(Delimiter::Brace) {
+        body
+    } else {
+        return fuzz_test_error("The fuzz_test attribute can only be used on functions with a body.");
+    };
+    if !function.generics.lifetime_params.is_empty() || !function.generics.type_params.is_empty() {
+        return fuzz_test_error("The fuzz_test attribute cannot be used on generic functions.");
+    }
+    if matches!(function.qualifiers.async_or_const, Some(AsyncOrConst::Async)) || function.qualifiers.unsafe_ {
+        return fuzz_test_error("The fuzz_test attribute cannot be used on async or unsafe functions.");
+    }
+    if function.parameters.is_empty() {
+        return fuzz_test_error("The fuzz_test attribute can only be used on functions taking at least one argument.");
+    }
+
+    let ident = &function.ident;
+    // the test function is declared inside of the test, under the same name, such that it is not visible
+    // outside of it and its name is the one reported by the panics
+    let inner_function = ts!(
+        "fn" ident "(" join_ts!(&function.parameters, p, p, separator: ",") ")"
+        if let Some(return_ty) = &function.return_ty {
+            ts!("->" return_ty)
+        } else {
+            ts!()
+        }
+        function.where_clause
+        body
+    );
+
+    let input = ident!("fuzzcheck_test_input");
+    let test_function = match function.parameters.as_slice() {
+        // a function taking a shared reference is given to fuzzcheck directly, which also handles unsized types
+        [FunctionParameter {
+            ty: Ty {
+                kind: TyKind::Ref(ref_ty),
+                ..
+            },
+            ..
+        }] if !ref_ty.mutable => ts!(ident),
+        [parameter] => {
+            let (fuzzed_ty, argument) = fuzzed_type_and_argument(&parameter.ty, ts!(input));
+            ts!("|" input ": &" fuzzed_ty "|" ident "(" argument ")")
+        }
+        parameters => {
+            // the fuzzer generates a tuple containing the arguments of each parameter
+            let (fuzzed_tys, arguments): (Vec<_>, Vec<_>) = parameters
+                .iter()
+                .enumerate()
+                .map(|(i, parameter)| fuzzed_type_and_argument(&parameter.ty, ts!("&" input "." i)))
+                .unzip();
+            ts!(
+                "|" input ": &(" join_ts!(&fuzzed_tys, ty, ty, separator: ",") ")|"
+                ident "(" join_ts!(&arguments, argument, argument, separator: ",") ")"
+            )
+        }
+    };
+
+    ts!(
+        function.attributes
+        "#[test]"
+        function.visibility "fn" ident "() {"
+            inner_function
+            "fuzzcheck::fuzz_test(" test_function ")
+                .default_mutator()
+                .serde_serializer()
+                .default_sensor_and_pool()
+                .launch_or_run_corpus(module_path!(), " Literal::string(&ident.to_string()) ");
+        }"
+    )
+}
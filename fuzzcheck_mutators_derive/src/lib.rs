@@ -3,14 +3,15 @@
 
 use decent_synquote_alternative::{
     self as synquote,
-    parser::{EnumItemData, Ty},
+    parser::{EnumItemData, StructField, Ty, TyKind},
 };
 
-use proc_macro2::{Delimiter, Ident, Span, TokenStream};
+use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream};
 use synquote::token_builder::*;
 use synquote::{parser::TokenParser, token_builder::TokenBuilder};
 
 mod enums;
+mod fuzz_test;
 mod single_variant;
 mod structs_and_enums;
 mod tuples;
@@ -18,7 +19,22 @@ mod tuples;
 #[macro_use]
 extern crate decent_synquote_alternative;
 
-/// Create a tuple-mutatpr for of the given arity.
+/// The largest arity of the tuple mutators that fuzzcheck creates with [`make_basic_tuple_mutator!`].
+///
+/// The mutator of a struct, or of an enum variant, with `N` fields is built on top of the tuple mutator of
+/// arity `N`, so the derived mutators are limited to this number of fields.
+const MAX_NBR_FIELDS: usize = 25;
+
+/// A `compile_error!` explaining that the `item` has more fields than the derived mutators support
+fn too_many_fields_error(macro_name: &str, item: &str, nbr_fields: usize) -> TokenStream {
+    let message = format!(
+        "The {} macro does not support {} with more than {} fields, but this one has {} fields. Note: group some of the fields into a separate struct.",
+        macro_name, item, MAX_NBR_FIELDS, nbr_fields
+    );
+    ts!("compile_error!(" Literal::string(&message) ");")
+}
+
+/// Create a tuple-mutator of the given arity.
 ///
 /// This function can only be used within fuzzcheck itself.
 ///
@@ -74,7 +90,7 @@ pub fn make_mutator(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_default_mutator_(parser, settings).into()
 }
 
-#[proc_macro_derive(DefaultMutator, attributes(field_mutator))]
+#[proc_macro_derive(DefaultMutator, attributes(field_mutator, field_range))]
 pub fn derive_default_mutator(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let settings = MakeMutatorSettings::default();
     let item = proc_macro2::TokenStream::from(item);
@@ -82,6 +98,15 @@ pub fn derive_default_mutator(item: proc_macro::TokenStream) -> proc_macro::Toke
     derive_default_mutator_(parser, settings).into()
 }
 
+/// Turn a function into a test that runs it on the saved test cases with `cargo test` and fuzzes it with
+/// `cargo fuzzcheck`.
+///
+/// See the documentation of `fuzzcheck::fuzz_test` for more details.
+#[proc_macro_attribute]
+pub fn fuzz_test(attribute: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    fuzz_test::make_fuzz_test(attribute.into(), item.into()).into()
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn make_single_variant_mutator(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -99,7 +124,9 @@ fn derive_tuple_structure_(item: proc_macro2::TokenStream) -> proc_macro2::Token
 
     if let Some(s) = parser.eat_struct() {
         let nbr_fields = s.struct_fields.len();
-        if nbr_fields > 0 {
+        if nbr_fields > MAX_NBR_FIELDS {
+            extend_ts!(&mut tb, too_many_fields_error("TupleStructure", "structs", nbr_fields));
+        } else if nbr_fields > 0 {
             tuples::impl_tuple_structure_trait(&mut tb, &s);
         } else {
             extend_ts!(
@@ -124,14 +151,31 @@ fn derive_default_mutator_(mut parser: TokenParser, settings: MakeMutatorSetting
     let mut tb = TokenBuilder::new();
     if let Some(s) = parser.eat_struct() {
         let nbr_fields = s.struct_fields.len();
-        if nbr_fields == 0 {
+        if nbr_fields > MAX_NBR_FIELDS {
+            extend_ts!(&mut tb, too_many_fields_error("DefaultMutator", "structs", nbr_fields));
+        } else if nbr_fields == 0 {
             tuples::impl_default_mutator_for_struct_with_0_field(&mut tb, &s);
         } else {
             tuples::impl_tuple_structure_trait(&mut tb, &s);
             tuples::impl_default_mutator_for_struct(&mut tb, &s, &settings);
         }
     } else if let Some(e) = parser.eat_enumeration() {
-        if e.items
+        let max_nbr_fields = e
+            .items
+            .iter()
+            .map(|item| match &item.data {
+                Some(EnumItemData::Struct(_, fields)) => fields.len(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0);
+        if max_nbr_fields > MAX_NBR_FIELDS {
+            extend_ts!(
+                &mut tb,
+                too_many_fields_error("DefaultMutator", "enum variants", max_nbr_fields)
+            );
+        } else if e
+            .items
             .iter()
             .any(|item| matches!(&item.data, Some(EnumItemData::Struct(_, fields)) if !fields.is_empty()))
         {
@@ -264,6 +308,7 @@ pub(crate) struct Common {
     Clone: TokenStream,
     Default: TokenStream,
     DefaultMutator: TokenStream,
+    DefaultMutatorWithinRange: TokenStream,
     fastrand_Rng: TokenStream,
     mutators: TokenStream,
     // fuzzcheck_mutator_traits_Mutator: TokenStream,
@@ -325,6 +370,7 @@ impl Common {
             Clone: ts!("::std::clone::Clone"),
             Default: ts!("::std::default::Default"),
             DefaultMutator: ts!(mutators "::DefaultMutator"),
+            DefaultMutatorWithinRange: ts!(mutators "::DefaultMutatorWithinRange"),
             fastrand_Rng,
             mutators: mutators.clone(),
             // fuzzcheck_mutator_traits_Mutator: ts!(mutators fuzzcheck_traits_Mutator),
@@ -392,3 +438,42 @@ fn read_field_default_mutator_attribute(attribute: TokenStream) -> Option<(Ty, O
     }
     // eprintln!("{:?}", ts!(ty));
 }
+
+fn read_field_range_attribute(attribute: TokenStream) -> Option<TokenStream> {
+    let mut parser = TokenParser::new(attribute);
+    let _ = parser.eat_punct('#');
+    let content = match parser.eat_group(Delimiter::Bracket) {
+        Some(proc_macro2::TokenTree::Group(group)) => group,
+        Some(_) => panic!(),
+        None => return None,
+    };
+    let mut parser = TokenParser::new(content.stream());
+    let _ = parser.eat_ident("field_range")?;
+    match parser.eat_any_group() {
+        Some(proc_macro2::TokenTree::Group(group)) => Some(group.stream()),
+        Some(_) => panic!(),
+        None => None,
+    }
+}
+
+/// The mutator prescribed by the `#[field_mutator(..)]` or `#[field_range(..)]` attribute of the field, if any,
+/// along with the expression that creates it.
+///
+/// `#[field_range(min..=max)]` is a shorthand for the mutator given by the field type's implementation of
+/// `DefaultMutatorWithinRange`.
+fn read_field_mutator_attributes(field: &StructField, cm: &Common) -> Option<(Ty, Option<TokenStream>)> {
+    let mut mutator = None;
+    for attribute in field.attributes.iter() {
+        if let Some((m, init)) = read_field_default_mutator_attribute(attribute.clone()) {
+            mutator = Some((m, init));
+        } else if let Some(range) = read_field_range_attribute(attribute.clone()) {
+            let m = Ty {
+                stream: ts!("<" field.ty "as" cm.DefaultMutatorWithinRange ">::Mutator"),
+                kind: TyKind::Other,
+            };
+            let init = ts!("<" field.ty "as" cm.DefaultMutatorWithinRange ">::default_mutator_within_range(" range ")");
+            mutator = Some((m, Some(init)));
+        }
+    }
+    mutator
+}
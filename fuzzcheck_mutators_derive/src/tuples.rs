@@ -153,13 +153,7 @@ pub(crate) fn impl_default_mutator_for_struct(tb: &mut TokenBuilder, struc: &Str
         .iter()
         .enumerate()
         .map(|(i, field)| {
-            let mut mutator = None;
-            for attribute in field.attributes.iter() {
-                if let Some((m, init)) = super::read_field_default_mutator_attribute(attribute.clone()) {
-                    mutator = Some((m, init));
-                }
-            }
-            if let Some(m) = mutator {
+            if let Some(m) = super::read_field_mutator_attributes(field, &cm) {
                 FieldMutator {
                     i,
                     j: None,
@@ -194,6 +188,26 @@ pub(crate) fn impl_default_mutator_for_struct(tb: &mut TokenBuilder, struc: &Str
 
     use crate::structs_and_enums::{make_mutator_type_and_impl, CreateWrapperMutatorParams};
 
+    let field_complexities_impl = if cfg!(feature = "field_complexities") {
+        ts!(
+            "/// The complexity of each field of `value`, as given by the mutator of that field.
+            ///
+            /// Returns `None` if `value` is not a valid value for this mutator.
+            #[no_coverage]
+            pub fn field_complexities(&self, value: &" struc.ident struc.generics.removing_bounds_and_eq_type() ") -> " cm.Option "<" cm.Vec "<(&'static str, f64)>> {
+                let mutators = self.mutator.mutator.mutators();
+                let mut complexities = " cm.Vec "::new();"
+                join_ts!(struc.struct_fields.iter().zip(field_mutators.iter().flatten()).enumerate(), (i, (field, mutator)),
+                    "let cache = <" mutator.mutator_stream(&cm) " as " cm.fuzzcheck_traits_Mutator "<" field.ty "> >::validate_value(mutators." i ", &value." field.access() ")?;
+                    complexities.push((" Literal::string(&field.access().to_string()) ", <" mutator.mutator_stream(&cm) " as " cm.fuzzcheck_traits_Mutator "<" field.ty "> >::complexity(mutators." i ", &value." field.access() ", &cache)));"
+                )
+                cm.Some "(complexities)
+            }"
+        )
+    } else {
+        ts!()
+    };
+
     let params = CreateWrapperMutatorParams {
         cm: &cm,
         visibility: &struc.visibility,
@@ -218,6 +232,7 @@ pub(crate) fn impl_default_mutator_for_struct(tb: &mut TokenBuilder, struc: &Str
                     "))
             }
             }"
+            field_complexities_impl
         ),
         default_impl: &ts!("
             #[no_coverage]
@@ -257,6 +272,11 @@ fn declare_tuple_mutator(tb: &mut TokenBuilder, nbr_elements: usize) {
                     )
                     "rng: <_>::default() ,"
                 "}
+            }
+            #[doc(hidden)]
+            #[no_coverage]
+            pub fn mutators(&self) -> (" join_ts!(0..nbr_elements, i, "&" ident!("M" i) ",") ") {
+                (" join_ts!(0..nbr_elements, i, "&self." ident!("mutator_" i) ",") ")
             }"
         "}"
     )